@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use vexide::smart::motor::Motor;
+
+use crate::{MotorGroup, read_cache::Change};
+
+/// A motor temporarily taken out of its group, returned by
+/// [`MotorGroup::checkout`].
+///
+/// The guard dereferences to the checked out [`Motor`], so it can be
+/// controlled directly. The rest of the group is available through
+/// [`MotorCheckout::group`], and its reads and writes skip the checked out
+/// motor. Dropping the guard returns the motor to the group.
+#[derive(Debug)]
+pub struct MotorCheckout<'a, M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>> {
+    group: &'a mut MotorGroup<M>,
+    index: usize,
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorCheckout<'_, M> {
+    /// Returns the index of the checked out motor in its group.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the rest of the motor group.
+    ///
+    /// Every read and write on the group skips the checked out motor until
+    /// this guard is dropped.
+    pub fn group(&mut self) -> &mut MotorGroup<M> {
+        self.group
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Deref for MotorCheckout<'_, M> {
+    type Target = Motor;
+
+    fn deref(&self) -> &Self::Target {
+        &self.group.motors.as_ref()[self.index]
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> DerefMut for MotorCheckout<'_, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.group.motors.as_mut()[self.index]
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Drop for MotorCheckout<'_, M> {
+    fn drop(&mut self) {
+        self.group.meta[self.index].checked_out = false;
+        self.group.read_cache.invalidate(Change::Aggregation);
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Temporarily takes the motor at `index` out of the group.
+    ///
+    /// The returned guard dereferences to the motor so it can be used
+    /// directly, such as for one side of a power take-off. While the guard
+    /// exists, every read and write on the group skips the motor. Dropping the
+    /// guard puts it back.
+    ///
+    /// Because the guard mutably borrows the group, the group itself can only
+    /// be used through [`MotorCheckout::group`] in the meantime. With
+    /// [`SharedMotors`](crate::SharedMotors), that means checking the motor out
+    /// through [`SharedMotors::lock`](crate::SharedMotors::lock) and keeping
+    /// the lock for as long as the motor is checked out.
+    ///
+    /// If the position reference is changed while the motor is checked out
+    /// (for example, with [`MotorGroup::reset_position`]), the motor misses the
+    /// change and is marked as stale. See [`MotorGroup::stale_motors`].
+    ///
+    /// Returns `None` if `index` is out of bounds, if the motor is disabled
+    /// (see [`MotorGroup::set_enabled`]), or if it's the only active motor
+    /// left in the group, since a group can never be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let mut pto = motor_group.checkout(2).unwrap();
+    ///     // Runs the intake with the checked out motor...
+    ///     pto.set_voltage(12.0).unwrap();
+    ///     // ...while the other two keep driving.
+    ///     pto.group().set_voltage(6.0).unwrap();
+    ///
+    ///     drop(pto);
+    ///     // All three motors drive again.
+    ///     motor_group.set_voltage(6.0).unwrap();
+    /// }
+    /// ```
+    pub fn checkout(&mut self, index: usize) -> Option<MotorCheckout<'_, M>> {
+        let active = self.meta.iter().filter(|meta| meta.is_active()).count();
+        let meta = self.meta.get_mut(index)?;
+        if !meta.is_active() || active <= 1 {
+            return None;
+        }
+        meta.checked_out = true;
+        self.read_cache.invalidate(Change::Aggregation);
+        Some(MotorCheckout { group: self, index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::prelude::*;
+
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn group(size: u8) -> MotorGroup {
+        MotorGroup::new(mock_motors(size, Gearset::Green))
+    }
+
+    #[test]
+    fn checked_out_motor_is_skipped() {
+        let mut group = group(3);
+        {
+            let mut checkout = group.checkout(1).unwrap();
+            assert_eq!(checkout.index(), 1);
+            assert!(checkout.set_voltage(12.0).is_err());
+
+            // Every mock motor fails, so the error count shows which motors
+            // were visited
+            let rest = checkout.group();
+            assert_eq!(rest.set_voltage(6.0).unwrap_err().errors.len(), 2);
+            assert_eq!(rest.velocity().unwrap_err().errors.len(), 2);
+            assert_eq!(rest.is_over_current().unwrap_err().errors.len(), 2);
+
+            // A reference change that reaches the rest of the group leaves the
+            // checked out motor behind
+            rest.write_reference(|_| Ok(())).unwrap();
+        }
+        assert_eq!(group.stale_motors(), vec![1]);
+        assert_eq!(group.set_voltage(6.0).unwrap_err().errors.len(), 3);
+        assert_eq!(group.velocity().unwrap_err().errors.len(), 3);
+    }
+
+    #[test]
+    fn last_motor_cannot_be_checked_out() {
+        let mut group = group(2);
+        assert!(group.checkout(2).is_none());
+
+        let mut checkout = group.checkout(0).unwrap();
+        assert!(checkout.group().checkout(1).is_none());
+        assert!(checkout.group().checkout(0).is_none());
+        drop(checkout);
+
+        assert!(group.checkout(1).is_some());
+        assert!(self::group(1).checkout(0).is_none());
+    }
+}
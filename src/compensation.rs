@@ -0,0 +1,209 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::{Motor, MotorControl};
+
+use crate::{MotorGroup, MotorGroupError, SharedMotors};
+
+/// The battery voltage that voltage commands are relative to. A command of
+/// this many Volts is full power with a battery at this voltage.
+const NOMINAL_BATTERY_VOLTAGE: f64 = Motor::V5_MAX_VOLTAGE;
+
+/// Returns the voltage to command for `desired` Volts of output from a
+/// battery at `battery` Volts, before clamping.
+///
+/// A battery voltage that isn't a positive number can't be compensated for,
+/// so `desired` is returned as it is.
+fn compensated_voltage(desired: f64, battery: f64) -> f64 {
+    if battery.is_finite() && battery > 0.0 {
+        desired * NOMINAL_BATTERY_VOLTAGE / battery
+    } else {
+        desired
+    }
+}
+
+/// Returns `target` with its voltage clamped to `±max_voltage`.
+fn clamp_voltage(target: MotorControl, max_voltage: f64) -> MotorControl {
+    match target {
+        MotorControl::Voltage(volts) => {
+            MotorControl::Voltage(volts.clamp(-max_voltage, max_voltage))
+        }
+        other => other,
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the group's command for
+    /// [`MotorGroup::set_voltage_compensated`], along with the target for each
+    /// motor in the group, in order.
+    fn compensated_targets(
+        &self,
+        desired_volts: f64,
+        battery_voltage: f64,
+    ) -> (MotorControl, Vec<MotorControl>) {
+        let motors = self.motors.as_ref();
+        let max_voltage = motors.iter().map(Motor::max_voltage).fold(0.0, f64::max);
+        let volts = compensated_voltage(desired_volts, battery_voltage);
+        let target = self.limited_target(clamp_voltage(MotorControl::Voltage(volts), max_voltage));
+        let targets = motors
+            .iter()
+            .zip(&self.meta)
+            .map(|(motor, meta)| clamp_voltage(meta.scale_target(target), motor.max_voltage()))
+            .collect();
+        (target, targets)
+    }
+
+    /// Sets the motor group's voltage so that its output matches
+    /// `desired_volts` whatever the charge of the battery, given the battery's
+    /// current voltage in `battery_voltage`.
+    ///
+    /// A voltage command is relative to a full 12V battery, so as the battery
+    /// sags under load or runs down, the same command turns the motors more
+    /// slowly and a mechanism tuned on a fresh battery falls short. This
+    /// scales the command up to make up for it (or down, for a battery above
+    /// 12V):
+    ///
+    /// ```text
+    /// volts = desired_volts * 12.0 / battery_voltage
+    /// ```
+    ///
+    /// The result is then clamped to `±` the highest maximum voltage of the
+    /// group's motors (see [`Motor::max_voltage`]), and again to each motor's
+    /// own maximum once that motor's output scale (see
+    /// [`MotorGroup::set_scale`]) is applied, so a deeply drained battery
+    /// gives full power rather than more than the motor can take. The hard
+    /// voltage cap (see [`MotorGroup::set_hard_voltage_cap`]) and position
+    /// limits still apply as they do to [`MotorGroup::set_voltage`].
+    ///
+    /// If `battery_voltage` isn't a positive number, as when the battery
+    /// couldn't be read, `desired_volts` is written as it is. Read the battery
+    /// every time this is called, since it sags further the harder the motors
+    /// work.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::{battery, prelude::*};
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         // The same shot on a fresh battery and a tired one
+    ///         _ = flywheel.set_voltage_compensated(9.0, battery::voltage());
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_voltage_compensated(
+        &mut self,
+        desired_volts: f64,
+        battery_voltage: f64,
+    ) -> Result<(), MotorGroupError> {
+        let (target, targets) = self.compensated_targets(desired_volts, battery_voltage);
+        self.last_command = Some(target);
+        let (result, written) = self.write_targets(&targets);
+        self.track_fallback(target, &written);
+        result
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_voltage_compensated`].
+    pub fn set_voltage_compensated(
+        &mut self,
+        desired_volts: f64,
+        battery_voltage: f64,
+    ) -> Result<(), MotorGroupError> {
+        let result = self
+            .0
+            .borrow_mut()
+            .set_voltage_compensated(desired_volts, battery_voltage);
+        self.mirror(|shadow| shadow.set_voltage_compensated(desired_volts, battery_voltage));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::smart::motor::MotorControl;
+
+    use super::compensated_voltage;
+    use crate::{
+        MotorGroup,
+        tests::{exp_motor, v5_motor},
+    };
+
+    fn volts(targets: &[MotorControl]) -> Vec<f64> {
+        targets
+            .iter()
+            .map(|target| match target {
+                MotorControl::Voltage(volts) => *volts,
+                other => panic!("expected a voltage, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn commands_are_scaled_by_the_battery_voltage() {
+        for (battery, expected) in [(12.0, 6.0), (10.0, 7.2), (8.0, 9.0), (14.4, 5.0)] {
+            let volts = compensated_voltage(6.0, battery);
+            assert!((volts - expected).abs() < 1e-9, "{battery}V: {volts}");
+        }
+        assert!((compensated_voltage(-6.0, 9.0) + 8.0).abs() < 1e-9);
+        assert_eq!(compensated_voltage(0.0, 9.0), 0.0);
+    }
+
+    #[test]
+    fn unreadable_batteries_are_not_compensated_for() {
+        for battery in [0.0, -12.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(compensated_voltage(6.0, battery), 6.0);
+        }
+    }
+
+    #[test]
+    fn commands_are_clamped_to_each_motor() {
+        let mut group = MotorGroup::new(vec![v5_motor(1), exp_motor(2)]);
+
+        let (target, targets) = group.compensated_targets(6.0, 10.0);
+        assert!((volts(&[target])[0] - 7.2).abs() < 1e-9);
+        assert!(
+            volts(&targets)
+                .iter()
+                .all(|volts| (volts - 7.2).abs() < 1e-9)
+        );
+
+        // Only the EXP motor is limited to 8V
+        let (target, targets) = group.compensated_targets(11.0, 10.0);
+        assert_eq!(target, MotorControl::Voltage(12.0));
+        assert_eq!(volts(&targets), [12.0, 8.0]);
+        let (_, targets) = group.compensated_targets(-11.0, 10.0);
+        assert_eq!(volts(&targets), [-12.0, -8.0]);
+
+        // The clamp comes after each motor's scale
+        group.set_scale(1, 0.5);
+        let (_, targets) = group.compensated_targets(11.0, 10.0);
+        assert_eq!(volts(&targets), [12.0, 6.0]);
+    }
+
+    #[test]
+    fn the_group_records_the_compensated_command() {
+        let mut group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+        let generation = group.command_generation();
+
+        // Every write to the mock motors fails
+        let error = group.set_voltage_compensated(9.0, 8.0).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(group.last_command, Some(MotorControl::Voltage(12.0)));
+        assert_eq!(group.command_generation(), generation + 1);
+    }
+}
@@ -0,0 +1,748 @@
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use vexide::{
+    math::Angle,
+    prelude::{Direction, Gearset},
+    smart::{
+        PortError,
+        motor::{BrakeMode, Motor, SetGearsetError},
+    },
+};
+
+#[cfg(feature = "vexide-unstable")]
+use vexide::smart::motor::MotorTuningConstants;
+
+use crate::{
+    ConfigValidation, ConfigWarning, CurrentLimitPolicy, DecodeError, MaxCurrentTable, MotorGroup,
+    MotorGroupError, PositionFallback, PredicateErrorStrategy, PresetError, SetCurrentLimitError,
+    TargetingMode, WriteErrorStrategy,
+};
+
+/// The complete configuration of a motor group as plain data.
+///
+/// This is everything about a group that isn't the motors themselves. Since
+/// it can be constructed in a `const` context, a robot's configuration can
+/// live in one place:
+///
+/// ```rust,ignore
+/// use vexide_motorgroup::*;
+///
+/// const DRIVE_CONFIG: GroupConfig = GroupConfig {
+///     write_error_strategy: WriteErrorStrategy::Ignore,
+///     gearset: Some(Gearset::Blue),
+///     current_limit: Some(2.0),
+///     ..GroupConfig::DEFAULT
+/// };
+///
+/// // later...
+/// drive.apply_config(&DRIVE_CONFIG).unwrap();
+/// ```
+///
+/// The hardware settings (`gearset`, `direction`, the limits, and the PID
+/// constants) are `None` when the group hasn't been told to change them, in
+/// which case applying the configuration leaves the motors alone. They record
+/// what the group was asked to do, not a hardware read.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroupConfig {
+    /// See [`MotorGroup::write_error_strategy`].
+    pub write_error_strategy: WriteErrorStrategy,
+    /// See [`MotorGroup::predicate_error_strategy`].
+    pub predicate_error_strategy: PredicateErrorStrategy,
+    /// See [`MotorGroup::set_current_limit_policy`].
+    pub current_limit_policy: CurrentLimitPolicy,
+    /// See [`MotorGroup::set_max_current_table`].
+    pub max_current_table: MaxCurrentTable,
+    /// See [`ConfigValidation`].
+    pub validation: ConfigValidation,
+    /// See [`MotorGroup::count_disabled_in_average`].
+    pub count_disabled_in_average: bool,
+    /// See [`MotorGroup::shift_ratio`].
+    pub external_ratio: f64,
+    /// See [`MotorGroup::stop_on_drop`].
+    pub stop_on_drop: Option<BrakeMode>,
+    /// See [`MotorGroup::position_fallback`].
+    pub position_fallback: Option<PositionFallback>,
+    /// See [`MotorGroup::tick_interval`].
+    pub tick_interval: Duration,
+    /// See [`MotorGroup::set_read_cache`].
+    pub read_cache: Option<Duration>,
+    /// See [`MotorGroup::set_position_limits`].
+    pub position_limits: Option<(Angle, Angle)>,
+    /// See [`MotorGroup::set_targeting_mode`].
+    pub targeting_mode: TargetingMode,
+    /// See [`MotorGroup::set_gearset`].
+    pub gearset: Option<Gearset>,
+    /// See [`MotorGroup::set_direction`].
+    pub direction: Option<Direction>,
+    /// See [`MotorGroup::set_voltage_limit`].
+    pub voltage_limit: Option<f64>,
+    /// See [`MotorGroup::set_current_limit`].
+    ///
+    /// Only one of this and `total_current_limit` is set at a time.
+    pub current_limit: Option<f64>,
+    /// See [`MotorGroup::set_total_current_limit`].
+    ///
+    /// Only one of this and `current_limit` is set at a time.
+    pub total_current_limit: Option<f64>,
+    /// See [`MotorGroup::set_velocity_pid_constants`].
+    #[cfg(feature = "vexide-unstable")]
+    pub velocity_pid_constants: Option<MotorTuningConstants>,
+}
+
+impl GroupConfig {
+    /// The configuration of a newly created motor group.
+    pub const DEFAULT: Self = Self {
+        write_error_strategy: WriteErrorStrategy::Ignore,
+        predicate_error_strategy: PredicateErrorStrategy::Unsatisfied,
+        current_limit_policy: CurrentLimitPolicy::Clamp,
+        max_current_table: MaxCurrentTable::DEFAULT,
+        validation: ConfigValidation::Lenient,
+        count_disabled_in_average: false,
+        external_ratio: 1.0,
+        stop_on_drop: None,
+        position_fallback: None,
+        tick_interval: Motor::WRITE_INTERVAL,
+        read_cache: None,
+        position_limits: None,
+        targeting_mode: TargetingMode::Absolute,
+        gearset: None,
+        direction: None,
+        voltage_limit: None,
+        current_limit: None,
+        total_current_limit: None,
+        #[cfg(feature = "vexide-unstable")]
+        velocity_pid_constants: None,
+    };
+
+    /// Returns the configuration of a newly created motor group.
+    ///
+    /// This is the same as [`GroupConfig::DEFAULT`].
+    pub const fn default_config() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A read-free summary of a motor group's software configuration, for
+/// logging.
+///
+/// Unlike [`GroupConfig`], which is meant to be applied to a group, this also
+/// covers the per-motor settings, and leaves out what doesn't help explain a
+/// match afterwards. It's returned by [`MotorGroup::config_snapshot`]. With
+/// the `serde` feature, it implements `serde::Serialize`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSnapshot {
+    /// The number of motors in the group, including disabled and checked out
+    /// ones.
+    pub motor_count: usize,
+    /// See [`MotorGroup::write_error_strategy`].
+    pub write_error_strategy: WriteErrorStrategy,
+    /// See [`MotorGroup::predicate_error_strategy`].
+    pub predicate_error_strategy: PredicateErrorStrategy,
+    /// See [`MotorGroup::count_disabled_in_average`].
+    pub count_disabled_in_average: bool,
+    /// Each motor's scale, see [`MotorGroup::set_scale`].
+    pub scales: Vec<f64>,
+    /// Whether each motor is enabled, see [`MotorGroup::set_enabled`].
+    pub enabled: Vec<bool>,
+    /// Each motor's label, see [`MotorGroup::set_label`].
+    pub labels: Vec<Option<String>>,
+    /// See [`MotorGroup::shift_ratio`].
+    pub external_ratio: f64,
+    /// See [`MotorGroup::set_voltage_limit`].
+    pub voltage_limit: Option<f64>,
+    /// See [`MotorGroup::set_current_limit`].
+    pub current_limit: Option<f64>,
+    /// See [`MotorGroup::set_total_current_limit`].
+    pub total_current_limit: Option<f64>,
+    /// See [`MotorGroup::set_current_limit_policy`].
+    pub current_limit_policy: CurrentLimitPolicy,
+}
+
+/// Error returned when applying hardware configuration to a motor group.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigureError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// Setting the gearset failed.
+    Gearset {
+        /// The source of the error.
+        source: SetGearsetError,
+    },
+    /// Setting the current limit failed.
+    CurrentLimit {
+        /// The source of the error.
+        source: SetCurrentLimitError,
+    },
+    /// The configuration failed validation under
+    /// [`ConfigValidation::Strict`], or has a setting the group can't work
+    /// with at all (see [`ConfigValidation::Lenient`]), so nothing was
+    /// applied.
+    Invalid {
+        /// The problem found.
+        warning: ConfigWarning,
+    },
+    /// A saved configuration couldn't be decoded, so nothing was applied.
+    /// See [`MotorGroup::apply_config_bytes`].
+    Decode {
+        /// The source of the error.
+        source: DecodeError,
+    },
+    /// A saved preset couldn't be restored. See
+    /// [`MotorGroup::apply_config_bytes`].
+    Preset {
+        /// The source of the error.
+        source: PresetError,
+    },
+}
+
+impl From<PortError> for ConfigureError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl From<SetGearsetError> for ConfigureError {
+    fn from(source: SetGearsetError) -> Self {
+        Self::Gearset { source }
+    }
+}
+
+impl From<SetCurrentLimitError> for ConfigureError {
+    fn from(source: SetCurrentLimitError) -> Self {
+        Self::CurrentLimit { source }
+    }
+}
+
+impl From<DecodeError> for ConfigureError {
+    fn from(source: DecodeError) -> Self {
+        Self::Decode { source }
+    }
+}
+
+impl From<PresetError> for ConfigureError {
+    fn from(source: PresetError) -> Self {
+        Self::Preset { source }
+    }
+}
+
+impl core::fmt::Display for ConfigureError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::Gearset { source } => write!(f, "{source}"),
+            Self::CurrentLimit { source } => write!(f, "{source}"),
+            Self::Invalid { warning } => write!(f, "{warning}"),
+            Self::Decode { source } => write!(f, "{source}"),
+            Self::Preset { source } => write!(f, "{source}"),
+        }
+    }
+}
+
+impl core::error::Error for ConfigureError {}
+
+/// Moves the errors of a single configuration step into `errors`.
+fn collect_errors<E: Into<ConfigureError>>(
+    errors: &mut alloc::vec::Vec<ConfigureError>,
+    result: Result<(), MotorGroupError<E>>,
+) {
+    if let Err(error) = result {
+        errors.extend(error.errors.into_iter().map(Into::into));
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Creates a new motor group with the same software configuration as
+    /// `template`.
+    ///
+    /// This is meant for symmetric subsystems, such as the two sides of a
+    /// drivetrain, so their setup only has to be written once. These settings
+    /// are copied:
+    ///
+    /// - the write error strategy (see [`MotorGroup::write_error_strategy`])
+    /// - the predicate error strategy (see
+    ///   [`MotorGroup::predicate_error_strategy`])
+    /// - the current limit policy (see
+    ///   [`MotorGroup::set_current_limit_policy`])
+    /// - the max current table (see [`MotorGroup::set_max_current_table`])
+    /// - the validation mode (see [`ConfigValidation`])
+    /// - whether disabled motors are counted in averages (see
+    ///   [`MotorGroup::count_disabled_in_average`])
+    /// - the external gear ratio (see [`MotorGroup::shift_ratio`])
+    /// - the stop-on-drop brake mode (see [`MotorGroup::stop_on_drop`])
+    /// - the position fallback (see [`MotorGroup::position_fallback`])
+    /// - the tick interval of async methods (see
+    ///   [`MotorGroup::tick_interval`])
+    /// - the read cache's staleness bound (see [`MotorGroup::set_read_cache`])
+    /// - the targeting mode (see [`MotorGroup::set_targeting_mode`])
+    ///
+    /// Nothing is written to the new motors, so the hardware settings of
+    /// `template` (gearset, direction, and voltage and current limits) aren't
+    /// copied. To mirror those as well, pass
+    /// [`template.current_config()`](MotorGroup::current_config) to
+    /// [`MotorGroup::apply_config`] on the new group.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut left = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     left.write_error_strategy(WriteErrorStrategy::Stop);
+    ///     left.set_current_limit(2.0).unwrap();
+    ///
+    ///     let mut right = MotorGroup::new_like(
+    ///         vec![
+    ///             Motor::new(peripherals.port_3, Gearset::Blue, Direction::Reverse),
+    ///             Motor::new(peripherals.port_4, Gearset::Blue, Direction::Reverse),
+    ///         ],
+    ///         &left,
+    ///     );
+    ///     // The write error strategy is copied, but the current limit has to
+    ///     // be applied to the new motors.
+    ///     right.apply_config(&left.current_config()).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no motors in the vector.
+    pub fn new_like<M2: AsRef<[Motor]> + AsMut<[Motor]>>(
+        motors: M,
+        template: &MotorGroup<M2>,
+    ) -> Self {
+        let mut group = Self::new(motors);
+        group.config = GroupConfig {
+            write_error_strategy: template.config.write_error_strategy,
+            predicate_error_strategy: template.config.predicate_error_strategy,
+            current_limit_policy: template.config.current_limit_policy,
+            max_current_table: template.config.max_current_table,
+            validation: template.config.validation,
+            count_disabled_in_average: template.config.count_disabled_in_average,
+            external_ratio: template.config.external_ratio,
+            stop_on_drop: template.config.stop_on_drop,
+            position_fallback: template.config.position_fallback,
+            tick_interval: template.config.tick_interval,
+            read_cache: template.config.read_cache,
+            targeting_mode: template.config.targeting_mode,
+            ..GroupConfig::DEFAULT
+        };
+        group
+    }
+
+    /// Returns the motor group's current configuration.
+    ///
+    /// This doesn't read from the motors. The result can be passed to
+    /// [`MotorGroup::apply_config`] to restore the configuration later or copy
+    /// it to another group.
+    pub fn current_config(&self) -> GroupConfig {
+        self.config
+    }
+
+    /// Returns a summary of the motor group's software configuration for
+    /// logging, such as in a match post-mortem.
+    ///
+    /// This doesn't read from the motors: the limits are those the group was
+    /// last asked to set, which may not have reached every motor. See
+    /// [`ConfigSnapshot`] for what's included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     _ = lift.set_voltage_limit(10.0);
+    ///
+    ///     println!("{:?}", lift.config_snapshot());
+    /// }
+    /// ```
+    pub fn config_snapshot(&self) -> ConfigSnapshot {
+        ConfigSnapshot {
+            motor_count: self.motors.as_ref().len(),
+            write_error_strategy: self.config.write_error_strategy,
+            predicate_error_strategy: self.config.predicate_error_strategy,
+            count_disabled_in_average: self.config.count_disabled_in_average,
+            scales: self.meta.iter().map(|meta| meta.scale).collect(),
+            enabled: self.meta.iter().map(|meta| meta.enabled).collect(),
+            labels: self.meta.iter().map(|meta| meta.label.clone()).collect(),
+            external_ratio: self.config.external_ratio,
+            voltage_limit: self.config.voltage_limit,
+            current_limit: self.config.current_limit,
+            total_current_limit: self.config.total_current_limit,
+            current_limit_policy: self.config.current_limit_policy,
+        }
+    }
+
+    /// Applies a complete configuration to the motor group.
+    ///
+    /// The software settings are always applied. Hardware settings are only
+    /// written if they are `Some`, in this order:
+    ///
+    /// 1. gearset
+    /// 2. direction
+    /// 3. voltage limit
+    /// 4. current limit (per motor or total)
+    /// 5. velocity PID constants (with the `vexide-unstable` feature)
+    ///
+    /// Every step is attempted even if an earlier one fails, and all errors
+    /// are returned together.
+    ///
+    /// If `config.validation` is [`ConfigValidation::Strict`], the
+    /// configuration is first checked with [`GroupConfig::validate`], and
+    /// nothing at all is applied if it has any warnings. Otherwise, nothing
+    /// is applied only if the external gear ratio isn't finite and positive
+    /// or the position limits aren't finite with `min <= max`, the same
+    /// values [`MotorGroup::shift_ratio`] and
+    /// [`MotorGroup::set_position_limits`] refuse.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing every error from
+    ///   every step that failed.
+    /// - Under [`ConfigValidation::Strict`], a [`MotorGroupError`] error is
+    ///   returned containing a [`ConfigureError::Invalid`] error for every
+    ///   validation warning.
+    /// - Under [`ConfigValidation::Lenient`], a [`MotorGroupError`] error is
+    ///   returned containing a [`ConfigureError::Invalid`] error for an
+    ///   invalid external gear ratio or position limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// const LIFT_CONFIG: GroupConfig = GroupConfig {
+    ///     gearset: Some(Gearset::Red),
+    ///     voltage_limit: Some(10.0),
+    ///     ..GroupConfig::DEFAULT
+    /// };
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group.apply_config(&LIFT_CONFIG).unwrap();
+    /// }
+    /// ```
+    pub fn apply_config(
+        &mut self,
+        config: &GroupConfig,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        let warnings = match config.validation {
+            ConfigValidation::Strict => config.validate().err().unwrap_or_default(),
+            ConfigValidation::Lenient => config.unusable(),
+        };
+        if !warnings.is_empty() {
+            return Err(MotorGroupError::new(
+                warnings
+                    .into_iter()
+                    .map(|warning| ConfigureError::Invalid { warning })
+                    .collect(),
+            ));
+        }
+
+        self.config.write_error_strategy = config.write_error_strategy;
+        self.config.predicate_error_strategy = config.predicate_error_strategy;
+        self.config.current_limit_policy = config.current_limit_policy;
+        self.config.max_current_table = config.max_current_table;
+        self.config.validation = config.validation;
+        self.config.count_disabled_in_average = config.count_disabled_in_average;
+        self.config.external_ratio = config.external_ratio;
+        self.config.stop_on_drop = config.stop_on_drop;
+        self.position_fallback(config.position_fallback);
+        self.config.tick_interval = config.tick_interval;
+        self.set_read_cache(config.read_cache);
+        self.config.position_limits = config.position_limits;
+        self.config.targeting_mode = config.targeting_mode;
+
+        let mut errors = alloc::vec::Vec::new();
+        if let Some(gearset) = config.gearset {
+            collect_errors(&mut errors, self.set_gearset(gearset));
+        }
+        if let Some(direction) = config.direction {
+            collect_errors(&mut errors, self.set_direction(direction));
+        }
+        if let Some(limit) = config.voltage_limit {
+            collect_errors(&mut errors, self.set_voltage_limit(limit));
+        }
+        if let Some(limit) = config.current_limit {
+            collect_errors(&mut errors, self.set_current_limit(limit));
+        } else if let Some(total) = config.total_current_limit {
+            collect_errors(&mut errors, self.set_total_current_limit(total));
+        }
+        #[cfg(feature = "vexide-unstable")]
+        if let Some(constants) = config.velocity_pid_constants {
+            collect_errors(&mut errors, self.set_velocity_pid_constants(constants));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Applies the given hardware settings to the motor group in one call.
+    ///
+    /// Settings that are `None` are left alone. This is a shorthand for
+    /// [`MotorGroup::apply_config`] with the group's current software
+    /// settings, so the settings are applied in the same order:
+    ///
+    /// 1. gearset
+    /// 2. direction
+    /// 3. voltage limit
+    /// 4. current limit
+    ///
+    /// Every setting is attempted even if an earlier one fails.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing every error from
+    ///   every setting that failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group
+    ///         .configure(Some(Gearset::Blue), Some(2.0), None, None)
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn configure(
+        &mut self,
+        gearset: Option<Gearset>,
+        current_limit: Option<f64>,
+        voltage_limit: Option<f64>,
+        direction: Option<Direction>,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        self.apply_config(&GroupConfig {
+            gearset,
+            direction,
+            voltage_limit,
+            current_limit,
+            total_current_limit: None,
+            ..self.config
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{math::Angle, prelude::*};
+
+    use super::{ConfigSnapshot, ConfigureError, GroupConfig};
+    use crate::{
+        ConfigWarning, CurrentLimitPolicy, MotorGroup, PredicateErrorStrategy, WriteErrorStrategy,
+        tests::{two_motor_group, v5_motor},
+    };
+
+    const DRIVE_CONFIG: GroupConfig = GroupConfig {
+        write_error_strategy: WriteErrorStrategy::Stop,
+        current_limit_policy: CurrentLimitPolicy::Error,
+        ..GroupConfig::DEFAULT
+    };
+
+    #[test]
+    fn default_config_matches_new_group() {
+        assert_eq!(two_motor_group().current_config(), GroupConfig::DEFAULT);
+        assert_eq!(GroupConfig::default_config(), GroupConfig::default());
+    }
+
+    #[test]
+    fn software_config_round_trips() {
+        let mut group = two_motor_group();
+        group.apply_config(&DRIVE_CONFIG).unwrap();
+        assert_eq!(group.current_config(), DRIVE_CONFIG);
+
+        let mut other = two_motor_group();
+        other.apply_config(&group.current_config()).unwrap();
+        assert_eq!(other.current_config(), DRIVE_CONFIG);
+    }
+
+    #[test]
+    fn hardware_config_errors_are_aggregated() {
+        let mut group = two_motor_group();
+        let config = GroupConfig {
+            gearset: Some(Gearset::Red),
+            voltage_limit: Some(10.0),
+            current_limit: Some(2.0),
+            ..GroupConfig::DEFAULT
+        };
+        // Every step fails on both mock motors, and none of them stop the others
+        let error = group.apply_config(&config).unwrap_err();
+        assert_eq!(error.errors.len(), 6);
+        assert!(matches!(error.errors[0], ConfigureError::Gearset { .. }));
+        assert!(matches!(error.errors[2], ConfigureError::Port { .. }));
+        assert!(matches!(
+            error.errors[4],
+            ConfigureError::CurrentLimit { .. }
+        ));
+
+        // The requested configuration is still recorded
+        assert_eq!(group.current_config(), config);
+    }
+
+    #[test]
+    fn configure_applies_every_provided_setting() {
+        let mut group = two_motor_group();
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        let error = group
+            .configure(
+                Some(Gearset::Blue),
+                Some(2.0),
+                None,
+                Some(Direction::Reverse),
+            )
+            .unwrap_err();
+        // Each setting stops at its first motor, but every setting is attempted
+        assert_eq!(error.errors.len(), 3);
+        assert!(matches!(error.errors[0], ConfigureError::Gearset { .. }));
+        assert!(matches!(error.errors[1], ConfigureError::Port { .. }));
+        assert!(matches!(
+            error.errors[2],
+            ConfigureError::CurrentLimit { .. }
+        ));
+
+        let config = group.current_config();
+        assert_eq!(config.gearset, Some(Gearset::Blue));
+        assert_eq!(config.direction, Some(Direction::Reverse));
+        assert_eq!(config.current_limit, Some(2.0));
+        assert_eq!(config.voltage_limit, None);
+        assert_eq!(config.write_error_strategy, WriteErrorStrategy::Stop);
+    }
+
+    #[test]
+    fn new_like_copies_software_settings() {
+        // The template can hold its motors in a different container
+        let mut template = MotorGroup::new([v5_motor(3)]);
+        template.write_error_strategy(WriteErrorStrategy::Stop);
+        template.predicate_error_strategy(PredicateErrorStrategy::Ignore);
+        _ = template.set_voltage_limit(10.0);
+
+        let motors = vec![v5_motor(1)];
+        let config = MotorGroup::new_like(motors, &template).current_config();
+        assert_eq!(config.write_error_strategy, WriteErrorStrategy::Stop);
+        assert_eq!(
+            config.predicate_error_strategy,
+            PredicateErrorStrategy::Ignore
+        );
+        // Hardware settings aren't copied since nothing is written
+        assert_eq!(template.current_config().voltage_limit, Some(10.0));
+        assert_eq!(config.voltage_limit, None);
+    }
+
+    #[test]
+    fn config_snapshot_matches_the_configured_group() {
+        let mut group = two_motor_group();
+        group
+            .write_error_strategy(WriteErrorStrategy::Stop)
+            .predicate_error_strategy(PredicateErrorStrategy::Ignore)
+            .count_disabled_in_average(true)
+            .set_scale(1, 0.9)
+            .set_label(0, "left");
+        group.set_enabled(1, false);
+        // The mock motors reject the limit, but it's still what was asked for
+        _ = group.set_voltage_limit(10.0);
+
+        assert_eq!(
+            group.config_snapshot(),
+            ConfigSnapshot {
+                motor_count: 2,
+                write_error_strategy: WriteErrorStrategy::Stop,
+                predicate_error_strategy: PredicateErrorStrategy::Ignore,
+                count_disabled_in_average: true,
+                scales: vec![1.0, 0.9],
+                enabled: vec![true, false],
+                labels: vec![Some("left".into()), None],
+                external_ratio: 1.0,
+                voltage_limit: Some(10.0),
+                current_limit: None,
+                total_current_limit: None,
+                current_limit_policy: CurrentLimitPolicy::Clamp,
+            }
+        );
+    }
+
+    #[test]
+    fn unusable_external_ratios_are_refused() {
+        let mut group = two_motor_group();
+        for ratio in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+            let config = GroupConfig {
+                write_error_strategy: WriteErrorStrategy::Stop,
+                external_ratio: ratio,
+                ..GroupConfig::DEFAULT
+            };
+            let error = group.apply_config(&config).unwrap_err();
+            assert!(matches!(
+                error.errors[..],
+                [ConfigureError::Invalid {
+                    warning: ConfigWarning::InvalidValue {
+                        field: "external_ratio",
+                        ..
+                    }
+                }]
+            ));
+            // Nothing was applied, even under lenient validation
+            assert_eq!(group.current_config(), GroupConfig::DEFAULT);
+        }
+    }
+
+    #[test]
+    fn reversed_position_limits_are_refused() {
+        let mut group = two_motor_group();
+        let config = GroupConfig {
+            write_error_strategy: WriteErrorStrategy::Stop,
+            position_limits: Some((Angle::from_turns(2.0), Angle::ZERO)),
+            ..GroupConfig::DEFAULT
+        };
+        let error = group.apply_config(&config).unwrap_err();
+        assert!(matches!(
+            error.errors[..],
+            [ConfigureError::Invalid {
+                warning: ConfigWarning::ReversedPositionLimits { .. }
+            }]
+        ));
+        assert_eq!(group.current_config(), GroupConfig::DEFAULT);
+
+        let config = GroupConfig {
+            position_limits: Some((Angle::ZERO, Angle::from_turns(2.0))),
+            ..config
+        };
+        group.apply_config(&config).unwrap();
+        assert_eq!(group.current_config(), config);
+    }
+}
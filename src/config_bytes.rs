@@ -0,0 +1,1047 @@
+//! A versioned binary format for [`GroupConfig`] and the rest of a group's tuned
+//! settings, for saving them without the `serde` feature. See
+//! [`GroupConfig::to_bytes`] and [`MotorGroup::config_to_bytes`] for the format.
+
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use vexide::{
+    math::{Angle, Direction},
+    smart::motor::{BrakeMode, Gearset, Motor},
+};
+
+use crate::{
+    ConfigValidation, ConfigureError, CurrentLimitPolicy, GroupConfig, MaxCurrentTable, MotorGroup,
+    MotorGroupError, PositionFallback, PredicateErrorStrategy, PresetError, SharedMotors,
+    TargetingMode, WriteErrorStrategy,
+};
+
+/// Error returned by [`GroupConfig::to_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The buffer is too small for the encoded configuration.
+    BufferTooSmall {
+        /// The length the encoded configuration needs.
+        needed: usize,
+        /// The length of the buffer given.
+        available: usize,
+    },
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BufferTooSmall { needed, available } => write!(
+                f,
+                "encoded configuration needs {needed} bytes, but the buffer has {available}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for EncodeError {}
+
+/// Error returned by [`GroupConfig::from_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The configuration was encoded in another version of the format.
+    VersionMismatch {
+        /// The version the configuration was encoded in.
+        found: u16,
+        /// The version this build of the crate reads, which is
+        /// [`GroupConfig::FORMAT_VERSION`].
+        expected: u16,
+    },
+    /// The input ends partway through the configuration.
+    Truncated {
+        /// The length of the input.
+        len: usize,
+    },
+    /// A byte doesn't hold a value its field can have, such as an unknown
+    /// enum variant.
+    InvalidValue {
+        /// The offset of the byte in the input.
+        offset: usize,
+    },
+    /// The input goes on after the configuration.
+    TrailingBytes {
+        /// How many bytes are left over.
+        count: usize,
+    },
+    /// The input was saved from a group with a different number of motors, so
+    /// its per-motor settings can't be applied.
+    MotorCount {
+        /// The number of motors the input has settings for.
+        found: usize,
+        /// The number of motors in the group.
+        expected: usize,
+    },
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::VersionMismatch { found, expected } => write!(
+                f,
+                "configuration is format version {found}, but version {expected} was expected"
+            ),
+            Self::Truncated { len } => {
+                write!(f, "configuration ends early, after {len} bytes")
+            }
+            Self::InvalidValue { offset } => write!(f, "invalid value at byte {offset}"),
+            Self::TrailingBytes { count } => {
+                write!(f, "{count} bytes left over after the configuration")
+            }
+            Self::MotorCount { found, expected } => write!(
+                f,
+                "configuration has settings for {found} motors, but the group has {expected}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Writes values into a buffer, counting how long the output would be even
+/// once the buffer is full.
+struct Writer<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        if let Some(out) = self.buffer.get_mut(self.len..end) {
+            out.copy_from_slice(bytes);
+        }
+        self.len = end;
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.bytes(&[value]);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn duration(&mut self, value: Duration) {
+        self.bytes(&value.as_secs().to_le_bytes());
+        self.bytes(&value.subsec_nanos().to_le_bytes());
+    }
+
+    fn option<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        self.bool(value.is_some());
+        if let Some(value) = value {
+            write(self, value);
+        }
+    }
+
+    /// Writes a length or count. Lengths past `u32::MAX` can't be encoded,
+    /// and can't come up on the Brain, whose `usize` is 32 bits.
+    fn len(&mut self, len: usize) {
+        self.bytes(&u32::try_from(len).unwrap_or(u32::MAX).to_le_bytes());
+    }
+
+    /// Returns how many bytes were written, or how many were needed if the
+    /// buffer was too small.
+    fn finish(self) -> Result<usize, EncodeError> {
+        let available = self.buffer.len();
+        if self.len > available {
+            return Err(EncodeError::BufferTooSmall {
+                needed: self.len,
+                available,
+            });
+        }
+        Ok(self.len)
+    }
+}
+
+/// Reads values from the input, keeping track of the offset for errors.
+struct Reader<'a> {
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes = self
+            .input
+            .get(self.offset..self.offset + N)
+            .ok_or(DecodeError::Truncated {
+                len: self.input.len(),
+            })?;
+        self.offset += N;
+        Ok(bytes.try_into().unwrap_or([0; N]))
+    }
+
+    /// Reads a `u8` holding the position of a variant in `variants`.
+    fn variant<T: Copy>(&mut self, variants: &[T]) -> Result<T, DecodeError> {
+        let offset = self.offset;
+        let [index] = self.bytes()?;
+        variants
+            .get(usize::from(index))
+            .copied()
+            .ok_or(DecodeError::InvalidValue { offset })
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeError> {
+        self.variant(&[false, true])
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        Ok(f64::from_le_bytes(self.bytes()?))
+    }
+
+    /// Reads an `f64`, which must be one that `valid` accepts.
+    fn f64_where(&mut self, valid: impl FnOnce(f64) -> bool) -> Result<f64, DecodeError> {
+        let offset = self.offset;
+        let value = self.f64()?;
+        if valid(value) {
+            Ok(value)
+        } else {
+            Err(DecodeError::InvalidValue { offset })
+        }
+    }
+
+    fn duration(&mut self) -> Result<Duration, DecodeError> {
+        let secs = u64::from_le_bytes(self.bytes()?);
+        let offset = self.offset;
+        let nanos = u32::from_le_bytes(self.bytes()?);
+        if nanos >= 1_000_000_000 {
+            return Err(DecodeError::InvalidValue { offset });
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn option<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<Option<T>, DecodeError> {
+        if self.bool()? {
+            read(self).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        let offset = self.offset;
+        let len = u32::from_le_bytes(self.bytes()?);
+        usize::try_from(len).map_err(|_| DecodeError::InvalidValue { offset })
+    }
+
+    /// Checks that the whole input was read.
+    fn finish(self) -> Result<(), DecodeError> {
+        match self.input.len() - self.offset {
+            0 => Ok(()),
+            count => Err(DecodeError::TrailingBytes { count }),
+        }
+    }
+}
+
+const WRITE_ERROR_STRATEGIES: [WriteErrorStrategy; 3] = [
+    WriteErrorStrategy::Ignore,
+    WriteErrorStrategy::Stop,
+    WriteErrorStrategy::Custom,
+];
+const PREDICATE_ERROR_STRATEGIES: [PredicateErrorStrategy; 2] = [
+    PredicateErrorStrategy::Unsatisfied,
+    PredicateErrorStrategy::Ignore,
+];
+const CURRENT_LIMIT_POLICIES: [CurrentLimitPolicy; 2] =
+    [CurrentLimitPolicy::Clamp, CurrentLimitPolicy::Error];
+const VALIDATIONS: [ConfigValidation; 2] = [ConfigValidation::Lenient, ConfigValidation::Strict];
+const BRAKE_MODES: [BrakeMode; 3] = [BrakeMode::Coast, BrakeMode::Brake, BrakeMode::Hold];
+const TARGETING_MODES: [TargetingMode; 2] = [TargetingMode::Absolute, TargetingMode::RelativeDelta];
+const GEARSETS: [Gearset; 3] = [Gearset::Red, Gearset::Green, Gearset::Blue];
+const DIRECTIONS: [Direction; 2] = [Direction::Forward, Direction::Reverse];
+
+/// Returns the position of `value` in `variants`, as it's encoded.
+fn variant<T: PartialEq>(variants: &[T], value: &T) -> u8 {
+    variants
+        .iter()
+        .position(|variant| variant == value)
+        .and_then(|index| u8::try_from(index).ok())
+        .unwrap_or_default()
+}
+
+/// The velocity PID constants as they're encoded: `kf`, `kp`, `ki`, `kd`,
+/// `filter`, `integral_limit` and `tolerance`, then `sample_rate`.
+type TuningFields = ([f64; 7], Duration);
+
+#[cfg(feature = "vexide-unstable")]
+fn tuning_fields(config: &GroupConfig) -> Option<TuningFields> {
+    config.velocity_pid_constants.map(|constants| {
+        (
+            [
+                constants.kf,
+                constants.kp,
+                constants.ki,
+                constants.kd,
+                constants.filter,
+                constants.integral_limit,
+                constants.tolerance,
+            ],
+            constants.sample_rate,
+        )
+    })
+}
+
+#[cfg(not(feature = "vexide-unstable"))]
+fn tuning_fields(_config: &GroupConfig) -> Option<TuningFields> {
+    None
+}
+
+#[cfg(feature = "vexide-unstable")]
+fn set_tuning_fields(config: &mut GroupConfig, fields: Option<TuningFields>) {
+    config.velocity_pid_constants = fields.map(
+        |([kf, kp, ki, kd, filter, integral_limit, tolerance], sample_rate)| {
+            vexide::smart::motor::MotorTuningConstants {
+                kf,
+                kp,
+                ki,
+                kd,
+                filter,
+                integral_limit,
+                tolerance,
+                sample_rate,
+            }
+        },
+    );
+}
+
+/// Without the `vexide-unstable` feature, a configuration has no velocity PID
+/// constants, so any that were saved are skipped.
+#[cfg(not(feature = "vexide-unstable"))]
+fn set_tuning_fields(_config: &mut GroupConfig, _fields: Option<TuningFields>) {}
+
+impl GroupConfig {
+    /// The version of the format written by [`GroupConfig::to_bytes`], and
+    /// the only one [`GroupConfig::from_bytes`] reads.
+    pub const FORMAT_VERSION: u16 = 1;
+
+    /// The most bytes [`GroupConfig::to_bytes`] can write, for sizing a
+    /// buffer. A configuration without every optional setting is shorter.
+    pub const MAX_ENCODED_LEN: usize = 197;
+
+    /// Encodes the configuration into `buffer`, returning how many bytes were
+    /// written, so that tuned settings can be saved to the SD card and loaded
+    /// after the program is rebuilt.
+    ///
+    /// The format is a compact binary one of the crate's own, and doesn't
+    /// need the `serde` feature. It starts with
+    /// [`GroupConfig::FORMAT_VERSION`], so that a configuration saved by
+    /// another version of the crate is rejected by
+    /// [`GroupConfig::from_bytes`] rather than misread. A buffer of
+    /// [`GroupConfig::MAX_ENCODED_LEN`] bytes always fits.
+    ///
+    /// Only what's in a [`GroupConfig`] is saved here. To save the per-motor
+    /// output scales and presets along with it, use
+    /// [`MotorGroup::config_to_bytes`] instead.
+    ///
+    /// # Format
+    ///
+    /// Every value is little endian. The version comes first as a `u16`,
+    /// followed by every field of the configuration in declaration order:
+    ///
+    /// - enums are a `u8` holding the variant's position in its declaration;
+    /// - `bool`s are a `u8` holding `0` or `1`;
+    /// - `f64`s are their IEEE 754 bits, and [`Angle`]s are an `f64` of
+    ///   radians, so both round-trip exactly;
+    /// - [`Duration`]s are their whole seconds as a `u64` followed by their
+    ///   nanoseconds as a `u32`;
+    /// - `Option`s are a `u8` of `0` for `None`, or `1` followed by the value;
+    /// - structs and tuples are their fields in order.
+    ///
+    /// The velocity PID constants are always part of the format, as `None`
+    /// without the `vexide-unstable` feature, so that a configuration saved
+    /// with the feature can be loaded without it and the other way around.
+    ///
+    /// # Errors
+    ///
+    /// - An [`EncodeError::BufferTooSmall`] error with the length needed is
+    ///   returned if `buffer` is too short. The contents of `buffer` are then
+    ///   unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use std::fs;
+    ///
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Load the settings tuned in an earlier run, if there are any
+    ///     if let Ok(bytes) = fs::read("lift.cfg")
+    ///         && let Ok(config) = GroupConfig::from_bytes(&bytes)
+    ///     {
+    ///         _ = lift.apply_config(&config);
+    ///     }
+    ///
+    ///     // ...tune the lift...
+    ///
+    ///     let mut buffer = [0; GroupConfig::MAX_ENCODED_LEN];
+    ///     if let Ok(len) = lift.current_config().to_bytes(&mut buffer) {
+    ///         _ = fs::write("lift.cfg", &buffer[..len]);
+    ///     }
+    /// }
+    /// ```
+    pub fn to_bytes(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        let mut out = Writer { buffer, len: 0 };
+        self.encode(&mut out);
+        out.finish()
+    }
+
+    /// Writes the version and then the configuration.
+    fn encode(&self, out: &mut Writer<'_>) {
+        out.bytes(&Self::FORMAT_VERSION.to_le_bytes());
+        out.u8(variant(&WRITE_ERROR_STRATEGIES, &self.write_error_strategy));
+        out.u8(variant(
+            &PREDICATE_ERROR_STRATEGIES,
+            &self.predicate_error_strategy,
+        ));
+        out.u8(variant(&CURRENT_LIMIT_POLICIES, &self.current_limit_policy));
+        out.f64(self.max_current_table.v5);
+        out.f64(self.max_current_table.exp);
+        out.u8(variant(&VALIDATIONS, &self.validation));
+        out.bool(self.count_disabled_in_average);
+        out.f64(self.external_ratio);
+        out.option(self.stop_on_drop, |out, mode| {
+            out.u8(variant(&BRAKE_MODES, &mode));
+        });
+        out.option(self.position_fallback, |out, fallback| {
+            out.bytes(&fallback.failures_to_enter.to_le_bytes());
+            out.f64(fallback.gain);
+            out.f64(fallback.max_voltage);
+        });
+        out.duration(self.tick_interval);
+        out.option(self.read_cache, Writer::duration);
+        out.option(self.position_limits, |out, (min, max)| {
+            out.f64(min.as_radians());
+            out.f64(max.as_radians());
+        });
+        out.u8(variant(&TARGETING_MODES, &self.targeting_mode));
+        out.option(self.gearset, |out, gearset| {
+            out.u8(variant(&GEARSETS, &gearset));
+        });
+        out.option(self.direction, |out, direction| {
+            out.u8(variant(&DIRECTIONS, &direction));
+        });
+        out.option(self.voltage_limit, Writer::f64);
+        out.option(self.current_limit, Writer::f64);
+        out.option(self.total_current_limit, Writer::f64);
+        out.option(tuning_fields(self), |out, (gains, sample_rate)| {
+            for gain in gains {
+                out.f64(gain);
+            }
+            out.duration(sample_rate);
+        });
+    }
+
+    /// Decodes a configuration written by [`GroupConfig::to_bytes`].
+    ///
+    /// The whole of `bytes` must be one configuration. Apart from an external
+    /// gear ratio or position limits that no group could use, nothing is
+    /// checked beyond the format itself, so apply the configuration with
+    /// [`MotorGroup::apply_config`](crate::MotorGroup::apply_config) to have
+    /// its values validated like any other. Velocity PID constants saved with
+    /// the `vexide-unstable` feature are skipped without it.
+    ///
+    /// # Errors
+    ///
+    /// - A [`DecodeError::VersionMismatch`] error is returned if `bytes` were
+    ///   encoded in a version of the format other than
+    ///   [`GroupConfig::FORMAT_VERSION`].
+    /// - A [`DecodeError::Truncated`] error is returned if `bytes` end
+    ///   partway through the configuration.
+    /// - A [`DecodeError::InvalidValue`] error is returned if a field holds a
+    ///   value it can't have, such as an unknown enum variant, an external
+    ///   gear ratio that isn't finite and positive, or position limits that
+    ///   aren't finite with `min <= max`.
+    /// - A [`DecodeError::TrailingBytes`] error is returned if `bytes` go on
+    ///   after the configuration.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut input = Reader {
+            input: bytes,
+            offset: 0,
+        };
+        let config = Self::decode(&mut input)?;
+        input.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the version and then the configuration.
+    fn decode(input: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        let version = u16::from_le_bytes(input.bytes()?);
+        if version != Self::FORMAT_VERSION {
+            return Err(DecodeError::VersionMismatch {
+                found: version,
+                expected: Self::FORMAT_VERSION,
+            });
+        }
+        let mut config = Self {
+            write_error_strategy: input.variant(&WRITE_ERROR_STRATEGIES)?,
+            predicate_error_strategy: input.variant(&PREDICATE_ERROR_STRATEGIES)?,
+            current_limit_policy: input.variant(&CURRENT_LIMIT_POLICIES)?,
+            max_current_table: MaxCurrentTable {
+                v5: input.f64()?,
+                exp: input.f64()?,
+            },
+            validation: input.variant(&VALIDATIONS)?,
+            count_disabled_in_average: input.bool()?,
+            external_ratio: input.f64_where(|ratio| ratio.is_finite() && ratio > 0.0)?,
+            stop_on_drop: input.option(|input| input.variant(&BRAKE_MODES))?,
+            position_fallback: input.option(|input| {
+                Ok(PositionFallback {
+                    failures_to_enter: u32::from_le_bytes(input.bytes()?),
+                    gain: input.f64()?,
+                    max_voltage: input.f64()?,
+                })
+            })?,
+            tick_interval: input.duration()?,
+            read_cache: input.option(Reader::duration)?,
+            position_limits: input.option(|input| {
+                let offset = input.offset;
+                let min = input.f64_where(f64::is_finite)?;
+                let max = input.f64_where(f64::is_finite)?;
+                if min > max {
+                    return Err(DecodeError::InvalidValue { offset });
+                }
+                Ok((Angle::from_radians(min), Angle::from_radians(max)))
+            })?,
+            targeting_mode: input.variant(&TARGETING_MODES)?,
+            gearset: input.option(|input| input.variant(&GEARSETS))?,
+            direction: input.option(|input| input.variant(&DIRECTIONS))?,
+            voltage_limit: input.option(Reader::f64)?,
+            current_limit: input.option(Reader::f64)?,
+            total_current_limit: input.option(Reader::f64)?,
+            #[cfg(feature = "vexide-unstable")]
+            velocity_pid_constants: None,
+        };
+        let tuning = input.option(|input| {
+            let mut gains = [0.0; 7];
+            for gain in &mut gains {
+                *gain = input.f64()?;
+            }
+            Ok((gains, input.duration()?))
+        })?;
+        set_tuning_fields(&mut config, tuning);
+        Ok(config)
+    }
+}
+
+/// Everything [`MotorGroup::config_to_bytes`] saves, as it's read back.
+struct SavedGroup {
+    config: GroupConfig,
+    scales: Vec<f64>,
+    /// The name, position and velocity of each preset.
+    presets: Vec<(String, Angle, i32)>,
+}
+
+impl SavedGroup {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut input = Reader {
+            input: bytes,
+            offset: 0,
+        };
+        let config = GroupConfig::decode(&mut input)?;
+
+        // Not preallocated, since a corrupt count could be anything
+        let mut scales = Vec::new();
+        for _ in 0..input.len()? {
+            scales.push(input.f64_where(f64::is_finite)?);
+        }
+
+        let mut presets = Vec::new();
+        for _ in 0..input.len()? {
+            let offset = input.offset;
+            let len = input.len()?;
+            let name = input
+                .input
+                .get(input.offset..input.offset.saturating_add(len))
+                .ok_or(DecodeError::Truncated {
+                    len: input.input.len(),
+                })?;
+            let name = String::from_utf8(name.to_vec())
+                .map_err(|_| DecodeError::InvalidValue { offset })?;
+            input.offset += len;
+            let position = Angle::from_radians(input.f64()?);
+            let velocity = i32::from_le_bytes(input.bytes()?);
+            presets.push((name, position, velocity));
+        }
+
+        input.finish()?;
+        Ok(Self {
+            config,
+            scales,
+            presets,
+        })
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Encodes the group's configuration (see [`MotorGroup::current_config`]),
+    /// along with each motor's output scale and every preset, into `buffer`,
+    /// returning how many bytes were written.
+    ///
+    /// This saves everything that's tuned on a group, for loading with
+    /// [`MotorGroup::apply_config_bytes`] after the program is rebuilt. The
+    /// configuration is encoded as by [`GroupConfig::to_bytes`], and the rest
+    /// follows it in the same format:
+    ///
+    /// - the number of motors as a `u32`, then each motor's output scale (see
+    ///   [`MotorGroup::set_scale`]) as an `f64`, in order;
+    /// - the number of presets as a `u32`, then each preset (see
+    ///   [`MotorGroup::add_preset`]) as the length of its name as a `u32`, its
+    ///   name in UTF-8, its position as an `f64` of radians, and its velocity
+    ///   as an `i32`.
+    ///
+    /// # Errors
+    ///
+    /// - An [`EncodeError::BufferTooSmall`] error with the length needed is
+    ///   returned if `buffer` is too short. The contents of `buffer` are then
+    ///   unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use std::fs;
+    ///
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     _ = lift.add_preset("intake", Angle::ZERO, 100);
+    ///     _ = lift.add_preset("score", Angle::from_turns(2.2), 60);
+    ///
+    ///     // Restore the trims and preset heights tuned in an earlier run
+    ///     if let Ok(bytes) = fs::read("lift.cfg") {
+    ///         _ = lift.apply_config_bytes(&bytes);
+    ///     }
+    ///
+    ///     // ...tune the lift...
+    ///
+    ///     let mut buffer = [0; 512];
+    ///     if let Ok(len) = lift.config_to_bytes(&mut buffer) {
+    ///         _ = fs::write("lift.cfg", &buffer[..len]);
+    ///     }
+    /// }
+    /// ```
+    pub fn config_to_bytes(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        let mut out = Writer { buffer, len: 0 };
+        self.current_config().encode(&mut out);
+        out.len(self.meta.len());
+        for meta in &self.meta {
+            out.f64(meta.scale);
+        }
+        out.len(self.presets.len());
+        for preset in &self.presets {
+            let name = preset.name.as_bytes();
+            out.len(name.len());
+            out.bytes(name);
+            out.f64(preset.position.as_radians());
+            out.bytes(&preset.velocity.to_le_bytes());
+        }
+        out.finish()
+    }
+
+    /// Applies a configuration, output scales and presets saved by
+    /// [`MotorGroup::config_to_bytes`].
+    ///
+    /// The input is decoded in full before anything is applied. The
+    /// configuration is then applied with [`MotorGroup::apply_config`], each
+    /// motor is given its saved output scale, and each saved preset replaces
+    /// the group's preset of the same name.
+    ///
+    /// Preset names are `&'static str`s, so presets can't be created from
+    /// saved data: add every preset in code first, and their saved positions
+    /// and velocities are restored onto them. Presets that weren't saved keep
+    /// what the code gave them.
+    ///
+    /// # Errors
+    ///
+    /// - A [`ConfigureError::Decode`] error is returned, and nothing is
+    ///   applied, if `bytes` can't be decoded (see
+    ///   [`GroupConfig::from_bytes`]), or with
+    ///   [`DecodeError::MotorCount`] if they were saved from a group with a
+    ///   different number of motors.
+    /// - The errors of [`MotorGroup::apply_config`] are returned as they are.
+    ///   If the configuration fails strict validation, nothing is applied.
+    /// - A [`ConfigureError::Preset`] error is returned for every saved preset
+    ///   that the group has no preset of the same name for
+    ///   ([`PresetError::Unknown`]), or that's outside the group's position
+    ///   limits ([`PresetError::OutsideLimits`]). The other settings are still
+    ///   applied.
+    pub fn apply_config_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        let decode_error =
+            |source| MotorGroupError::new(alloc::vec![ConfigureError::Decode { source }]);
+        let saved = SavedGroup::from_bytes(bytes).map_err(decode_error)?;
+        if saved.scales.len() != self.meta.len() {
+            return Err(decode_error(DecodeError::MotorCount {
+                found: saved.scales.len(),
+                expected: self.meta.len(),
+            }));
+        }
+
+        let mut errors = Vec::new();
+        if let Err(error) = self.apply_config(&saved.config) {
+            if error
+                .errors
+                .iter()
+                .any(|error| matches!(error, ConfigureError::Invalid { .. }))
+            {
+                return Err(error);
+            }
+            errors = error.errors;
+        }
+        for (index, scale) in saved.scales.into_iter().enumerate() {
+            self.set_scale(index, scale);
+        }
+        for (name, position, velocity) in saved.presets {
+            let result = match self.presets.iter().find(|preset| preset.name == name) {
+                Some(preset) => self.add_preset(preset.name, position, velocity),
+                None => Err(PresetError::Unknown),
+            };
+            if let Err(source) = result {
+                errors.push(ConfigureError::Preset { source });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::config_to_bytes`].
+    pub fn config_to_bytes(&self, buffer: &mut [u8]) -> Result<usize, EncodeError> {
+        self.0.borrow().config_to_bytes(buffer)
+    }
+
+    /// See [`MotorGroup::apply_config_bytes`].
+    pub fn apply_config_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        self.0.borrow_mut().apply_config_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use vexide::{
+        math::{Angle, Direction},
+        smart::motor::{BrakeMode, Gearset},
+    };
+
+    use super::DecodeError;
+    use crate::{
+        ConfigValidation, ConfigureError, CurrentLimitPolicy, GroupConfig, MaxCurrentTable,
+        MotorGroup, PositionFallback, PredicateErrorStrategy, Preset, PresetError, TargetingMode,
+        WriteErrorStrategy, tests::mock_motors,
+    };
+
+    fn group(size: u8) -> MotorGroup {
+        MotorGroup::new(mock_motors(size, Gearset::Green))
+    }
+
+    /// A tuned lift, saved with its scales and presets.
+    fn saved_lift() -> (MotorGroup, Vec<u8>) {
+        let mut lift = group(2);
+        lift.set_position_limits(Angle::ZERO, Angle::from_turns(3.0));
+        lift.set_scale(1, 0.9);
+        lift.add_preset("intake", Angle::ZERO, 100).unwrap();
+        lift.add_preset("score", Angle::from_turns(2.2), 60)
+            .unwrap();
+        let mut buffer = vec![0; 512];
+        let len = lift.config_to_bytes(&mut buffer).unwrap();
+        buffer.truncate(len);
+        (lift, buffer)
+    }
+
+    /// A configuration with every optional setting, and no default values.
+    fn tuned() -> GroupConfig {
+        GroupConfig {
+            write_error_strategy: WriteErrorStrategy::Custom,
+            predicate_error_strategy: PredicateErrorStrategy::Ignore,
+            current_limit_policy: CurrentLimitPolicy::Error,
+            max_current_table: MaxCurrentTable { v5: 2.0, exp: 1.0 },
+            validation: ConfigValidation::Strict,
+            count_disabled_in_average: true,
+            external_ratio: 84.0 / 36.0,
+            stop_on_drop: Some(BrakeMode::Hold),
+            position_fallback: Some(PositionFallback {
+                failures_to_enter: 3,
+                gain: 0.015,
+                max_voltage: 6.0,
+            }),
+            tick_interval: Duration::from_micros(2500),
+            read_cache: Some(Duration::from_millis(10)),
+            position_limits: Some((Angle::from_degrees(-12.5), Angle::from_turns(2.4))),
+            targeting_mode: TargetingMode::RelativeDelta,
+            gearset: Some(Gearset::Blue),
+            direction: Some(Direction::Reverse),
+            voltage_limit: Some(10.5),
+            current_limit: Some(1.8),
+            total_current_limit: Some(f64::MIN_POSITIVE),
+            #[cfg(feature = "vexide-unstable")]
+            velocity_pid_constants: Some(vexide::smart::motor::MotorTuningConstants {
+                kf: 0.1,
+                kp: 0.2,
+                ki: 0.3,
+                kd: 0.4,
+                filter: 0.5,
+                integral_limit: 0.6,
+                tolerance: 0.7,
+                sample_rate: Duration::from_millis(10),
+            }),
+        }
+    }
+
+    fn round_trip(config: GroupConfig) -> GroupConfig {
+        let mut buffer = [0; GroupConfig::MAX_ENCODED_LEN];
+        let len = config.to_bytes(&mut buffer).unwrap();
+        GroupConfig::from_bytes(&buffer[..len]).unwrap()
+    }
+
+    #[test]
+    fn configs_round_trip() {
+        assert_eq!(round_trip(GroupConfig::DEFAULT), GroupConfig::DEFAULT);
+        assert_eq!(round_trip(tuned()), tuned());
+
+        // Some of each setting
+        let config = GroupConfig {
+            write_error_strategy: WriteErrorStrategy::Stop,
+            stop_on_drop: Some(BrakeMode::Coast),
+            gearset: Some(Gearset::Red),
+            direction: Some(Direction::Forward),
+            current_limit: Some(0.0),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(round_trip(config), config);
+    }
+
+    #[test]
+    fn max_encoded_len_fits_every_setting() {
+        let mut buffer = [0; GroupConfig::MAX_ENCODED_LEN + 1];
+        let len = tuned().to_bytes(&mut buffer).unwrap();
+        // The PID constants are always encoded, so they fill the format
+        // without the `vexide-unstable` feature too
+        let pid_len = 1 + 7 * 8 + 12;
+        if cfg!(feature = "vexide-unstable") {
+            assert_eq!(len, GroupConfig::MAX_ENCODED_LEN);
+        } else {
+            assert_eq!(len, GroupConfig::MAX_ENCODED_LEN - pid_len + 1);
+        }
+    }
+
+    #[test]
+    fn short_buffers_report_the_length_needed() {
+        let mut full = [0; GroupConfig::MAX_ENCODED_LEN];
+        let len = tuned().to_bytes(&mut full).unwrap();
+
+        let mut short = [0; 10];
+        assert_eq!(
+            tuned().to_bytes(&mut short),
+            Err(super::EncodeError::BufferTooSmall {
+                needed: len,
+                available: 10
+            })
+        );
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let mut buffer = [0; GroupConfig::MAX_ENCODED_LEN];
+        let len = tuned().to_bytes(&mut buffer).unwrap();
+        buffer[..2].copy_from_slice(&7u16.to_le_bytes());
+        assert_eq!(
+            GroupConfig::from_bytes(&buffer[..len]),
+            Err(DecodeError::VersionMismatch {
+                found: 7,
+                expected: GroupConfig::FORMAT_VERSION
+            })
+        );
+    }
+
+    #[test]
+    fn truncated_and_corrupt_input_is_rejected() {
+        let mut buffer = [0; GroupConfig::MAX_ENCODED_LEN + 1];
+        let len = tuned().to_bytes(&mut buffer).unwrap();
+
+        for end in 0..len {
+            assert_eq!(
+                GroupConfig::from_bytes(&buffer[..end]),
+                Err(DecodeError::Truncated { len: end }),
+                "cut at {end}"
+            );
+        }
+        assert_eq!(
+            GroupConfig::from_bytes(&buffer[..=len]),
+            Err(DecodeError::TrailingBytes { count: 1 })
+        );
+        // The write error strategy is the first field after the version
+        buffer[2] = 3;
+        assert_eq!(
+            GroupConfig::from_bytes(&buffer[..len]),
+            Err(DecodeError::InvalidValue { offset: 2 })
+        );
+
+        // The external ratio has to be usable, as with scales
+        let mut broken = [0; GroupConfig::MAX_ENCODED_LEN];
+        for ratio in [0.0, -1.0, f64::NAN] {
+            let config = GroupConfig {
+                external_ratio: ratio,
+                ..GroupConfig::DEFAULT
+            };
+            let len = config.to_bytes(&mut broken).unwrap();
+            assert_eq!(
+                GroupConfig::from_bytes(&broken[..len]),
+                Err(DecodeError::InvalidValue { offset: 23 }),
+                "{ratio}"
+            );
+        }
+        // So do the position limits, which start after the flag at byte 46
+        for limits in [
+            (Angle::from_turns(2.0), Angle::ZERO),
+            (Angle::ZERO, Angle::from_radians(f64::INFINITY)),
+        ] {
+            let config = GroupConfig {
+                position_limits: Some(limits),
+                ..GroupConfig::DEFAULT
+            };
+            let len = config.to_bytes(&mut broken).unwrap();
+            let offset = if limits.0 > limits.1 { 47 } else { 55 };
+            assert_eq!(
+                GroupConfig::from_bytes(&broken[..len]),
+                Err(DecodeError::InvalidValue { offset }),
+                "{limits:?}"
+            );
+        }
+
+        // Flipping bits anywhere never panics
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        for _ in 0..2000 {
+            let mut corrupt = buffer;
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let index = (state % len as u64) as usize;
+            corrupt[index] ^= 1 << (state >> 61);
+            if let Ok(config) = GroupConfig::from_bytes(&corrupt[..len]) {
+                assert_eq!(round_trip(config).tick_interval, config.tick_interval);
+            }
+        }
+    }
+
+    #[test]
+    fn groups_round_trip_with_scales_and_presets() {
+        let (saved, bytes) = saved_lift();
+
+        // Presets are restored onto the ones added in code, by name
+        let mut lift = group(2);
+        lift.add_preset("score", Angle::ZERO, 100).unwrap();
+        lift.add_preset("park", Angle::from_degrees(10.0), 50)
+            .unwrap();
+        lift.add_preset("intake", Angle::from_degrees(45.0), 10)
+            .unwrap();
+        lift.apply_config_bytes(&bytes).unwrap();
+
+        assert_eq!(lift.current_config(), saved.current_config());
+        assert_eq!([lift.scale(0), lift.scale(1)], [1.0, 0.9]);
+        assert_eq!(
+            lift.presets(),
+            [
+                Preset {
+                    name: "score",
+                    position: Angle::from_turns(2.2),
+                    velocity: 60
+                },
+                Preset {
+                    name: "park",
+                    position: Angle::from_degrees(10.0),
+                    velocity: 50
+                },
+                Preset {
+                    name: "intake",
+                    position: Angle::ZERO,
+                    velocity: 100
+                },
+            ]
+        );
+
+        let mut short = [0; 16];
+        assert!(matches!(
+            saved.config_to_bytes(&mut short),
+            Err(super::EncodeError::BufferTooSmall { needed, available: 16 })
+                if needed == bytes.len()
+        ));
+    }
+
+    #[test]
+    fn groups_only_take_settings_that_fit() {
+        let (_, bytes) = saved_lift();
+
+        let mut bigger = group(3);
+        assert_eq!(
+            bigger.apply_config_bytes(&bytes).unwrap_err().errors,
+            [ConfigureError::Decode {
+                source: DecodeError::MotorCount {
+                    found: 2,
+                    expected: 3
+                }
+            }]
+        );
+
+        // Presets the code never added can't be restored, but everything
+        // else still is
+        let mut lift = group(2);
+        lift.add_preset("score", Angle::ZERO, 100).unwrap();
+        assert_eq!(
+            lift.apply_config_bytes(&bytes).unwrap_err().errors,
+            [ConfigureError::Preset {
+                source: PresetError::Unknown
+            }]
+        );
+        assert_eq!(lift.scale(1), 0.9);
+        assert_eq!(lift.presets()[0].position, Angle::from_turns(2.2));
+
+        // Nothing is applied from input that can't be decoded
+        let mut lift = group(2);
+        for end in 0..bytes.len() {
+            let error = lift.apply_config_bytes(&bytes[..end]).unwrap_err();
+            assert!(
+                matches!(error.errors[..], [ConfigureError::Decode { .. }]),
+                "cut at {end}"
+            );
+        }
+        assert_eq!(lift.current_config(), GroupConfig::DEFAULT);
+        assert_eq!(lift.scale(1), 1.0);
+    }
+}
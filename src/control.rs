@@ -0,0 +1,994 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::{
+    smart::{
+        PortError,
+        motor::{BrakeMode, Motor, MotorControl},
+    },
+    time::sleep,
+};
+
+use crate::{MotorGroup, MotorGroupError, WriteErrorStrategy, safety, tick::tick_loop, velocity};
+
+/// Error returned by [`MotorGroup::transition`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The start and end targets can't be interpolated between, because they
+    /// aren't both voltages or both velocities.
+    IncompatibleTargets {
+        /// The start target of the transition.
+        from: MotorControl,
+        /// The end target of the transition.
+        to: MotorControl,
+    },
+}
+
+impl From<PortError> for TransitionError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for TransitionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::IncompatibleTargets { from, to } => {
+                write!(f, "cannot transition between {from:?} and {to:?}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for TransitionError {}
+
+/// Returns the target `fraction` of the way from `from` to `to`, or `None` if
+/// the two targets can't be interpolated between.
+///
+/// `fraction` is clamped to `0.0..=1.0`. Velocities are rounded to the nearest
+/// RPM.
+pub(crate) fn interpolate_control(
+    from: MotorControl,
+    to: MotorControl,
+    fraction: f64,
+) -> Option<MotorControl> {
+    let fraction = fraction.clamp(0.0, 1.0);
+    match (from, to) {
+        (MotorControl::Voltage(from), MotorControl::Voltage(to)) => {
+            Some(MotorControl::Voltage(from + (to - from) * fraction))
+        }
+        (MotorControl::Velocity(from), MotorControl::Velocity(to)) => {
+            let rpm = from as f64 + (to - from) as f64 * fraction;
+            Some(MotorControl::Velocity(velocity::round_rpm(rpm)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns how far through a ramp of `duration` the time `elapsed` is, from
+/// `0.0` at the start to `1.0` at the end. A ramp of zero duration is over
+/// straight away.
+pub(crate) fn ramp_progress(elapsed: Duration, duration: Duration) -> f64 {
+    if duration.is_zero() {
+        1.0
+    } else {
+        elapsed.as_secs_f64() / duration.as_secs_f64()
+    }
+}
+
+/// Gives a motor group its end command when dropped, so that a ramp ends in a
+/// known state even if its future is cancelled midway.
+pub(crate) struct EndCommandGuard<'a, M: AsRef<[Motor]> + AsMut<[Motor]>> {
+    pub(crate) group: &'a mut MotorGroup<M>,
+    pub(crate) end: Option<MotorControl>,
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> EndCommandGuard<'_, M> {
+    /// Gives the group its end command now, rather than when dropped.
+    pub(crate) fn finish(mut self) -> Result<(), MotorGroupError> {
+        match self.end.take() {
+            Some(end) => self.group.set_target(end),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Drop for EndCommandGuard<'_, M> {
+    fn drop(&mut self) {
+        if let Some(end) = self.end.take() {
+            safety::best_effort_stop(self.group, end, false);
+        }
+    }
+}
+
+/// Returns the voltage a proportional velocity controller outputs:
+/// `kp * (target_rpm - measured_rpm)`, limited to `±max_volts`.
+pub(crate) fn proportional_voltage(
+    target_rpm: f64,
+    measured_rpm: f64,
+    kp: f64,
+    max_volts: f64,
+) -> f64 {
+    feedforward_proportional_voltage(target_rpm, measured_rpm, 0.0, kp, max_volts)
+}
+
+/// Returns the voltage a feedforward plus proportional velocity controller
+/// outputs: `kv * target_rpm + kp * (target_rpm - measured_rpm)`, limited to
+/// `±max_volts`.
+pub(crate) fn feedforward_proportional_voltage(
+    target_rpm: f64,
+    measured_rpm: f64,
+    kv: f64,
+    kp: f64,
+    max_volts: f64,
+) -> f64 {
+    let limit = max_volts.abs();
+    (kv * target_rpm + kp * (target_rpm - measured_rpm))
+        .max(-limit)
+        .min(limit)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Smoothly transitions the motor group from one target to another over
+    /// `duration`.
+    ///
+    /// The commanded value is linearly interpolated and written once every
+    /// tick (see [`MotorGroup::tick_interval`], 5ms by default). The final
+    /// write is always exactly `to`. Each tick writes once to every motor,
+    /// the same way [`MotorGroup::set_target`] does, so a motor that missed
+    /// the last [`MotorGroup::set_direction`] is sent the direction again
+    /// before its target.
+    ///
+    /// Only the following combinations are supported:
+    ///
+    /// - [`MotorControl::Voltage`] to [`MotorControl::Voltage`]
+    /// - [`MotorControl::Velocity`] to [`MotorControl::Velocity`] (rounded to
+    ///   the nearest RPM)
+    ///
+    /// This future completes after `duration` has elapsed.
+    ///
+    /// # Errors
+    ///
+    /// - A [`TransitionError::IncompatibleTargets`] error is returned before
+    ///   anything is written if the targets aren't one of the supported
+    ///   combinations.
+    /// - A [`TransitionError::Port`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port. Under
+    ///   [`WriteErrorStrategy::Stop`] the transition is aborted on the first
+    ///   failed write; otherwise it runs to completion and the errors of the
+    ///   final write are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Ease from 2V up to 10V over half a second
+    ///     motor_group
+    ///         .transition(
+    ///             MotorControl::Voltage(2.0),
+    ///             MotorControl::Voltage(10.0),
+    ///             Duration::from_millis(500),
+    ///         )
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn transition(
+        &mut self,
+        from: MotorControl,
+        to: MotorControl,
+        duration: Duration,
+    ) -> Result<(), MotorGroupError<TransitionError>> {
+        if interpolate_control(from, to, 0.0).is_none() {
+            return Err(MotorGroupError::new(alloc::vec![
+                TransitionError::IncompatibleTargets { from, to }
+            ]));
+        }
+
+        tick_loop(self.config.tick_interval, Some(duration), |elapsed| {
+            let fraction = ramp_progress(elapsed, duration);
+            let target = self.limited_target(interpolate_control(from, to, fraction).unwrap());
+            self.last_command = Some(target);
+            let targets = self.scaled_targets(target);
+            let (result, written) = self.write_targets(&targets);
+            self.track_fallback(target, &written);
+            let result = result.map_err(|error| {
+                MotorGroupError::new(
+                    error
+                        .errors
+                        .into_iter()
+                        .map(TransitionError::from)
+                        .collect(),
+                )
+            });
+
+            let aborted =
+                result.is_err() && self.config.write_error_strategy == WriteErrorStrategy::Stop;
+            if fraction >= 1.0 || aborted {
+                ControlFlow::Break(result)
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await
+    }
+
+    /// Runs one step of a proportional velocity controller in software,
+    /// driving the group by voltage towards `target_rpm`.
+    ///
+    /// This reads the group's average velocity (see [`MotorGroup::velocity`])
+    /// and sets the voltage to `kp * (target_rpm - velocity)`, limited to
+    /// `±max_volts`. Unlike [`MotorGroup::set_velocity`], the motors' internal
+    /// velocity PID isn't involved, so the gain is entirely up to the caller.
+    ///
+    /// Each call is a single step: the voltage is only corrected when this is
+    /// called again. Call it once every iteration of a control loop, ideally
+    /// every [`Motor::WRITE_INTERVAL`], for as long as the group should hold
+    /// the velocity. A group that stops being stepped keeps its last voltage.
+    ///
+    /// A proportional controller alone settles short of the target under
+    /// load, by however much error it takes for `kp` to produce the voltage
+    /// the load needs.
+    ///
+    /// If only some motors could be read, their average is used. If none
+    /// could, nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error, containing the errors of the velocity read
+    ///   followed by those of the voltage write.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         _ = flywheel.approach_velocity(450.0, 0.05, 12.0);
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn approach_velocity(
+        &mut self,
+        target_rpm: f64,
+        kp: f64,
+        max_volts: f64,
+    ) -> Result<(), MotorGroupError> {
+        self.step_velocity_control(|velocity| {
+            proportional_voltage(target_rpm, velocity, kp, max_volts)
+        })
+    }
+
+    /// Runs one step of a feedforward plus proportional velocity controller
+    /// in software, driving the group by voltage towards `target_rpm`.
+    ///
+    /// This is the control law most flywheels actually use. It reads the
+    /// group's average velocity (see [`MotorGroup::velocity`]) and sets the
+    /// voltage to `kv * target_rpm + kp * (target_rpm - velocity)`, limited to
+    /// `±max_volts`:
+    ///
+    /// - `kv` is the feedforward gain in volts per RPM: the voltage it takes
+    ///   to hold each RPM of speed with no error. A good starting point is the
+    ///   motor's maximum voltage divided by its free speed, such as
+    ///   `12.0 / 600.0` for a blue cartridge.
+    /// - `kp` is the proportional gain in volts per RPM of error, which
+    ///   corrects for load and for `kv` being slightly off.
+    ///
+    /// With a well tuned `kv`, the feedforward does most of the work, so the
+    /// group settles much closer to the target under load than with
+    /// [`MotorGroup::approach_velocity`] alone.
+    ///
+    /// Each call is a single step: the voltage is only corrected when this is
+    /// called again. Call it once every iteration of a control loop, ideally
+    /// every [`Motor::WRITE_INTERVAL`], for as long as the group should hold
+    /// the velocity. A group that stops being stepped keeps its last voltage.
+    ///
+    /// If only some motors could be read, their average is used. If none
+    /// could, nothing is written.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error, containing the errors of the velocity read
+    ///   followed by those of the voltage write.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         _ = flywheel.set_velocity_fp(450.0, 12.0 / 600.0, 0.02, 12.0);
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_velocity_fp(
+        &mut self,
+        target_rpm: f64,
+        kv: f64,
+        kp: f64,
+        max_volts: f64,
+    ) -> Result<(), MotorGroupError> {
+        self.step_velocity_control(|velocity| {
+            feedforward_proportional_voltage(target_rpm, velocity, kv, kp, max_volts)
+        })
+    }
+
+    /// Reads the group's average velocity and sets the voltage `control`
+    /// returns for it, for one step of a software velocity controller.
+    fn step_velocity_control(
+        &mut self,
+        control: impl FnOnce(f64) -> f64,
+    ) -> Result<(), MotorGroupError> {
+        let (velocity, mut errors) = match self.velocity() {
+            Ok(velocity) => (Some(velocity), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        if let Some(velocity) = velocity
+            && let Err(error) = self.set_voltage(control(velocity))
+        {
+            errors.extend(error.errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Returns the voltage a ramp down to zero should start from.
+    ///
+    /// This is the last commanded voltage if the group was last given a
+    /// voltage, or otherwise the measured output voltage (averaged over the
+    /// motors that could be read). If neither is known, the ramp starts at
+    /// zero.
+    pub(crate) fn ramp_start_voltage(&self) -> f64 {
+        match self.last_command {
+            Some(MotorControl::Voltage(volts)) => volts,
+            _ => match self.voltage() {
+                Ok(volts) => volts,
+                Err(error) => error.result.unwrap_or(0.0),
+            },
+        }
+    }
+
+    /// Ramps the motor group's voltage down to zero over `ramp`, then gives it
+    /// the `end` command.
+    ///
+    /// Cutting a heavy mechanism from full power to zero at once makes it
+    /// drop or lurch before a brake catches it. Instead, the voltage is
+    /// linearly interpolated down to zero and written once every tick (see
+    /// [`MotorGroup::tick_interval`], 5ms by default), like
+    /// [`MotorGroup::transition`]. The ramp starts from the last commanded
+    /// voltage, or from the measured voltage if the group wasn't last given a
+    /// voltage. `end` is usually a brake, such as
+    /// [`BrakeMode::Hold`] to keep a lift in place.
+    ///
+    /// `end` is always applied, even if writes fail during the ramp. Under
+    /// [`WriteErrorStrategy::Stop`], the first failed write cuts the ramp
+    /// short and `end` is applied straight away. If this future is dropped
+    /// before it completes, `end` is applied when it's dropped, so a
+    /// cancelled stop still stops.
+    ///
+    /// This future completes after `ramp` has elapsed. Each tick of the ramp
+    /// writes once to every motor.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port, containing every distinct
+    ///   error of the ramp's writes in the order they first happened,
+    ///   followed by the errors of the `end` command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     lift.set_voltage(8.0).unwrap();
+    ///     sleep(Duration::from_secs(1)).await;
+    ///
+    ///     // Ease the lift down to a stop instead of dropping it
+    ///     lift.soft_stop(
+    ///         Duration::from_millis(250),
+    ///         MotorControl::Brake(BrakeMode::Hold),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// }
+    /// ```
+    pub async fn soft_stop(
+        &mut self,
+        ramp: Duration,
+        end: MotorControl,
+    ) -> Result<(), MotorGroupError> {
+        let start = MotorControl::Voltage(self.ramp_start_voltage());
+        let interval = self.config.tick_interval;
+        let guard = EndCommandGuard {
+            group: self,
+            end: Some(end),
+        };
+
+        let mut errors: Vec<PortError> = Vec::new();
+        tick_loop(interval, Some(ramp), |elapsed| {
+            let fraction = ramp_progress(elapsed, ramp);
+            let target = interpolate_control(start, MotorControl::Voltage(0.0), fraction).unwrap();
+            let result = guard.group.set_target(target);
+
+            let aborted = result.is_err()
+                && guard.group.config.write_error_strategy == WriteErrorStrategy::Stop;
+            if let Err(error) = result {
+                for error in error.errors {
+                    if !errors.contains(&error) {
+                        errors.push(error);
+                    }
+                }
+            }
+            if fraction >= 1.0 || aborted {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .await;
+        if let Err(error) = guard.finish() {
+            errors.extend(error.errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Gracefully stops the motor group.
+    ///
+    /// This is [`MotorGroup::soft_stop`] ending in `final_brake`: the output
+    /// voltage is ramped down to zero over `ramp_duration`, then
+    /// `final_brake` is applied, leaving the group in a known state. The
+    /// brake is always applied, even if writes fail during the ramp or this
+    /// future is dropped before it completes.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing the errors of the
+    ///   ramp and of the brake if a motor device is not currently connected
+    ///   to the Smart Port. See [`MotorGroup::soft_stop`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     motor_group.set_voltage(8.0).unwrap();
+    ///     sleep(Duration::from_secs(2)).await;
+    ///
+    ///     // End of the match
+    ///     motor_group
+    ///         .shutdown(Duration::from_millis(300), BrakeMode::Hold)
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn shutdown(
+        &mut self,
+        ramp_duration: Duration,
+        final_brake: BrakeMode,
+    ) -> Result<(), MotorGroupError> {
+        self.soft_stop(ramp_duration, MotorControl::Brake(final_brake))
+            .await
+    }
+
+    /// Brakes the motor group gently, by ramping its voltage down to zero
+    /// over `ramp_duration` before applying `final_mode`.
+    ///
+    /// An instant [`MotorGroup::brake`] with [`BrakeMode::Hold`] at high
+    /// speed jolts the mechanism and stresses its gearbox. This ramps from
+    /// the last commanded voltage instead (see [`MotorGroup::soft_stop`]).
+    /// It does the same thing as [`MotorGroup::shutdown`], under a name for
+    /// stops in the middle of a routine rather than at the end of one.
+    ///
+    /// This future completes after `ramp_duration` has elapsed, and the group
+    /// can't be given other commands until then, so budget for the ramp in
+    /// autonomous routines. `final_mode` is always applied, even if writes
+    /// fail during the ramp or this future is dropped before it completes.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing the errors of the
+    ///   ramp and of the brake if a motor device is not currently connected
+    ///   to the Smart Port. See [`MotorGroup::soft_stop`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut arm = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     arm.set_voltage(12.0).unwrap();
+    ///     sleep(Duration::from_millis(400)).await;
+    ///
+    ///     // Settle the arm over 150ms, then hold it there
+    ///     _ = arm
+    ///         .brake_ramped(Duration::from_millis(150), BrakeMode::Hold)
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn brake_ramped(
+        &mut self,
+        ramp_duration: Duration,
+        final_mode: BrakeMode,
+    ) -> Result<(), MotorGroupError> {
+        self.shutdown(ramp_duration, final_mode).await
+    }
+
+    /// Applies `voltage` for `duration`, then lets the motors coast.
+    ///
+    /// This is the usual way to fire a flicker or puncher: a short burst of
+    /// power followed by a free spin, so the mechanism can reset on its own.
+    /// The future completes after `duration` has elapsed, and the group can't
+    /// be given other commands until then. It sleeps through the whole
+    /// `duration` at once, writing to every motor only before and after.
+    ///
+    /// The motors are always told to coast at the end, even if the voltage
+    /// write failed, so they are never left running.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing the errors of the
+    ///   voltage write followed by those of the coast if a motor device is not
+    ///   currently connected to the Smart Port.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flicker = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     flicker
+    ///         .impulse(12.0, Duration::from_millis(150))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn impulse(
+        &mut self,
+        voltage: f64,
+        duration: Duration,
+    ) -> Result<(), MotorGroupError> {
+        let mut errors = match self.set_voltage(voltage) {
+            Ok(_) => Vec::new(),
+            Err(error) => error.errors,
+        };
+        sleep(duration).await;
+        if let Err(error) = self.brake(BrakeMode::Coast) {
+            errors.extend(error.errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Waker},
+        time::Duration,
+    };
+    use std::time::Instant;
+
+    use vexide::{
+        prelude::*,
+        smart::{
+            PortError,
+            motor::{BrakeMode, MotorControl},
+        },
+    };
+
+    use super::{
+        TransitionError, feedforward_proportional_voltage, interpolate_control,
+        proportional_voltage, ramp_progress,
+    };
+    use crate::{
+        MotorGroup, WriteErrorStrategy,
+        tests::{mock_motors, two_motor_group},
+    };
+
+    const HOLD: MotorControl = MotorControl::Brake(BrakeMode::Hold);
+
+    #[test]
+    fn voltage_transition_interpolates_linearly() {
+        let from = MotorControl::Voltage(2.0);
+        let to = MotorControl::Voltage(10.0);
+        assert_eq!(
+            interpolate_control(from, to, 0.0),
+            Some(MotorControl::Voltage(2.0))
+        );
+        assert_eq!(
+            interpolate_control(from, to, 0.25),
+            Some(MotorControl::Voltage(4.0))
+        );
+        assert_eq!(
+            interpolate_control(from, to, 1.0),
+            Some(MotorControl::Voltage(10.0))
+        );
+        // Overshooting the duration never overshoots the target
+        assert_eq!(
+            interpolate_control(from, to, 1.5),
+            Some(MotorControl::Voltage(10.0))
+        );
+        assert_eq!(
+            interpolate_control(MotorControl::Velocity(0), MotorControl::Velocity(-101), 0.5),
+            Some(MotorControl::Velocity(-51))
+        );
+    }
+
+    #[test]
+    fn mixed_transition_is_rejected() {
+        let from = MotorControl::Voltage(2.0);
+        let to = MotorControl::Brake(BrakeMode::Hold);
+        assert_eq!(interpolate_control(from, to, 0.5), None);
+
+        let group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let error = vexide::runtime::block_on(async move {
+            let mut group = group;
+            group
+                .transition(from, to, Duration::from_millis(20))
+                .await
+                .unwrap_err()
+        });
+        assert_eq!(
+            error.errors,
+            vec![TransitionError::IncompatibleTargets { from, to }]
+        );
+    }
+
+    #[test]
+    fn shutdown_ramps_then_brakes() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        _ = group.set_voltage(6.0);
+        // The ramp starts from the last commanded voltage, not a reading
+        assert_eq!(group.ramp_start_voltage(), 6.0);
+
+        let start = Instant::now();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .shutdown(Duration::from_millis(20), BrakeMode::Hold)
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        // The final ramp write and the brake both fail on the mock motor
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Hold))
+        );
+    }
+
+    #[test]
+    fn brake_ramped_ramps_then_brakes() {
+        let mut group = two_motor_group();
+        _ = group.set_voltage(-9.0);
+        let generation = group.command_generation();
+
+        let start = Instant::now();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .brake_ramped(Duration::from_millis(20), BrakeMode::Coast)
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        // Several ramp writes, then the brake
+        assert!(group.command_generation() - generation > 2);
+        assert_eq!(error.errors.len(), 4);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Coast))
+        );
+    }
+
+    #[test]
+    fn ramp_progress_runs_from_zero_to_one() {
+        let ramp = Duration::from_millis(200);
+        assert_eq!(ramp_progress(Duration::ZERO, ramp), 0.0);
+        assert_eq!(ramp_progress(Duration::from_millis(50), ramp), 0.25);
+        assert_eq!(ramp_progress(ramp, ramp), 1.0);
+        assert_eq!(ramp_progress(Duration::ZERO, Duration::ZERO), 1.0);
+    }
+
+    #[test]
+    fn soft_stop_reports_each_ramp_error_once() {
+        let mut group = two_motor_group();
+        _ = group.set_voltage(6.0);
+        let generation = group.command_generation();
+
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .soft_stop(Duration::from_millis(20), HOLD)
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        // Every ramp write fails on both mock motors, but each error is only
+        // reported once, followed by the end command's
+        assert!(group.command_generation() - generation > 2);
+        let ramp_errors = [
+            PortError::Disconnected { port: 1 },
+            PortError::Disconnected { port: 2 },
+        ];
+        assert_eq!(error.errors, [ramp_errors, ramp_errors].concat());
+        assert_eq!(group.last_command, Some(HOLD));
+    }
+
+    #[test]
+    fn soft_stop_applies_the_end_command_when_cancelled() {
+        let mut group = two_motor_group();
+        group.tick_interval(Duration::ZERO);
+        _ = group.set_voltage(8.0);
+        let generation = group.command_generation();
+
+        {
+            let mut stop = pin!(group.soft_stop(Duration::from_secs(10), HOLD));
+            let mut cx = Context::from_waker(Waker::noop());
+            assert!(stop.as_mut().poll(&mut cx).is_pending());
+        }
+        // The first ramp write, then the end command on drop
+        assert_eq!(group.command_generation() - generation, 2);
+        assert_eq!(group.last_command, Some(HOLD));
+    }
+
+    #[test]
+    fn soft_stop_skips_the_ramp_after_a_failed_write_under_stop() {
+        let mut group = two_motor_group();
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        _ = group.set_voltage(8.0);
+        let generation = group.command_generation();
+
+        let start = Instant::now();
+        let group = vexide::runtime::block_on(async move {
+            let mut group = group;
+            _ = group.soft_stop(Duration::from_secs(10), HOLD).await;
+            group
+        });
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(group.command_generation() - generation, 2);
+        assert_eq!(group.last_command, Some(HOLD));
+    }
+
+    #[test]
+    fn impulse_applies_voltage_then_coasts() {
+        let group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let generation = group.command_generation();
+
+        let start = Instant::now();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .impulse(12.0, Duration::from_millis(20))
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        // Exactly two writes were made: the voltage, then the coast
+        assert_eq!(group.command_generation() - generation, 2);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Coast))
+        );
+        // Both writes fail on the mock motor
+        assert_eq!(error.errors.len(), 2);
+    }
+
+    #[test]
+    fn ramp_start_without_voltage_command() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        // Nothing commanded and nothing readable
+        assert_eq!(group.ramp_start_voltage(), 0.0);
+        _ = group.set_velocity(100);
+        assert_eq!(group.ramp_start_voltage(), 0.0);
+    }
+
+    #[test]
+    fn voltage_transition_runs_for_duration() {
+        let group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let start = Instant::now();
+        let error = vexide::runtime::block_on(async move {
+            let mut group = group;
+            group
+                .transition(
+                    MotorControl::Voltage(0.0),
+                    MotorControl::Voltage(6.0),
+                    Duration::from_millis(20),
+                )
+                .await
+                .unwrap_err()
+        });
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        // The mock motor is disconnected, so only the final write's error is
+        // reported
+        assert_eq!(error.errors.len(), 1);
+        assert!(matches!(error.errors[0], TransitionError::Port { .. }));
+    }
+
+    #[test]
+    fn transition_writes_like_set_target() {
+        let mut group = two_motor_group();
+        #[cfg(feature = "events")]
+        let mut events = group.events();
+        // The mock motors miss the direction write
+        assert!(group.set_direction(Direction::Reverse).is_err());
+
+        let group = vexide::runtime::block_on(async move {
+            let mut group = group;
+            _ = group
+                .transition(
+                    MotorControl::Voltage(0.0),
+                    MotorControl::Voltage(6.0),
+                    Duration::from_millis(20),
+                )
+                .await;
+            group
+        });
+        // Every step retried the direction, which still didn't get through,
+        // so it's still owed before the next target
+        assert!(
+            group
+                .meta
+                .iter()
+                .all(|meta| meta.pending_direction() == Some(Direction::Reverse))
+        );
+        assert_eq!(group.last_command, Some(MotorControl::Voltage(6.0)));
+        // The steps are tracked like any other write
+        #[cfg(feature = "events")]
+        assert_eq!(
+            core::iter::from_fn(|| events.try_recv()).collect::<Vec<_>>(),
+            [
+                crate::GroupEvent::MotorDisconnected(0),
+                crate::GroupEvent::MotorDisconnected(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn velocity_error_is_scaled_and_limited() {
+        // 100 RPM short at a gain of 0.05 is 5V
+        assert_eq!(proportional_voltage(400.0, 300.0, 0.05, 12.0), 5.0);
+        // Overshooting drives the other way
+        assert_eq!(proportional_voltage(400.0, 440.0, 0.05, 12.0), -2.0);
+        assert_eq!(proportional_voltage(400.0, 400.0, 0.05, 12.0), 0.0);
+        // Large errors are limited either way, whatever the sign of the limit
+        assert_eq!(proportional_voltage(600.0, 0.0, 0.05, 12.0), 12.0);
+        assert_eq!(proportional_voltage(-600.0, 0.0, 0.05, 8.0), -8.0);
+        assert_eq!(proportional_voltage(-600.0, 0.0, 0.05, -8.0), -8.0);
+    }
+
+    #[test]
+    fn feedforward_and_error_are_combined() {
+        // 400 RPM at 0.02V/RPM is 8V, plus 100 RPM short at 0.01V/RPM is 1V
+        assert_eq!(
+            feedforward_proportional_voltage(400.0, 300.0, 0.02, 0.01, 12.0),
+            9.0
+        );
+        // At the target, only the feedforward is left
+        assert_eq!(
+            feedforward_proportional_voltage(400.0, 400.0, 0.02, 0.01, 12.0),
+            8.0
+        );
+        // Overshooting takes away from the feedforward
+        assert_eq!(
+            feedforward_proportional_voltage(400.0, 500.0, 0.02, 0.01, 12.0),
+            7.0
+        );
+        // Reversed targets are reversed too
+        assert_eq!(
+            feedforward_proportional_voltage(-400.0, -300.0, 0.02, 0.01, 12.0),
+            -9.0
+        );
+        // The sum is limited, whatever the sign of the limit
+        assert_eq!(
+            feedforward_proportional_voltage(600.0, 0.0, 0.02, 0.01, 12.0),
+            12.0
+        );
+        assert_eq!(
+            feedforward_proportional_voltage(-600.0, 0.0, 0.02, 0.01, -8.0),
+            -8.0
+        );
+    }
+
+    #[test]
+    fn approach_velocity_needs_a_reading() {
+        let mut group = two_motor_group();
+        // The mock motors can't be read, so nothing is written
+        let error = group.approach_velocity(200.0, 0.05, 12.0).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(group.command_generation(), 0);
+        assert_eq!(group.last_command, None);
+    }
+
+    #[test]
+    fn set_velocity_fp_needs_a_reading() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Blue));
+        let error = group.set_velocity_fp(450.0, 0.02, 0.01, 12.0).unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        assert_eq!(group.command_generation(), 0);
+        assert_eq!(group.last_command, None);
+    }
+}
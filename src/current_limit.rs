@@ -0,0 +1,454 @@
+use alloc::vec::Vec;
+use vexide::smart::{
+    PortError,
+    motor::{Motor, MotorType},
+};
+
+use crate::{GetterResult, GroupConfig, MotorGroup, MotorGroupError, WriteKind, readings};
+
+/// The hardware current maximums used by a motor group, per motor type.
+///
+/// VEXos limits the stall current of an 11W (V5) Smart Motor to 2.5A and the
+/// current of a 5.5W (EXP) Smart Motor to roughly half of that. Limits
+/// requested above these values can't actually be reached by the hardware.
+///
+/// The defaults are available as [`MaxCurrentTable::DEFAULT`]. If future
+/// hardware or firmware changes these values, a custom table can be given to
+/// a group with [`MotorGroup::set_max_current_table`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxCurrentTable {
+    /// The maximum current of an 11W (V5) Smart Motor in Amperes.
+    pub v5: f64,
+    /// The maximum current of a 5.5W (EXP) Smart Motor in Amperes.
+    pub exp: f64,
+}
+
+impl MaxCurrentTable {
+    /// The maximum current of an 11W (V5) Smart Motor in Amperes.
+    pub const V5_MAX_CURRENT: f64 = 2.5;
+    /// The maximum current of a 5.5W (EXP) Smart Motor in Amperes.
+    pub const EXP_MAX_CURRENT: f64 = 1.25;
+
+    /// The hardware maximums of the current V5 and EXP Smart Motors.
+    pub const DEFAULT: Self = Self {
+        v5: Self::V5_MAX_CURRENT,
+        exp: Self::EXP_MAX_CURRENT,
+    };
+
+    /// Returns the maximum current for a motor of the given type.
+    pub const fn max_current(&self, motor_type: MotorType) -> f64 {
+        match motor_type {
+            MotorType::V5 => self.v5,
+            MotorType::Exp => self.exp,
+        }
+    }
+}
+
+impl Default for MaxCurrentTable {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// What to do when a requested current limit exceeds a motor's hardware
+/// maximum.
+///
+/// See [`MaxCurrentTable`] for the maximums used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CurrentLimitPolicy {
+    /// Clamp the requested limit to each motor's hardware maximum.
+    ///
+    /// This is the default policy.
+    #[default]
+    Clamp,
+    /// Refuse to write the limit to any motor if it exceeds the hardware
+    /// maximum of at least one motor in the group.
+    Error,
+}
+
+/// Error returned by [`MotorGroup::set_current_limit`] and
+/// [`MotorGroup::set_total_current_limit`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetCurrentLimitError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The requested limit exceeds the hardware maximum and the group's
+    /// policy is [`CurrentLimitPolicy::Error`].
+    ExceedsMaximum {
+        /// The index of the offending motor, or `None` if the limit was
+        /// requested for the whole group.
+        index: Option<usize>,
+        /// The requested limit in Amperes.
+        requested: f64,
+        /// The hardware maximum in Amperes.
+        maximum: f64,
+    },
+}
+
+impl From<PortError> for SetCurrentLimitError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl From<MotorGroupError<SetCurrentLimitError>> for SetCurrentLimitError {
+    fn from(error: MotorGroupError<SetCurrentLimitError>) -> Self {
+        error.errors.into_iter().next().unwrap()
+    }
+}
+
+impl core::fmt::Display for SetCurrentLimitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::ExceedsMaximum {
+                index: Some(index),
+                requested,
+                maximum,
+            } => write!(
+                f,
+                "current limit of {requested}A exceeds the {maximum}A maximum of motor {index}"
+            ),
+            Self::ExceedsMaximum {
+                index: None,
+                requested,
+                maximum,
+            } => write!(
+                f,
+                "current limit of {requested}A exceeds the group's {maximum}A maximum"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for SetCurrentLimitError {}
+
+/// Splits a total current budget between motors proportionally to their
+/// hardware maximums.
+///
+/// A budget equal to the sum of `maximums` gives every motor its maximum.
+pub(crate) fn distribute_current_budget(total: f64, maximums: &[f64]) -> Vec<f64> {
+    let sum: f64 = maximums.iter().sum();
+    maximums
+        .iter()
+        .map(|maximum| total * maximum / sum)
+        .collect()
+}
+
+/// Returns the current limit each motor has been configured with, given each
+/// motor's hardware maximum.
+///
+/// This is the per-motor or total limit configured on the group if there is
+/// one, and otherwise the hardware maximum.
+pub(crate) fn configured_current_limits(config: &GroupConfig, maximums: &[f64]) -> Vec<f64> {
+    match (config.current_limit, config.total_current_limit) {
+        (Some(limit), _) => maximums.iter().map(|max| limit.min(*max)).collect(),
+        (None, Some(total)) => distribute_current_budget(total, maximums),
+        (None, None) => maximums.to_vec(),
+    }
+}
+
+/// The fraction of the maximum torque at which [`MotorGroup::apply_torque_limit`]
+/// starts lowering the current limit.
+const TORQUE_LIMIT_KNEE: f64 = 0.8;
+
+/// Returns the current limit a motor should have at the given torque.
+///
+/// Below [`TORQUE_LIMIT_KNEE`] of `max_torque` this is the motor's configured
+/// `limit`. From there it falls linearly to `floor`, which it reaches at
+/// `max_torque`.
+pub(crate) fn torque_limited_current(torque: f64, max_torque: f64, floor: f64, limit: f64) -> f64 {
+    let knee = max_torque * TORQUE_LIMIT_KNEE;
+    let scale = if torque.abs() <= knee {
+        1.0
+    } else {
+        ((max_torque - torque.abs()) / (max_torque - knee)).clamp(0.0, 1.0)
+    };
+    floor + (limit - floor).max(0.0) * scale
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets the table of hardware maximum currents used by the group.
+    ///
+    /// This only needs to be changed if the defaults in
+    /// [`MaxCurrentTable::DEFAULT`] don't match your hardware.
+    pub fn set_max_current_table(&mut self, table: MaxCurrentTable) -> &mut Self {
+        self.config.max_current_table = table;
+        self
+    }
+
+    /// Sets what happens when a requested current limit exceeds a motor's
+    /// hardware maximum.
+    ///
+    /// See [`CurrentLimitPolicy`] for the available policies.
+    pub fn set_current_limit_policy(&mut self, policy: CurrentLimitPolicy) -> &mut Self {
+        self.config.current_limit_policy = policy;
+        self
+    }
+
+    /// Returns the total hardware maximum current of the motor group in
+    /// Amperes.
+    ///
+    /// This is the sum of each motor's maximum, based off of its
+    /// [motor type](Motor::motor_type) and the group's [`MaxCurrentTable`]. No
+    /// hardware is read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new_exp(peripherals.port_2, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // 2.5A + 1.25A
+    ///     assert_eq!(motor_group.max_current(), 3.75);
+    /// }
+    /// ```
+    pub fn max_current(&self) -> f64 {
+        self.max_current_per_motor().iter().sum()
+    }
+
+    /// Returns the hardware maximum current of each motor in the group in
+    /// Amperes.
+    ///
+    /// See [`MotorGroup::max_current`].
+    pub fn max_current_per_motor(&self) -> Vec<f64> {
+        self.motors
+            .as_ref()
+            .iter()
+            .map(|motor| {
+                self.config
+                    .max_current_table
+                    .max_current(motor.motor_type())
+            })
+            .collect()
+    }
+
+    /// Sets a current limit for the whole group in Amperes, split between the
+    /// motors proportionally to their hardware maximums.
+    ///
+    /// For example, a total of 3A on a group of two V5 motors sets each
+    /// motor's limit to 1.5A, while on a group of one V5 motor and one EXP
+    /// motor it sets 2A and 1A respectively.
+    ///
+    /// If the total exceeds [`MotorGroup::max_current`], the group's
+    /// [`CurrentLimitPolicy`] decides whether the budget is clamped or
+    /// refused.
+    ///
+    /// # Errors
+    ///
+    /// - A [`SetCurrentLimitError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
+    /// - A [`SetCurrentLimitError::ExceedsMaximum`] error is returned if the total exceeds the group's maximum under [`CurrentLimitPolicy::Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     motor_group.set_total_current_limit(3.0).unwrap();
+    /// }
+    /// ```
+    pub fn set_total_current_limit(
+        &mut self,
+        total: f64,
+    ) -> Result<(), MotorGroupError<SetCurrentLimitError>> {
+        let maximums = self.max_current_per_motor();
+        let maximum: f64 = maximums.iter().sum();
+        let total = if total > maximum {
+            match self.config.current_limit_policy {
+                CurrentLimitPolicy::Clamp => maximum,
+                CurrentLimitPolicy::Error => {
+                    return Err(MotorGroupError::new(alloc::vec![
+                        SetCurrentLimitError::ExceedsMaximum {
+                            index: None,
+                            requested: total,
+                            maximum,
+                        }
+                    ]));
+                }
+            }
+        } else {
+            total
+        };
+        self.config.total_current_limit = Some(total);
+        self.config.current_limit = None;
+        let limits = distribute_current_budget(total, &maximums);
+        self.write_each(WriteKind::Configuration, |index, motor| {
+            motor
+                .set_current_limit(limits[index])
+                .map_err(SetCurrentLimitError::from)
+        })
+    }
+
+    /// Lowers the group's current limit as its measured torque approaches
+    /// `max_torque` in Newton-meters, to keep a mechanism from being
+    /// overstressed.
+    ///
+    /// This reads the average [`MotorGroup::torque`] once and writes a new
+    /// current limit to every motor:
+    ///
+    /// - Below 80% of `max_torque`, each motor gets its configured current
+    ///   limit (see [`MotorGroup::set_current_limit`]), or its hardware
+    ///   maximum if none was set.
+    /// - From 80% of `max_torque`, the limit falls linearly, reaching
+    ///   `current_floor` Amperes at `max_torque` and staying there above it.
+    ///
+    /// The limit never goes below `current_floor`, so the mechanism always
+    /// keeps some holding power. The limits written here don't replace the
+    /// configured limit, so once the torque drops the configured limit is
+    /// restored.
+    ///
+    /// This is a single read-then-write step, so it must be called
+    /// periodically (for example, once per control loop iteration) to protect
+    /// anything. Between calls, the last written limit stays in effect.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor can't be read or
+    ///   written to. If no motor could be read, no limit is written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         _ = lift.apply_torque_limit(1.5, 0.5);
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn apply_torque_limit(&mut self, max_torque: f64, current_floor: f64) -> GetterResult<()> {
+        let (torque, mut errors) = match self.torque() {
+            Ok(torque) => (Some(torque), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        if let Some(torque) = torque {
+            let limits = configured_current_limits(&self.config, &self.max_current_per_motor());
+            if let Err(error) = self.write_each(WriteKind::Configuration, |index, motor| {
+                motor.set_current_limit(torque_limited_current(
+                    torque,
+                    max_torque,
+                    current_floor,
+                    limits[index],
+                ))
+            }) {
+                errors.extend(error.errors);
+            }
+        }
+        readings::finish(torque.map(|_| ()), errors)
+    }
+
+    /// Returns the limit each motor should receive for a requested per-motor
+    /// limit, according to the group's [`CurrentLimitPolicy`].
+    pub(crate) fn checked_current_limits(
+        &self,
+        limit: f64,
+    ) -> Result<Vec<f64>, MotorGroupError<SetCurrentLimitError>> {
+        let maximums = self.max_current_per_motor();
+        match self.config.current_limit_policy {
+            CurrentLimitPolicy::Clamp => Ok(maximums
+                .into_iter()
+                .map(|maximum| limit.min(maximum))
+                .collect()),
+            CurrentLimitPolicy::Error => {
+                let errors: Vec<_> = maximums
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, maximum)| limit > **maximum)
+                    .map(|(index, maximum)| SetCurrentLimitError::ExceedsMaximum {
+                        index: Some(index),
+                        requested: limit,
+                        maximum: *maximum,
+                    })
+                    .collect();
+                if errors.is_empty() {
+                    Ok(alloc::vec![limit; maximums.len()])
+                } else {
+                    Err(MotorGroupError::new(errors))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{configured_current_limits, torque_limited_current};
+    use crate::GroupConfig;
+
+    #[test]
+    fn configured_limits_fall_back_to_maximums() {
+        let maximums = [2.5, 1.25];
+        assert_eq!(
+            configured_current_limits(&GroupConfig::DEFAULT, &maximums),
+            vec![2.5, 1.25]
+        );
+        let config = GroupConfig {
+            current_limit: Some(2.0),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(
+            configured_current_limits(&config, &maximums),
+            vec![2.0, 1.25]
+        );
+        let config = GroupConfig {
+            total_current_limit: Some(3.0),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(
+            configured_current_limits(&config, &maximums),
+            vec![2.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn current_falls_as_torque_rises() {
+        let limits: Vec<f64> = [0.0, 0.5, 0.8, 0.9, 0.95, 1.0, 1.2]
+            .into_iter()
+            .map(|torque| torque_limited_current(torque, 1.0, 0.5, 2.5))
+            .collect();
+
+        // Untouched up to the knee
+        assert_eq!(limits[..3], [2.5, 2.5, 2.5]);
+        // Then strictly falling...
+        assert!(limits[2..6].windows(2).all(|pair| pair[1] < pair[0]));
+        assert!((limits[3] - 1.5).abs() < 1e-9);
+        // ...until it bottoms out at the floor
+        assert_eq!(limits[5..], [0.5, 0.5]);
+    }
+
+    #[test]
+    fn current_never_goes_below_floor() {
+        // A floor above the configured limit wins
+        assert_eq!(torque_limited_current(0.0, 1.0, 2.0, 1.0), 2.0);
+        assert_eq!(torque_limited_current(5.0, 1.0, 2.0, 1.0), 2.0);
+        // Negative torque is treated by its magnitude
+        assert_eq!(torque_limited_current(-1.0, 1.0, 0.5, 2.5), 0.5);
+    }
+}
@@ -0,0 +1,763 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{
+    GetterResult, MotorGroup,
+    readings::{self, Reading},
+};
+
+/// Returns the indices of the motors whose velocity magnitude is below
+/// `dead_threshold`, as long as the average magnitude of all readable motors
+/// exceeds `min_group_velocity`.
+pub(crate) fn find_dead_motors(
+    readings: impl IntoIterator<Item = Reading<f64>>,
+    min_group_velocity: f64,
+    dead_threshold: f64,
+) -> GetterResult<Vec<usize>> {
+    let (values, errors) = readings::partition(readings);
+    let dead = if values.is_empty() {
+        None
+    } else {
+        let average = values
+            .iter()
+            .map(|(_, velocity)| velocity.abs())
+            .sum::<f64>()
+            / values.len() as f64;
+        Some(if average > min_group_velocity {
+            values
+                .into_iter()
+                .filter(|(_, velocity)| velocity.abs() < dead_threshold)
+                .map(|(index, _)| index)
+                .collect()
+        } else {
+            Vec::new()
+        })
+    };
+    readings::finish(dead, errors)
+}
+
+/// Returns how many readings are above `threshold`, counting only the motors
+/// that could be read.
+pub(crate) fn count_above(
+    readings: impl IntoIterator<Item = Reading<f64>>,
+    threshold: f64,
+) -> GetterResult<usize> {
+    let (values, errors) = readings::partition(readings);
+    let count = values
+        .iter()
+        .filter(|(_, value)| *value > threshold)
+        .count();
+    readings::finish((!values.is_empty()).then_some(count), errors)
+}
+
+/// Returns each reading as a fraction of the sum of all readings that could
+/// be read, or all zeros if that sum is zero.
+pub(crate) fn shares(readings: impl IntoIterator<Item = Reading<f64>>) -> GetterResult<Vec<f64>> {
+    let (values, errors) = readings::partition(readings);
+    let total: f64 = values.iter().map(|(_, value)| value).sum();
+    let shares = values
+        .iter()
+        .map(|(_, value)| if total == 0.0 { 0.0 } else { value / total })
+        .collect();
+    readings::finish((!values.is_empty()).then_some(shares), errors)
+}
+
+/// Packs a motor's readings into a telemetry tuple, with NaN in place of
+/// each reading that failed.
+pub(crate) fn telemetry_tuple(
+    velocity: Result<f64, PortError>,
+    temperature: Result<f64, PortError>,
+    current: Result<f64, PortError>,
+) -> (f64, f64, f64) {
+    (
+        velocity.unwrap_or(f64::NAN),
+        temperature.unwrap_or(f64::NAN),
+        current.unwrap_or(f64::NAN),
+    )
+}
+
+/// A fixed-size history of samples, used to find trends in a reading.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SampleHistory {
+    samples: VecDeque<f64>,
+}
+
+impl SampleHistory {
+    /// The maximum number of samples kept. Older samples are dropped.
+    pub(crate) const CAPACITY: usize = 64;
+
+    /// Adds a sample, dropping the oldest one if the history is full.
+    pub(crate) fn push(&mut self, sample: f64) {
+        if self.samples.len() == Self::CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Returns the least-squares slope of the most recent `window` samples,
+    /// in units per sample.
+    ///
+    /// Returns `0.0` if there are fewer than two samples to compare.
+    pub(crate) fn slope(&self, window: usize) -> f64 {
+        let window = window.min(self.samples.len());
+        if window < 2 {
+            return 0.0;
+        }
+        let samples = self.samples.range(self.samples.len() - window..);
+        let mean_x = (window - 1) as f64 / 2.0;
+        let mean_y = samples.clone().sum::<f64>() / window as f64;
+        let (covariance, variance) =
+            samples
+                .enumerate()
+                .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                    let dx = x as f64 - mean_x;
+                    (covariance + dx * (y - mean_y), variance + dx * dx)
+                });
+        covariance / variance
+    }
+
+    /// Returns the root-mean-square of the most recent `window` samples.
+    ///
+    /// Returns `0.0` if there are no samples.
+    pub(crate) fn rms(&self, window: usize) -> f64 {
+        let window = window.min(self.samples.len());
+        if window == 0 {
+            return 0.0;
+        }
+        let squares: f64 = self
+            .samples
+            .range(self.samples.len() - window..)
+            .map(|sample| sample * sample)
+            .sum();
+        (squares / window as f64).sqrt()
+    }
+
+    /// Returns the mean of the most recent `window` samples, or `None` if
+    /// there are no samples.
+    ///
+    /// A `window` of zero is treated as one.
+    pub(crate) fn mean(&self, window: usize) -> Option<f64> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let window = window.clamp(1, self.samples.len());
+        let sum: f64 = self.samples.range(self.samples.len() - window..).sum();
+        Some(sum / window as f64)
+    }
+
+    /// Drops every sample.
+    pub(crate) fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the indices of motors in the group that are likely dead.
+    ///
+    /// A fully dead motor (unplugged from power, burnt out, or with a stripped
+    /// gearbox) reads a velocity near zero while the motors sharing its load
+    /// keep spinning. If the average velocity magnitude of the group exceeds
+    /// `min_group_velocity` RPM, every motor spinning slower than
+    /// `dead_threshold` RPM is flagged. Otherwise the group is considered to
+    /// be at rest and nothing is flagged, since a stopped motor can't be told
+    /// apart from a dead one.
+    ///
+    /// This is only a heuristic, and it can report false positives when:
+    ///
+    /// - the motors aren't mechanically linked, so one can legitimately stop
+    ///   while the others move,
+    /// - the group is accelerating from rest and one motor lags behind, or
+    /// - `dead_threshold` is close to `min_group_velocity`, so a slightly
+    ///   slower motor looks dead.
+    ///
+    /// It also can't find a dead motor that is still being backdriven by the
+    /// others. In that case, check for motors drawing no current instead.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is computed from the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     motor_group.set_velocity(150).unwrap();
+    ///     sleep(Duration::from_secs(1)).await;
+    ///
+    ///     for index in motor_group.dead_motors(50.0, 5.0).unwrap() {
+    ///         println!("Motor {index} might be dead");
+    ///     }
+    /// }
+    /// ```
+    pub fn dead_motors(
+        &self,
+        min_group_velocity: f64,
+        dead_threshold: f64,
+    ) -> GetterResult<Vec<usize>> {
+        find_dead_motors(
+            self.read_each(Motor::velocity),
+            min_group_velocity,
+            dead_threshold,
+        )
+    }
+
+    /// Returns how many motors in the group are hotter than `celsius` degrees
+    /// Celsius.
+    ///
+    /// Unlike [`MotorGroup::is_over_temperature`], which only reports whether
+    /// any motor has reached VEXos's own limit, this shows how far heat has
+    /// spread through the group. That can decide how hard to derate: one hot
+    /// motor might be a mechanical problem local to it, while the whole group
+    /// heating up means the subsystem is being pushed too hard.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is the count among the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let hot = motor_group.motors_above_temperature(45.0).unwrap_or(0);
+    ///     let limit = match hot {
+    ///         0 => 12.0,
+    ///         1 => 10.0,
+    ///         _ => 8.0,
+    ///     };
+    ///     _ = motor_group.set_voltage_limit(limit);
+    /// }
+    /// ```
+    pub fn motors_above_temperature(&self, celsius: f64) -> GetterResult<usize> {
+        count_above(self.read_each(Motor::temperature), celsius)
+    }
+
+    /// Returns each motor's share of the group's total current draw, as a
+    /// fraction between `0.0` and `1.0`.
+    ///
+    /// The shares are in the order of the motors in the group and add up to
+    /// `1.0`. Motors sharing a load evenly should each carry about
+    /// `1.0 / motor_count`, so a motor carrying a much larger share than the
+    /// others may be misaligned or binding. If the group isn't drawing any
+    /// current, every share is `0.0`.
+    ///
+    /// Disabled motors (see [`MotorGroup::set_enabled`]) and checked out
+    /// motors (see [`MotorGroup::checkout`]) are skipped, so they have no
+    /// entry.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result has the shares of the motors that could be read, out of
+    ///   their total alone, so it has no entry for the motors that failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if let Ok(shares) = motor_group.current_distribution() {
+    ///         for (index, share) in shares.iter().enumerate() {
+    ///             if *share > 0.75 {
+    ///                 println!("Motor {index} is carrying {:.0}% of the load", share * 100.0);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn current_distribution(&self) -> GetterResult<Vec<f64>> {
+        shares(self.read_each(Motor::current))
+    }
+
+    /// Returns `(velocity, temperature, current)` for every motor in the
+    /// group, in RPM, degrees Celsius, and Amperes.
+    ///
+    /// This is a compact, fixed-shape payload for logging over a slow link
+    /// such as the serial console: there is always exactly one tuple per motor,
+    /// in the order of the group, and each motor is read once.
+    ///
+    /// Errors aren't returned. Instead, a reading that fails is replaced with
+    /// [`f64::NAN`], so a disconnected motor shows up as
+    /// `(NaN, NaN, NaN)`. Disabled motors (see [`MotorGroup::set_enabled`])
+    /// and checked out motors (see [`MotorGroup::checkout`]) aren't read, so
+    /// their tuples are all NaN too. Check values with [`f64::is_nan`], since
+    /// NaN never compares equal to anything.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         for (velocity, temperature, current) in motor_group.telemetry_tuples() {
+    ///             print!("{velocity:.0},{temperature:.0},{current:.2};");
+    ///         }
+    ///         println!();
+    ///         sleep(Duration::from_millis(100)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn telemetry_tuples(&self) -> Vec<(f64, f64, f64)> {
+        self.motors
+            .as_ref()
+            .iter()
+            .zip(&self.meta)
+            .map(|(motor, meta)| {
+                if meta.is_active() {
+                    telemetry_tuple(motor.velocity(), motor.temperature(), motor.current())
+                } else {
+                    (f64::NAN, f64::NAN, f64::NAN)
+                }
+            })
+            .collect()
+    }
+
+    /// Samples the group's average efficiency and returns its trend over the
+    /// last `window` samples, in percent per sample.
+    ///
+    /// Every call reads [`MotorGroup::efficiency`] once and stores it in a
+    /// history of the most recent 64 samples, then fits a line to the last
+    /// `window` of them (clamped to the number stored). A negative trend
+    /// means efficiency is dropping, which over a match can point to a
+    /// developing mechanical problem such as friction or a failing gearbox.
+    ///
+    /// Because the trend is measured per sample, it depends on how often this
+    /// is called: call it at a fixed interval (for example, once per loop
+    /// iteration of a telemetry task) so the results are comparable. A
+    /// window spanning 64 samples taken every 500ms covers the last 32
+    /// seconds. Efficiency is also naturally low while the group is
+    /// accelerating or stalled, so the trend is most meaningful under steady
+    /// load.
+    ///
+    /// The trend is `0.0` until at least two samples have been taken.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   The average of the motors that could be read is still sampled, and
+    ///   the result is the trend including it. If no motor could be read,
+    ///   nothing is sampled and the result is the trend of the existing
+    ///   samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         if let Ok(trend) = motor_group.efficiency_trend(20) {
+    ///             if trend < -0.5 {
+    ///                 println!("Efficiency is dropping by {:.2}% per sample", -trend);
+    ///             }
+    ///         }
+    ///         sleep(Duration::from_millis(500)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn efficiency_trend(&mut self, window: usize) -> GetterResult<f64> {
+        let (sample, errors) = match self.efficiency() {
+            Ok(efficiency) => (Some(efficiency), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        if let Some(sample) = sample {
+            self.efficiency_history.push(sample);
+        }
+        readings::finish(Some(self.efficiency_history.slope(window)), errors)
+    }
+
+    /// Samples the group's total current draw and returns its root-mean-square
+    /// over the last `window` samples, in Amperes.
+    ///
+    /// A motor's heating follows the square of its current, so the RMS
+    /// current over a stretch of time predicts heat buildup much better than
+    /// any single reading: a drivetrain pulsing between 0A and 10A heats up
+    /// like one drawing a steady 7A, not 5A. The total is the sum over every
+    /// motor that could be read.
+    ///
+    /// Each call takes one sample, and the group keeps the last 64. The window
+    /// is counted in samples and clamped to the number stored, so what it
+    /// covers in time depends on how often this is called: call it at a fixed
+    /// interval, such as once per loop iteration of a telemetry task. A
+    /// window of 40 samples taken every 50ms covers the last 2 seconds. A
+    /// window much shorter than the thermal time constant of the motors
+    /// (minutes) tracks bursts rather than heat, while the full 64 samples
+    /// at a slow cadence smooth over them.
+    ///
+    /// The RMS is `0.0` until the first sample has been taken.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   The total of the motors that could be read is still sampled, and
+    ///   the result is the RMS including it. If no motor could be read,
+    ///   nothing is sampled and the result is the RMS of the existing
+    ///   samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         // 40 samples every 50ms: the last 2 seconds
+    ///         if drive.rms_current(40).is_ok_and(|rms| rms > 4.0) {
+    ///             println!("The drive is running hot");
+    ///         }
+    ///         sleep(Duration::from_millis(50)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn rms_current(&mut self, window: usize) -> GetterResult<f64> {
+        let (currents, errors) = readings::partition(self.read_each(Motor::current));
+        if !currents.is_empty() {
+            let total = currents.iter().map(|(_, current)| current).sum();
+            self.current_history.push(total);
+        }
+        readings::finish(Some(self.current_history.rms(window)), errors)
+    }
+
+    /// Samples the group's average current draw (see [`MotorGroup::current`])
+    /// and returns its moving average over the last `window` samples, in
+    /// Amperes.
+    ///
+    /// A single current reading jumps around from one loop iteration to the
+    /// next, which makes it hard to read on a display and prone to flickering
+    /// across a threshold. Averaging the recent readings smooths that out at
+    /// the cost of lagging behind real changes by about half the window.
+    ///
+    /// Each call takes one sample, and the group keeps the last 64. The window
+    /// is counted in samples and clamped to the number stored, so what it
+    /// covers in time depends on how often this is called: call it at a fixed
+    /// interval, such as once per loop iteration of a telemetry task. A
+    /// window of 20 samples taken every 50ms averages the last second. A
+    /// window of zero is treated as one, which is the latest sample. Use
+    /// [`MotorGroup::reset_current_average`] to start over, such as when the
+    /// cadence changes.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   The average of the motors that could be read is still sampled, and
+    ///   the result is the moving average including it. If no motor could be
+    ///   read, nothing is sampled and the result is the moving average of the
+    ///   existing samples, or `None` if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///     _ = intake.set_voltage(12.0);
+    ///
+    ///     loop {
+    ///         // 10 samples every 20ms: the last 200ms
+    ///         if intake.averaged_current(10).is_ok_and(|current| current > 2.0) {
+    ///             println!("The intake is jammed");
+    ///         }
+    ///         sleep(Duration::from_millis(20)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn averaged_current(&mut self, window: usize) -> GetterResult<f64> {
+        let (current, errors) = match self.current() {
+            Ok(current) => (Some(current), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        if let Some(current) = current {
+            self.average_current_history.push(current);
+        }
+        readings::finish(self.average_current_history.mean(window), errors)
+    }
+
+    /// Drops every sample taken by [`MotorGroup::averaged_current`], so that
+    /// the next call averages only fresh readings.
+    pub fn reset_current_average(&mut self) {
+        self.average_current_history.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{SampleHistory, count_above, find_dead_motors, shares, telemetry_tuple};
+    use crate::{
+        MotorGroup,
+        tests::{mock_motors, two_motor_group},
+    };
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    #[test]
+    fn dead_motor_is_flagged_while_group_spins() {
+        let readings = [Ok(190.0), Ok(0.4), Ok(185.0), Ok(-2.0)];
+        assert_eq!(
+            find_dead_motors(readings.into_iter().enumerate(), 50.0, 5.0).unwrap(),
+            vec![1, 3]
+        );
+
+        // The same applies when spinning in reverse
+        let readings = [Ok(-190.0), Ok(-185.0), Ok(1.0)];
+        assert_eq!(
+            find_dead_motors(readings.into_iter().enumerate(), 50.0, 5.0).unwrap(),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn nothing_is_flagged_at_rest() {
+        let readings = [Ok(3.0), Ok(0.0), Ok(60.0)];
+        // The average is only 21 RPM
+        assert_eq!(
+            find_dead_motors(readings.into_iter().enumerate(), 50.0, 5.0).unwrap(),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn unreadable_motors_are_not_flagged() {
+        let readings = [Ok(190.0), Err(DISCONNECTED), Ok(0.0)];
+        let error = find_dead_motors(readings.into_iter().enumerate(), 50.0, 5.0).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(vec![2]));
+
+        let error = find_dead_motors([(0, Err(DISCONNECTED))], 50.0, 5.0).unwrap_err();
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn motors_above_temperature_are_counted() {
+        let readings = [Ok(38.0), Ok(51.5), Ok(45.0), Ok(47.0)];
+        // A motor exactly at the threshold isn't above it
+        assert_eq!(
+            count_above(readings.into_iter().enumerate(), 45.0).unwrap(),
+            2
+        );
+        assert_eq!(
+            count_above(readings.into_iter().enumerate(), 55.0).unwrap(),
+            0
+        );
+
+        let readings = [Ok(51.5), Err(DISCONNECTED), Ok(30.0)];
+        let error = count_above(readings.into_iter().enumerate(), 45.0).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(1));
+
+        let error = count_above([(0, Err(DISCONNECTED))], 45.0).unwrap_err();
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn current_shares_sum_to_one() {
+        let readings = [Ok(0.5), Ok(1.5), Ok(1.0), Ok(1.0)];
+        let fractions = shares(readings.into_iter().enumerate()).unwrap();
+        assert_eq!(fractions, vec![0.125, 0.375, 0.25, 0.25]);
+        assert!((fractions.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let readings = [Ok(0.7), Ok(0.2), Ok(0.4)];
+        let fractions = shares(readings.into_iter().enumerate()).unwrap();
+        assert!((fractions.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // A group drawing no current has no meaningful shares
+        let readings = [Ok(0.0), Ok(0.0)];
+        assert_eq!(
+            shares(readings.into_iter().enumerate()).unwrap(),
+            vec![0.0, 0.0]
+        );
+
+        let readings = [Ok(1.0), Err(DISCONNECTED), Ok(3.0)];
+        let error = shares(readings.into_iter().enumerate()).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(vec![0.25, 0.75]));
+    }
+
+    #[test]
+    fn telemetry_tuples_substitute_nan() {
+        assert_eq!(
+            telemetry_tuple(Ok(120.0), Ok(41.0), Ok(1.5)),
+            (120.0, 41.0, 1.5)
+        );
+
+        let (velocity, temperature, current) =
+            telemetry_tuple(Ok(-60.0), Err(DISCONNECTED), Ok(0.8));
+        assert_eq!((velocity, current), (-60.0, 0.8));
+        assert!(temperature.is_nan());
+
+        let group = two_motor_group();
+        // Every mock motor fails, but each still gets a tuple
+        let tuples = group.telemetry_tuples();
+        assert_eq!(tuples.len(), 2);
+        assert!(
+            tuples
+                .iter()
+                .all(|(a, b, c)| a.is_nan() && b.is_nan() && c.is_nan())
+        );
+    }
+
+    #[test]
+    fn declining_efficiency_has_negative_trend() {
+        let mut history = SampleHistory::default();
+        assert_eq!(history.slope(10), 0.0);
+        history.push(80.0);
+        assert_eq!(history.slope(10), 0.0);
+
+        // Steady, then dropping by 2% per sample
+        for _ in 0..10 {
+            history.push(80.0);
+        }
+        for sample in 1..=5 {
+            history.push(80.0 - 2.0 * sample as f64);
+        }
+        assert_eq!(history.slope(5), -2.0);
+        assert!(history.slope(16) < 0.0 && history.slope(16) > -2.0);
+        // The window is clamped to the samples available
+        assert_eq!(history.slope(1000), history.slope(16));
+    }
+
+    #[test]
+    fn history_keeps_only_recent_samples() {
+        let mut history = SampleHistory::default();
+        for sample in 0..SampleHistory::CAPACITY {
+            history.push(sample as f64);
+        }
+        assert_eq!(history.slope(usize::MAX), 1.0);
+        // Older samples are dropped as new ones arrive
+        for _ in 0..SampleHistory::CAPACITY {
+            history.push(0.0);
+        }
+        assert_eq!(history.slope(usize::MAX), 0.0);
+    }
+
+    #[test]
+    fn rms_weights_bursts_by_their_square() {
+        let mut history = SampleHistory::default();
+        assert_eq!(history.rms(10), 0.0);
+
+        // A steady draw is its own RMS
+        for _ in 0..4 {
+            history.push(3.0);
+        }
+        assert_eq!(history.rms(4), 3.0);
+
+        // Pulsing between 0A and 10A averages 5A but heats like ~7.07A
+        for sample in [0.0, 10.0, 0.0, 10.0] {
+            history.push(sample);
+        }
+        assert!((history.rms(4) - 50.0_f64.sqrt()).abs() < 1e-12);
+        // The window is clamped to the samples available: (4×9 + 2×100) / 8
+        assert!((history.rms(1000) - 29.5_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(history.rms(1), 10.0);
+    }
+
+    #[test]
+    fn mean_covers_the_most_recent_samples() {
+        let mut history = SampleHistory::default();
+        assert_eq!(history.mean(10), None);
+
+        for sample in [1.0, 2.0, 3.0, 4.0, 10.0] {
+            history.push(sample);
+        }
+        assert_eq!(history.mean(2), Some(7.0));
+        // The window is clamped to the samples available
+        assert_eq!(history.mean(1000), Some(4.0));
+        // A window of zero is the latest sample
+        assert_eq!(history.mean(0), Some(10.0));
+
+        // Only the most recent samples are kept
+        for _ in 0..SampleHistory::CAPACITY {
+            history.push(1.5);
+        }
+        assert_eq!(history.mean(usize::MAX), Some(1.5));
+
+        history.clear();
+        assert_eq!(history.mean(10), None);
+    }
+
+    #[test]
+    fn unreadable_current_keeps_the_average() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let error = group.averaged_current(10).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, None);
+
+        // A known sequence of group averages
+        for sample in [1.0, 3.0, 2.0, 6.0] {
+            group.average_current_history.push(sample);
+        }
+        let error = group.averaged_current(3).unwrap_err();
+        assert_eq!(error.result, Some(11.0 / 3.0));
+        assert_eq!(group.averaged_current(10).unwrap_err().result, Some(3.0));
+
+        group.reset_current_average();
+        assert_eq!(group.averaged_current(10).unwrap_err().result, None);
+    }
+
+    #[test]
+    fn unreadable_current_keeps_the_rms() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        group.current_history.push(2.0);
+        let error = group.rms_current(10).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(2.0));
+    }
+}
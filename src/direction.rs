@@ -0,0 +1,414 @@
+use alloc::vec::Vec;
+
+use vexide::{prelude::Direction, smart::motor::Motor};
+
+use crate::{
+    GetterResult, MotorGroup, MotorGroupError, WriteKind,
+    meta::MotorMeta,
+    read_cache::Change,
+    readings::{self, Reading},
+};
+
+/// Updates each motor's direction state after a direction write.
+///
+/// `succeeded[i]` is whether the write reached motor `i`. Unlike a position
+/// reference, a direction is absolute, so every motor that missed the write
+/// may disagree with the group's intent, even if no motor received it.
+pub(crate) fn record_direction_writes(meta: &mut [MotorMeta], succeeded: &[bool]) {
+    for (meta, succeeded) in meta.iter_mut().zip(succeeded) {
+        meta.direction_stale = !succeeded;
+    }
+}
+
+/// Returns the indices of the motors whose direction isn't their intended
+/// one, `intents[index]`.
+///
+/// Motors without an intended direction are left out, along with their read
+/// errors.
+pub(crate) fn disagreeing(
+    directions: impl IntoIterator<Item = Reading<Direction>>,
+    intents: &[Option<Direction>],
+) -> GetterResult<Vec<usize>> {
+    let directions = directions
+        .into_iter()
+        .filter(|(index, _)| intents.get(*index).is_some_and(Option::is_some));
+    let (directions, errors) = readings::partition(directions);
+    let read_any = !directions.is_empty();
+    let disagreeing = directions
+        .into_iter()
+        .filter(|(index, direction)| intents[*index] != Some(*direction))
+        .map(|(index, _)| index)
+        .collect();
+    readings::finish(read_any.then_some(disagreeing), errors)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the direction each motor in the group should have, in order.
+    ///
+    /// A group can mix directions, such as a drivetrain side where motors
+    /// face each other. Each motor's direction is read when it joins the
+    /// group (with [`MotorGroup::new`], [`MotorGroup::replace_motors`], or
+    /// [`MotorGroup::add_motor`]), and then follows
+    /// [`MotorGroup::set_direction`] and [`MotorGroup::flip_all`], even for
+    /// motors that missed the write. This doesn't read the motors, so use
+    /// [`MotorGroup::misdirected_motors`] to check that they agree.
+    ///
+    /// A motor's direction is `None` if it couldn't be read when it joined,
+    /// such as when the brain hadn't detected it yet, and the group hasn't
+    /// set one since.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let left = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     println!("Left drive directions: {:?}", left.directions());
+    /// }
+    /// ```
+    pub fn directions(&self) -> Vec<Option<Direction>> {
+        self.meta.iter().map(|meta| meta.direction).collect()
+    }
+
+    /// Reverses the direction of every motor in the group relative to its
+    /// current direction.
+    ///
+    /// Unlike [`MotorGroup::set_direction`], which gives every motor the same
+    /// direction, this keeps the motors' directions relative to each other,
+    /// so a group that mixes directions stays mixed. It's how to change
+    /// which way is forward for a mechanism whose motors face different
+    /// ways.
+    ///
+    /// Each motor is flipped from its direction in
+    /// [`MotorGroup::directions`]. Motors whose direction isn't known are
+    /// read first, and are left alone if that fails. Like
+    /// [`MotorGroup::set_direction`], the flipped directions are recorded
+    /// even for motors that miss the write, and are sent again before their
+    /// next target. Disabled and checked out motors aren't written to, but
+    /// their recorded direction is flipped too.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port, containing the errors of the
+    ///   motors whose direction couldn't be read, followed by those of the
+    ///   write.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     // The intake was mounted the other way around
+    ///     intake.flip_all().unwrap();
+    ///     assert_eq!(
+    ///         intake.directions(),
+    ///         [Some(Direction::Reverse), Some(Direction::Forward)]
+    ///     );
+    /// }
+    /// ```
+    pub fn flip_all(&mut self) -> Result<(), MotorGroupError> {
+        let mut errors = Vec::new();
+        let flipped: Vec<Option<Direction>> = self
+            .motors
+            .as_ref()
+            .iter()
+            .zip(&self.meta)
+            .map(|(motor, meta)| match meta.direction {
+                Some(direction) => Some(!direction),
+                None if meta.is_active() => match motor.direction() {
+                    Ok(direction) => Some(!direction),
+                    Err(error) => {
+                        errors.push(error);
+                        None
+                    }
+                },
+                None => None,
+            })
+            .collect();
+        for (meta, direction) in self.meta.iter_mut().zip(&flipped) {
+            if direction.is_some() {
+                meta.direction = *direction;
+            }
+        }
+        self.config.direction = self.config.direction.map(|direction| !direction);
+
+        // Motors left alone have nothing to miss
+        let mut succeeded: Vec<bool> = flipped.iter().map(Option::is_none).collect();
+        let result = self.write_each(WriteKind::Direction, |index, motor| match flipped[index] {
+            Some(direction) => motor
+                .set_direction(direction)
+                .inspect(|()| succeeded[index] = true),
+            None => Ok(()),
+        });
+        record_direction_writes(&mut self.meta, &succeeded);
+        self.read_cache.invalidate(Change::Aggregation);
+
+        if let Err(error) = result {
+            errors.extend(error.errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Returns the indices of the motors whose configured direction disagrees
+    /// with the direction the group expects them to have (see
+    /// [`MotorGroup::directions`]).
+    ///
+    /// Each motor is compared with its own direction, so a motor that's meant
+    /// to be reversed in a group that mixes directions isn't reported. This
+    /// reads each motor's direction, so it also catches motors whose
+    /// direction was changed outside of the group. Motors that missed a
+    /// direction write are re-sent the direction before their next target
+    /// (see [`MotorGroup::set_direction`]), so this is normally empty again
+    /// after the next command that reaches them.
+    ///
+    /// Motors whose direction isn't known aren't compared, and if no motor's
+    /// is, nothing is read.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any compared motor
+    ///   encounters an error. Its result is the disagreeing motors among
+    ///   those that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if motor_group.set_direction(Direction::Reverse).is_err() {
+    ///         if let Ok(motors) = motor_group.misdirected_motors() {
+    ///             println!("Motors {motors:?} are still spinning forward");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn misdirected_motors(&self) -> GetterResult<Vec<usize>> {
+        let intents = self.directions();
+        if intents.iter().all(Option::is_none) {
+            return Ok(Vec::new());
+        }
+        disagreeing(self.read_each(Motor::direction), &intents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{disagreeing, record_direction_writes};
+    use crate::{
+        MotorGroup,
+        meta::MotorMeta,
+        tests::{mock_motors, two_motor_group, v5_motor},
+    };
+
+    const FORWARD: Option<Direction> = Some(Direction::Forward);
+    const REVERSE: Option<Direction> = Some(Direction::Reverse);
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    fn stale(meta: &[MotorMeta]) -> Vec<bool> {
+        meta.iter().map(|meta| meta.direction_stale).collect()
+    }
+
+    /// A group of mock motors set up as if their directions had been read
+    /// when they joined.
+    fn mixed_group(directions: &[Option<Direction>]) -> MotorGroup {
+        let mut group = MotorGroup::new(mock_motors(directions.len() as u8, Gearset::Green));
+        for (meta, direction) in group.meta.iter_mut().zip(directions) {
+            meta.direction = *direction;
+        }
+        group
+    }
+
+    #[test]
+    fn partial_direction_writes_are_tracked() {
+        let mut meta = vec![MotorMeta::default(); 3];
+        record_direction_writes(&mut meta, &[true, false, true]);
+        assert_eq!(stale(&meta), [false, true, false]);
+        // A write that reaches nobody leaves every motor disagreeing
+        record_direction_writes(&mut meta, &[false, false, false]);
+        assert_eq!(stale(&meta), [true, true, true]);
+        record_direction_writes(&mut meta, &[true, true, true]);
+        assert_eq!(stale(&meta), [false, false, false]);
+    }
+
+    #[test]
+    fn every_direction_combination_is_compared() {
+        let combinations = [
+            (Direction::Forward, Direction::Forward, false),
+            (Direction::Forward, Direction::Reverse, true),
+            (Direction::Reverse, Direction::Forward, true),
+            (Direction::Reverse, Direction::Reverse, false),
+        ];
+        for (intent, actual, disagrees) in combinations {
+            let expected = if disagrees { vec![0] } else { vec![] };
+            assert_eq!(
+                disagreeing([(0, Ok(actual))], &[Some(intent)]).unwrap(),
+                expected
+            );
+        }
+
+        let directions = [
+            (0, Ok(Direction::Reverse)),
+            (1, Err(DISCONNECTED)),
+            (2, Ok(Direction::Forward)),
+        ];
+        let error = disagreeing(directions, &[Some(Direction::Reverse); 3]).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(vec![2]));
+    }
+
+    #[test]
+    fn missed_direction_is_resent_before_targets() {
+        let mut group = two_motor_group();
+        // Without a direction set, nothing is compared or re-sent
+        assert_eq!(group.misdirected_motors().unwrap(), vec![]);
+        assert_eq!(group.set_velocity(100).unwrap_err().errors.len(), 2);
+
+        // The mock motors miss the direction write
+        assert!(group.set_direction(Direction::Reverse).is_err());
+        assert_eq!(stale(&group.meta), [true, true]);
+        assert_eq!(group.misdirected_motors().unwrap_err().result, None);
+
+        // Each command first retries the direction. A motor that still can't
+        // take it isn't given the target either, so it can't spin the wrong
+        // way, and the command is still recorded
+        for rpm in [100, -100] {
+            let error = group.set_velocity(rpm).unwrap_err();
+            assert_eq!(error.errors.len(), 2);
+            assert_eq!(stale(&group.meta), [true, true]);
+            assert!(group.commanded_direction().is_some());
+        }
+
+        // Equivalent commands give the same result whatever happened before
+        let mut fresh = two_motor_group();
+        _ = fresh.set_direction(Direction::Reverse);
+        _ = fresh.set_velocity(-100);
+        assert_eq!(fresh.last_command, group.last_command);
+        assert_eq!(stale(&fresh.meta), stale(&group.meta));
+    }
+
+    #[test]
+    fn each_motor_is_compared_with_its_own_direction() {
+        let intents = [FORWARD, REVERSE, None];
+        // Motor 1 is meant to be reversed, and motor 2 could be either
+        let directions = [
+            (0, Ok(Direction::Forward)),
+            (1, Ok(Direction::Reverse)),
+            (2, Ok(Direction::Reverse)),
+        ];
+        assert_eq!(disagreeing(directions, &intents).unwrap(), vec![]);
+
+        let directions = [
+            (0, Ok(Direction::Reverse)),
+            (1, Ok(Direction::Reverse)),
+            (2, Err(DISCONNECTED)),
+        ];
+        assert_eq!(disagreeing(directions, &intents).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn motors_joining_a_group_keep_their_direction() {
+        // The mock motors' directions can't be read
+        let mut group = two_motor_group();
+        assert_eq!(group.directions(), [None, None]);
+        assert_eq!(group.meta[0], MotorMeta::default());
+
+        _ = group.set_direction(Direction::Reverse);
+        assert_eq!(group.directions(), [REVERSE, REVERSE]);
+        let index = group.add_motor(v5_motor(3));
+        assert_eq!(group.directions()[index], None);
+
+        let other = group.split(1).unwrap();
+        assert_eq!(group.directions(), [REVERSE]);
+        assert_eq!(other.directions(), [REVERSE, None]);
+    }
+
+    #[test]
+    fn flip_all_keeps_mixed_directions_mixed() {
+        let mut group = mixed_group(&[FORWARD, REVERSE, FORWARD]);
+        group.set_enabled(2, false);
+
+        // The mock motors miss the write, but the flip is still recorded and
+        // each motor is due its own flipped direction
+        let error = group.flip_all().unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(group.directions(), [REVERSE, FORWARD, REVERSE]);
+        assert_eq!(group.current_config().direction, None);
+        let pending: Vec<_> = group
+            .meta
+            .iter()
+            .map(MotorMeta::pending_direction)
+            .collect();
+        assert_eq!(pending, [REVERSE, FORWARD, REVERSE]);
+
+        // Commands retry each motor's own direction before the target
+        for rpm in [100, -100] {
+            assert_eq!(group.set_velocity(rpm).unwrap_err().errors.len(), 2);
+            assert_eq!(stale(&group.meta), [true, true, true]);
+        }
+
+        // Flipping twice is back where it started
+        _ = group.flip_all();
+        assert_eq!(group.directions(), [FORWARD, REVERSE, FORWARD]);
+    }
+
+    #[test]
+    fn flip_all_flips_a_uniform_group_direction() {
+        let mut group = two_motor_group();
+        _ = group.set_direction(Direction::Forward);
+        _ = group.flip_all();
+        assert_eq!(group.current_config().direction, REVERSE);
+        assert_eq!(group.directions(), [REVERSE, REVERSE]);
+    }
+
+    #[test]
+    fn unknown_directions_are_read_before_flipping() {
+        let mut group = mixed_group(&[FORWARD, None]);
+        let error = group.flip_all().unwrap_err();
+        // Motor 1's direction can't be read, so it's left alone rather than
+        // guessed, and isn't due a direction
+        assert_eq!(
+            error.errors,
+            vec![
+                PortError::Disconnected { port: 2 },
+                PortError::Disconnected { port: 1 }
+            ]
+        );
+        assert_eq!(group.directions(), [REVERSE, None]);
+        assert_eq!(stale(&group.meta), [true, false]);
+
+        // Only motors with a known direction are compared
+        let error = group.misdirected_motors().unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+    }
+}
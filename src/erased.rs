@@ -0,0 +1,257 @@
+use alloc::{boxed::Box, string::String};
+use core::fmt;
+
+use vexide::smart::motor::{BrakeMode, Motor};
+
+use crate::{GroupSnapshot, MotorGroup, MotorGroupError, SharedMotors};
+#[cfg(feature = "diagnostics")]
+use crate::{ReadinessCriteria, ReadinessReport, fleet::MotorHealth};
+
+/// The operations an [`ErasedGroup`] forwards to the group it holds.
+trait GroupOps {
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError>;
+    #[cfg(feature = "diagnostics")]
+    fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport;
+    fn snapshot(&self) -> GroupSnapshot;
+    #[cfg(feature = "diagnostics")]
+    fn diagnostic_report(&self, out: &mut dyn fmt::Write) -> fmt::Result;
+    #[cfg(feature = "diagnostics")]
+    fn motor_health(&self) -> alloc::vec::Vec<MotorHealth>;
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> GroupOps for MotorGroup<M> {
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+        MotorGroup::brake(self, mode)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport {
+        MotorGroup::readiness(self, criteria)
+    }
+
+    fn snapshot(&self) -> GroupSnapshot {
+        MotorGroup::snapshot(self)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn diagnostic_report(&self, mut out: &mut dyn fmt::Write) -> fmt::Result {
+        MotorGroup::diagnostic_report(self, &mut out)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn motor_health(&self) -> alloc::vec::Vec<MotorHealth> {
+        MotorGroup::motor_health(self)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> GroupOps for SharedMotors<M> {
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+        SharedMotors::brake(self, mode)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport {
+        SharedMotors::readiness(self, criteria)
+    }
+
+    fn snapshot(&self) -> GroupSnapshot {
+        SharedMotors::snapshot(self)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn diagnostic_report(&self, mut out: &mut dyn fmt::Write) -> fmt::Result {
+        SharedMotors::diagnostic_report(self, &mut out)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    fn motor_health(&self) -> alloc::vec::Vec<MotorHealth> {
+        SharedMotors::motor_health(self)
+    }
+}
+
+/// A motor group of any kind, for keeping groups of different types in one
+/// collection.
+///
+/// [`MotorGroup<Vec<Motor>>`], [`MotorGroup<[Motor; 4]>`](MotorGroup), and
+/// [`SharedMotors`] are all different types, so a robot's groups can't be put
+/// in one `Vec` as they are. Each of them converts into an `ErasedGroup`,
+/// which supports the operations needed to sweep over every group on the
+/// robot: braking, snapshots, and with the `diagnostics` feature, readiness
+/// checks, diagnostic reports and [`FleetSummary`](crate::FleetSummary). To
+/// command groups without knowing their type, use
+/// [`GroupControl`](crate::GroupControl). For anything else, keep the group
+/// itself.
+///
+/// An `ErasedGroup` owns the group it's made from. To keep using a group
+/// directly, convert a clone of its [`SharedMotors`] instead; the clone
+/// shares the same motors.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use vexide::prelude::*;
+/// use vexide_motorgroup::*;
+///
+/// #[vexide::main]
+/// async fn main(peripherals: Peripherals) {
+///     let drive = SharedMotors::from_motors(vec![
+///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+///     ]);
+///     let intake = MotorGroup::new([
+///         Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward),
+///     ]);
+///
+///     let mut groups = vec![
+///         ErasedGroup::from(drive.clone()).with_label("drive"),
+///         ErasedGroup::from(intake).with_label("intake"),
+///     ];
+///
+///     // ...at the end of the match...
+///     for group in &mut groups {
+///         _ = group.brake(BrakeMode::Coast);
+///     }
+/// }
+/// ```
+pub struct ErasedGroup {
+    group: Box<dyn GroupOps>,
+    label: Option<String>,
+}
+
+impl ErasedGroup {
+    /// Sets the label the group is identified by, such as in
+    /// [`ErasedGroup::diagnostic_report`].
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Returns the group's label, or `None` if it has none.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// See [`MotorGroup::brake`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    pub fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+        self.group.brake(mode)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::readiness`].
+    pub fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport {
+        self.group.readiness(criteria)
+    }
+
+    /// See [`MotorGroup::snapshot`].
+    pub fn snapshot(&self) -> GroupSnapshot {
+        self.group.snapshot()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// Writes the group's label, if it has one, followed by its
+    /// [`MotorGroup::diagnostic_report`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if writing to `out` fails.
+    pub fn diagnostic_report(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        if let Some(label) = &self.label {
+            writeln!(out, "{label}:")?;
+        }
+        self.group.diagnostic_report(out)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::motor_health`].
+    pub(crate) fn motor_health(&self) -> alloc::vec::Vec<MotorHealth> {
+        self.group.motor_health()
+    }
+}
+
+impl fmt::Debug for ErasedGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErasedGroup")
+            .field("label", &self.label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]> + 'static> From<MotorGroup<M>> for ErasedGroup {
+    fn from(group: MotorGroup<M>) -> Self {
+        Self {
+            group: Box::new(group),
+            label: None,
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]> + 'static> From<SharedMotors<M>> for ErasedGroup {
+    fn from(group: SharedMotors<M>) -> Self {
+        Self {
+            group: Box::new(group),
+            label: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::smart::motor::BrakeMode;
+
+    use super::ErasedGroup;
+    #[cfg(feature = "diagnostics")]
+    use crate::ReadinessCriteria;
+    use crate::{MotorGroup, SharedMotors, tests::v5_motor};
+
+    #[test]
+    fn mixed_groups_can_be_swept_together() {
+        let shared = SharedMotors::from_motors(vec![v5_motor(1), v5_motor(2)]);
+        let mut groups = vec![
+            ErasedGroup::from(MotorGroup::new(vec![v5_motor(3)])).with_label("intake"),
+            ErasedGroup::from(MotorGroup::new([v5_motor(4), v5_motor(5), v5_motor(6)])),
+            ErasedGroup::from(shared.clone()).with_label("drive"),
+        ];
+        assert_eq!(
+            groups.iter().map(ErasedGroup::label).collect::<Vec<_>>(),
+            [Some("intake"), None, Some("drive")]
+        );
+
+        // Every mock motor fails, so the error counts show each group was
+        // reached with all of its motors
+        let failures: Vec<_> = groups
+            .iter_mut()
+            .map(|group| group.brake(BrakeMode::Coast).unwrap_err().errors.len())
+            .collect();
+        assert_eq!(failures, [1, 3, 2]);
+        for group in &groups {
+            assert_eq!(group.snapshot().command_generation, Some(1));
+        }
+        // The shared group is the same group
+        assert_eq!(shared.command_generation(), 1);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn mixed_groups_report_their_health() {
+        let groups = [
+            ErasedGroup::from(SharedMotors::from_motors(vec![v5_motor(1), v5_motor(2)]))
+                .with_label("drive"),
+            ErasedGroup::from(MotorGroup::new([v5_motor(4), v5_motor(5), v5_motor(6)])),
+        ];
+        for group in &groups {
+            assert!(!group.readiness(&ReadinessCriteria::DEFAULT).is_ready());
+        }
+
+        let mut report = String::new();
+        groups[0].diagnostic_report(&mut report).unwrap();
+        assert!(report.starts_with("drive:\nMotor group: 2 motors, 2 active\n"));
+        let mut report = String::new();
+        groups[1].diagnostic_report(&mut report).unwrap();
+        assert!(report.starts_with("Motor group: 3 motors, 3 active\n"));
+    }
+}
@@ -0,0 +1,378 @@
+#[cfg(feature = "events")]
+use alloc::{collections::VecDeque, sync::Arc};
+#[cfg(feature = "events")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use vexide::smart::motor::Motor;
+
+#[cfg(feature = "events")]
+use crate::meta::MotorMeta;
+use crate::{MotorGroup, readings::Reading};
+
+/// A change in a motor group's state, delivered by an [`EventReceiver`].
+///
+/// Events are produced as the group is used: a motor is only noticed to be
+/// disconnected once a read or a target write fails on it. See
+/// [`MotorGroup::events`].
+///
+/// Events are only delivered with the `events` feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupEvent {
+    /// The motor at this index couldn't be reached, for the first time or
+    /// after having been reached before.
+    MotorDisconnected(usize),
+    /// The motor at this index was reached again after being disconnected.
+    MotorReconnected(usize),
+    /// The motor at this index reported that it's over temperature, after not
+    /// being so.
+    OverTemperature(usize),
+    /// The motor at this index started following the rest of the group (see
+    /// [`MotorGroup::position_fallback`]).
+    FallbackEntered(usize),
+    /// The motor at this index stopped following the rest of the group.
+    FallbackExited(usize),
+    /// The group's gearset was changed with [`MotorGroup::set_gearset`], so
+    /// velocities now refer to a different free speed (see
+    /// [`MotorGroup::max_rpm`]).
+    GearsetChanged,
+}
+
+/// The events buffered by an [`EventBus`].
+#[cfg(feature = "events")]
+#[derive(Debug)]
+struct Buffer {
+    events: VecDeque<GroupEvent>,
+    /// The sequence number of the oldest buffered event.
+    first: u64,
+}
+
+/// The bounded buffer shared by a group and its [`EventReceiver`]s.
+///
+/// It's behind a mutex rather than a `RefCell` so that the group and its
+/// receivers stay `Send`.
+#[cfg(feature = "events")]
+#[derive(Debug)]
+pub(crate) struct EventBus(Mutex<Buffer>);
+
+#[cfg(feature = "events")]
+impl EventBus {
+    fn new() -> Self {
+        Self(Mutex::new(Buffer {
+            events: VecDeque::with_capacity(EventReceiver::CAPACITY),
+            first: 0,
+        }))
+    }
+
+    /// Locks the buffer. Every critical section leaves it consistent, so a
+    /// poisoned lock is used as is.
+    fn buffer(&self) -> MutexGuard<'_, Buffer> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The sequence number the next event will get.
+    fn end(&self) -> u64 {
+        let buffer = self.buffer();
+        buffer.first + buffer.events.len() as u64
+    }
+
+    /// Buffers `event`, dropping the oldest one if the buffer is full.
+    pub(crate) fn push(&self, event: GroupEvent) {
+        let mut buffer = self.buffer();
+        if buffer.events.len() == EventReceiver::CAPACITY {
+            buffer.events.pop_front();
+            buffer.first += 1;
+        }
+        buffer.events.push_back(event);
+    }
+}
+
+/// Receives the [`GroupEvent`]s of a motor group, returned by
+/// [`MotorGroup::events`].
+///
+/// Every receiver sees every event, in order, from when it was created.
+/// Cloning a receiver creates another one at the same point in the stream,
+/// which then reads on independently.
+///
+/// The group keeps the last [`EventReceiver::CAPACITY`] events for all of its
+/// receivers, so a receiver that falls further behind misses the oldest ones.
+/// Those are counted by [`EventReceiver::missed`].
+#[cfg(feature = "events")]
+#[derive(Debug, Clone)]
+pub struct EventReceiver {
+    bus: Arc<EventBus>,
+    /// The sequence number of the next event to receive.
+    next: u64,
+    missed: u64,
+}
+
+#[cfg(feature = "events")]
+impl EventReceiver {
+    /// How many events are kept for receivers that haven't received them yet.
+    pub const CAPACITY: usize = 64;
+
+    /// Returns the next event, or `None` if there are no new events.
+    ///
+    /// This never waits, so it's meant to be called once per loop iteration,
+    /// draining the events that happened since the last one.
+    pub fn try_recv(&mut self) -> Option<GroupEvent> {
+        let buffer = self.bus.buffer();
+        if self.next < buffer.first {
+            self.missed += buffer.first - self.next;
+            self.next = buffer.first;
+        }
+        let event = buffer
+            .events
+            .get((self.next - buffer.first) as usize)
+            .copied()?;
+        self.next += 1;
+        Some(event)
+    }
+
+    /// Returns how many events this receiver missed because it fell more
+    /// than [`EventReceiver::CAPACITY`] events behind.
+    ///
+    /// Missed events are only counted once [`EventReceiver::try_recv`] skips
+    /// over them.
+    pub fn missed(&self) -> u64 {
+        self.missed
+    }
+}
+
+/// Records whether a motor could be reached, returning the event if that
+/// changed.
+///
+/// A motor that has never been reached is only reported once it fails.
+#[cfg(feature = "events")]
+fn connection_change(meta: &MotorMeta, index: usize, connected: bool) -> Option<GroupEvent> {
+    let previous = meta.connected.replace(Some(connected));
+    match (previous, connected) {
+        (None | Some(true), false) => Some(GroupEvent::MotorDisconnected(index)),
+        (Some(false), true) => Some(GroupEvent::MotorReconnected(index)),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "events")]
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Subscribes to the group's state changes.
+    ///
+    /// Rather than polling each kind of problem separately, a task can drain
+    /// one receiver for everything that changed, as it changes. These are
+    /// produced as the group is used:
+    ///
+    /// - [`GroupEvent::MotorDisconnected`] and
+    ///   [`GroupEvent::MotorReconnected`] by every getter and every target
+    ///   write, when a motor that could be reached no longer can or the other
+    ///   way around.
+    /// - [`GroupEvent::OverTemperature`] by
+    ///   [`MotorGroup::is_over_temperature`].
+    /// - [`GroupEvent::FallbackEntered`] and [`GroupEvent::FallbackExited`]
+    ///   by target writes (see [`MotorGroup::position_fallback`]).
+    /// - [`GroupEvent::GearsetChanged`] by [`MotorGroup::set_gearset`].
+    ///
+    /// Each event is reported once, when the state changes, not on every
+    /// read. The receiver only gets events from after it was created. Events
+    /// are buffered in a fixed size buffer shared by every receiver of the
+    /// group, so producing one doesn't allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     let mut events = drive.events();
+    ///
+    ///     loop {
+    ///         while let Some(event) = events.try_recv() {
+    ///             match event {
+    ///                 GroupEvent::MotorDisconnected(index) => {
+    ///                     println!("Drive motor {index} unplugged")
+    ///                 }
+    ///                 other => println!("Drive: {other:?}"),
+    ///             }
+    ///         }
+    ///         sleep(Duration::from_millis(20)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn events(&mut self) -> EventReceiver {
+        let bus = self.events.get_or_insert_with(|| Arc::new(EventBus::new()));
+        EventReceiver {
+            next: bus.end(),
+            bus: bus.clone(),
+            missed: 0,
+        }
+    }
+
+    /// Delivers `event` to the group's receivers, if there are any.
+    pub(crate) fn emit(&self, event: GroupEvent) {
+        if let Some(bus) = &self.events {
+            bus.push(event);
+        }
+    }
+
+    /// Records whether the motor at `index` could be reached, delivering an
+    /// event if that changed.
+    pub(crate) fn observe_connection(&self, index: usize, connected: bool) {
+        if let Some(event) = connection_change(&self.meta[index], index, connected) {
+            self.emit(event);
+        }
+    }
+
+    /// Records which motors are over temperature, delivering an event for
+    /// each that just became so.
+    pub(crate) fn observe_over_temperature(&self, readings: &[Reading<bool>]) {
+        for (index, reading) in readings {
+            if let Ok(over) = reading
+                && !self.meta[*index].over_temperature.replace(*over)
+                && *over
+            {
+                self.emit(GroupEvent::OverTemperature(*index));
+            }
+        }
+    }
+}
+
+/// Without the `events` feature there are no receivers, so nothing is
+/// recorded.
+#[cfg(not(feature = "events"))]
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    pub(crate) fn emit(&self, _event: GroupEvent) {}
+
+    pub(crate) fn observe_connection(&self, _index: usize, _connected: bool) {}
+
+    pub(crate) fn observe_over_temperature(&self, _readings: &[Reading<bool>]) {}
+}
+
+#[cfg(all(test, feature = "events"))]
+mod tests {
+    use vexide::{
+        math::Angle,
+        smart::{PortError, motor::MotorControl},
+    };
+
+    use super::{EventReceiver, GroupEvent, connection_change};
+    use crate::{PositionFallback, meta::MotorMeta, tests::two_motor_group};
+
+    fn drain(receiver: &mut EventReceiver) -> Vec<GroupEvent> {
+        core::iter::from_fn(|| receiver.try_recv()).collect()
+    }
+
+    #[test]
+    fn only_connection_changes_are_events() {
+        let meta = MotorMeta::default();
+        assert_eq!(connection_change(&meta, 3, true), None);
+        assert_eq!(
+            connection_change(&meta, 3, false),
+            Some(GroupEvent::MotorDisconnected(3))
+        );
+        assert_eq!(connection_change(&meta, 3, false), None);
+        assert_eq!(
+            connection_change(&meta, 3, true),
+            Some(GroupEvent::MotorReconnected(3))
+        );
+        assert_eq!(connection_change(&meta, 3, true), None);
+
+        // A motor that was never reached is reported as soon as it fails
+        let meta = MotorMeta::default();
+        assert_eq!(
+            connection_change(&meta, 0, false),
+            Some(GroupEvent::MotorDisconnected(0))
+        );
+    }
+
+    #[test]
+    fn getters_and_writes_produce_events() {
+        let mut group = two_motor_group();
+        // Nothing is recorded before subscribing
+        _ = group.velocity();
+        let mut events = group.events();
+        _ = group.velocity();
+        assert_eq!(drain(&mut events), vec![]);
+
+        // Over temperature readings only fail on the mock motors
+        group.meta[0].connected.set(Some(true));
+        group.observe_over_temperature(&[
+            (0, Ok(true)),
+            (1, Err(PortError::Disconnected { port: 2 })),
+        ]);
+        group.observe_over_temperature(&[(0, Ok(true))]);
+        group.observe_over_temperature(&[(0, Ok(false))]);
+        group.observe_over_temperature(&[(0, Ok(true))]);
+        _ = group.set_voltage(6.0);
+        assert_eq!(
+            drain(&mut events),
+            vec![
+                GroupEvent::OverTemperature(0),
+                GroupEvent::OverTemperature(0),
+                GroupEvent::MotorDisconnected(0),
+            ]
+        );
+
+        // Fallback transitions are reported per motor
+        let position = MotorControl::Position(Angle::from_degrees(90.0), 100);
+        group.position_fallback(Some(PositionFallback {
+            failures_to_enter: 1,
+            gain: 0.05,
+            max_voltage: 10.0,
+        }));
+        group.track_fallback(position, &[(0, true), (1, false)]);
+        group.track_fallback(position, &[(0, true), (1, false)]);
+        group.track_fallback(position, &[(0, true), (1, true)]);
+        assert_eq!(
+            drain(&mut events),
+            vec![
+                GroupEvent::FallbackEntered(1),
+                GroupEvent::FallbackExited(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn every_receiver_sees_every_event() {
+        let mut group = two_motor_group();
+        let mut first = group.events();
+        group.emit(GroupEvent::MotorDisconnected(0));
+        let mut second = first.clone();
+        let mut late = group.events();
+        group.emit(GroupEvent::MotorReconnected(0));
+
+        let both = vec![
+            GroupEvent::MotorDisconnected(0),
+            GroupEvent::MotorReconnected(0),
+        ];
+        assert_eq!(drain(&mut first), both);
+        assert_eq!(drain(&mut second), both);
+        assert_eq!(drain(&mut late), vec![GroupEvent::MotorReconnected(0)]);
+        // Receiving doesn't take events away from the others
+        group.emit(GroupEvent::OverTemperature(1));
+        for receiver in [&mut first, &mut second, &mut late] {
+            assert_eq!(drain(receiver), vec![GroupEvent::OverTemperature(1)]);
+        }
+    }
+
+    #[test]
+    fn slow_receivers_count_what_they_missed() {
+        let mut group = two_motor_group();
+        let mut slow = group.events();
+        let mut fast = group.events();
+        for index in 0..EventReceiver::CAPACITY + 10 {
+            group.emit(GroupEvent::MotorDisconnected(index));
+            assert_eq!(fast.try_recv(), Some(GroupEvent::MotorDisconnected(index)));
+        }
+        assert_eq!(fast.missed(), 0);
+
+        // The oldest events were dropped to make room
+        assert_eq!(slow.missed(), 0);
+        assert_eq!(slow.try_recv(), Some(GroupEvent::MotorDisconnected(10)));
+        assert_eq!(slow.missed(), 10);
+        assert_eq!(drain(&mut slow).len(), EventReceiver::CAPACITY - 1);
+    }
+}
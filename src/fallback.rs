@@ -0,0 +1,396 @@
+use alloc::vec::Vec;
+
+use vexide::smart::{
+    PortError,
+    motor::{Motor, MotorControl, MotorType},
+};
+
+use crate::{GroupEvent, MotorGroup, MotorGroupError, hard_cap, read_cache::Change, readings};
+
+/// Settings for driving motors that reject position targets by following the
+/// rest of the group, enabled with [`MotorGroup::position_fallback`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionFallback {
+    /// How many position targets in a row a motor has to reject before it
+    /// falls back. A single failed write is usually a glitch that the next
+    /// target fixes, so this should be at least `2`.
+    pub failures_to_enter: u32,
+    /// How many Volts are added per RPM that a fallback motor is slower than
+    /// the rest of the group.
+    pub gain: f64,
+    /// The largest voltage magnitude a fallback motor is ever given. The
+    /// motor's own maximum voltage also applies.
+    pub max_voltage: f64,
+}
+
+/// Returns the voltage that makes a motor follow `target_rpm`, given its own
+/// velocity and the free speed of its gearset.
+///
+/// The voltage is a feedforward proportional to the target, corrected by
+/// `gain` Volts per RPM of error, and capped at `max_voltage`. Without a
+/// target, the motor is given no voltage at all. Without its own velocity,
+/// only the feedforward is used.
+pub(crate) fn follow_voltage(
+    target_rpm: Option<f64>,
+    measured_rpm: Option<f64>,
+    free_rpm: f64,
+    nominal_voltage: f64,
+    fallback: &PositionFallback,
+) -> f64 {
+    let Some(target_rpm) = target_rpm else {
+        return 0.0;
+    };
+    let feedforward = target_rpm / free_rpm * nominal_voltage;
+    let correction = measured_rpm.map_or(0.0, |measured| fallback.gain * (target_rpm - measured));
+    let cap = fallback.max_voltage.min(nominal_voltage);
+    (feedforward + correction).clamp(-cap, cap)
+}
+
+/// Returns the voltage a motor of `motor_type` is driven at when it's given
+/// its full output.
+fn nominal_voltage(motor_type: MotorType) -> f64 {
+    match motor_type {
+        MotorType::Exp => Motor::EXP_MAX_VOLTAGE,
+        MotorType::V5 => Motor::V5_MAX_VOLTAGE,
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Enables or disables falling back to following the rest of the group
+    /// for motors that keep rejecting position targets.
+    ///
+    /// After a partial gearset change or a firmware hiccup, some motors can
+    /// reject position targets while still accepting voltages. Normally those
+    /// motors just sit idle while the rest of the group moves, which is the
+    /// worst outcome for a linked mechanism. With a fallback set:
+    ///
+    /// - **Entry**: a motor falls back once it has rejected
+    ///   [`PositionFallback::failures_to_enter`] position targets in a row,
+    ///   as long as the latest target reached at least one other motor to
+    ///   follow. The write that makes it fall back still returns its errors.
+    /// - **Following**: every call to [`MotorGroup::update_fallback`] gives
+    ///   each fallback motor a voltage that tracks the average velocity of
+    ///   the motors that accepted the target.
+    /// - **Exit**: a motor leaves the fallback as soon as it accepts a
+    ///   position target (every new target is still tried on every motor),
+    ///   and every motor leaves it when the group is given any other kind of
+    ///   target, such as a velocity or a brake.
+    ///
+    /// Setting the fallback to `None` disables it, and any motors currently
+    /// in the fallback leave it. They are left at their last voltage until
+    /// the group is given a new target.
+    ///
+    /// Check [`MotorGroup::is_in_fallback`] to find out when this happens,
+    /// since a motor in the fallback doesn't hold its position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///     lift.position_fallback(Some(PositionFallback {
+    ///         failures_to_enter: 3,
+    ///         gain: 0.05,
+    ///         max_voltage: 8.0,
+    ///     }));
+    ///
+    ///     loop {
+    ///         _ = lift.set_position_target(Angle::from_degrees(360.0), 100);
+    ///         _ = lift.update_fallback();
+    ///         if lift.is_in_fallback() {
+    ///             println!("Lift motors {:?} are following", lift.fallback_motors());
+    ///         }
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn position_fallback(&mut self, fallback: Option<PositionFallback>) -> &mut Self {
+        self.config.position_fallback = fallback;
+        self.read_cache.invalidate(Change::Aggregation);
+        if fallback.is_none() {
+            for meta in &mut self.meta {
+                meta.in_fallback = false;
+            }
+        }
+        self
+    }
+
+    /// Returns `true` if any motor in the group is following the rest of the
+    /// group instead of its position target.
+    ///
+    /// See [`MotorGroup::position_fallback`].
+    pub fn is_in_fallback(&self) -> bool {
+        self.meta.iter().any(|meta| meta.in_fallback)
+    }
+
+    /// Returns the indices of the motors following the rest of the group
+    /// instead of their position target.
+    ///
+    /// See [`MotorGroup::position_fallback`].
+    pub fn fallback_motors(&self) -> Vec<usize> {
+        self.meta
+            .iter()
+            .enumerate()
+            .filter(|(_, meta)| meta.in_fallback)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Drives every motor in the fallback to follow the rest of the group.
+    ///
+    /// Call this regularly, such as every [`Motor::WRITE_INTERVAL`] (5ms),
+    /// for as long as the group is moving to a position target. It does
+    /// nothing unless a motor is in the fallback (see
+    /// [`MotorGroup::position_fallback`]).
+    ///
+    /// Each fallback motor is given a voltage proportional to the average
+    /// velocity of the other motors, as a fraction of its gearset's free
+    /// speed, plus [`PositionFallback::gain`] Volts per RPM that it's slower
+    /// than them. The voltage is capped at [`PositionFallback::max_voltage`]
+    /// and at the motor's own maximum. If no other motor can be read, the
+    /// fallback motors are given no voltage, so they never run on their own.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port. Every fallback motor is still
+    ///   written to.
+    pub fn update_fallback(&mut self) -> Result<(), MotorGroupError> {
+        let Some(fallback) = self.config.position_fallback else {
+            return Ok(());
+        };
+        if !self.is_in_fallback() {
+            return Ok(());
+        }
+
+        let mut errors = Vec::new();
+        let healthy: Vec<_> = self
+            .motors
+            .as_ref()
+            .iter()
+            .zip(&self.meta)
+            .enumerate()
+            .filter(|(_, (_, meta))| meta.is_active() && !meta.in_fallback)
+            .map(|(index, (motor, _))| (index, motor.velocity()))
+            .collect();
+        let (velocities, read_errors) = readings::partition(healthy);
+        errors.extend(read_errors);
+        let target = readings::mean(velocities.into_iter().map(|(_, velocity)| velocity));
+
+        let gearset = self.config.gearset;
+        let cap = self.hard_voltage_cap;
+        for (motor, meta) in self.motors.as_mut().iter_mut().zip(&self.meta) {
+            if !meta.is_active() || !meta.in_fallback {
+                continue;
+            }
+            let result = (|| -> Result<(), PortError> {
+                let free_rpm = match gearset {
+                    Some(gearset) => gearset.max_rpm(),
+                    None => motor.gearset()?.max_rpm(),
+                };
+                let voltage = follow_voltage(
+                    target,
+                    motor.velocity().ok(),
+                    free_rpm,
+                    nominal_voltage(motor.motor_type()),
+                    &fallback,
+                );
+                let target = MotorControl::Voltage(voltage);
+                motor.set_target(hard_cap::capped_target(motor, target, cap, gearset)?)
+            })();
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Updates each motor's fallback state after `target` was written,
+    /// given which motors accepted it as `(index, accepted)` pairs, and
+    /// delivers an event for each motor that entered or left the fallback.
+    pub(crate) fn track_fallback(&mut self, target: MotorControl, written: &[(usize, bool)]) {
+        let before: Vec<bool> = self.meta.iter().map(|meta| meta.in_fallback).collect();
+        self.update_fallback_state(target, written);
+        for (index, (meta, before)) in self.meta.iter().zip(before).enumerate() {
+            match (before, meta.in_fallback) {
+                (false, true) => self.emit(GroupEvent::FallbackEntered(index)),
+                (true, false) => self.emit(GroupEvent::FallbackExited(index)),
+                _ => {}
+            }
+        }
+    }
+
+    fn update_fallback_state(&mut self, target: MotorControl, written: &[(usize, bool)]) {
+        if !matches!(target, MotorControl::Position(..)) {
+            for meta in &mut self.meta {
+                meta.position_failures = 0;
+                meta.in_fallback = false;
+            }
+            return;
+        }
+
+        let any_accepted = written.iter().any(|(_, accepted)| *accepted);
+        for &(index, accepted) in written {
+            let meta = &mut self.meta[index];
+            if accepted {
+                meta.position_failures = 0;
+                meta.in_fallback = false;
+                continue;
+            }
+            meta.position_failures = meta.position_failures.saturating_add(1);
+            if let Some(fallback) = self.config.position_fallback
+                && any_accepted
+                && meta.position_failures >= fallback.failures_to_enter
+            {
+                meta.in_fallback = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{math::Angle, prelude::*, smart::motor::MotorControl};
+
+    use super::{PositionFallback, follow_voltage};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    const FALLBACK: PositionFallback = PositionFallback {
+        failures_to_enter: 2,
+        gain: 0.05,
+        max_voltage: 10.0,
+    };
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(3, Gearset::Red))
+    }
+
+    fn position() -> MotorControl {
+        MotorControl::Position(Angle::from_degrees(360.0), 100)
+    }
+
+    #[test]
+    fn follower_tracks_the_group_velocity() {
+        // Half the free speed, already matched: feedforward only
+        assert_eq!(
+            follow_voltage(Some(50.0), Some(50.0), 100.0, 12.0, &FALLBACK),
+            6.0
+        );
+        // Lagging by 20 RPM adds a volt, and leading by 20 RPM takes one off
+        assert_eq!(
+            follow_voltage(Some(50.0), Some(30.0), 100.0, 12.0, &FALLBACK),
+            7.0
+        );
+        assert_eq!(
+            follow_voltage(Some(-50.0), Some(-30.0), 100.0, 12.0, &FALLBACK),
+            -7.0
+        );
+        // Without its own velocity, only the feedforward is used
+        assert_eq!(
+            follow_voltage(Some(25.0), None, 100.0, 12.0, &FALLBACK),
+            3.0
+        );
+    }
+
+    #[test]
+    fn follower_voltage_is_capped() {
+        // By the configured cap
+        assert_eq!(
+            follow_voltage(Some(100.0), Some(0.0), 100.0, 12.0, &FALLBACK),
+            10.0
+        );
+        assert_eq!(
+            follow_voltage(Some(-100.0), Some(0.0), 100.0, 12.0, &FALLBACK),
+            -10.0
+        );
+        // By an EXP motor's lower maximum
+        assert_eq!(
+            follow_voltage(Some(100.0), Some(100.0), 100.0, 8.0, &FALLBACK),
+            8.0
+        );
+        // With nothing to follow, nothing is driven
+        assert_eq!(
+            follow_voltage(None, Some(80.0), 100.0, 12.0, &FALLBACK),
+            0.0
+        );
+    }
+
+    #[test]
+    fn motors_enter_and_exit_the_fallback() {
+        let mut group = group();
+        group.position_fallback(Some(FALLBACK));
+        let partial = [(0, true), (1, false), (2, true)];
+
+        // One rejected target isn't enough
+        group.track_fallback(position(), &partial);
+        assert!(!group.is_in_fallback());
+        group.track_fallback(position(), &partial);
+        assert_eq!(group.fallback_motors(), vec![1]);
+
+        // Accepting a position target leaves the fallback
+        group.track_fallback(position(), &[(0, true), (1, true), (2, true)]);
+        assert!(!group.is_in_fallback());
+
+        // Any other kind of target leaves it too, and starts the count over
+        group.track_fallback(position(), &partial);
+        group.track_fallback(position(), &partial);
+        assert!(group.is_in_fallback());
+        group.track_fallback(MotorControl::Velocity(50), &partial);
+        assert!(!group.is_in_fallback());
+        group.track_fallback(position(), &partial);
+        assert!(!group.is_in_fallback());
+
+        // Disabling the fallback leaves it
+        group.track_fallback(position(), &partial);
+        assert!(group.is_in_fallback());
+        group.position_fallback(None);
+        assert!(!group.is_in_fallback());
+    }
+
+    #[test]
+    fn fallback_needs_a_motor_to_follow() {
+        let mut group = group();
+        group.position_fallback(Some(FALLBACK));
+        let rejected = [(0, false), (1, false), (2, false)];
+        for _ in 0..5 {
+            group.track_fallback(position(), &rejected);
+        }
+        assert!(!group.is_in_fallback());
+
+        // Without a fallback set, rejected targets are only counted
+        let mut group = self::group();
+        for _ in 0..5 {
+            group.track_fallback(position(), &[(0, true), (1, false)]);
+        }
+        assert!(!group.is_in_fallback());
+    }
+
+    #[test]
+    fn fallback_motors_get_no_voltage_without_a_leader() {
+        let mut group = group();
+        group.position_fallback(Some(FALLBACK));
+        // Nothing to do outside the fallback
+        assert!(group.update_fallback().is_ok());
+
+        group.track_fallback(position(), &[(0, true), (1, false), (2, true)]);
+        group.track_fallback(position(), &[(0, true), (1, false), (2, true)]);
+        // No mock motor can be read or written: the two leaders' reads and
+        // the follower's write fail
+        let error = group.update_fallback().unwrap_err();
+        assert_eq!(error.errors.len(), 3);
+        // The group's own command isn't changed by following
+        assert_eq!(group.last_command, None);
+    }
+}
@@ -0,0 +1,251 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::{
+    PortError,
+    motor::{Motor, MotorFaults},
+};
+
+use crate::{MotorGroup, MotorGroupError, SharedMotors, readings::Reading, tick::tick_loop};
+
+/// Error returned by [`MotorGroup::wait_for_fault`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The motor at `index` reported faults.
+    Faulted {
+        /// The index of the motor in the group.
+        index: usize,
+        /// The fault flags the motor reported.
+        faults: MotorFaults,
+    },
+}
+
+impl From<PortError> for FaultError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for FaultError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::Faulted { index, faults } => write!(f, "motor {index} reported {faults:?}"),
+        }
+    }
+}
+
+impl core::error::Error for FaultError {}
+
+/// Returns the faults in one poll of every motor's fault flags, or `None` if
+/// no motor reported any.
+///
+/// Read errors are only returned alongside a fault.
+pub(crate) fn faults_found(
+    readings: Vec<Reading<MotorFaults>>,
+) -> Option<MotorGroupError<FaultError>> {
+    let mut faulted = false;
+    let errors: Vec<_> = readings
+        .into_iter()
+        .filter_map(|(index, reading)| match reading {
+            Ok(faults) => {
+                let faults = faults & MotorFaults::all();
+                faulted |= !faults.is_empty();
+                (!faults.is_empty()).then_some(FaultError::Faulted { index, faults })
+            }
+            Err(source) => Some(source.into()),
+        })
+        .collect();
+    faulted.then(|| MotorGroupError::new(errors))
+}
+
+/// Polls `read` every `poll_interval` until it reports a fault.
+async fn wait_for(
+    poll_interval: Duration,
+    mut read: impl FnMut() -> Vec<Reading<MotorFaults>>,
+) -> MotorGroupError<FaultError> {
+    tick_loop(poll_interval, None, |_| match faults_found(read()) {
+        Some(error) => ControlFlow::Break(error),
+        None => ControlFlow::Continue(()),
+    })
+    .await
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Waits until any motor in the group reports a fault, returning every
+    /// fault reported at that point.
+    ///
+    /// The motors don't signal faults on their own, and the brain has no
+    /// interrupts for them, so this reads every motor's fault flags (see
+    /// [`Motor::faults`]) once every `poll_interval`, using the same loop as
+    /// the group's other async methods (see [`MotorGroup::tick_interval`]).
+    /// A fault can therefore be noticed up to `poll_interval` after it
+    /// happens. Each poll reads every motor's faults once. Over temperature,
+    /// over current, driver fault, and driver over current flags all count.
+    ///
+    /// A motor that can't be read isn't a fault, since a motor that was never
+    /// plugged in would end the wait straight away; subscribe to
+    /// [`MotorGroup::events`] to be told about disconnections. Read errors
+    /// from the poll that found the fault are still returned with it.
+    ///
+    /// This borrows the group for as long as it waits. To race it against
+    /// normal operation from a supervising task, use
+    /// [`SharedMotors::wait_for_fault`], which only borrows the group while
+    /// polling.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let error = lift.wait_for_fault(Duration::from_millis(20)).await;
+    ///     println!("Lift faulted: {error}");
+    /// }
+    /// ```
+    pub async fn wait_for_fault(&self, poll_interval: Duration) -> MotorGroupError<FaultError> {
+        wait_for(poll_interval, || self.read_each(Motor::faults)).await
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Waits until any motor in the group reports a fault, borrowing the group
+    /// only while polling it.
+    ///
+    /// Since the group is free between polls, a supervising task can race
+    /// this against the tasks controlling the group. See
+    /// [`MotorGroup::wait_for_fault`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let supervisor = spawn({
+    ///         let mut drive = drive.clone();
+    ///         async move {
+    ///             let error = drive.wait_for_fault(Duration::from_millis(20)).await;
+    ///             println!("Drive faulted: {error}");
+    ///             _ = drive.brake(BrakeMode::Coast);
+    ///         }
+    ///     });
+    ///     // ...drive as normal...
+    /// }
+    /// ```
+    pub async fn wait_for_fault(&self, poll_interval: Duration) -> MotorGroupError<FaultError> {
+        wait_for(poll_interval, || self.0.borrow().read_each(Motor::faults)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    use vexide::smart::{PortError, motor::MotorFaults};
+
+    use super::{FaultError, faults_found, wait_for};
+    use crate::readings::Reading;
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 2 };
+
+    /// A motor whose fault flags are clear until it has been polled `healthy`
+    /// times.
+    struct FaultingMotor {
+        healthy: usize,
+        polls: Cell<usize>,
+    }
+
+    impl FaultingMotor {
+        fn faults(&self) -> Result<MotorFaults, PortError> {
+            self.polls.set(self.polls.get() + 1);
+            Ok(if self.polls.get() > self.healthy {
+                MotorFaults::OVER_TEMPERATURE | MotorFaults::DRIVER_FAULT
+            } else {
+                MotorFaults::empty()
+            })
+        }
+    }
+
+    #[test]
+    fn only_fault_flags_end_the_wait() {
+        assert!(faults_found(vec![(0, Ok(MotorFaults::empty()))]).is_none());
+        assert!(faults_found(vec![(0, Err(DISCONNECTED))]).is_none());
+        // Bits the motor reports that aren't faults are ignored
+        assert!(faults_found(vec![(0, Ok(MotorFaults::from_bits_retain(0x100)))]).is_none());
+
+        let readings: Vec<Reading<MotorFaults>> = vec![
+            (0, Ok(MotorFaults::empty())),
+            (1, Err(DISCONNECTED)),
+            (2, Ok(MotorFaults::OVER_CURRENT)),
+        ];
+        assert_eq!(
+            faults_found(readings).unwrap().errors,
+            vec![
+                FaultError::Port {
+                    source: DISCONNECTED
+                },
+                FaultError::Faulted {
+                    index: 2,
+                    faults: MotorFaults::OVER_CURRENT,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_motor_that_faults_later_ends_the_wait() {
+        let motor = FaultingMotor {
+            healthy: 3,
+            polls: Cell::new(0),
+        };
+        let future = wait_for(Duration::ZERO, || {
+            vec![(0, Ok(MotorFaults::empty())), (1, motor.faults())]
+        });
+
+        // Every poll but the last yields to other tasks
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(future);
+        let mut pending = 0;
+        let error = loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(error) => break error,
+                Poll::Pending => pending += 1,
+            }
+        };
+        assert_eq!(pending, 3);
+        assert_eq!(motor.polls.get(), 4);
+        assert_eq!(
+            error.errors,
+            vec![FaultError::Faulted {
+                index: 1,
+                faults: MotorFaults::OVER_TEMPERATURE | MotorFaults::DRIVER_FAULT,
+            }]
+        );
+    }
+}
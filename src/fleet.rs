@@ -0,0 +1,285 @@
+use alloc::{string::String, vec::Vec};
+
+use vexide::smart::{PortError, SmartDevice, motor::Motor};
+
+use crate::{ErasedGroup, MotorGroup, SharedMotors};
+
+/// The readings of one motor that go into a [`FleetSummary`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MotorHealth {
+    pub(crate) index: usize,
+    pub(crate) connected: bool,
+    pub(crate) current: Result<f64, PortError>,
+    pub(crate) temperature: Result<f64, PortError>,
+}
+
+/// A motor somewhere on the robot, identified by the group it's in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FleetMotor {
+    /// The position of the motor's group among the groups summarized.
+    pub group: usize,
+    /// The label of the motor's group (see [`ErasedGroup::with_label`]), if
+    /// it has one.
+    pub label: Option<String>,
+    /// The index of the motor in its group.
+    pub index: usize,
+}
+
+/// Whole-robot numbers summed up from several motor groups, returned by
+/// [`FleetSummary::from_groups`].
+///
+/// Only the active motors of each group are included, not disabled or checked
+/// out ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FleetSummary {
+    /// The number of groups summarized.
+    pub group_count: usize,
+    /// The number of motors summarized.
+    pub motor_count: usize,
+    /// The total current drawn by every motor that could be read, in Amperes.
+    pub total_current: f64,
+    /// The hottest motor that could be read and its temperature in degrees
+    /// Celsius, or `None` if no motor's temperature could be read.
+    pub max_temperature: Option<(FleetMotor, f64)>,
+    /// Every motor that isn't connected to its port.
+    pub disconnected: Vec<FleetMotor>,
+    /// The errors of every read that failed on a connected motor.
+    pub errors: Vec<(FleetMotor, PortError)>,
+}
+
+impl FleetSummary {
+    /// Reads every group in `groups` once and sums up the whole robot.
+    ///
+    /// Each group is read in a single pass over its motors, for their
+    /// connection, current and temperature. A motor that can't be read
+    /// doesn't stop the summary: a disconnected motor is listed in
+    /// [`FleetSummary::disconnected`], any other failed read is listed in
+    /// [`FleetSummary::errors`], and the rest of the readings are still
+    /// summed up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let groups = [
+    ///         ErasedGroup::from(MotorGroup::new(vec![
+    ///             Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///             Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         ]))
+    ///         .with_label("drive"),
+    ///         ErasedGroup::from(MotorGroup::new([
+    ///             Motor::new(peripherals.port_3, Gearset::Green, Direction::Forward),
+    ///         ]))
+    ///         .with_label("intake"),
+    ///     ];
+    ///
+    ///     loop {
+    ///         let summary = FleetSummary::from_groups(&groups);
+    ///         println!("Drawing {:.1}A", summary.total_current);
+    ///         if let Some((motor, temperature)) = &summary.max_temperature {
+    ///             println!("Hottest: {:?} motor {} at {temperature}°C", motor.label, motor.index);
+    ///         }
+    ///         sleep(Duration::from_secs(1)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn from_groups<'a>(groups: impl IntoIterator<Item = &'a ErasedGroup>) -> Self {
+        let mut summary = Self {
+            group_count: 0,
+            motor_count: 0,
+            total_current: 0.0,
+            max_temperature: None,
+            disconnected: Vec::new(),
+            errors: Vec::new(),
+        };
+        for group in groups {
+            summary.add_group(group.label(), group.motor_health());
+        }
+        summary
+    }
+
+    /// Adds the readings of the next group, labelled `label`, to the summary.
+    pub(crate) fn add_group(&mut self, label: Option<&str>, motors: Vec<MotorHealth>) {
+        let group = self.group_count;
+        self.group_count += 1;
+        let motor = |index| FleetMotor {
+            group,
+            label: label.map(String::from),
+            index,
+        };
+
+        for health in motors {
+            self.motor_count += 1;
+            if !health.connected {
+                self.disconnected.push(motor(health.index));
+                continue;
+            }
+            match health.current {
+                Ok(current) => self.total_current += current,
+                Err(error) => self.errors.push((motor(health.index), error)),
+            }
+            match health.temperature {
+                Ok(temperature)
+                    if self
+                        .max_temperature
+                        .as_ref()
+                        .is_none_or(|(_, hottest)| temperature > *hottest) =>
+                {
+                    self.max_temperature = Some((motor(health.index), temperature));
+                }
+                Ok(_) => {}
+                Err(error) => self.errors.push((motor(health.index), error)),
+            }
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Reads the connection, current and temperature of each active motor
+    /// for a [`FleetSummary`].
+    pub(crate) fn motor_health(&self) -> Vec<MotorHealth> {
+        self.motors
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.meta[*index].is_active())
+            .map(|(index, motor)| MotorHealth {
+                index,
+                connected: motor.is_connected(),
+                current: motor.current(),
+                temperature: motor.temperature(),
+            })
+            .collect()
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::motor_health`].
+    pub(crate) fn motor_health(&self) -> Vec<MotorHealth> {
+        self.0.borrow().motor_health()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::smart::{PortError, SmartDeviceType};
+
+    use super::{FleetMotor, FleetSummary, MotorHealth};
+    use crate::{ErasedGroup, MotorGroup, SharedMotors, tests::v5_motor};
+
+    fn healthy(index: usize, current: f64, temperature: f64) -> MotorHealth {
+        MotorHealth {
+            index,
+            connected: true,
+            current: Ok(current),
+            temperature: Ok(temperature),
+        }
+    }
+
+    fn fleet_motor(group: usize, label: Option<&str>, index: usize) -> FleetMotor {
+        FleetMotor {
+            group,
+            label: label.map(String::from),
+            index,
+        }
+    }
+
+    fn empty() -> FleetSummary {
+        FleetSummary::from_groups([])
+    }
+
+    #[test]
+    fn readings_roll_up_across_groups() {
+        let mut summary = empty();
+        summary.add_group(
+            Some("drive"),
+            vec![healthy(0, 1.5, 40.0), healthy(1, 2.0, 45.0)],
+        );
+        summary.add_group(None, vec![healthy(0, 0.5, 52.0)]);
+        summary.add_group(Some("lift"), vec![healthy(2, 1.0, 48.0)]);
+
+        assert_eq!(summary.group_count, 3);
+        assert_eq!(summary.motor_count, 4);
+        assert!((summary.total_current - 5.0).abs() < 1e-9);
+        assert_eq!(
+            summary.max_temperature,
+            Some((fleet_motor(1, None, 0), 52.0))
+        );
+        assert!(summary.disconnected.is_empty());
+        assert!(summary.errors.is_empty());
+    }
+
+    #[test]
+    fn partial_failures_do_not_abort_the_summary() {
+        let error = PortError::IncorrectDevice {
+            expected: SmartDeviceType::Motor,
+            actual: SmartDeviceType::Imu,
+            port: 3,
+        };
+        let mut summary = empty();
+        summary.add_group(
+            Some("drive"),
+            vec![
+                healthy(0, 2.0, 40.0),
+                MotorHealth {
+                    index: 1,
+                    connected: false,
+                    current: Err(PortError::Disconnected { port: 2 }),
+                    temperature: Err(PortError::Disconnected { port: 2 }),
+                },
+            ],
+        );
+        summary.add_group(
+            Some("intake"),
+            vec![MotorHealth {
+                index: 0,
+                connected: true,
+                current: Ok(1.0),
+                temperature: Err(error),
+            }],
+        );
+
+        assert_eq!(summary.motor_count, 3);
+        assert!((summary.total_current - 3.0).abs() < 1e-9);
+        assert_eq!(
+            summary.max_temperature,
+            Some((fleet_motor(0, Some("drive"), 0), 40.0))
+        );
+        assert_eq!(summary.disconnected, [fleet_motor(0, Some("drive"), 1)]);
+        assert_eq!(summary.errors, [(fleet_motor(1, Some("intake"), 0), error)]);
+    }
+
+    #[test]
+    fn mixed_mock_groups_are_summarized() {
+        let mut with_disabled = MotorGroup::new([v5_motor(4), v5_motor(5), v5_motor(6)]);
+        with_disabled.set_enabled(1, false);
+        let groups = [
+            ErasedGroup::from(SharedMotors::from_motors(vec![v5_motor(1), v5_motor(2)]))
+                .with_label("drive"),
+            ErasedGroup::from(MotorGroup::new(vec![v5_motor(3)])),
+            ErasedGroup::from(with_disabled).with_label("lift"),
+        ];
+
+        // Every mock motor is disconnected, so nothing could be read
+        let summary = FleetSummary::from_groups(&groups);
+        assert_eq!(summary.group_count, 3);
+        assert_eq!(summary.motor_count, 5);
+        assert_eq!(summary.total_current, 0.0);
+        assert_eq!(summary.max_temperature, None);
+        assert_eq!(
+            summary.disconnected,
+            [
+                fleet_motor(0, Some("drive"), 0),
+                fleet_motor(0, Some("drive"), 1),
+                fleet_motor(1, None, 0),
+                fleet_motor(2, Some("lift"), 0),
+                fleet_motor(2, Some("lift"), 2),
+            ]
+        );
+        assert!(summary.errors.is_empty());
+    }
+}
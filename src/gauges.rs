@@ -0,0 +1,403 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::{Motor, MotorControl};
+
+use crate::{
+    GetterResult, GroupConfig, MotorGroup,
+    current_limit::configured_current_limits,
+    readings::{self, Reading},
+};
+
+/// The temperature in degrees Celsius at which VEXos starts limiting a motor's
+/// output.
+const TEMPERATURE_LIMIT: f64 = 55.0;
+
+/// The direction a motor group was commanded to move in, returned by
+/// [`MotorGroup::commanded_direction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sign {
+    /// The group was commanded to move forward.
+    Forward,
+    /// The group was commanded to move in reverse.
+    Reverse,
+    /// The group was commanded to stop, either by braking or with a target
+    /// of zero.
+    Stopped,
+}
+
+impl Sign {
+    /// Returns the sign of `value`, treating zero as [`Sign::Stopped`].
+    fn of(value: f64) -> Self {
+        if value > 0.0 {
+            Self::Forward
+        } else if value < 0.0 {
+            Self::Reverse
+        } else {
+            Self::Stopped
+        }
+    }
+}
+
+/// Returns the direction of motion requested by `command`, or `None` if it
+/// can't be known without reading the motors.
+pub(crate) fn command_direction(command: MotorControl) -> Option<Sign> {
+    match command {
+        MotorControl::Brake(_) => Some(Sign::Stopped),
+        MotorControl::Voltage(volts) => Some(Sign::of(volts)),
+        MotorControl::Velocity(rpm) => Some(Sign::of(rpm.into())),
+        // The direction to a position target depends on the current position
+        _ => None,
+    }
+}
+
+/// Returns the indices of the motors whose velocity is more than
+/// `min_velocity` RPM in the direction opposite to `direction`.
+///
+/// Nothing opposes [`Sign::Stopped`].
+pub(crate) fn opposing(
+    velocities: impl IntoIterator<Item = Reading<f64>>,
+    direction: Sign,
+    min_velocity: f64,
+) -> GetterResult<Vec<usize>> {
+    let (velocities, errors) = readings::partition(velocities);
+    let read_any = !velocities.is_empty();
+    let mismatched = velocities
+        .into_iter()
+        .filter(|(_, velocity)| match direction {
+            Sign::Forward => *velocity < -min_velocity,
+            Sign::Reverse => *velocity > min_velocity,
+            Sign::Stopped => false,
+        })
+        .map(|(index, _)| index)
+        .collect();
+    readings::finish(read_any.then_some(mismatched), errors)
+}
+
+/// Returns `value` as a fraction of `max`, clamped to `0.0..=1.0`.
+fn fraction(value: f64, max: f64) -> f64 {
+    (value / max).clamp(0.0, 1.0)
+}
+
+/// Returns the mean current limit of each motor in Amperes, given each motor's
+/// hardware maximum.
+///
+/// See [`configured_current_limits`].
+pub(crate) fn effective_current_limit(config: &GroupConfig, maximums: &[f64]) -> f64 {
+    let limits = configured_current_limits(config, maximums);
+    limits.iter().sum::<f64>() / limits.len() as f64
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the group's temperature as a fraction of the 55°C at which
+    /// VEXos starts limiting motor output, from `0.0` to `1.0`.
+    ///
+    /// This is meant for gauges on a driver display. See
+    /// [`MotorGroup::temperature`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is computed from the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let heat = motor_group.temperature_fraction().unwrap_or(1.0);
+    ///     println!("Drive temperature: {:.0}%", heat * 100.0);
+    /// }
+    /// ```
+    pub fn temperature_fraction(&self) -> GetterResult<f64> {
+        readings::map_result(self.temperature(), |temperature| {
+            fraction(temperature, TEMPERATURE_LIMIT)
+        })
+    }
+
+    /// Returns the group's current draw as a fraction of its current limit,
+    /// from `0.0` to `1.0`.
+    ///
+    /// The limit is the one last set with [`MotorGroup::set_current_limit`] or
+    /// [`MotorGroup::set_total_current_limit`]. If neither has been called, it
+    /// falls back to the hardware maximum of each motor (see
+    /// [`MotorGroup::max_current`]).
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is computed from the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group.set_current_limit(2.0).unwrap();
+    ///
+    ///     let load = motor_group.current_fraction().unwrap_or(0.0);
+    ///     println!("Drive current: {:.0}% of the limit", load * 100.0);
+    /// }
+    /// ```
+    pub fn current_fraction(&self) -> GetterResult<f64> {
+        let limit = effective_current_limit(&self.config, &self.max_current_per_motor());
+        readings::map_result(self.current(), |current| fraction(current, limit))
+    }
+
+    /// Returns the magnitude of the group's output voltage as a fraction of
+    /// [`MotorGroup::max_voltage`], from `0.0` to `1.0`.
+    ///
+    /// If the group was last given a voltage, that commanded voltage is used
+    /// without reading the motors. After a brake the output is `0.0`.
+    /// Otherwise, such as while following a velocity or position target, the
+    /// measured [`MotorGroup::voltage`] is used.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if the voltage has to be measured and any motor in the group encounters an error.
+    ///   Its result is computed from the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     motor_group.set_voltage(6.0).unwrap();
+    ///     assert_eq!(motor_group.output_fraction().unwrap(), 0.5);
+    /// }
+    /// ```
+    pub fn output_fraction(&self) -> GetterResult<f64> {
+        let max_voltage = self.max_voltage();
+        match self.last_command {
+            Some(MotorControl::Voltage(volts)) => Ok(fraction(volts.abs(), max_voltage)),
+            Some(MotorControl::Brake(_)) => Ok(0.0),
+            _ => readings::map_result(self.voltage(), |volts| fraction(volts.abs(), max_voltage)),
+        }
+    }
+
+    /// Returns the direction the group was last commanded to move in, without
+    /// reading the motors.
+    ///
+    /// This is meant for indicators such as arrows on a driver display, which
+    /// should update instantly. It's derived from the sign of the last
+    /// commanded voltage or velocity, and a brake (or a target of zero) is
+    /// [`Sign::Stopped`]. It reflects the command, not actual motion: a group
+    /// that is stalled, still spinning down, or being backdriven reports the
+    /// direction it was told to move in.
+    ///
+    /// Returns `None` if nothing has been commanded yet, or if the last
+    /// command was a position target, since the direction to a position
+    /// depends on where the motors are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     _ = motor_group.set_voltage(-6.0);
+    ///     assert_eq!(motor_group.commanded_direction(), Some(Sign::Reverse));
+    /// }
+    /// ```
+    pub fn commanded_direction(&self) -> Option<Sign> {
+        self.last_command.and_then(command_direction)
+    }
+
+    /// Returns the indices of the motors spinning against the group's
+    /// commanded direction.
+    ///
+    /// A motor that is wired or configured backwards spins opposite to every
+    /// command, fighting the rest of the group. This compares each motor's
+    /// measured velocity with [`MotorGroup::commanded_direction`], and
+    /// reports the motors moving the wrong way faster than `min_velocity`
+    /// RPM. The threshold keeps a motor that is just starting up, or being
+    /// jostled at rest, from being reported.
+    ///
+    /// Each motor measures its velocity relative to its own direction (see
+    /// [`MotorGroup::directions`]), just like it's given its command, so a
+    /// motor that's meant to be reversed in a group that mixes directions
+    /// isn't reported. To find motors whose direction itself is
+    /// wrong, use [`MotorGroup::misdirected_motors`].
+    ///
+    /// This relies on the group's record of its last command, so it only
+    /// works when the group has been commanded a nonzero voltage or
+    /// velocity. If it hasn't (nothing commanded yet, a brake, a target of
+    /// zero, or a position target), nothing is reported. Check it once the
+    /// group has had time to respond to the command.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is the mismatched motors among those that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     _ = drive.set_voltage(6.0);
+    ///     sleep(Duration::from_millis(300)).await;
+    ///     if let Ok(backwards) = drive.direction_mismatch(50.0) {
+    ///         for index in backwards {
+    ///             println!("Motor {index} is spinning backwards!");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn direction_mismatch(&self, min_velocity: f64) -> GetterResult<Vec<usize>> {
+        match self.commanded_direction() {
+            Some(direction @ (Sign::Forward | Sign::Reverse)) => {
+                opposing(self.read_each(Motor::velocity), direction, min_velocity)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::motor::BrakeMode};
+
+    use vexide::smart::PortError;
+
+    use super::{Sign, effective_current_limit, opposing};
+    use crate::{Angle, GroupConfig, MotorGroup, tests::mock_motors};
+
+    #[test]
+    fn current_limit_falls_back_to_hardware_maximum() {
+        // Nothing configured, so a V5 and an EXP motor average their maximums
+        assert_eq!(
+            effective_current_limit(&GroupConfig::DEFAULT, &[2.5, 1.25]),
+            1.875
+        );
+
+        let config = GroupConfig {
+            current_limit: Some(2.0),
+            ..GroupConfig::DEFAULT
+        };
+        // The EXP motor's limit is clamped to its maximum
+        assert_eq!(effective_current_limit(&config, &[2.5, 1.25]), 1.625);
+
+        let config = GroupConfig {
+            total_current_limit: Some(3.0),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(effective_current_limit(&config, &[2.5, 2.5]), 1.5);
+    }
+
+    #[test]
+    fn output_fraction_uses_last_command() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        // Nothing has been commanded, so the voltage has to be read
+        assert_eq!(group.output_fraction().unwrap_err().result, None);
+
+        _ = group.set_voltage(-6.0);
+        assert_eq!(group.output_fraction().unwrap(), 0.5);
+        _ = group.set_voltage(20.0);
+        assert_eq!(group.output_fraction().unwrap(), 1.0);
+        _ = group.brake(BrakeMode::Coast);
+        assert_eq!(group.output_fraction().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn commanded_direction_follows_last_command() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        assert_eq!(group.commanded_direction(), None);
+
+        // The mock motors fail every write, but the command is still recorded
+        _ = group.set_voltage(6.0);
+        assert_eq!(group.commanded_direction(), Some(Sign::Forward));
+        _ = group.set_voltage(-6.0);
+        assert_eq!(group.commanded_direction(), Some(Sign::Reverse));
+        _ = group.set_velocity(-200);
+        assert_eq!(group.commanded_direction(), Some(Sign::Reverse));
+        _ = group.set_velocity(0);
+        assert_eq!(group.commanded_direction(), Some(Sign::Stopped));
+        _ = group.set_voltage(12.0);
+        _ = group.brake(BrakeMode::Hold);
+        assert_eq!(group.commanded_direction(), Some(Sign::Stopped));
+        _ = group.set_position_target(Angle::from_degrees(90.0), 200);
+        assert_eq!(group.commanded_direction(), None);
+    }
+
+    #[test]
+    fn motors_against_the_command_are_found() {
+        let velocities = vec![
+            (0, Ok(480.0)),
+            (1, Ok(-460.0)),
+            (2, Ok(-30.0)),
+            (3, Ok(510.0)),
+        ];
+        // Only the motor clearly spinning backwards is reported
+        assert_eq!(
+            opposing(velocities.clone(), Sign::Forward, 50.0).unwrap(),
+            vec![1]
+        );
+        assert_eq!(
+            opposing(velocities.clone(), Sign::Forward, 10.0).unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            opposing(velocities.clone(), Sign::Reverse, 50.0).unwrap(),
+            vec![0, 3]
+        );
+        assert!(opposing(velocities, Sign::Stopped, 0.0).unwrap().is_empty());
+
+        let disconnected = PortError::Disconnected { port: 1 };
+        let velocities = vec![(0, Err(disconnected)), (1, Ok(-200.0))];
+        let error = opposing(velocities, Sign::Forward, 50.0).unwrap_err();
+        assert_eq!(error.errors, vec![disconnected]);
+        assert_eq!(error.result, Some(vec![1]));
+        let error = opposing(vec![(0, Err(disconnected))], Sign::Forward, 50.0).unwrap_err();
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn direction_mismatch_needs_a_nonzero_command() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        // Without a direction to compare with, the motors aren't read
+        assert_eq!(group.direction_mismatch(50.0).unwrap(), vec![]);
+        _ = group.set_velocity(0);
+        assert_eq!(group.direction_mismatch(50.0).unwrap(), vec![]);
+
+        _ = group.set_velocity(150);
+        assert_eq!(group.direction_mismatch(50.0).unwrap_err().result, None);
+    }
+}
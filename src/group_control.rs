@@ -0,0 +1,289 @@
+use alloc::boxed::Box;
+
+use vexide::{
+    math::Angle,
+    smart::{
+        PortError, SmartDevice,
+        motor::{BrakeMode, Motor, MotorControl},
+    },
+};
+
+use crate::{MotorGroup, MotorGroupError, SharedMotors};
+
+/// Returns the index of the motor on the port that `error` is about.
+fn index_of(motors: &[Motor], error: &PortError) -> Option<usize> {
+    let port = match *error {
+        PortError::Disconnected { port } | PortError::IncorrectDevice { port, .. } => port,
+    };
+    motors.iter().position(|motor| motor.port_number() == port)
+}
+
+/// Commands for a motor group of any type, for code that is handed a group
+/// without knowing what it is.
+///
+/// This is implemented by every [`MotorGroup`] and [`SharedMotors`], and is
+/// object safe, so a framework can keep subsystems' groups as
+/// `Box<dyn GroupControl>` or take `&mut dyn GroupControl`. Boxes and mutable
+/// references to a `GroupControl` are `GroupControl`s too. None of its
+/// methods are generic, and every error is a plain [`MotorGroupError`]: each
+/// [`PortError`] in it says what went wrong and on which port, and
+/// [`GroupControl::motor_index`] finds the motor in the group on that port.
+///
+/// Unlike [`ErasedGroup`](crate::ErasedGroup), which owns its group and
+/// supports the sweeps done over every group on a robot, this only covers
+/// commanding a group and reading back where it is. Write and read errors
+/// are handled as described on the methods of [`MotorGroup`] of the same
+/// names.
+///
+/// # Examples
+///
+/// ```
+/// # use vexide::{prelude::*, smart::SmartPort};
+/// # use vexide_motorgroup::*;
+/// # let [port_1, port_2, port_3] = [1, 2, 3].map(|port| unsafe { SmartPort::new(port) });
+/// let drive = SharedMotors::from_motors(vec![
+///     Motor::new(port_1, Gearset::Blue, Direction::Forward),
+///     Motor::new(port_2, Gearset::Blue, Direction::Reverse),
+/// ]);
+/// let intake = MotorGroup::new([Motor::new(port_3, Gearset::Green, Direction::Forward)]);
+///
+/// let mut subsystems: Vec<Box<dyn GroupControl>> = vec![Box::new(drive.clone()), Box::new(intake)];
+///
+/// for group in &mut subsystems {
+///     if let Err(error) = group.set_voltage(6.0) {
+///         // No motors are plugged in here, so every motor reports its port
+///         for error in &error.errors {
+///             println!("Motor {:?} failed: {error}", group.motor_index(error));
+///         }
+///     }
+/// }
+/// assert_eq!(drive.command_generation(), 1);
+/// ```
+pub trait GroupControl {
+    /// See [`MotorGroup::set_target`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError>;
+
+    /// See [`MotorGroup::set_voltage`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Voltage(volts))
+    }
+
+    /// See [`MotorGroup::set_velocity`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    fn set_velocity(&mut self, rpm: i32) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Velocity(rpm))
+    }
+
+    /// See [`MotorGroup::set_position_target`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    fn set_position_target(
+        &mut self,
+        position: Angle,
+        velocity: i32,
+    ) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Position(position, velocity))
+    }
+
+    /// See [`MotorGroup::brake`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+        self.set_target(MotorControl::Brake(mode))
+    }
+
+    /// See [`MotorGroup::position`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is the average of the motors that
+    ///   could be read.
+    fn position(&self) -> Result<Angle, MotorGroupError<PortError, Angle>>;
+
+    /// See [`MotorGroup::velocity`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is the average of the motors that
+    ///   could be read.
+    fn velocity(&self) -> Result<f64, MotorGroupError<PortError, f64>>;
+
+    /// Returns the index in the group of the motor that `error` is about, or
+    /// `None` if no motor in the group is on its port.
+    fn motor_index(&self, error: &PortError) -> Option<usize>;
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> GroupControl for MotorGroup<M> {
+    fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
+        MotorGroup::set_target(self, target)
+    }
+
+    fn position(&self) -> Result<Angle, MotorGroupError<PortError, Angle>> {
+        MotorGroup::position(self)
+    }
+
+    fn velocity(&self) -> Result<f64, MotorGroupError<PortError, f64>> {
+        MotorGroup::velocity(self)
+    }
+
+    fn motor_index(&self, error: &PortError) -> Option<usize> {
+        index_of(self.motors.as_ref(), error)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> GroupControl for SharedMotors<M> {
+    fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
+        SharedMotors::set_target(self, target)
+    }
+
+    fn position(&self) -> Result<Angle, MotorGroupError<PortError, Angle>> {
+        SharedMotors::position(self)
+    }
+
+    fn velocity(&self) -> Result<f64, MotorGroupError<PortError, f64>> {
+        SharedMotors::velocity(self)
+    }
+
+    fn motor_index(&self, error: &PortError) -> Option<usize> {
+        index_of(self.0.borrow().motors.as_ref(), error)
+    }
+}
+
+/// Implements [`GroupControl`] for a pointer to a `GroupControl`, forwarding
+/// every method so that the group's own implementations are used.
+macro_rules! forward_group_control {
+    ($($pointer:ty),+) => {$(
+        impl<G: GroupControl + ?Sized> GroupControl for $pointer {
+            fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
+                (**self).set_target(target)
+            }
+
+            fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
+                (**self).set_voltage(volts)
+            }
+
+            fn set_velocity(&mut self, rpm: i32) -> Result<(), MotorGroupError> {
+                (**self).set_velocity(rpm)
+            }
+
+            fn set_position_target(
+                &mut self,
+                position: Angle,
+                velocity: i32,
+            ) -> Result<(), MotorGroupError> {
+                (**self).set_position_target(position, velocity)
+            }
+
+            fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
+                (**self).brake(mode)
+            }
+
+            fn position(&self) -> Result<Angle, MotorGroupError<PortError, Angle>> {
+                (**self).position()
+            }
+
+            fn velocity(&self) -> Result<f64, MotorGroupError<PortError, f64>> {
+                (**self).velocity()
+            }
+
+            fn motor_index(&self, error: &PortError) -> Option<usize> {
+                (**self).motor_index(error)
+            }
+        }
+    )+};
+}
+
+forward_group_control!(Box<G>, &mut G);
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        smart::{
+            PortError, SmartDeviceType,
+            motor::{BrakeMode, MotorControl},
+        },
+    };
+
+    use super::GroupControl;
+    use crate::{MotorGroup, SharedMotors, tests::v5_motor};
+
+    /// Takes any `GroupControl`, to check that the forwarding impls apply.
+    fn command(mut group: impl GroupControl) -> usize {
+        group.set_voltage(6.0).unwrap_err().errors.len()
+    }
+
+    #[test]
+    fn groups_are_commanded_through_trait_objects() {
+        let shared = SharedMotors::from_motors(vec![v5_motor(1), v5_motor(2)]);
+        let mut groups: Vec<Box<dyn GroupControl>> = vec![
+            Box::new(MotorGroup::new([v5_motor(3), v5_motor(4), v5_motor(5)])),
+            Box::new(shared.clone()),
+        ];
+
+        // Every mock motor fails, so the error counts show each group was
+        // reached with all of its motors
+        let failures: Vec<_> = groups
+            .iter_mut()
+            .map(|group| group.brake(BrakeMode::Hold).unwrap_err().errors.len())
+            .collect();
+        assert_eq!(failures, [3, 2]);
+        assert_eq!(
+            shared.0.borrow().last_command,
+            Some(MotorControl::Brake(BrakeMode::Hold))
+        );
+
+        _ = groups[1].set_position_target(Angle::from_degrees(90.0), 100);
+        assert_eq!(
+            shared.0.borrow().last_command,
+            Some(MotorControl::Position(Angle::from_degrees(90.0), 100))
+        );
+        assert!(groups[0].position().unwrap_err().result.is_none());
+
+        // Boxes and references forward to the group
+        assert_eq!(command(&mut groups[0]), 3);
+        assert_eq!(command(groups.remove(1)), 2);
+        assert_eq!(command(&mut *groups[0]), 3);
+    }
+
+    #[test]
+    fn errors_lead_back_to_the_motor() {
+        let group: Box<dyn GroupControl> =
+            Box::new(MotorGroup::new(vec![v5_motor(4), v5_motor(7)]));
+        let error = group.velocity().unwrap_err();
+        let indices: Vec<_> = error
+            .errors
+            .iter()
+            .map(|error| group.motor_index(error))
+            .collect();
+        assert_eq!(indices, [Some(0), Some(1)]);
+
+        let wrong_device = PortError::IncorrectDevice {
+            expected: SmartDeviceType::Motor,
+            actual: SmartDeviceType::Imu,
+            port: 7,
+        };
+        assert_eq!(group.motor_index(&wrong_device), Some(1));
+        assert_eq!(
+            group.motor_index(&PortError::Disconnected { port: 1 }),
+            None
+        );
+    }
+}
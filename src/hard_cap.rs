@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+
+use vexide::{
+    prelude::Gearset,
+    smart::{
+        PortError,
+        motor::{Motor, MotorControl},
+    },
+};
+
+use crate::MotorGroup;
+
+/// Returns `target` limited to `cap` volts, for a motor with a maximum
+/// voltage of `max_voltage` and a free speed of `free_rpm`.
+///
+/// Voltages are clamped to `±cap`. Velocities, including the velocity of a
+/// position target, are clamped to the same fraction of the free speed as
+/// `cap` is of the maximum voltage. Brakes are left alone, and so is every
+/// target if `cap` is at least the maximum voltage.
+pub(crate) fn cap_target(
+    target: MotorControl,
+    cap: f64,
+    max_voltage: f64,
+    free_rpm: f64,
+) -> MotorControl {
+    if cap >= max_voltage {
+        return target;
+    }
+    let max_rpm = (free_rpm * cap / max_voltage).floor();
+    let cap_rpm = |rpm: i32| f64::from(rpm).clamp(-max_rpm, max_rpm) as i32;
+    match target {
+        MotorControl::Voltage(volts) => MotorControl::Voltage(volts.clamp(-cap, cap)),
+        MotorControl::Velocity(rpm) => MotorControl::Velocity(cap_rpm(rpm)),
+        MotorControl::Position(position, velocity) => {
+            MotorControl::Position(position, cap_rpm(velocity))
+        }
+        other => other,
+    }
+}
+
+/// Returns `target` limited to the hard voltage cap `cap` for `motor`, if
+/// there is one.
+///
+/// The free speed comes from `gearset` if it's known, or else from reading the
+/// motor's gearset, which is only needed for velocity and position targets
+/// that the cap actually limits.
+pub(crate) fn capped_target(
+    motor: &Motor,
+    target: MotorControl,
+    cap: Option<f64>,
+    gearset: Option<Gearset>,
+) -> Result<MotorControl, PortError> {
+    let max_voltage = motor.max_voltage();
+    let Some(cap) = cap.filter(|&cap| cap < max_voltage) else {
+        return Ok(target);
+    };
+    let free_rpm = match (target, gearset) {
+        // Only velocities are scaled by the free speed
+        (MotorControl::Voltage(_) | MotorControl::Brake(_), _) => 0.0,
+        (_, Some(gearset)) => gearset.max_rpm(),
+        (_, None) => motor.gearset()?.max_rpm(),
+    };
+    Ok(cap_target(target, cap, max_voltage, free_rpm))
+}
+
+/// Returns whether `volts` can be used as a hard voltage cap. Anything else
+/// would make the clamps in [`cap_target`] panic.
+fn is_valid_cap(volts: f64) -> bool {
+    volts.is_finite() && volts >= 0.0
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Caps the output of every motor in the group at `volts`, for good.
+    ///
+    /// This is a failsafe for when the group must never run at full power,
+    /// such as a demo robot driven by beginners. Unlike
+    /// [`MotorGroup::set_voltage_limit`] or [`MotorGroup::apply_config`], the
+    /// cap can only be lowered: if the group already has a lower cap, this
+    /// returns `false` and leaves it alone. Raising it takes a new group or
+    /// [`MotorGroup::override_hard_voltage_cap`].
+    ///
+    /// The cap applies to every command the group writes, after everything
+    /// else that changes the command (such as [`MotorGroup::set_scale`] and
+    /// [`MotorGroup::set_position_limits`]):
+    ///
+    /// - Voltages are clamped to `±volts`.
+    /// - Velocities, including the velocity of a position target and
+    ///   [`MotorGroup::set_profiled_velocity`], are clamped to the same
+    ///   fraction of the gearset's free speed as `volts` is of the motor's
+    ///   maximum voltage. The free speed is that of the group's gearset (see
+    ///   [`MotorGroup::max_rpm`]), or read from each motor if it hasn't been
+    ///   set.
+    ///
+    /// [`MotorGroup::max_voltage`] reports the cap if it's lower than the
+    /// motors' maximum, so code that scales its output by it keeps its full
+    /// range of control. The cap is software only: it isn't written to the
+    /// motors, and a [checked out](MotorGroup::checkout) motor isn't capped.
+    ///
+    /// A cap that is negative or not finite (including NaN) can't be applied,
+    /// so this returns `false` and leaves the current cap alone.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     // Never more than half power, whatever the rest of the code does
+    ///     drive.set_hard_voltage_cap(6.0);
+    ///
+    ///     // Still capped at 6V
+    ///     assert!(!drive.set_hard_voltage_cap(12.0));
+    ///     _ = drive.set_voltage(12.0);
+    /// }
+    /// ```
+    pub fn set_hard_voltage_cap(&mut self, volts: f64) -> bool {
+        if !is_valid_cap(volts) || self.hard_voltage_cap.is_some_and(|cap| cap < volts) {
+            return false;
+        }
+        self.hard_voltage_cap = Some(volts);
+        true
+    }
+
+    /// Sets or removes the group's hard voltage cap, even if that raises it.
+    ///
+    /// This defeats the point of [`MotorGroup::set_hard_voltage_cap`], so
+    /// only use it from code that's meant to lift the cap, such as a
+    /// supervisor's override.
+    ///
+    /// Returns `false` and leaves the current cap alone if `volts` is negative
+    /// or not finite (including NaN).
+    pub fn override_hard_voltage_cap(&mut self, volts: Option<f64>) -> bool {
+        if volts.is_some_and(|volts| !is_valid_cap(volts)) {
+            return false;
+        }
+        self.hard_voltage_cap = volts;
+        true
+    }
+
+    /// Returns the group's hard voltage cap in volts, if it has one.
+    ///
+    /// See [`MotorGroup::set_hard_voltage_cap`].
+    pub fn hard_voltage_cap(&self) -> Option<f64> {
+        self.hard_voltage_cap
+    }
+
+    /// Returns `targets[i]` limited to the hard voltage cap for motor `i`, for
+    /// every active motor in the group. Inactive motors get their target as
+    /// is, since it's never written.
+    pub(crate) fn capped_targets(
+        &self,
+        targets: &[MotorControl],
+    ) -> Vec<Result<MotorControl, PortError>> {
+        self.motors
+            .as_ref()
+            .iter()
+            .zip(&self.meta)
+            .zip(targets)
+            .map(|((motor, meta), &target)| {
+                if meta.is_active() {
+                    capped_target(motor, target, self.hard_voltage_cap, self.config.gearset)
+                } else {
+                    Ok(target)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use super::{cap_target, capped_target};
+    use crate::{
+        GroupConfig, MotorGroup,
+        tests::{mock_motors, v5_motor},
+    };
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(2, Gearset::Blue))
+    }
+
+    #[test]
+    fn voltages_are_clamped() {
+        for (volts, capped) in [(12.0, 6.0), (-12.0, -6.0), (4.0, 4.0), (-6.0, -6.0)] {
+            assert_eq!(
+                cap_target(MotorControl::Voltage(volts), 6.0, 12.0, 600.0),
+                MotorControl::Voltage(capped)
+            );
+        }
+        let brake = MotorControl::Brake(BrakeMode::Hold);
+        assert_eq!(cap_target(brake, 6.0, 12.0, 600.0), brake);
+    }
+
+    #[test]
+    fn velocities_are_scaled_proportionally() {
+        // Half the voltage allows half the free speed
+        assert_eq!(
+            cap_target(MotorControl::Velocity(600), 6.0, 12.0, 600.0),
+            MotorControl::Velocity(300)
+        );
+        assert_eq!(
+            cap_target(MotorControl::Velocity(-450), 6.0, 12.0, 600.0),
+            MotorControl::Velocity(-300)
+        );
+        assert_eq!(
+            cap_target(MotorControl::Velocity(100), 6.0, 12.0, 600.0),
+            MotorControl::Velocity(100)
+        );
+
+        // An EXP motor at 4V out of 8V
+        let target = MotorControl::Position(Angle::from_degrees(90.0), 200);
+        assert_eq!(
+            cap_target(target, 4.0, 8.0, 200.0),
+            MotorControl::Position(Angle::from_degrees(90.0), 100)
+        );
+    }
+
+    #[test]
+    fn a_cap_above_the_maximum_does_nothing() {
+        let target = MotorControl::Velocity(700);
+        assert_eq!(cap_target(target, 12.0, 12.0, 600.0), target);
+        assert_eq!(
+            cap_target(MotorControl::Voltage(12.0), 10.0, 8.0, 200.0),
+            MotorControl::Voltage(12.0)
+        );
+    }
+
+    #[test]
+    fn the_gearset_is_only_read_if_the_cap_applies() {
+        // The mock motor's gearset can't be read
+        let motor = v5_motor(1);
+        let target = MotorControl::Velocity(700);
+        assert_eq!(capped_target(&motor, target, Some(12.0), None), Ok(target));
+        assert_eq!(capped_target(&motor, target, None, None), Ok(target));
+        assert!(capped_target(&motor, target, Some(6.0), None).is_err());
+    }
+
+    #[test]
+    fn the_cap_can_only_be_lowered() {
+        let mut group = group();
+        assert_eq!(group.max_voltage(), Motor::V5_MAX_VOLTAGE);
+
+        assert!(group.set_hard_voltage_cap(8.0));
+        assert!(group.set_hard_voltage_cap(6.0));
+        assert!(!group.set_hard_voltage_cap(10.0));
+        assert_eq!(group.hard_voltage_cap(), Some(6.0));
+        assert_eq!(group.max_voltage(), 6.0);
+
+        // Configuration can't raise it either
+        _ = group.apply_config(&GroupConfig {
+            voltage_limit: Some(12.0),
+            ..GroupConfig::DEFAULT
+        });
+        assert_eq!(group.hard_voltage_cap(), Some(6.0));
+
+        group.override_hard_voltage_cap(Some(10.0));
+        assert_eq!(group.max_voltage(), 10.0);
+        group.override_hard_voltage_cap(None);
+        assert_eq!(group.max_voltage(), Motor::V5_MAX_VOLTAGE);
+    }
+
+    #[test]
+    fn invalid_caps_are_rejected() {
+        let mut group = group();
+        for volts in [f64::NAN, -1.0, f64::NEG_INFINITY, f64::INFINITY] {
+            assert!(!group.set_hard_voltage_cap(volts), "{volts}");
+            assert!(!group.override_hard_voltage_cap(Some(volts)), "{volts}");
+        }
+        assert_eq!(group.hard_voltage_cap(), None);
+
+        assert!(group.set_hard_voltage_cap(6.0));
+        for volts in [f64::NAN, -1.0] {
+            assert!(!group.set_hard_voltage_cap(volts), "{volts}");
+            assert!(!group.override_hard_voltage_cap(Some(volts)), "{volts}");
+        }
+        assert_eq!(group.hard_voltage_cap(), Some(6.0));
+        // Writes still go through the valid cap without panicking
+        _ = group.set_voltage(12.0);
+        assert!(!group.set_hard_voltage_cap(8.0));
+    }
+
+    #[test]
+    fn the_cap_survives_splits_and_merges() {
+        let mut group = MotorGroup::new(mock_motors(4, Gearset::Blue));
+        group.set_hard_voltage_cap(6.0);
+
+        // The split-off motors are capped too, and can't be raised
+        let mut back = group.split(2).unwrap();
+        assert_eq!(back.hard_voltage_cap(), Some(6.0));
+        assert!(!back.set_hard_voltage_cap(12.0));
+
+        // Merging into an uncapped group keeps the cap
+        let mut uncapped = back.split(1).unwrap();
+        uncapped.override_hard_voltage_cap(None);
+        uncapped.merge(back);
+        assert_eq!(uncapped.hard_voltage_cap(), Some(6.0));
+
+        // Merging a group with a higher cap doesn't raise it
+        let mut raised = uncapped.split(1).unwrap();
+        raised.override_hard_voltage_cap(Some(10.0));
+        group.merge(raised);
+        assert_eq!(group.hard_voltage_cap(), Some(6.0));
+        raised = group.split(2).unwrap();
+        raised.override_hard_voltage_cap(Some(4.0));
+        group.merge(raised);
+        assert_eq!(group.hard_voltage_cap(), Some(4.0));
+    }
+
+    #[test]
+    fn every_written_target_is_capped() {
+        let mut group = group();
+        group.set_hard_voltage_cap(6.0);
+        _ = group.set_gearset(Gearset::Blue);
+        group.set_scale(1, 0.5);
+
+        // Scaling a target past the cap doesn't get around it
+        let targets = group.scaled_targets(MotorControl::Voltage(24.0));
+        assert_eq!(
+            group.capped_targets(&targets),
+            [
+                Ok(MotorControl::Voltage(6.0)),
+                Ok(MotorControl::Voltage(6.0))
+            ]
+        );
+        let targets = group.scaled_targets(MotorControl::Velocity(600));
+        assert_eq!(
+            group.capped_targets(&targets),
+            [
+                Ok(MotorControl::Velocity(300)),
+                Ok(MotorControl::Velocity(300))
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unknown_gearset_is_read_for_velocities() {
+        let mut group = group();
+        group.set_hard_voltage_cap(6.0);
+
+        // The mock motors' gearsets can't be read, so the velocity isn't
+        // written at all
+        let targets = group.scaled_targets(MotorControl::Velocity(600));
+        assert!(group.capped_targets(&targets).iter().all(Result::is_err));
+        assert!(group.set_velocity(600).is_err());
+
+        // Voltages don't need the gearset
+        let targets = group.scaled_targets(MotorControl::Voltage(12.0));
+        assert!(group.capped_targets(&targets).iter().all(Result::is_ok));
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::vec::Vec;
+
+use vexide::smart::{
+    PortError,
+    motor::{Motor, MotorFaults},
+};
+
+use crate::{
+    GetterResult, MotorGroup, MotorGroupError, SharedMotors,
+    readings::{self, Reading},
+};
+
+/// Returns whether a command should be written after one read of every
+/// motor's fault flags, along with the errors of the motors that couldn't be
+/// read.
+///
+/// A command is written if at least one motor could be read and none of the
+/// motors that could be read reported a fault.
+fn should_write(readings: Vec<Reading<MotorFaults>>) -> (bool, Vec<PortError>) {
+    let (faults, errors) = readings::partition(readings);
+    let healthy = !faults.is_empty()
+        && faults
+            .iter()
+            .all(|(_, faults)| (*faults & MotorFaults::all()).is_empty());
+    (healthy, errors)
+}
+
+/// Finishes [`MotorGroup::set_voltage_if_healthy`]: reports whether the
+/// voltage was written, with the read errors followed by those of the write.
+fn written(
+    mut errors: Vec<PortError>,
+    write: Option<Result<(), MotorGroupError>>,
+) -> GetterResult<bool> {
+    if let Some(Err(error)) = &write {
+        errors.extend_from_slice(&error.errors);
+    }
+    readings::finish(Some(write.is_some()), errors)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets the motor group's voltage (see [`MotorGroup::set_voltage`]) only
+    /// if no motor reports a fault, returning whether the voltage was written.
+    ///
+    /// Every motor's fault flags (see [`Motor::faults`]) are read first, and
+    /// the voltage is then written in a separate step, so a fault that
+    /// appears between the read and the write isn't caught until the next
+    /// call. Over temperature, over current, driver fault and driver over
+    /// current flags all count. If any motor reports one, nothing is written
+    /// and the motors keep their last command, so a faulted mechanism isn't
+    /// driven any harder; brake it or lower its voltage to take load off it.
+    ///
+    /// A motor whose flags can't be read isn't treated as faulted, since it
+    /// can't be written to either, but at least one motor must be read for
+    /// the voltage to be written. Call this every iteration of the control
+    /// loop in place of [`MotorGroup::set_voltage`], so the group picks up
+    /// again once the faults clear. Each call reads every motor's faults
+    /// once.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error, either reading its faults or being written to.
+    ///   The read errors come first. Its result is whether the voltage was
+    ///   written.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     let controller = peripherals.primary_controller;
+    ///
+    ///     loop {
+    ///         let state = controller.state().unwrap_or_default();
+    ///         let volts = state.left_stick.y() * lift.max_voltage();
+    ///         if let Ok(false) = lift.set_voltage_if_healthy(volts) {
+    ///             _ = lift.brake(BrakeMode::Coast);
+    ///         }
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_voltage_if_healthy(&mut self, volts: f64) -> GetterResult<bool> {
+        let (healthy, errors) = should_write(self.read_each(Motor::faults));
+        let write = healthy.then(|| self.set_voltage(volts));
+        written(errors, write)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_voltage_if_healthy`].
+    pub fn set_voltage_if_healthy(&mut self, volts: f64) -> GetterResult<bool> {
+        let result = self.0.borrow_mut().set_voltage_if_healthy(volts);
+        let applied = match &result {
+            Ok(applied) => *applied,
+            Err(error) => error.result == Some(true),
+        };
+        if applied {
+            self.mirror(|shadow| shadow.set_voltage(volts));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        prelude::*,
+        smart::{PortError, motor::MotorFaults},
+    };
+
+    use super::{should_write, written};
+    use crate::{MotorGroup, MotorGroupError, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 2 };
+
+    #[test]
+    fn healthy_groups_are_written() {
+        assert_eq!(
+            should_write(vec![
+                (0, Ok(MotorFaults::empty())),
+                (1, Ok(MotorFaults::empty()))
+            ]),
+            (true, vec![])
+        );
+        // Bits the motor reports that aren't faults are ignored
+        assert_eq!(
+            should_write(vec![(0, Ok(MotorFaults::from_bits_retain(0x100)))]),
+            (true, vec![])
+        );
+        // A motor that can't be read isn't a fault
+        assert_eq!(
+            should_write(vec![(0, Ok(MotorFaults::empty())), (1, Err(DISCONNECTED))]),
+            (true, vec![DISCONNECTED])
+        );
+    }
+
+    #[test]
+    fn faulted_groups_are_not_written() {
+        for fault in [
+            MotorFaults::OVER_TEMPERATURE,
+            MotorFaults::OVER_CURRENT,
+            MotorFaults::DRIVER_FAULT,
+            MotorFaults::DRIVER_OVER_CURRENT,
+        ] {
+            assert_eq!(
+                should_write(vec![(0, Ok(MotorFaults::empty())), (1, Ok(fault))]),
+                (false, vec![])
+            );
+        }
+        // Nothing is known about a group that can't be read at all
+        assert_eq!(
+            should_write(vec![(0, Err(DISCONNECTED))]),
+            (false, vec![DISCONNECTED])
+        );
+    }
+
+    #[test]
+    fn errors_report_whether_the_voltage_was_written() {
+        assert!(written(vec![], Some(Ok(()))).unwrap());
+        assert!(!written(vec![], None).unwrap());
+
+        let write_error = PortError::Disconnected { port: 1 };
+        let error = written(
+            vec![DISCONNECTED],
+            Some(Err(MotorGroupError::new(vec![write_error]))),
+        )
+        .unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED, write_error]);
+        assert_eq!(error.result, Some(true));
+    }
+
+    #[test]
+    fn unreadable_groups_are_not_written() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let generation = group.command_generation();
+
+        let error = group.set_voltage_if_healthy(6.0).unwrap_err();
+        assert_eq!(error.errors, vec![PortError::Disconnected { port: 1 }]);
+        assert_eq!(error.result, Some(false));
+        assert_eq!(group.command_generation(), generation);
+        assert_eq!(group.last_command, None);
+    }
+}
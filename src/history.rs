@@ -0,0 +1,395 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::time::Instant;
+
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError};
+
+/// An aggregate reading of a motor group that can be recorded with
+/// [`MotorGroup::enable_metric_history`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    /// See [`MotorGroup::velocity`], in RPM.
+    Velocity,
+    /// See [`MotorGroup::current`], in Amperes.
+    Current,
+    /// See [`MotorGroup::voltage`], in Volts.
+    Voltage,
+    /// See [`MotorGroup::power`], in Watts.
+    Power,
+    /// See [`MotorGroup::temperature`], in °C.
+    Temperature,
+    /// See [`MotorGroup::position`], in degrees.
+    Position,
+}
+
+impl Metric {
+    /// Every metric, in declaration order.
+    pub const ALL: [Self; 6] = [
+        Self::Velocity,
+        Self::Current,
+        Self::Voltage,
+        Self::Power,
+        Self::Temperature,
+        Self::Position,
+    ];
+}
+
+/// Which metrics [`MotorGroup::record_metrics`] records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetricSet {
+    /// Whether to record [`Metric::Velocity`].
+    pub velocity: bool,
+    /// Whether to record [`Metric::Current`].
+    pub current: bool,
+    /// Whether to record [`Metric::Voltage`].
+    pub voltage: bool,
+    /// Whether to record [`Metric::Power`].
+    pub power: bool,
+    /// Whether to record [`Metric::Temperature`].
+    pub temperature: bool,
+    /// Whether to record [`Metric::Position`].
+    pub position: bool,
+}
+
+impl MetricSet {
+    /// Velocity and current, the usual pair for tuning.
+    pub const TUNING: Self = Self {
+        velocity: true,
+        current: true,
+        voltage: false,
+        power: false,
+        temperature: false,
+        position: false,
+    };
+
+    /// Returns whether `metric` is in the set.
+    pub const fn contains(&self, metric: Metric) -> bool {
+        match metric {
+            Metric::Velocity => self.velocity,
+            Metric::Current => self.current,
+            Metric::Voltage => self.voltage,
+            Metric::Power => self.power,
+            Metric::Temperature => self.temperature,
+            Metric::Position => self.position,
+        }
+    }
+}
+
+/// How [`MotorGroup::enable_metric_history`] records metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryConfig {
+    /// How many samples are kept for each metric. Older samples are dropped.
+    pub capacity: usize,
+    /// The metrics to record.
+    pub metrics: MetricSet,
+}
+
+/// A fixed-capacity ring buffer of `(time, value)` samples.
+///
+/// Every sample is stored twice, `capacity` apart, so that the samples are
+/// always contiguous in chronological order starting at `head`, however the
+/// buffer has wrapped around.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SampleRing {
+    samples: Vec<(Duration, f64)>,
+    capacity: usize,
+    /// The index of the oldest sample.
+    head: usize,
+    len: usize,
+}
+
+impl SampleRing {
+    /// Creates an empty ring, allocating all of its memory up front.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            samples: alloc::vec![(Duration::ZERO, 0.0); capacity * 2],
+            capacity,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Adds a sample, replacing the oldest one if the ring is full.
+    pub(crate) fn push(&mut self, sample: (Duration, f64)) {
+        if self.capacity == 0 {
+            return;
+        }
+        let index = (self.head + self.len) % self.capacity;
+        if self.len < self.capacity {
+            self.len += 1;
+        } else {
+            self.head = (self.head + 1) % self.capacity;
+        }
+        self.samples[index] = sample;
+        self.samples[index + self.capacity] = sample;
+    }
+
+    /// Returns the samples, oldest first.
+    pub(crate) fn as_slice(&self) -> &[(Duration, f64)] {
+        &self.samples[self.head..self.head + self.len]
+    }
+
+    /// Returns the smallest and largest value in the ring.
+    pub(crate) fn range(&self) -> Option<(f64, f64)> {
+        self.as_slice().iter().fold(None, |range, (_, value)| {
+            Some(match range {
+                None => (*value, *value),
+                Some((min, max)) => (value.min(min), value.max(max)),
+            })
+        })
+    }
+}
+
+/// The recorded metrics of a motor group.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MetricHistory {
+    /// When recording started, which sample times are relative to.
+    pub(crate) start: Instant,
+    pub(crate) rings: Vec<(Metric, SampleRing)>,
+}
+
+impl MetricHistory {
+    pub(crate) fn new(config: HistoryConfig, start: Instant) -> Self {
+        Self {
+            start,
+            rings: Metric::ALL
+                .into_iter()
+                .filter(|metric| config.metrics.contains(*metric))
+                .map(|metric| (metric, SampleRing::new(config.capacity)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn ring(&self, metric: Metric) -> Option<&SampleRing> {
+        self.rings
+            .iter()
+            .find(|(candidate, _)| *candidate == metric)
+            .map(|(_, ring)| ring)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Starts recording the group's metrics for plotting.
+    ///
+    /// Every call to [`MotorGroup::record_metrics`] then adds a sample of each
+    /// metric in `config.metrics`, keeping the last `config.capacity` of them.
+    /// The buffers are allocated here, once, and never grow. At one sample
+    /// every 20ms, a capacity of 250 covers the last 5 seconds.
+    ///
+    /// Enabling the history again starts over with the new configuration,
+    /// discarding the samples recorded so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     flywheel.enable_metric_history(HistoryConfig {
+    ///         capacity: 250,
+    ///         metrics: MetricSet::TUNING,
+    ///     });
+    ///     _ = flywheel.set_velocity(550);
+    ///
+    ///     loop {
+    ///         _ = flywheel.record_metrics();
+    ///         if let (Some(samples), Some((min, max))) = (
+    ///             flywheel.metric_history(Metric::Velocity),
+    ///             flywheel.metric_range(Metric::Velocity),
+    ///         ) {
+    ///             // Scale the plot's y axis from min to max and draw the
+    ///             // samples, oldest first
+    ///         }
+    ///         sleep(Duration::from_millis(20)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn enable_metric_history(&mut self, config: HistoryConfig) {
+        self.metric_history = Some(MetricHistory::new(config, Instant::now()));
+    }
+
+    /// Stops recording metrics and frees the recorded history.
+    pub fn disable_metric_history(&mut self) {
+        self.metric_history = None;
+    }
+
+    /// Samples every recorded metric (see
+    /// [`MotorGroup::enable_metric_history`]).
+    ///
+    /// Call this at a steady interval, such as from a telemetry task, so the
+    /// history covers a predictable stretch of time. Each sample is stamped
+    /// with the time since the history was enabled. This does nothing if the
+    /// history isn't enabled.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. A metric is still sampled from the motors that
+    ///   could be read, and is skipped if none could.
+    pub fn record_metrics(&mut self) -> Result<(), MotorGroupError> {
+        // Taken out while sampling, since sampling reads through `self`
+        let Some(mut history) = self.metric_history.take() else {
+            return Ok(());
+        };
+        let at = history.start.elapsed();
+
+        let mut errors: Vec<PortError> = Vec::new();
+        for (metric, ring) in &mut history.rings {
+            let reading = match metric {
+                Metric::Velocity => self.velocity(),
+                Metric::Current => self.current(),
+                Metric::Voltage => self.voltage(),
+                Metric::Power => self.power(),
+                Metric::Temperature => self.temperature(),
+                Metric::Position => self
+                    .position()
+                    .map(|position| position.as_degrees())
+                    .map_err(|error| match error.result {
+                        Some(position) => {
+                            MotorGroupError::with_result(error.errors, position.as_degrees())
+                        }
+                        None => MotorGroupError::with_empty_result(error.errors),
+                    }),
+            };
+            if let Some(value) = partial(reading, &mut errors) {
+                ring.push((at, value));
+            }
+        }
+        self.metric_history = Some(history);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Returns the recorded samples of `metric` as `(time, value)` pairs,
+    /// oldest first, or `None` if it isn't being recorded.
+    ///
+    /// The time of each sample is measured from when the history was
+    /// enabled.
+    pub fn metric_history(&self, metric: Metric) -> Option<&[(Duration, f64)]> {
+        Some(self.metric_history.as_ref()?.ring(metric)?.as_slice())
+    }
+
+    /// Returns the smallest and largest recorded value of `metric`, for
+    /// scaling a plot's axis, or `None` if it isn't being recorded or has no
+    /// samples yet.
+    pub fn metric_range(&self, metric: Metric) -> Option<(f64, f64)> {
+        self.metric_history.as_ref()?.ring(metric)?.range()
+    }
+}
+
+/// Returns a reading's value, including a partial one, moving its errors into
+/// `errors`.
+fn partial(reading: GetterResult<f64>, errors: &mut Vec<PortError>) -> Option<f64> {
+    match reading {
+        Ok(value) => Some(value),
+        Err(error) => {
+            errors.extend(error.errors);
+            error.result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use vexide::prelude::*;
+
+    use super::{HistoryConfig, Metric, MetricHistory, MetricSet, SampleRing};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn ring(capacity: usize, values: &[f64]) -> SampleRing {
+        let mut ring = SampleRing::new(capacity);
+        for (second, value) in values.iter().enumerate() {
+            ring.push((Duration::from_secs(second as u64), *value));
+        }
+        ring
+    }
+
+    fn values(ring: &SampleRing) -> Vec<f64> {
+        ring.as_slice().iter().map(|(_, value)| *value).collect()
+    }
+
+    #[test]
+    fn ring_is_chronological_after_wrapping() {
+        assert_eq!(values(&ring(4, &[])), vec![]);
+        assert_eq!(values(&ring(4, &[1.0, 2.0])), vec![1.0, 2.0]);
+        assert_eq!(
+            values(&ring(4, &[1.0, 2.0, 3.0, 4.0])),
+            vec![1.0, 2.0, 3.0, 4.0]
+        );
+        // Every wrap position keeps the newest samples in order
+        for extra in 1..=8 {
+            let input: Vec<f64> = (0..4 + extra).map(f64::from).collect();
+            assert_eq!(values(&ring(4, &input)), input[input.len() - 4..]);
+        }
+        let ring = ring(3, &[5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(
+            ring.as_slice(),
+            [
+                (Duration::from_secs(1), 6.0),
+                (Duration::from_secs(2), 7.0),
+                (Duration::from_secs(3), 8.0),
+            ]
+        );
+        // Nothing is kept without any capacity
+        assert_eq!(values(&self::ring(0, &[1.0])), vec![]);
+    }
+
+    #[test]
+    fn range_covers_only_the_window() {
+        assert_eq!(ring(4, &[]).range(), None);
+        assert_eq!(ring(4, &[3.0]).range(), Some((3.0, 3.0)));
+        assert_eq!(ring(4, &[-2.0, 9.0, 4.0]).range(), Some((-2.0, 9.0)));
+        // The extremes drop out along with their samples
+        assert_eq!(
+            ring(3, &[-2.0, 9.0, 4.0, 5.0, 6.0]).range(),
+            Some((4.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn only_selected_metrics_are_recorded() {
+        let history = MetricHistory::new(
+            HistoryConfig {
+                capacity: 8,
+                metrics: MetricSet::TUNING,
+            },
+            Instant::now(),
+        );
+        assert!(history.ring(Metric::Velocity).is_some());
+        assert!(history.ring(Metric::Current).is_some());
+        assert!(history.ring(Metric::Position).is_none());
+
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        // Recording does nothing until enabled
+        assert!(group.record_metrics().is_ok());
+        assert_eq!(group.metric_history(Metric::Velocity), None);
+
+        group.enable_metric_history(HistoryConfig {
+            capacity: 8,
+            metrics: MetricSet::TUNING,
+        });
+        // The mock motor can't be read, so nothing is sampled
+        assert_eq!(group.record_metrics().unwrap_err().errors.len(), 2);
+        assert_eq!(group.metric_history(Metric::Velocity), Some(&[][..]));
+        assert_eq!(group.metric_range(Metric::Velocity), None);
+        assert_eq!(group.metric_history(Metric::Voltage), None);
+
+        group.disable_metric_history();
+        assert_eq!(group.metric_history(Metric::Velocity), None);
+    }
+}
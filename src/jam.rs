@@ -0,0 +1,292 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::{
+    PortError,
+    motor::{BrakeMode, Motor},
+};
+
+use crate::{MotorGroup, MotorGroupError, load::StopOnCancel, tick::tick_loop};
+
+/// How long a stall has to last before [`MotorGroup::anti_jam`] reverses.
+///
+/// This rides out the motors' spin up, which looks just like a stall, both
+/// at the start and after every reversal.
+const STALL_CONFIRM: Duration = Duration::from_millis(150);
+
+/// What [`MotorGroup::anti_jam`] is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JamPhase {
+    /// Driving at the drive voltage.
+    Driving,
+    /// Reversing to clear a jam.
+    Reversing,
+}
+
+/// Watches averaged `(current, velocity)` samples for stalls and decides when
+/// to reverse and resume.
+///
+/// This is kept free of hardware access and timing so that it can be tested
+/// with scripted readings.
+#[derive(Debug, Clone)]
+pub(crate) struct JamDetector {
+    stall_current: f64,
+    stall_velocity: f64,
+    reverse_duration: Duration,
+    phase: JamPhase,
+    /// When the current stall started, while driving.
+    stalled_since: Option<Duration>,
+    /// When the current reversal ends, while reversing.
+    reversing_until: Duration,
+}
+
+impl JamDetector {
+    pub(crate) fn new(stall_current: f64, stall_velocity: f64, reverse_duration: Duration) -> Self {
+        Self {
+            stall_current,
+            stall_velocity,
+            reverse_duration,
+            phase: JamPhase::Driving,
+            stalled_since: None,
+            reversing_until: Duration::ZERO,
+        }
+    }
+
+    /// Adds a sample taken `elapsed` after the start, or `None` if the group
+    /// couldn't be read, and returns the new phase if it changed.
+    pub(crate) fn update(
+        &mut self,
+        elapsed: Duration,
+        sample: Option<(f64, f64)>,
+    ) -> Option<JamPhase> {
+        match self.phase {
+            JamPhase::Reversing => {
+                if elapsed < self.reversing_until {
+                    return None;
+                }
+                self.phase = JamPhase::Driving;
+                self.stalled_since = None;
+            }
+            JamPhase::Driving => {
+                let stalled = sample.is_some_and(|(current, velocity)| {
+                    current >= self.stall_current && velocity.abs() <= self.stall_velocity
+                });
+                if !stalled {
+                    self.stalled_since = None;
+                    return None;
+                }
+                let since = *self.stalled_since.get_or_insert(elapsed);
+                if elapsed - since < STALL_CONFIRM {
+                    return None;
+                }
+                self.phase = JamPhase::Reversing;
+                self.reversing_until = elapsed + self.reverse_duration;
+            }
+        }
+        Some(self.phase)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Runs the group at `drive_voltage`, briefly reversing whenever it jams.
+    ///
+    /// This is the usual intake pattern for game pieces that get stuck. Every
+    /// tick (see [`MotorGroup::tick_interval`], 5ms by default), the group's
+    /// average current and velocity are read, and the control loop is:
+    ///
+    /// 1. Drive at `drive_voltage`.
+    /// 2. Once the current is at least `stall_current` Amperes **and** the
+    ///    velocity is within `stall_velocity` RPM of zero continuously for
+    ///    150ms, the group is stalled. The wait rides out the motors' spin up,
+    ///    which looks just like a stall.
+    /// 3. Run at `reverse_voltage` (usually the opposite sign of
+    ///    `drive_voltage`) for `reverse_duration`, then go back to 1.
+    ///
+    /// A sample that can't be read from any motor doesn't count as a stall.
+    /// Readings from only some motors are averaged as usual. Each tick reads
+    /// every motor's current and velocity once, and writes to every motor
+    /// only when switching between driving and reversing.
+    ///
+    /// This runs until the future is dropped, such as by racing it against
+    /// a button press, and the motors are then braked with
+    /// [`BrakeMode::Brake`] so they are never left running. A voltage that
+    /// can't be written to some motors is tolerated, since the others can
+    /// still clear a jam.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned, after braking the motors,
+    ///   if no motor device is currently connected to the Smart Port when a
+    ///   voltage is written. It contains the errors of that write followed by
+    ///   those of the brake. Read errors aren't reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Intake for the rest of autonomous, clearing jams as they happen
+    ///     if let Err(error) = intake
+    ///         .anti_jam(12.0, -8.0, 2.0, 20.0, Duration::from_millis(250))
+    ///         .await
+    ///     {
+    ///         println!("Intake error: {error:?}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn anti_jam(
+        &mut self,
+        drive_voltage: f64,
+        reverse_voltage: f64,
+        stall_current: f64,
+        stall_velocity: f64,
+        reverse_duration: Duration,
+    ) -> Result<(), MotorGroupError> {
+        let mut guard = StopOnCancel {
+            group: self,
+            armed: true,
+        };
+        let mut detector = JamDetector::new(stall_current, stall_velocity, reverse_duration);
+        let active = guard
+            .group
+            .meta
+            .iter()
+            .filter(|meta| meta.is_active())
+            .count();
+        let interval = guard.group.config.tick_interval;
+        let mut write = Some(drive_voltage);
+        let mut errors: Vec<PortError> = tick_loop(interval, None, |elapsed| {
+            if write.is_none() {
+                let sample = guard
+                    .group
+                    .current()
+                    .or_else(|error| error.result.ok_or(()))
+                    .ok()
+                    .zip(
+                        guard
+                            .group
+                            .velocity()
+                            .or_else(|error| error.result.ok_or(()))
+                            .ok(),
+                    );
+                write = match detector.update(elapsed, sample) {
+                    Some(JamPhase::Reversing) => Some(reverse_voltage),
+                    Some(JamPhase::Driving) => Some(drive_voltage),
+                    None => None,
+                };
+            }
+            // Writes that fail on only some motors are tolerated, since the
+            // others can still clear the jam
+            if let Some(voltage) = write.take()
+                && let Err(error) = guard.group.set_voltage(voltage)
+                && error.errors.len() >= active
+            {
+                return ControlFlow::Break(error.errors);
+            }
+            ControlFlow::Continue(())
+        })
+        .await;
+
+        guard.armed = false;
+        if let Err(error) = guard.group.brake(BrakeMode::Brake) {
+            errors.extend(error.errors);
+        }
+        Err(MotorGroupError::new(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use vexide::{
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use super::{JamDetector, JamPhase};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(1, Gearset::Blue))
+    }
+
+    /// Feeds `samples` to a detector, one every 50ms starting at `start`
+    /// milliseconds, and returns each phase change with its time.
+    fn feed(
+        detector: &mut JamDetector,
+        start: u64,
+        samples: &[Option<(f64, f64)>],
+    ) -> Vec<(u64, JamPhase)> {
+        samples
+            .iter()
+            .enumerate()
+            .filter_map(|(index, sample)| {
+                let millis = start + 50 * index as u64;
+                detector
+                    .update(Duration::from_millis(millis), *sample)
+                    .map(|phase| (millis, phase))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stall_is_reversed_then_cleared() {
+        let mut detector = JamDetector::new(2.0, 20.0, Duration::from_millis(200));
+        let running = Some((0.8, 550.0));
+        let stalled = Some((2.4, -5.0));
+
+        // A short stall, such as while spinning up, isn't a jam, and neither
+        // is a failed read
+        let spin_up = [stalled, stalled, running, None, running];
+        assert_eq!(feed(&mut detector, 0, &spin_up), vec![]);
+
+        // A jam held for 150ms is reversed, for 200ms, and then driving
+        // resumes
+        let jam = [stalled; 8];
+        assert_eq!(
+            feed(&mut detector, 250, &jam),
+            vec![(400, JamPhase::Reversing), (600, JamPhase::Driving)]
+        );
+        // Resuming starts a fresh wait, so spinning back up isn't a jam
+        assert_eq!(feed(&mut detector, 650, &[running; 4]), vec![]);
+
+        // A jam that doesn't clear is reversed again, once it has lasted
+        // long enough after resuming
+        assert_eq!(
+            feed(&mut detector, 850, &[stalled; 12]),
+            vec![
+                (1000, JamPhase::Reversing),
+                (1200, JamPhase::Driving),
+                (1400, JamPhase::Reversing),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_errors_stop_the_group() {
+        let group = group();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .anti_jam(12.0, -8.0, 2.0, 20.0, Duration::from_millis(250))
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        // The mock motor can't be written to: the drive and the brake fail
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Brake))
+        );
+    }
+}
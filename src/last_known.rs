@@ -0,0 +1,139 @@
+//! The "last known values" cache used by [`SharedMotors`](crate::SharedMotors).
+//!
+//! Values are stored in plain [`Cell`]s so they can be read while the shared
+//! motor group is mutably borrowed elsewhere.
+
+use core::{cell::Cell, time::Duration};
+use std::time::Instant;
+
+use vexide::math::Angle;
+
+use crate::GetterResult;
+
+/// A single cached value and the time it was read.
+#[derive(Debug)]
+pub(crate) struct LastKnownValue<T: Copy>(Cell<Option<(T, Instant)>>);
+
+impl<T: Copy> LastKnownValue<T> {
+    /// Stores `value` as read at `at`.
+    pub(crate) fn record(&self, value: T, at: Instant) {
+        self.0.set(Some((value, at)));
+    }
+
+    /// Returns the stored value and its age at `now`, or `None` if no value
+    /// has been stored.
+    pub(crate) fn get(&self, now: Instant) -> Option<(T, Duration)> {
+        self.0
+            .get()
+            .map(|(value, at)| (value, now.saturating_duration_since(at)))
+    }
+
+    /// Forgets the stored value.
+    pub(crate) fn clear(&self) {
+        self.0.set(None);
+    }
+}
+
+impl<T: Copy> Default for LastKnownValue<T> {
+    fn default() -> Self {
+        Self(Cell::new(None))
+    }
+}
+
+/// The last successful reading of each getter on a shared motor group.
+///
+/// Nothing is recorded unless the cache is enabled.
+#[derive(Debug, Default)]
+pub(crate) struct LastKnownCache {
+    pub(crate) enabled: Cell<bool>,
+    pub(crate) velocity: LastKnownValue<f64>,
+    pub(crate) position: LastKnownValue<Angle>,
+    pub(crate) voltage: LastKnownValue<f64>,
+    pub(crate) current: LastKnownValue<f64>,
+    pub(crate) power: LastKnownValue<f64>,
+    pub(crate) torque: LastKnownValue<f64>,
+    pub(crate) efficiency: LastKnownValue<f64>,
+    pub(crate) temperature: LastKnownValue<f64>,
+}
+
+impl LastKnownCache {
+    /// Stores the value of `result` in `slot` if the cache is enabled and the
+    /// getter succeeded for every motor.
+    pub(crate) fn remember<T: Copy>(&self, slot: &LastKnownValue<T>, result: &GetterResult<T>) {
+        if let (true, Ok(value)) = (self.enabled.get(), result) {
+            slot.record(*value, Instant::now());
+        }
+    }
+
+    /// Enables or disables the cache. Disabling it forgets every stored value.
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+        if !enabled {
+            self.velocity.clear();
+            self.position.clear();
+            self.voltage.clear();
+            self.current.clear();
+            self.power.clear();
+            self.torque.clear();
+            self.efficiency.clear();
+            self.temperature.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use vexide::smart::PortError;
+
+    use super::{LastKnownCache, LastKnownValue};
+    use crate::MotorGroupError;
+
+    #[test]
+    fn never_read_value_is_none() {
+        let value = LastKnownValue::<f64>::default();
+        assert_eq!(value.get(Instant::now()), None);
+    }
+
+    #[test]
+    fn age_is_measured_from_the_read() {
+        let value = LastKnownValue::default();
+        let read_at = Instant::now();
+        value.record(12.0, read_at);
+        assert_eq!(
+            value.get(read_at + Duration::from_millis(40)),
+            Some((12.0, Duration::from_millis(40)))
+        );
+        // A timestamp from before the read doesn't underflow
+        value.record(12.0, read_at + Duration::from_millis(10));
+        assert_eq!(value.get(read_at), Some((12.0, Duration::ZERO)));
+
+        value.clear();
+        assert_eq!(value.get(read_at), None);
+    }
+
+    #[test]
+    fn only_successful_reads_are_remembered() {
+        let cache = LastKnownCache::default();
+        // Disabled by default
+        cache.remember(&cache.velocity, &Ok(100.0));
+        assert_eq!(cache.velocity.get(Instant::now()), None);
+
+        cache.set_enabled(true);
+        cache.remember(&cache.velocity, &Ok(100.0));
+        // A partial result doesn't replace the last fully successful one
+        cache.remember(
+            &cache.velocity,
+            &Err(MotorGroupError::with_result(
+                vec![PortError::Disconnected { port: 1 }],
+                50.0,
+            )),
+        );
+        assert_eq!(cache.velocity.get(Instant::now()).unwrap().0, 100.0);
+
+        cache.set_enabled(false);
+        assert_eq!(cache.velocity.get(Instant::now()), None);
+    }
+}
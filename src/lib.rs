@@ -20,6 +20,34 @@
 //! cargo add vexide-motorgroup
 //! ```
 //!
+//! ### Features
+//!
+//! The core group, its errors and its write strategies are always available.
+//! Everything else can be compiled out by turning off default features and
+//! picking the subsystems you use:
+//!
+//! - `control` (default): Async and closed-loop control, such as
+//!   `MotorGroup::transition`, `MotorGroup::approach_velocity` and
+//!   `MotorGroup::require_velocity`.
+//! - `drivetrain` (default): Drivetrain helpers, such as `MotorGroup::set_tank`
+//!   and `MotorGroup::weighted_output_velocity`.
+//! - `telemetry` (default): Write timing and the metric history.
+//! - `diagnostics` (default): Health checks and reports, such as
+//!   `MotorGroup::diagnostic_report`, `MotorGroup::readiness`,
+//!   `FleetSummary` and wear tracking.
+//! - `events` (default): The group event channel, `MotorGroup::events`.
+//!   Without it, [`GroupEvent`] still exists but nothing is delivered.
+//! - `mock`: Builds vexide against its mock SDK, so code using motor groups
+//!   can be unit tested on the host.
+//! - `serde`: Implements `serde::Serialize` for `ConfigSnapshot`,
+//!   `OutputModifiers` and the settings they contain, for logging.
+//! - `vexide-unstable`: Exposes group-level passthroughs for vexide APIs that
+//!   aren't stable yet, such as `MotorGroup::set_velocity_pid_constants`.
+//!   These follow upstream vexide and can change with any release.
+//! - `no-panic`: Only checks for an empty motor group in [`MotorGroup::new`]
+//!   in debug builds, so release builds never panic there. Create groups with
+//!   [`MotorGroup::try_from`] to check at runtime instead.
+//!
 //! ## Usage
 //!
 //! Normally, you would have to set each motor's target and other values
@@ -64,6 +92,31 @@
 //! }
 //! ```
 //!
+//! ## Positions
+//!
+//! Every position in this crate, whether read with [`MotorGroup::position`] or
+//! written with [`MotorGroup::set_position_target`], is a vexide
+//! [`Angle`]. It's re-exported from this crate so `use vexide_motorgroup::*`
+//! brings it into scope alongside the motor group types.
+//!
+//! ## Tasks and threads
+//!
+//! vexide runs every task on a single thread, and `vexide::task::spawn` doesn't
+//! require its future to be `Send`. To use a group from several tasks, share
+//! it with a [`SharedMotors`], the sanctioned cross-task handle: its clones,
+//! [`WeakSharedMotors`] and [`MotorGroupGuard`] are built on `Rc` and
+//! `RefCell`, so they are neither `Send` nor `Sync`, and the compiler keeps
+//! them on the thread that created them.
+//!
+//! A [`MotorGroup`] itself owns its motors and is `Send`, so it can be moved
+//! to another thread, such as in host tests with the `mock` feature. It isn't
+//! `Sync`, since reading through `&MotorGroup` updates its caches. To share a
+//! group across threads, use a [`SendSharedMotors`] instead, which locks the
+//! group with a `Mutex` and is both `Send` and `Sync`. Custom write policies
+//! (see [`MotorGroup::set_custom_write_policy`]) have to be `Send` to keep
+//! the group `Send`. [`EventReceiver`]s and the plain data types, such as
+//! [`GroupConfig`] and the errors, are both `Send` and `Sync`.
+//!
 //! ## Error handling
 //!
 //! ### Read errors
@@ -86,22 +139,157 @@
 
 extern crate alloc;
 
+mod checkout;
+mod compensation;
+mod config;
+mod config_bytes;
+#[cfg(feature = "control")]
+mod control;
+mod current_limit;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod direction;
+mod erased;
+mod events;
+mod fallback;
+#[cfg(feature = "diagnostics")]
+mod faults;
+#[cfg(feature = "diagnostics")]
+mod fleet;
+mod gauges;
+mod group_control;
+mod hard_cap;
+mod healthy;
+#[cfg(feature = "telemetry")]
+mod history;
+#[cfg(feature = "control")]
+mod jam;
+mod last_known;
+mod limits;
+#[cfg(feature = "control")]
+mod load;
 mod macros;
+mod markers;
+mod mechanical;
+mod membership;
+mod meta;
+mod modifiers;
+mod position;
+mod predicates;
+mod presets;
+#[cfg(feature = "control")]
+mod profile;
+mod read_cache;
+#[cfg(feature = "diagnostics")]
+mod readiness;
+mod readings;
+mod recovery;
+mod reference;
+#[cfg(feature = "diagnostics")]
+mod report;
+#[cfg(feature = "control")]
+mod require;
+mod reset;
+mod safety;
+mod send_shared_motors;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "control")]
+mod settle;
+mod shadow;
 mod shared_motors;
+mod shift;
+#[cfg(feature = "drivetrain")]
+mod sides;
+mod snapshot;
+mod startup;
+mod stats;
+#[cfg(feature = "drivetrain")]
+mod tank;
+mod targeting;
+mod task_guard;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "diagnostics")]
+mod thermal;
+mod tick;
+#[cfg(feature = "telemetry")]
+mod timing;
+#[cfg(feature = "vexide-unstable")]
+mod tuning;
+mod units;
+mod validation;
+mod velocity;
+#[cfg(feature = "diagnostics")]
+mod verify;
+mod visitor;
+#[cfg(feature = "diagnostics")]
+mod wear;
+#[cfg(feature = "drivetrain")]
+mod wheels;
+mod write_policy;
+mod zero;
 
-pub use shared_motors::SharedMotors;
+pub use checkout::MotorCheckout;
+pub use config::{ConfigSnapshot, ConfigureError, GroupConfig};
+pub use config_bytes::{DecodeError, EncodeError};
+#[cfg(feature = "control")]
+pub use control::TransitionError;
+pub use current_limit::{CurrentLimitPolicy, MaxCurrentTable, SetCurrentLimitError};
+pub use erased::ErasedGroup;
+#[cfg(feature = "events")]
+pub use events::EventReceiver;
+pub use events::GroupEvent;
+pub use fallback::PositionFallback;
+#[cfg(feature = "diagnostics")]
+pub use faults::FaultError;
+#[cfg(feature = "diagnostics")]
+pub use fleet::{FleetMotor, FleetSummary};
+pub use gauges::Sign;
+pub use group_control::GroupControl;
+#[cfg(feature = "telemetry")]
+pub use history::{HistoryConfig, Metric, MetricSet};
+#[cfg(feature = "control")]
+pub use load::{LoadResult, LoadSignature};
+pub use modifiers::OutputModifiers;
+pub use position::{GroupPosition, TargetDistanceError};
+pub use predicates::{GroupPredicateResult, PredicateErrorStrategy};
+pub use presets::{Preset, PresetError};
+#[cfg(feature = "diagnostics")]
+pub use readiness::{ReadinessCheck, ReadinessCriteria, ReadinessReport};
+pub use recovery::{RecoveryOptions, RecoveryReport, RecoveryStep};
+#[cfg(feature = "control")]
+pub use require::RequireVelocityError;
+pub use send_shared_motors::SendSharedMotors;
+#[cfg(feature = "control")]
+pub use settle::StopError;
+pub use shared_motors::{MotorGroupGuard, SharedMotors};
+#[cfg(feature = "drivetrain")]
+pub use sides::DriveSide;
+pub use snapshot::GroupSnapshot;
+pub use stats::Stats;
+#[cfg(feature = "drivetrain")]
+pub use tank::TankError;
+pub use targeting::TargetingMode;
+pub use task_guard::{TaskGuard, WeakSharedMotors};
+#[cfg(feature = "telemetry")]
+pub use timing::{WriteTiming, WriteTimingStats};
+pub use units::VelocityUnit;
+pub use validation::{ConfigValidation, ConfigWarning};
+pub use velocity::SetVelocityError;
+pub use vexide::math::Angle;
+pub use visitor::MotorVisitor;
+pub use write_policy::{PolicyDecision, WriteContext, WriteKind};
 
 use alloc::vec::Vec;
 use vexide::{
-    math::Angle,
     prelude::{Direction, Gearset},
     smart::{
         PortError,
         motor::{BrakeMode, Motor, MotorControl, SetGearsetError},
     },
 };
+use write_policy::WriteFailure;
 
 /// An error that occurs when controlling a motor group.
 ///
@@ -120,6 +308,18 @@ pub struct MotorGroupError<E = PortError, T = ()> {
     pub result: Option<T>,
 }
 
+/// Panics with `message` if `condition` is false.
+///
+/// With the `no-panic` feature, the condition is only checked in debug builds.
+#[track_caller]
+fn check_invariant(condition: bool, message: &str) {
+    if cfg!(feature = "no-panic") {
+        debug_assert!(condition, "{message}");
+    } else {
+        assert!(condition, "{message}");
+    }
+}
+
 impl<E> MotorGroupError<E, ()> {
     /// Creates a new motor group error from a `Vec` of motor errors.
     ///
@@ -127,9 +327,9 @@ impl<E> MotorGroupError<E, ()> {
     ///
     /// Panics if the errors vector is empty.
     pub(crate) fn new(errors: Vec<E>) -> Self {
-        assert!(
+        check_invariant(
             !errors.is_empty(),
-            "Cannot create a MotorGroupError with no errors"
+            "Cannot create a MotorGroupError with no errors",
         );
         Self {
             errors,
@@ -140,9 +340,9 @@ impl<E> MotorGroupError<E, ()> {
 
 impl<E, T> MotorGroupError<E, T> {
     pub(crate) fn with_result(errors: Vec<E>, result: T) -> Self {
-        assert!(
+        check_invariant(
             !errors.is_empty(),
-            "Cannot create a MotorGroupError with no errors"
+            "Cannot create a MotorGroupError with no errors",
         );
         Self {
             errors,
@@ -151,9 +351,9 @@ impl<E, T> MotorGroupError<E, T> {
     }
 
     pub(crate) fn with_empty_result(errors: Vec<E>) -> Self {
-        assert!(
+        check_invariant(
             !errors.is_empty(),
-            "Cannot create a MotorGroupError with no errors"
+            "Cannot create a MotorGroupError with no errors",
         );
         Self {
             errors,
@@ -222,6 +422,11 @@ pub enum WriteErrorStrategy {
     /// state at all times (e.g. a subsystem should either 100% work or not work
     /// at all.)
     Stop,
+    /// Ask the callback given to [`MotorGroup::set_custom_write_policy`] what
+    /// to do after each failed write.
+    ///
+    /// Without a callback, this acts like [`WriteErrorStrategy::Ignore`].
+    Custom,
 }
 
 /// A group of motors that can be controlled together.
@@ -230,10 +435,59 @@ pub enum WriteErrorStrategy {
 /// control all motors in the group at once as if they were a single motor.
 ///
 /// A motor group is guaranteed to have at least one motor in it.
+///
+/// # Group size
+///
+/// Groups of 1 to 21 motors (every Smart Port on a V5 Brain) are supported
+/// and tested. Every getter and write visits each motor once, so its cost
+/// grows linearly with the size of the group; on a large group it's worth
+/// reading a value once per loop iteration rather than calling the same getter
+/// several times. Averages are computed with compensated summation, so they
+/// stay accurate regardless of group size or the order of the motors.
 #[derive(Debug)]
 pub struct MotorGroup<M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>> {
     pub(crate) motors: M,
-    write_error_strategy: WriteErrorStrategy,
+    pub(crate) config: GroupConfig,
+    pub(crate) meta: Vec<meta::MotorMeta>,
+    /// The last motion command given to the group, whether or not it reached
+    /// every motor. A position target's velocity follows
+    /// [`MotorGroup::set_profiled_velocity`].
+    pub(crate) last_command: Option<MotorControl>,
+    /// See [`MotorGroup::command_generation`].
+    pub(crate) command_generation: u64,
+    /// See [`MotorGroup::set_read_cache`].
+    pub(crate) read_cache: read_cache::ReadCache,
+    /// See [`MotorGroup::set_hard_voltage_cap`]. This is kept out of
+    /// [`GroupConfig`] so that applying a configuration can't raise it.
+    pub(crate) hard_voltage_cap: Option<f64>,
+    /// See [`MotorGroup::set_custom_write_policy`].
+    pub(crate) write_policy: Option<write_policy::CustomWritePolicy>,
+    /// See [`MotorGroup::set_zero_here`].
+    pub(crate) zeros: zero::Zeros,
+    /// See [`MotorGroup::add_preset`].
+    pub(crate) presets: Vec<presets::Preset>,
+    /// Recent samples for [`MotorGroup::efficiency_trend`].
+    #[cfg(feature = "diagnostics")]
+    pub(crate) efficiency_history: diagnostics::SampleHistory,
+    /// Recent samples for [`MotorGroup::rms_current`].
+    #[cfg(feature = "diagnostics")]
+    pub(crate) current_history: diagnostics::SampleHistory,
+    /// Recent samples for [`MotorGroup::averaged_current`].
+    #[cfg(feature = "diagnostics")]
+    pub(crate) average_current_history: diagnostics::SampleHistory,
+    /// Recent samples for [`MotorGroup::time_to_cutout`].
+    #[cfg(feature = "diagnostics")]
+    pub(crate) thermal_history: thermal::ThermalHistory,
+    /// See [`MotorGroup::enable_metric_history`].
+    #[cfg(feature = "telemetry")]
+    pub(crate) metric_history: Option<history::MetricHistory>,
+    /// Write timing state, or `None` if timing is disabled.
+    #[cfg(feature = "telemetry")]
+    pub(crate) write_timer: Option<timing::WriteTimer>,
+    /// The buffer for [`MotorGroup::events`], or `None` if nothing has
+    /// subscribed.
+    #[cfg(feature = "events")]
+    pub(crate) events: Option<alloc::sync::Arc<events::EventBus>>,
 }
 
 type GetterResult<T> = Result<T, MotorGroupError<PortError, T>>;
@@ -263,14 +517,49 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// # Panics
     ///
     /// Panics if there are no motors in the vector.
+    ///
+    /// With the `no-panic` feature, this is only checked in debug builds. A
+    /// release build creates the empty group anyway, and since every method
+    /// assumes there is at least one motor, later calls can misbehave: getters
+    /// return errors without any entries, and methods like
+    /// [`MotorGroupError::first`] panic after all. Use
+    /// [`MotorGroup::try_from`] to check for an empty group at runtime
+    /// instead.
     pub fn new(motors: M) -> Self {
-        assert!(
+        check_invariant(
             !motors.as_ref().is_empty(),
-            "Cannot create a motor group with no motors"
+            "Cannot create a motor group with no motors",
         );
+        let meta = motors
+            .as_ref()
+            .iter()
+            .map(meta::MotorMeta::for_motor)
+            .collect();
         Self {
             motors,
-            write_error_strategy: WriteErrorStrategy::default(),
+            config: GroupConfig::DEFAULT,
+            meta,
+            last_command: None,
+            command_generation: 0,
+            read_cache: read_cache::ReadCache::default(),
+            hard_voltage_cap: None,
+            write_policy: None,
+            zeros: zero::Zeros::default(),
+            presets: Vec::new(),
+            #[cfg(feature = "diagnostics")]
+            efficiency_history: diagnostics::SampleHistory::default(),
+            #[cfg(feature = "diagnostics")]
+            current_history: diagnostics::SampleHistory::default(),
+            #[cfg(feature = "diagnostics")]
+            average_current_history: diagnostics::SampleHistory::default(),
+            #[cfg(feature = "diagnostics")]
+            thermal_history: thermal::ThermalHistory::default(),
+            #[cfg(feature = "telemetry")]
+            metric_history: None,
+            #[cfg(feature = "telemetry")]
+            write_timer: None,
+            #[cfg(feature = "events")]
+            events: None,
         }
     }
 
@@ -286,14 +575,154 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// use vexide_motorgroup::*;
     ///
     pub fn write_error_strategy(&mut self, mode: WriteErrorStrategy) -> &mut Self {
-        self.write_error_strategy = mode;
+        self.config.write_error_strategy = mode;
         self
     }
 
+    /// Runs `write` on every active motor in the group, collecting errors
+    /// according to the group's [`WriteErrorStrategy`].
+    ///
+    /// The closure is given the index of the motor in the group along with the
+    /// motor itself. Disabled motors (see [`MotorGroup::set_enabled`]) and
+    /// checked out motors (see [`MotorGroup::checkout`]) are skipped. A custom
+    /// write policy (see [`MotorGroup::set_custom_write_policy`]) may call the
+    /// closure again for a motor whose write failed, told that it's writing
+    /// `kind`. Every call advances the [`MotorGroup::command_generation`], even
+    /// if no motor was written to, and is timed if write timing is enabled.
+    pub(crate) fn write_each<E: WriteFailure>(
+        &mut self,
+        kind: WriteKind,
+        write: impl FnMut(usize, &mut Motor) -> Result<(), E>,
+    ) -> Result<(), MotorGroupError<E>> {
+        self.command_generation = self.command_generation.wrapping_add(1);
+        self.read_cache.invalidate(read_cache::Change::Output);
+        #[cfg(feature = "telemetry")]
+        if let Some(timer) = &self.write_timer {
+            return self.timed_write_each(timer.clock, kind, write);
+        }
+        self.write_active(kind, write)
+    }
+
+    /// The loop behind [`MotorGroup::write_each`], without the bookkeeping.
+    ///
+    /// Every failed write is handled according to the group's
+    /// [`WriteErrorStrategy`], built-in or custom, through
+    /// [`write_policy::decide`].
+    pub(crate) fn write_active<E: WriteFailure>(
+        &mut self,
+        kind: WriteKind,
+        mut write: impl FnMut(usize, &mut Motor) -> Result<(), E>,
+    ) -> Result<(), MotorGroupError<E>> {
+        let strategy = self.config.write_error_strategy;
+        let mut context = WriteContext {
+            kind,
+            succeeded: 0,
+            failed: 0,
+            retries: 0,
+        };
+        let mut errors = Vec::new();
+        'motors: for (index, motor) in self.motors.as_mut().iter_mut().enumerate() {
+            if !self.meta[index].is_active() {
+                continue;
+            }
+            context.retries = 0;
+            while let Err(error) = write(index, motor) {
+                let policy = self.write_policy.as_mut();
+                match write_policy::decide(strategy, policy, index, &error, &context) {
+                    PolicyDecision::RetryThisMotor
+                        if context.retries < WriteContext::MAX_RETRIES =>
+                    {
+                        context.retries += 1;
+                    }
+                    PolicyDecision::StopRemaining => {
+                        errors.push(error);
+                        break 'motors;
+                    }
+                    _ => {
+                        errors.push(error);
+                        context.failed += 1;
+                        continue 'motors;
+                    }
+                }
+            }
+            context.succeeded += 1;
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Writes `targets[i]` to motor `i`, for every active motor in the group.
+    ///
+    /// Any motor that missed the last [`MotorGroup::set_direction`] is sent
+    /// the direction again first, and isn't given its target if that fails.
+    /// Each target is limited to the group's hard voltage cap on the way.
+    /// Along with the result, this returns which motors accepted their target
+    /// as `(index, accepted)` pairs.
+    pub(crate) fn write_targets(
+        &mut self,
+        targets: &[MotorControl],
+    ) -> (Result<(), MotorGroupError>, Vec<(usize, bool)>) {
+        self.write_targets_where(targets, |_| true)
+    }
+
+    /// [`MotorGroup::write_targets`], only writing to the motors whose index
+    /// `include` returns `true` for. The other motors keep their targets and
+    /// are left out of the returned pairs.
+    pub(crate) fn write_targets_where(
+        &mut self,
+        targets: &[MotorControl],
+        include: impl Fn(usize) -> bool,
+    ) -> (Result<(), MotorGroupError>, Vec<(usize, bool)>) {
+        let pending: Vec<Option<Direction>> = self
+            .meta
+            .iter()
+            .map(meta::MotorMeta::pending_direction)
+            .collect();
+        let mut stale: Vec<bool> = self.meta.iter().map(|meta| meta.direction_stale).collect();
+        let capped = self.capped_targets(targets);
+        let mut accepted: Vec<Option<bool>> = alloc::vec![None; targets.len()];
+        let result = self.write_each(WriteKind::Target, |index, motor| {
+            if !include(index) {
+                return Ok(());
+            }
+            let result = match pending[index] {
+                // A motor that missed its direction gets it first, so it
+                // never runs a target the wrong way
+                Some(direction) => motor
+                    .set_direction(direction)
+                    .inspect(|()| stale[index] = false),
+                None => Ok(()),
+            }
+            .and_then(|()| capped[index])
+            .and_then(|target| motor.set_target(target));
+            accepted[index] = Some(result.is_ok());
+            result
+        });
+        let written: Vec<(usize, bool)> = accepted
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, accepted)| Some((index, accepted?)))
+            .collect();
+        for (meta, stale) in self.meta.iter_mut().zip(stale) {
+            meta.direction_stale = stale;
+        }
+        for &(index, accepted) in &written {
+            self.observe_connection(index, accepted);
+        }
+        (result, written)
+    }
+
     /// Sets the target that the motor group should attempt to reach.
     ///
     /// This could be a voltage, velocity, position, or even brake mode.
     ///
+    /// Any motor that missed the last [`MotorGroup::set_direction`] is sent
+    /// the direction again first, and isn't given the target if that fails.
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -318,20 +747,30 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_target).
     pub fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_target(target) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
+        self.write_command(target).0
+    }
+
+    /// [`MotorGroup::set_target`], also returning which motors accepted the
+    /// target as `(index, accepted)` pairs.
+    pub(crate) fn write_command(
+        &mut self,
+        target: MotorControl,
+    ) -> (Result<(), MotorGroupError>, Vec<(usize, bool)>) {
+        let target = self.limited_target(target);
+        let (targets, mut errors) = match self.fanned_out_targets(target) {
+            Ok(fanned_out) => fanned_out,
+            Err(error) => return (Err(error), Vec::new()),
+        };
+        self.last_command = Some(target);
+        let (result, written) = self.write_targets(&targets);
+        self.track_fallback(target, &written);
         if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
+            return (result, written);
+        }
+        if let Err(error) = result {
+            errors.extend(error.errors);
         }
+        (Err(MotorGroupError::new(errors)), written)
     }
 
     /// Sets the motor group's target to a given [`BrakeMode`].
@@ -358,20 +797,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.brake).
     pub fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.brake(mode) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.set_target(MotorControl::Brake(mode))
     }
 
     /// Spins the motor group at a target velocity.
@@ -380,6 +806,14 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// Velocity is held with an internal PID controller to ensure consistent speed, as opposed to setting the
     /// motor's voltage.
     ///
+    /// The sign of `rpm` is relative to each motor's [`Direction`]: a positive
+    /// velocity spins a motor forward as defined by its direction, so
+    /// `-100` on a [`Direction::Forward`] motor spins it the same way as
+    /// `100` on a [`Direction::Reverse`] one. The group passes the signed
+    /// velocity through unchanged and leaves the direction to the motors (see
+    /// [`MotorGroup::set_direction`]). The same goes for voltages and
+    /// position targets.
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -405,20 +839,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_velocity).
     pub fn set_velocity(&mut self, rpm: i32) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_velocity(rpm) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.set_target(MotorControl::Velocity(rpm))
     }
 
     /// Sets the motor group's output voltage.
@@ -471,20 +892,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_voltage).
     pub fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_voltage(volts) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.set_target(MotorControl::Voltage(volts))
     }
 
     /// Sets an absolute position target for the motor group to attempt to reach.
@@ -516,26 +924,19 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
         position: Angle,
         velocity: i32,
     ) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_position_target(position, velocity) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.set_target(MotorControl::Position(position, velocity))
     }
 
     /// Changes the output velocity for a profiled movement (motor_move_absolute or motor_move_relative).
     ///
     /// This will have no effect if the motor group is not following a profiled movement.
     ///
+    /// Like on a single [`Motor`], the new velocity becomes part of the group's
+    /// position target, so features built on the last command (such as
+    /// [`MotorGroup::commanded_direction`] or [`MotorGroup::shift_ratio`]) see
+    /// the velocity the motors are actually moving at. The velocity is always
+    /// written, even if it's the same as the target's.
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -552,32 +953,45 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
     ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
     ///     ]);
-    ///     // Set the motor group's target to a Angle so that changing the velocity isn't a noop.
-    ///     let _ = motor_group.set_target(MotorControl::Angle(Angle::from_degrees(90.0), 200));
+    ///     // Set the motor group's target to a position so that changing the velocity isn't a noop.
+    ///     let _ = motor_group.set_target(MotorControl::Position(Angle::from_degrees(90.0), 200));
     ///     let _ = motor_group.set_profiled_velocity(100).unwrap();
     /// }
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_profiled_velocity).
     pub fn set_profiled_velocity(&mut self, velocity: i32) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_profiled_velocity(velocity) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
+        if let Some(MotorControl::Position(position, _)) = self.last_command {
+            self.last_command = Some(MotorControl::Position(position, velocity));
         }
+        let cap = self.hard_voltage_cap;
+        let gearset = self.config.gearset;
+        self.write_each(WriteKind::Target, |_, motor| {
+            let target = MotorControl::Velocity(velocity);
+            let velocity = match hard_cap::capped_target(motor, target, cap, gearset)? {
+                MotorControl::Velocity(capped) => capped,
+                _ => velocity,
+            };
+            motor.set_profiled_velocity(velocity)
+        })
     }
 
     /// Sets the gearset of an 11W motor group.
     ///
+    /// The gearset is recorded even if some writes fail, and everything in the
+    /// group that depends on it follows from then on without rebuilding the
+    /// group: [`MotorGroup::max_rpm`], the free speed that motors in
+    /// [position fallback](MotorGroup::position_fallback) are driven against,
+    /// and the gearset expected by [`MotorGroup::readiness`]. If the
+    /// gearset changed, a [`GroupEvent::GearsetChanged`] event is delivered
+    /// (see [`MotorGroup::events`]).
+    ///
+    /// Velocity targets are in RPM of the cartridge's output, so a velocity
+    /// given before the change isn't rescaled; the same RPM becomes a
+    /// different fraction of the new free speed. Velocity PID constants (see
+    /// [`MotorGroup::set_velocity_pid_constants`]) tuned for the old
+    /// cartridge are kept as well.
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -607,20 +1021,46 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
         &mut self,
         gearset: Gearset,
     ) -> Result<(), MotorGroupError<SetGearsetError>> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_gearset(gearset) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
+        let previous = self.config.gearset.replace(gearset);
+        let result = self.write_each(WriteKind::Configuration, |_, motor| {
+            motor.set_gearset(gearset)
+        });
+        self.read_cache.invalidate(read_cache::Change::Aggregation);
+        if previous != Some(gearset) {
+            self.emit(GroupEvent::GearsetChanged);
         }
+        result
+    }
+
+    /// Returns the free speed of the group's gearset in RPM, or `None` if the
+    /// gearset hasn't been set on the group.
+    ///
+    /// This is the gearset last set with [`MotorGroup::set_gearset`] (or
+    /// through [`MotorGroup::apply_config`]), so it's updated as soon as the
+    /// gearset changes and never reads from the motors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     _ = flywheel.set_gearset(Gearset::Blue);
+    ///
+    ///     // Run at 80% of the cartridge's free speed
+    ///     if let Some(max_rpm) = flywheel.max_rpm() {
+    ///         _ = flywheel.set_velocity_f64(max_rpm * 0.8);
+    ///     }
+    /// }
+    /// ```
+    pub fn max_rpm(&self) -> Option<f64> {
+        self.config.gearset.map(|gearset| gearset.max_rpm())
     }
 
     /// Returns `true` if the motor group has a 5.5W (EXP) Smart Motor.
@@ -673,8 +1113,58 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
         self.motors.as_ref().iter().any(|motor| motor.is_v5())
     }
 
+    /// Returns the indices of the 5.5W (EXP) Smart Motors in the motor group,
+    /// in order.
+    ///
+    /// Together with [`MotorGroup::v5_motors`], this partitions the group by
+    /// motor type, which makes it easy to configure each type differently
+    /// with the per-motor methods, such as [`MotorGroup::set_label`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new_exp(peripherals.port_2, Direction::Forward),
+    ///     ]);
+    ///     for index in motor_group.exp_motors() {
+    ///         motor_group.set_label(index, "exp");
+    ///     }
+    /// }
+    /// ```
+    pub fn exp_motors(&self) -> Vec<usize> {
+        self.indices_where(Motor::is_exp)
+    }
+
+    /// Returns the indices of the 11W (V5) Smart Motors in the motor group,
+    /// in order.
+    ///
+    /// See [`MotorGroup::exp_motors`].
+    pub fn v5_motors(&self) -> Vec<usize> {
+        self.indices_where(Motor::is_v5)
+    }
+
+    /// Returns the indices of the motors for which `predicate` is `true`.
+    fn indices_where(&self, mut predicate: impl FnMut(&Motor) -> bool) -> Vec<usize> {
+        self.motors
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, motor)| predicate(motor))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
     /// Returns the maximum voltage for the motor group based off of its [motor type](Motor::motor_type).
     ///
+    /// If the group has a lower [hard voltage cap](MotorGroup::set_hard_voltage_cap),
+    /// that is returned instead.
+    ///
     /// # Examples
     ///
     /// Run a motor group at max speed, agnostic of its type:
@@ -689,12 +1179,15 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.max_voltage).
     pub fn max_voltage(&self) -> f64 {
-        self.motors
+        let max_voltage = self
+            .motors
             .as_ref()
             .iter()
             .map(|motor| motor.max_voltage())
             .reduce(f64::max)
-            .unwrap()
+            .unwrap();
+        self.hard_voltage_cap
+            .map_or(max_voltage, |cap| max_voltage.min(cap))
     }
 
     /// Returns the average estimated angular velocity of motors in a motor group in rotations per minute (RPM).
@@ -765,25 +1258,78 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.velocity).
     pub fn velocity(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.velocity() {
-                Ok(velocity) => {
-                    sum += velocity;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(
+            |cache| &cache.velocity,
+            || self.average_each(Motor::velocity),
+        )
+    }
+
+    /// Returns the measured velocity of the slowest motor in the motor group in
+    /// rotations per minute (RPM).
+    ///
+    /// "Slowest" means the velocity with the smallest magnitude, so this works
+    /// the same whether the group is spinning forwards or in reverse. The
+    /// returned velocity keeps its sign. Use [`MotorGroup::slowest_motor`] to
+    /// find out which motor it is.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    ///   Its result is the minimum of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     println!("{:?}", motor_group.min_velocity().unwrap());
+    /// }
+    /// ```
+    pub fn min_velocity(&self) -> GetterResult<f64> {
+        readings::map_result(
+            readings::min_magnitude(self.read_each(Motor::velocity)),
+            |(_, velocity)| velocity,
+        )
+    }
+
+    /// Returns the index of the slowest motor in the motor group.
+    ///
+    /// See [`MotorGroup::min_velocity`] for how "slowest" is defined.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group encounters an error.
+    ///   Its result is the index of the slowest motor that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     println!("Motor {} is holding the group back", motor_group.slowest_motor().unwrap());
+    /// }
+    /// ```
+    pub fn slowest_motor(&self) -> GetterResult<usize> {
+        readings::map_result(
+            readings::min_magnitude(self.read_each(Motor::velocity)),
+            |(index, _)| index,
+        )
     }
 
     /// Returns the average power drawn by a motor in this the motor group in Watts.
@@ -812,25 +1358,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.power).
     pub fn power(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.power() {
-                Ok(power) => {
-                    sum += power;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(|cache| &cache.power, || self.average_each(Motor::power))
     }
 
     /// Returns the average torque of motors in the motor group in Newton-meters.
@@ -859,25 +1387,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.torque).
     pub fn torque(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.torque() {
-                Ok(torque) => {
-                    sum += torque;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(|cache| &cache.torque, || self.average_each(Motor::torque))
     }
 
     /// Returns the motor group's output voltage.
@@ -906,28 +1416,14 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.voltage).
     pub fn voltage(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.voltage() {
-                Ok(voltage) => {
-                    sum += voltage;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(|cache| &cache.voltage, || self.average_each(Motor::voltage))
     }
 
-    /// Returns the motor group's average position in ticks.
+    /// Returns the motor group's average position as an [`Angle`].
+    ///
+    /// Motors whose position reference is known to be inconsistent with the
+    /// rest of the group (see [`MotorGroup::stale_motors`]) are left out of the
+    /// average.
     ///
     /// # Errors
     ///
@@ -953,25 +1449,10 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.position).
     pub fn position(&self) -> GetterResult<Angle> {
-        let mut errors = Vec::new();
-        let mut sum = Angle::ZERO;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.position() {
-                Ok(position) => {
-                    sum += position;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(
+            |cache| &cache.position,
+            || readings::map_result(self.group_position(), GroupPosition::to_position),
+        )
     }
 
     /// Returns the motor group's average current in Amperes.
@@ -1000,25 +1481,7 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.current).
     pub fn current(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.current() {
-                Ok(current) => {
-                    sum += current;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(|cache| &cache.current, || self.average_each(Motor::current))
     }
 
     /// Returns the motor group's average efficiency as a percentage.
@@ -1047,29 +1510,18 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.efficiency).
     pub fn efficiency(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.efficiency() {
-                Ok(efficiency) => {
-                    sum += efficiency;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(
+            |cache| &cache.efficiency,
+            || self.average_each(Motor::efficiency),
+        )
     }
 
     /// Resets every motor in the motor group's position to zero.
     ///
+    /// If only some of the motors are reset, the others are marked as stale
+    /// and left out of [`MotorGroup::position`]. See
+    /// [`MotorGroup::stale_motors`].
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -1094,24 +1546,14 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.reset_position).
     pub fn reset_position(&mut self) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.reset_position() {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.write_reference(|motor| motor.reset_position())
     }
 
     /// Sets the motor group's position to a given value.
     ///
+    /// If only some of the motors are set, the others are marked as stale and
+    /// left out of [`MotorGroup::position`]. See [`MotorGroup::stale_motors`].
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -1136,27 +1578,21 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_position).
     pub fn set_position(&mut self, position: Angle) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_position(position) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.write_reference(|motor| motor.set_position(position))
     }
 
     /// Sets the motor group's current limit in Amperes.
     ///
+    /// The limit is applied to each motor individually. If it exceeds a motor's
+    /// hardware maximum (see [`MotorGroup::max_current_per_motor`]), the
+    /// group's [`CurrentLimitPolicy`] decides whether the limit is clamped to
+    /// the maximum or refused. To limit the current of the group as a whole,
+    /// use [`MotorGroup::set_total_current_limit`].
+    ///
     /// # Errors
     ///
-    /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
+    /// - A [`SetCurrentLimitError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
+    /// - A [`SetCurrentLimitError::ExceedsMaximum`] error is returned for each motor whose maximum is exceeded under [`CurrentLimitPolicy::Error`]. No limits are written in this case.
     ///
     /// # Examples
     ///
@@ -1177,21 +1613,18 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_current_limit).
-    pub fn set_current_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_current_limit(limit) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+    pub fn set_current_limit(
+        &mut self,
+        limit: f64,
+    ) -> Result<(), MotorGroupError<SetCurrentLimitError>> {
+        let limits = self.checked_current_limits(limit)?;
+        self.config.current_limit = Some(limit);
+        self.config.total_current_limit = None;
+        self.write_each(WriteKind::Configuration, |index, motor| {
+            motor
+                .set_current_limit(limits[index])
+                .map_err(SetCurrentLimitError::from)
+        })
     }
 
     /// Sets the motor group's voltage limit in Volts.
@@ -1220,20 +1653,10 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_voltage_limit).
     pub fn set_voltage_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_voltage_limit(limit) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
-        }
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+        self.config.voltage_limit = Some(limit);
+        self.write_each(WriteKind::Configuration, |_, motor| {
+            motor.set_voltage_limit(limit)
+        })
     }
 
     /// Returns the motor group's temperature in degrees Celsius.
@@ -1262,35 +1685,23 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.temperature).
     pub fn temperature(&self) -> GetterResult<f64> {
-        let mut errors = Vec::new();
-        let mut sum = 0.0;
-        let mut count = 0;
-        for motor in self.motors.as_ref() {
-            match motor.temperature() {
-                Ok(temperature) => {
-                    sum += temperature;
-                    count += 1;
-                }
-                Err(error) => errors.push(error),
-            }
-        }
-        if errors.is_empty() {
-            Ok(sum / count as f64)
-        } else if count > 0 {
-            Err(MotorGroupError::with_result(errors, sum / count as f64))
-        } else {
-            Err(MotorGroupError::with_empty_result(errors))
-        }
+        self.cached(
+            |cache| &cache.temperature,
+            || self.average_each(Motor::temperature),
+        )
     }
 
     /// Returns `true` if any motor in the motor group is over temperature.
     ///
     /// # Errors
     ///
-    /// - A [`MotorGroupError`] error is returned if any motor encounters an error.
+    /// - A [`MotorGroupError`] error is returned if any motor encounters an
+    ///   error and no motor that could be read is over temperature. Its result
+    ///   is `false` if any motor could be read.
     ///
-    /// Note that this method will still return `Ok` if a motor encounters an
-    /// error but a motor in a group is still over temperature.
+    /// Every motor is read, so this returns `Ok(true)` whenever a motor that
+    /// could be read is over temperature, regardless of errors from other
+    /// motors or their order in the group.
     ///
     /// # Examples
     ///
@@ -1311,30 +1722,23 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.is_over_temperature).
-    pub fn is_over_temperature(&self) -> Result<bool, MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_ref() {
-            match motor.is_over_temperature() {
-                Ok(true) => return Ok(true),
-                Err(error) => errors.push(error),
-                _ => {}
-            }
-        }
-        if errors.is_empty() {
-            Ok(false)
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+    pub fn is_over_temperature(&self) -> GetterResult<bool> {
+        let readings = self.read_each(Motor::is_over_temperature);
+        self.observe_over_temperature(&readings);
+        readings::any(readings)
     }
 
     /// Returns `true` if any motor in the motor group is over current.
     ///
     /// # Errors
     ///
-    /// - A [`MotorGroupError`] error is returned if any motor encounters an error.
+    /// - A [`MotorGroupError`] error is returned if any motor encounters an
+    ///   error and no motor that could be read is over current. Its result is
+    ///   `false` if any motor could be read.
     ///
-    /// Note that this method will still return `Ok` if a motor encounters an
-    /// error but a motor in a group is still over current.
+    /// Every motor is read, so this returns `Ok(true)` whenever a motor that
+    /// could be read is over current, regardless of errors from other motors or
+    /// their order in the group.
     ///
     /// # Examples
     ///
@@ -1355,30 +1759,21 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.is_over_current).
-    pub fn is_over_current(&self) -> Result<bool, MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_ref() {
-            match motor.is_over_current() {
-                Ok(true) => return Ok(true),
-                Err(error) => errors.push(error),
-                _ => {}
-            }
-        }
-        if errors.is_empty() {
-            Ok(false)
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+    pub fn is_over_current(&self) -> GetterResult<bool> {
+        readings::any(self.read_each(Motor::is_over_current))
     }
 
     /// Returns `true` if any motor in the motor group has a driver fault.
     ///
     /// # Errors
     ///
-    /// - A [`MotorGroupError`] error is returned if any motor encounters an error.
+    /// - A [`MotorGroupError`] error is returned if any motor encounters an
+    ///   error and no motor that could be read has a driver fault. Its result
+    ///   is `false` if any motor could be read.
     ///
-    /// Note that this method will still return `Ok` if a motor encounters an
-    /// error but a motor in a group has a driver fault.
+    /// Every motor is read, so this returns `Ok(true)` whenever a motor that
+    /// could be read has a driver fault, regardless of errors from other motors
+    /// or their order in the group.
     ///
     /// # Examples
     ///
@@ -1399,30 +1794,21 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.is_driver_fault).
-    pub fn is_driver_fault(&self) -> Result<bool, MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_ref() {
-            match motor.is_driver_fault() {
-                Ok(true) => return Ok(true),
-                Err(error) => errors.push(error),
-                _ => {}
-            }
-        }
-        if errors.is_empty() {
-            Ok(false)
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+    pub fn is_driver_fault(&self) -> GetterResult<bool> {
+        readings::any(self.read_each(Motor::is_driver_fault))
     }
 
-    /// Returns `true` if the any motor in the motor group is over current.
+    /// Returns `true` if any motor in the motor group has a driver over current.
     ///
     /// # Errors
     ///
-    /// - A [`MotorGroupError`] error is returned if any motor encounters an error.
+    /// - A [`MotorGroupError`] error is returned if any motor encounters an
+    ///   error and no motor that could be read has a driver over current. Its
+    ///   result is `false` if any motor could be read.
     ///
-    /// Note that this method will still return `Ok` if a motor encounters an
-    /// error but a motor in a group is still over current.
+    /// Every motor is read, so this returns `Ok(true)` whenever a motor that
+    /// could be read has a driver over current, regardless of errors from other
+    /// motors or their order in the group.
     ///
     /// # Examples
     ///
@@ -1443,24 +1829,25 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     /// ```
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.is_driver_over_current).
-    pub fn is_driver_over_current(&self) -> Result<bool, MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_ref() {
-            match motor.is_driver_over_current() {
-                Ok(true) => return Ok(true),
-                Err(error) => errors.push(error),
-                _ => {}
-            }
-        }
-        if errors.is_empty() {
-            Ok(false)
-        } else {
-            Err(MotorGroupError::new(errors))
-        }
+    pub fn is_driver_over_current(&self) -> GetterResult<bool> {
+        readings::any(self.read_each(Motor::is_driver_over_current))
     }
 
     /// Sets the motor group's direction.
     ///
+    /// Every motor is given the same direction, which defines which way a
+    /// positive velocity, voltage, or position spins it (see
+    /// [`MotorGroup::set_velocity`]). For a group whose motors face different
+    /// ways, such as one side of a drivetrain, use [`MotorGroup::flip_all`]
+    /// instead to reverse each motor relative to its own direction.
+    ///
+    /// The direction is recorded as each motor's intent (see
+    /// [`MotorGroup::directions`]) even if some motors miss it. Those motors are sent the direction again before their next
+    /// target, and aren't given the target until they accept it, so a group
+    /// behaves the same after a failed direction write as one where the
+    /// write succeeded, as soon as the motors are reachable again. Use
+    /// [`MotorGroup::misdirected_motors`] to check the motors' directions.
+    ///
     /// # Errors
     ///
     /// - A [`MotorError::Port`] error is returned if a motor device is not currently connected to the Smart Port.
@@ -1485,19 +1872,62 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
     ///
     /// See the original method [here](https://docs.rs/vexide/latest/vexide/devices/smart/struct.Motor.html#method.set_direction).
     pub fn set_direction(&mut self, direction: Direction) -> Result<(), MotorGroupError> {
-        let mut errors = Vec::new();
-        for motor in self.motors.as_mut() {
-            if let Err(error) = motor.set_direction(direction) {
-                errors.push(error);
-                if self.write_error_strategy == WriteErrorStrategy::Stop {
-                    break;
-                }
-            }
+        self.config.direction = Some(direction);
+        for meta in &mut self.meta {
+            meta.direction = Some(direction);
         }
-        if errors.is_empty() {
+        let mut succeeded = alloc::vec![false; self.motors.as_ref().len()];
+        let result = self.write_each(WriteKind::Direction, |index, motor| {
+            motor.set_direction(direction)?;
+            succeeded[index] = true;
             Ok(())
+        });
+        direction::record_direction_writes(&mut self.meta, &succeeded);
+        self.read_cache.invalidate(read_cache::Change::Aggregation);
+        result
+    }
+}
+
+/// Error returned when creating a motor group with no motors using
+/// [`MotorGroup::try_from`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyGroupError;
+
+impl core::fmt::Display for EmptyGroupError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Cannot create a motor group with no motors")
+    }
+}
+
+impl core::error::Error for EmptyGroupError {}
+
+impl TryFrom<Vec<Motor>> for MotorGroup<Vec<Motor>> {
+    type Error = EmptyGroupError;
+
+    /// Creates a new motor group, or returns an error if there are no motors.
+    ///
+    /// Unlike [`MotorGroup::new`], this never panics, so it's the safe way to
+    /// create a group from a list of motors that might be empty.
+    fn try_from(motors: Vec<Motor>) -> Result<Self, Self::Error> {
+        if motors.is_empty() {
+            Err(EmptyGroupError)
         } else {
-            Err(MotorGroupError::new(errors))
+            Ok(Self::new(motors))
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<[Motor; N]> for MotorGroup<[Motor; N]> {
+    type Error = EmptyGroupError;
+
+    /// Creates a new motor group, or returns an error if `N` is zero.
+    ///
+    /// Unlike [`MotorGroup::new`], this never panics.
+    fn try_from(motors: [Motor; N]) -> Result<Self, Self::Error> {
+        if N == 0 {
+            Err(EmptyGroupError)
+        } else {
+            Ok(Self::new(motors))
         }
     }
 }
@@ -0,0 +1,319 @@
+use vexide::{
+    math::Angle,
+    smart::motor::{Motor, MotorControl},
+};
+
+use crate::{GetterResult, MotorGroup, readings};
+
+/// Returns whether driving in the direction of `sign` from `position` would
+/// go further past one of the limits `min` and `max`.
+///
+/// An unknown position never counts as past a limit.
+fn pushes_past(sign: f64, (min, max): (Angle, Angle), position: Option<Angle>) -> bool {
+    position
+        .is_some_and(|position| (sign > 0.0 && position >= max) || (sign < 0.0 && position <= min))
+}
+
+/// Returns whether `position` is within the position limits `(min, max)`,
+/// including the limits themselves.
+pub(crate) fn is_within((min, max): (Angle, Angle), position: Angle) -> bool {
+    min <= position && position <= max
+}
+
+/// Returns `target` kept within the position limits `(min, max)`.
+///
+/// Position targets are clamped to the range. Voltage and velocity targets
+/// are zeroed if `position` is at or past a limit and they would drive
+/// further past it. Other targets are returned as is.
+pub(crate) fn limit_target(
+    target: MotorControl,
+    limits: (Angle, Angle),
+    position: Option<Angle>,
+) -> MotorControl {
+    let (min, max) = limits;
+    match target {
+        MotorControl::Position(position, velocity) if position > max => {
+            MotorControl::Position(max, velocity)
+        }
+        MotorControl::Position(position, velocity) if position < min => {
+            MotorControl::Position(min, velocity)
+        }
+        MotorControl::Voltage(volts) if pushes_past(volts, limits, position) => {
+            MotorControl::Voltage(0.0)
+        }
+        MotorControl::Velocity(rpm) if pushes_past(f64::from(rpm), limits, position) => {
+            MotorControl::Velocity(0)
+        }
+        other => other,
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets soft limits on the motor group's position, to keep a mechanism
+    /// such as a lift or an arm from being driven into its hard stops.
+    ///
+    /// From the next command on, every target given to the group (through
+    /// [`MotorGroup::set_target`] or the methods built on it, and
+    /// [`MotorGroup::transition`]) is kept within `[min, max]`:
+    ///
+    /// - Position targets are clamped to the range, so
+    ///   [`MotorGroup::set_position_target`] stops at the limit instead of
+    ///   going past it.
+    /// - Voltage and velocity targets are replaced with zero if the group's
+    ///   position (see [`MotorGroup::position`]) is at or past a limit and
+    ///   they would drive further past it. Driving back into the range is
+    ///   still allowed.
+    ///
+    /// The limits are in the same frame as the group's position and position
+    /// targets: the motors' own positions, not the output's.
+    ///
+    /// Limiting a voltage or velocity target costs a read of every motor's
+    /// position before each write, unless the target is zero. If the position
+    /// can't be read, the target is written as is. The limit is only checked
+    /// when a target is written, so a voltage that's set once keeps driving
+    /// past the limit. Set it every iteration of the control loop instead,
+    /// as usual.
+    ///
+    /// The limits are software only. They aren't written to the motors, and
+    /// targets given to a [checked out](MotorGroup::checkout) motor directly
+    /// aren't limited.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is greater than `max`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     let controller = peripherals.primary_controller;
+    ///
+    ///     // The lift hits its top hard stop after 2.5 turns of the motors.
+    ///     lift.set_position_limits(Angle::ZERO, Angle::from_turns(2.4));
+    ///
+    ///     loop {
+    ///         let state = controller.state().unwrap_or_default();
+    ///         _ = lift.set_voltage(state.left_stick.y() * lift.max_voltage());
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_position_limits(&mut self, min: Angle, max: Angle) {
+        crate::check_invariant(min <= max, "position limits must have min <= max");
+        self.config.position_limits = Some((min, max));
+    }
+
+    /// Removes the position limits set by [`MotorGroup::set_position_limits`].
+    pub fn clear_position_limits(&mut self) {
+        self.config.position_limits = None;
+    }
+
+    /// Returns the position limits set by [`MotorGroup::set_position_limits`]
+    /// as `(min, max)`, if any.
+    pub fn position_limits(&self) -> Option<(Angle, Angle)> {
+        self.config.position_limits
+    }
+
+    /// Returns whether the group's position (see [`MotorGroup::position`]) is
+    /// within the limits set by [`MotorGroup::set_position_limits`],
+    /// including the limits themselves.
+    ///
+    /// This lets control code check before commanding more motion, such as
+    /// to skip a step of a routine that would start outside the range. If the
+    /// group has no position limits, this is `true` without reading the
+    /// motors.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is whether
+    ///   the average position of the motors that could be read is within the
+    ///   limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut arm = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     arm.set_position_limits(Angle::ZERO, Angle::from_degrees(540.0));
+    ///
+    ///     if let Ok(false) = arm.within_position_limits() {
+    ///         println!("The arm is out of range, so it can only be moved back");
+    ///     }
+    /// }
+    /// ```
+    pub fn within_position_limits(&self) -> GetterResult<bool> {
+        let Some(limits) = self.config.position_limits else {
+            return Ok(true);
+        };
+        readings::map_result(self.position(), |position| is_within(limits, position))
+    }
+
+    /// Returns `target` kept within the group's position limits, if it has
+    /// any, reading the group's position if that's needed.
+    pub(crate) fn limited_target(&self, target: MotorControl) -> MotorControl {
+        let Some(limits) = self.config.position_limits else {
+            return target;
+        };
+        let position = match target {
+            MotorControl::Voltage(volts) if volts != 0.0 => self.position().ok(),
+            MotorControl::Velocity(rpm) if rpm != 0 => self.position().ok(),
+            _ => None,
+        };
+        limit_target(target, limits, position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use super::{is_within, limit_target};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn degrees(degrees: f64) -> Angle {
+        Angle::from_degrees(degrees)
+    }
+
+    fn limits() -> (Angle, Angle) {
+        (degrees(0.0), degrees(900.0))
+    }
+
+    #[test]
+    fn position_targets_inside_the_range_are_unchanged() {
+        for target in [0.0, 450.0, 900.0] {
+            let target = MotorControl::Position(degrees(target), 100);
+            assert_eq!(limit_target(target, limits(), None), target);
+        }
+    }
+
+    #[test]
+    fn position_targets_outside_the_range_are_clamped() {
+        assert_eq!(
+            limit_target(MotorControl::Position(degrees(1000.0), 100), limits(), None),
+            MotorControl::Position(degrees(900.0), 100)
+        );
+        assert_eq!(
+            limit_target(MotorControl::Position(degrees(-45.0), 50), limits(), None),
+            MotorControl::Position(degrees(0.0), 50)
+        );
+    }
+
+    #[test]
+    fn the_limits_themselves_are_within_the_range() {
+        for inside in [0.0, 0.5, 450.0, 899.5, 900.0] {
+            assert!(is_within(limits(), degrees(inside)), "{inside}");
+        }
+        for outside in [-0.5, -90.0, 900.5, 1080.0] {
+            assert!(!is_within(limits(), degrees(outside)), "{outside}");
+        }
+    }
+
+    #[test]
+    fn unlimited_groups_are_always_within_their_limits() {
+        let mut group = MotorGroup::new(mock_motors(2, Gearset::Red));
+        assert!(matches!(group.within_position_limits(), Ok(true)));
+
+        // With limits, the mock motors' positions have to be read
+        group.set_position_limits(degrees(0.0), degrees(900.0));
+        let error = group.within_position_limits().unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn driving_past_a_limit_is_stopped() {
+        let at_top = Some(degrees(900.0));
+        let past_bottom = Some(degrees(-10.0));
+
+        assert_eq!(
+            limit_target(MotorControl::Voltage(6.0), limits(), at_top),
+            MotorControl::Voltage(0.0)
+        );
+        assert_eq!(
+            limit_target(MotorControl::Velocity(-100), limits(), past_bottom),
+            MotorControl::Velocity(0)
+        );
+
+        // Driving back into the range is allowed
+        assert_eq!(
+            limit_target(MotorControl::Voltage(-6.0), limits(), at_top),
+            MotorControl::Voltage(-6.0)
+        );
+        assert_eq!(
+            limit_target(MotorControl::Velocity(100), limits(), past_bottom),
+            MotorControl::Velocity(100)
+        );
+    }
+
+    #[test]
+    fn driving_inside_the_range_is_unchanged() {
+        let middle = Some(degrees(450.0));
+        for target in [
+            MotorControl::Voltage(12.0),
+            MotorControl::Voltage(-12.0),
+            MotorControl::Velocity(200),
+            MotorControl::Brake(BrakeMode::Hold),
+        ] {
+            assert_eq!(limit_target(target, limits(), middle), target);
+        }
+
+        // Without a position, there's nothing to limit against
+        assert_eq!(
+            limit_target(MotorControl::Voltage(12.0), limits(), None),
+            MotorControl::Voltage(12.0)
+        );
+    }
+
+    #[test]
+    fn commands_are_limited_before_being_written() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Red));
+        group.set_position_limits(degrees(0.0), degrees(900.0));
+        assert_eq!(group.position_limits(), Some(limits()));
+
+        _ = group.set_position_target(degrees(1080.0), 100);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Position(degrees(900.0), 100))
+        );
+
+        // The mock motor's position can't be read, so the voltage is sent
+        _ = group.set_voltage(6.0);
+        assert_eq!(group.last_command, Some(MotorControl::Voltage(6.0)));
+
+        group.clear_position_limits();
+        _ = group.set_position_target(degrees(1080.0), 100);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Position(degrees(1080.0), 100))
+        );
+    }
+
+    // With the `no-panic` feature, this is only checked in debug builds
+    #[cfg(any(not(feature = "no-panic"), debug_assertions))]
+    #[test]
+    #[should_panic = "position limits must have min <= max"]
+    fn reversed_limits_panic() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Red));
+        group.set_position_limits(degrees(900.0), degrees(0.0));
+    }
+}
@@ -0,0 +1,353 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::{
+    PortError,
+    motor::{BrakeMode, Motor, MotorControl},
+};
+
+use crate::{MotorGroup, MotorGroupError, safety, tick::tick_loop};
+
+/// How a captured game piece shows up in a motor group's readings, used by
+/// [`MotorGroup::run_until_load`].
+///
+/// A load is detected once, compared with the group's free-running baseline,
+/// the current has risen by at least `current_rise` **and** the velocity has
+/// dropped by at least `velocity_drop_fraction`, continuously for
+/// `confirm_for`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadSignature {
+    /// How long the group runs before its baseline is sampled, so the
+    /// baseline isn't taken while the motors are still accelerating.
+    pub spin_up: Duration,
+    /// How far the average current has to rise above the baseline, in
+    /// Amperes.
+    pub current_rise: f64,
+    /// How much of its baseline speed the group has to lose, as a fraction
+    /// between `0.0` and `1.0`. For example, `0.3` means the average velocity
+    /// has to drop below 70% of the baseline.
+    pub velocity_drop_fraction: f64,
+    /// How long both conditions have to hold before a load is detected. This
+    /// is also how long the baseline is sampled for.
+    pub confirm_for: Duration,
+}
+
+/// The outcome of [`MotorGroup::run_until_load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadResult {
+    /// A load was detected and the group is holding it.
+    Loaded {
+        /// How long after the start the load was confirmed.
+        after: Duration,
+    },
+    /// No load was detected before the timeout, and the group was stopped.
+    TimedOut,
+}
+
+/// Watches averaged `(current, velocity)` samples for a [`LoadSignature`].
+///
+/// This is kept free of hardware access and timing so that it can be tested
+/// with scripted readings.
+#[derive(Debug, Clone)]
+pub(crate) struct LoadDetector {
+    signature: LoadSignature,
+    /// The sums and count of the samples taken for the baseline so far.
+    samples: (f64, f64, usize),
+    /// The baseline `(current, velocity)`, once it's been sampled.
+    baseline: Option<(f64, f64)>,
+    /// When the signature started matching without interruption.
+    matching_since: Option<Duration>,
+}
+
+impl LoadDetector {
+    pub(crate) fn new(signature: LoadSignature) -> Self {
+        Self {
+            signature,
+            samples: (0.0, 0.0, 0),
+            baseline: None,
+            matching_since: None,
+        }
+    }
+
+    /// Returns the baseline `(current, velocity)`, once it's been sampled.
+    #[cfg(test)]
+    pub(crate) fn baseline(&self) -> Option<(f64, f64)> {
+        self.baseline
+    }
+
+    /// Adds a sample taken `elapsed` after the start, or `None` if the group
+    /// couldn't be read, and returns `true` once a load is confirmed.
+    pub(crate) fn update(&mut self, elapsed: Duration, sample: Option<(f64, f64)>) -> bool {
+        let signature = self.signature;
+        if elapsed < signature.spin_up {
+            return false;
+        }
+
+        let Some((base_current, base_velocity)) = self.baseline else {
+            if let Some((current, velocity)) = sample {
+                self.samples.0 += current;
+                self.samples.1 += velocity.abs();
+                self.samples.2 += 1;
+            }
+            // The window is extended until at least one sample is read
+            let (current, velocity, count) = self.samples;
+            if elapsed >= signature.spin_up + signature.confirm_for && count > 0 {
+                self.baseline = Some((current / count as f64, velocity / count as f64));
+            }
+            return false;
+        };
+
+        let matches = sample.is_some_and(|(current, velocity)| {
+            current >= base_current + signature.current_rise
+                && velocity.abs() <= base_velocity * (1.0 - signature.velocity_drop_fraction)
+        });
+        if !matches {
+            self.matching_since = None;
+            return false;
+        }
+        let since = *self.matching_since.get_or_insert(elapsed);
+        elapsed - since >= signature.confirm_for
+    }
+}
+
+/// Stops the group when dropped, unless it has been disarmed.
+///
+/// This makes sure a cancelled [`MotorGroup::run_until_load`] or
+/// [`MotorGroup::anti_jam`] doesn't leave the motors running.
+pub(crate) struct StopOnCancel<'a, M: AsRef<[Motor]> + AsMut<[Motor]>> {
+    pub(crate) group: &'a mut MotorGroup<M>,
+    pub(crate) armed: bool,
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Drop for StopOnCancel<'_, M> {
+    fn drop(&mut self) {
+        if self.armed {
+            safety::best_effort_stop(self.group, MotorControl::Brake(BrakeMode::Brake), false);
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Runs the group at `voltage` until a game piece is captured, then holds
+    /// it.
+    ///
+    /// This is the usual intake pattern: a captured piece makes the motors
+    /// draw more current and slow down. The group runs for
+    /// `signature.spin_up`, then samples its free-running average current
+    /// and velocity for `signature.confirm_for` as a baseline, and then
+    /// watches for the [`LoadSignature`] every tick (see
+    /// [`MotorGroup::tick_interval`], 5ms by default). Each tick reads every
+    /// motor's current and velocity once; the motors are only written to at
+    /// the start and the end.
+    ///
+    /// Once a load is confirmed, the motors are braked with
+    /// [`BrakeMode::Hold`]. If `timeout` passes first (counted from the start,
+    /// including the spin up and baseline), they are braked with
+    /// [`BrakeMode::Brake`] instead. If the future is dropped before it
+    /// completes, such as by racing it against another one, the motors are
+    /// also braked with [`BrakeMode::Brake`], so they are never left running.
+    ///
+    /// A sample that can't be read from any motor doesn't count towards the
+    /// baseline and interrupts the confirmation window. Readings from only
+    /// some motors are averaged as usual.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing the errors of the
+    ///   voltage write and of the final brake if a motor device is not
+    ///   currently connected to the Smart Port. Its result is still the
+    ///   outcome of the run. Read errors aren't reported.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let signature = LoadSignature {
+    ///         spin_up: Duration::from_millis(300),
+    ///         current_rise: 0.5,
+    ///         velocity_drop_fraction: 0.3,
+    ///         confirm_for: Duration::from_millis(50),
+    ///     };
+    ///     match intake.run_until_load(12.0, signature, Duration::from_secs(3)).await {
+    ///         Ok(LoadResult::Loaded { .. }) => println!("Got one!"),
+    ///         Ok(LoadResult::TimedOut) => println!("Nothing to pick up"),
+    ///         Err(error) => println!("Intake error: {error:?}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn run_until_load(
+        &mut self,
+        voltage: f64,
+        signature: LoadSignature,
+        timeout: Duration,
+    ) -> Result<LoadResult, MotorGroupError<PortError, LoadResult>> {
+        let mut guard = StopOnCancel {
+            group: self,
+            armed: true,
+        };
+        let mut errors = match guard.group.set_voltage(voltage) {
+            Ok(_) => Vec::new(),
+            Err(error) => error.errors,
+        };
+
+        let mut detector = LoadDetector::new(signature);
+        let interval = guard.group.config.tick_interval;
+        let result = tick_loop(interval, Some(timeout), |elapsed| {
+            let sample = guard
+                .group
+                .current()
+                .or_else(|error| error.result.ok_or(()))
+                .ok()
+                .zip(
+                    guard
+                        .group
+                        .velocity()
+                        .or_else(|error| error.result.ok_or(()))
+                        .ok(),
+                );
+            if detector.update(elapsed, sample) {
+                return ControlFlow::Break(LoadResult::Loaded { after: elapsed });
+            }
+            if elapsed >= timeout {
+                return ControlFlow::Break(LoadResult::TimedOut);
+            }
+            ControlFlow::Continue(())
+        })
+        .await;
+
+        guard.armed = false;
+        let mode = match result {
+            LoadResult::Loaded { .. } => BrakeMode::Hold,
+            LoadResult::TimedOut => BrakeMode::Brake,
+        };
+        if let Err(error) = guard.group.brake(mode) {
+            errors.extend(error.errors);
+        }
+
+        if errors.is_empty() {
+            Ok(result)
+        } else {
+            Err(MotorGroupError::with_result(errors, result))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        pin::pin,
+        task::{Context, Waker},
+        time::Duration,
+    };
+
+    use vexide::{
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use super::{LoadDetector, LoadResult, LoadSignature};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    const SIGNATURE: LoadSignature = LoadSignature {
+        spin_up: Duration::from_millis(100),
+        current_rise: 0.5,
+        velocity_drop_fraction: 0.3,
+        confirm_for: Duration::from_millis(20),
+    };
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(1, Gearset::Blue))
+    }
+
+    /// Feeds `samples` to a detector, one every 10ms starting at `start`
+    /// milliseconds, and returns the time of the first detection.
+    fn feed(
+        detector: &mut LoadDetector,
+        start: u64,
+        samples: &[Option<(f64, f64)>],
+    ) -> Option<u64> {
+        samples.iter().enumerate().find_map(|(index, sample)| {
+            let millis = start + 10 * index as u64;
+            detector
+                .update(Duration::from_millis(millis), *sample)
+                .then_some(millis)
+        })
+    }
+
+    #[test]
+    fn load_is_detected_against_baseline() {
+        let mut detector = LoadDetector::new(SIGNATURE);
+        // Spinning up, drawing a lot of current: ignored
+        assert_eq!(feed(&mut detector, 0, &[Some((2.0, 100.0)); 10]), None);
+        // Free running; a failed read doesn't count
+        let free = [Some((0.4, -500.0)), None, Some((0.6, -500.0))];
+        assert_eq!(feed(&mut detector, 100, &free), None);
+        assert_eq!(detector.baseline(), Some((0.5, 500.0)));
+
+        // A current spike alone isn't a load, and neither is a brief dip
+        let noise = [
+            Some((1.5, -480.0)),
+            Some((1.2, -300.0)),
+            Some((0.5, -490.0)),
+        ];
+        assert_eq!(feed(&mut detector, 130, &noise), None);
+        // A failed read interrupts the confirmation window
+        let interrupted = [Some((1.2, -300.0)), Some((1.2, -300.0)), None];
+        assert_eq!(feed(&mut detector, 160, &interrupted), None);
+        // A captured piece, held for the confirmation window
+        let captured = [Some((1.1, -340.0)); 3];
+        assert_eq!(feed(&mut detector, 190, &captured), Some(210));
+    }
+
+    #[test]
+    fn baseline_waits_for_a_reading() {
+        let mut detector = LoadDetector::new(SIGNATURE);
+        assert_eq!(feed(&mut detector, 100, &[None; 5]), None);
+        assert_eq!(detector.baseline(), None);
+        assert_eq!(feed(&mut detector, 150, &[Some((0.5, 400.0))]), None);
+        assert_eq!(detector.baseline(), Some((0.5, 400.0)));
+    }
+
+    #[test]
+    fn timeout_stops_the_group() {
+        let group = group();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let mut group = group;
+            let error = group
+                .run_until_load(12.0, SIGNATURE, Duration::from_millis(20))
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        // The mock motor can't be read, so no load is ever seen
+        assert_eq!(error.result, Some(LoadResult::TimedOut));
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Brake))
+        );
+    }
+
+    #[test]
+    fn cancelling_stops_the_group() {
+        let mut group = group();
+        {
+            let mut run = pin!(group.run_until_load(12.0, SIGNATURE, Duration::from_secs(10)));
+            let mut context = Context::from_waker(Waker::noop());
+            assert!(run.as_mut().poll(&mut context).is_pending());
+        }
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Brake))
+        );
+    }
+}
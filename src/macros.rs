@@ -1,9 +1,25 @@
 /// A macro that creates a set of sharable motors.
 ///
-/// See [`motor_group!`] for more details.
+/// See [`motor_group!`] for more details. A
+/// [`WriteErrorStrategy`](crate::WriteErrorStrategy) can optionally be given
+/// before the motors:
+///
+/// ```rust,ignore
+/// let drive = shared_motors![
+///     strategy = WriteErrorStrategy::Stop;
+///     Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+///     Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+/// ];
+/// ```
 #[macro_export]
 macro_rules! shared_motors {
-    ( $item:tt ) => {{ $crate::shared_motors::SharedMotors::new($crate::motor_group!($item)) }};
+    ( strategy = $strategy:expr ; $( $item:expr ),* $(,)? ) => {{
+        $crate::SharedMotors::from_motors_with(
+            ::std::vec![$( $item ),*],
+            $strategy,
+        )
+    }};
+    ( $( $item:expr ),* $(,)? ) => {{ $crate::SharedMotors::new($crate::motor_group![$( $item ),*]) }};
 }
 
 /// A macro that creates a set of motors using [`MotorGroup`](crate::MotorGroup).
@@ -14,27 +30,20 @@ macro_rules! shared_motors {
 macro_rules! motor_group {
     ( $( $item:expr ),* $(,)?) => {
         {
-            use ::std::vec::Vec;
             use ::vexide::smart::motor::Motor;
 
-            let mut temp_vec: Vec<Motor> = Vec::new();
-
-            $(
-                temp_vec.push($item);
-            )*
-
-            $crate::MotorGroup::new(temp_vec)
+            let motors: ::std::vec::Vec<Motor> = ::std::vec![$( $item ),*];
+            $crate::MotorGroup::new(motors)
         }
     };
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::MotorGroup;
-
-    use super::*;
     use vexide::{prelude::*, smart::SmartPort};
 
+    use crate::{WriteErrorStrategy, tests::v5_motor};
+
     #[test]
     fn motor_group_compiles() {
         let mg_1 = motor_group![
@@ -51,4 +60,24 @@ mod tests {
         ];
         println!("{:?}", mg_1);
     }
+
+    #[test]
+    fn shared_motors_accepts_strategy() {
+        let shared = shared_motors![v5_motor(1)];
+        assert_eq!(
+            shared.current_config().write_error_strategy,
+            WriteErrorStrategy::Ignore
+        );
+
+        let shared = shared_motors![
+            strategy = WriteErrorStrategy::Stop;
+            v5_motor(1),
+            v5_motor(2),
+        ];
+        assert_eq!(
+            shared.current_config().write_error_strategy,
+            WriteErrorStrategy::Stop
+        );
+        assert_eq!(shared.lock().motors.len(), 2);
+    }
 }
@@ -0,0 +1,128 @@
+//! Compile-time checks of which public types are `Send` and `Sync`.
+//!
+//! These pin the surface described in [Tasks and threads](crate#tasks-and-threads):
+//! a change that makes an `Rc`-based group handle `Send`, or a [`MotorGroup`]
+//! or [`SendSharedMotors`] `!Send`, fails to build instead of silently
+//! changing where the types can be used.
+
+#[cfg(feature = "drivetrain")]
+use crate::DriveSide;
+#[cfg(feature = "events")]
+use crate::EventReceiver;
+use crate::{
+    ConfigSnapshot, ErasedGroup, GroupConfig, GroupEvent, GroupSnapshot, MotorCheckout, MotorGroup,
+    MotorGroupError, MotorGroupGuard, OutputModifiers, Preset, RecoveryOptions, RecoveryReport,
+    SendSharedMotors, SharedMotors, TaskGuard, WeakSharedMotors, WriteContext, WriteErrorStrategy,
+};
+#[cfg(feature = "diagnostics")]
+use crate::{FleetSummary, ReadinessReport};
+
+/// Fails to build unless every type implements `$trait`.
+macro_rules! assert_impl {
+    ($trait:path: $($ty:ty),+ $(,)?) => {
+        const _: () = {
+            const fn check<T: ?Sized + $trait>() {}
+            $(check::<$ty>();)+
+        };
+    };
+}
+
+/// Fails to build if any of the types implements `$trait`.
+///
+/// If `$ty` implements `$trait`, both impls of `Ambiguous` apply to it and
+/// the type of `_` can't be inferred.
+macro_rules! assert_not_impl {
+    ($trait:path: $($ty:ty),+ $(,)?) => {
+        $(
+            const _: fn() = || {
+                trait Ambiguous<A> {
+                    fn item() {}
+                }
+                impl<T: ?Sized> Ambiguous<()> for T {}
+                impl<T: ?Sized + $trait> Ambiguous<u8> for T {}
+                let _ = <$ty as Ambiguous<_>>::item;
+            };
+        )+
+    };
+}
+
+// Groups own their motors, so they can be moved between threads
+assert_impl!(Send: MotorGroup, MotorCheckout<'static>);
+// Reads through `&MotorGroup` update its caches
+assert_not_impl!(Sync: MotorGroup, MotorCheckout<'static>);
+
+// The cross-thread handle locks the group, so it can be shared as well
+assert_impl!(Send: SendSharedMotors);
+assert_impl!(Sync: SendSharedMotors);
+
+// The cross-task handles are `Rc`-based and stay on one thread
+assert_not_impl!(Send: SharedMotors, WeakSharedMotors, MotorGroupGuard<'static>, TaskGuard, ErasedGroup);
+assert_not_impl!(Sync: SharedMotors, WeakSharedMotors, MotorGroupGuard<'static>, TaskGuard, ErasedGroup);
+#[cfg(feature = "drivetrain")]
+assert_not_impl!(Send: DriveSide);
+#[cfg(feature = "drivetrain")]
+assert_not_impl!(Sync: DriveSide);
+
+// Plain data can go anywhere
+assert_impl!(
+    Send: MotorGroupError,
+    GroupConfig,
+    ConfigSnapshot,
+    OutputModifiers,
+    Preset,
+    RecoveryOptions,
+    RecoveryReport,
+    GroupSnapshot,
+    GroupEvent,
+    WriteContext,
+    WriteErrorStrategy,
+);
+assert_impl!(
+    Sync: MotorGroupError,
+    GroupConfig,
+    ConfigSnapshot,
+    OutputModifiers,
+    Preset,
+    RecoveryOptions,
+    RecoveryReport,
+    GroupSnapshot,
+    GroupEvent,
+    WriteContext,
+    WriteErrorStrategy,
+);
+#[cfg(feature = "diagnostics")]
+assert_impl!(Send: FleetSummary, ReadinessReport);
+#[cfg(feature = "diagnostics")]
+assert_impl!(Sync: FleetSummary, ReadinessReport);
+#[cfg(feature = "events")]
+assert_impl!(Send: EventReceiver);
+#[cfg(feature = "events")]
+assert_impl!(Sync: EventReceiver);
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use vexide::prelude::*;
+
+    use crate::{MotorGroup, tests::mock_motors};
+
+    #[test]
+    fn groups_can_move_to_another_thread() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        #[cfg(feature = "events")]
+        let mut events = group.events();
+        group.set_custom_write_policy(|_, _, _| crate::PolicyDecision::Continue);
+
+        let group = thread::spawn(move || {
+            _ = group.set_voltage(6.0);
+            group
+        })
+        .join()
+        .unwrap();
+        assert_eq!(group.command_generation(), 1);
+        // The failed write was reported to the receiver left behind
+        #[cfg(feature = "events")]
+        assert!(events.try_recv().is_some());
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::vec::Vec;
+use core::f64::consts::TAU;
+
+use vexide::smart::motor::Motor;
+
+use crate::{
+    GetterResult, MotorGroup, SharedMotors,
+    readings::{self, Reading},
+};
+
+/// Returns the total mechanical power of the motors in Watts, from their
+/// torques in Newton-meters and velocities in RPM.
+///
+/// Only motors with both readings count. The errors of the torque reads come
+/// first, followed by those of the velocity reads.
+pub(crate) fn net_mechanical_power(
+    torques: Vec<Reading<f64>>,
+    velocities: Vec<Reading<f64>>,
+) -> GetterResult<f64> {
+    let (torques, mut errors) = readings::partition(torques);
+    let (velocities, velocity_errors) = readings::partition(velocities);
+    errors.extend(velocity_errors);
+
+    let powers: Vec<f64> = torques
+        .into_iter()
+        .filter_map(|(index, torque)| {
+            let (_, rpm) = velocities.iter().find(|(other, _)| *other == index)?;
+            Some(torque * rpm * TAU / 60.0)
+        })
+        .collect();
+    readings::finish(
+        (!powers.is_empty()).then(|| powers.into_iter().sum()),
+        errors,
+    )
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the net mechanical power output of the motor group in Watts.
+    ///
+    /// Unlike [`MotorGroup::power`], which is the electrical power a motor
+    /// draws, this is the power the motors actually deliver at their output
+    /// shafts: each motor's torque times its angular velocity, summed over
+    /// the group. Its ratio to the total electrical power (the average power
+    /// times the number of motors) is the group's efficiency.
+    ///
+    /// Each motor's torque is read, then each motor's velocity. The power of
+    /// a motor is negative while it resists its motion, such as when a
+    /// mechanism is being braked or back-driven, so the total is the net
+    /// power the group puts into the mechanism.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error, containing the errors of
+    ///   the torque reads followed by those of the velocity reads. Its result
+    ///   is the total of the motors whose torque and velocity could both be
+    ///   read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     _ = flywheel.set_voltage(12.0);
+    ///
+    ///     if let Ok(watts) = flywheel.mechanical_power() {
+    ///         println!("The flywheel is taking in {watts:.1}W");
+    ///     }
+    /// }
+    /// ```
+    pub fn mechanical_power(&self) -> GetterResult<f64> {
+        let torques = self.read_each(Motor::torque);
+        let velocities = self.read_each(Motor::velocity);
+        net_mechanical_power(torques, velocities)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::mechanical_power`].
+    pub fn mechanical_power(&self) -> GetterResult<f64> {
+        self.0.borrow().mechanical_power()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::net_mechanical_power;
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn power_is_torque_times_angular_velocity() {
+        // 60 RPM is 2π rad/s, and 300 RPM is 10π rad/s
+        let power = net_mechanical_power(
+            vec![(0, Ok(1.5)), (1, Ok(0.5))],
+            vec![(0, Ok(60.0)), (1, Ok(300.0))],
+        )
+        .unwrap();
+        assert_close(power, 1.5 * 2.0 * PI + 0.5 * 10.0 * PI);
+
+        // A back-driven motor takes power out of the total
+        let power = net_mechanical_power(
+            vec![(0, Ok(1.0)), (1, Ok(-1.0))],
+            vec![(0, Ok(120.0)), (1, Ok(60.0))],
+        )
+        .unwrap();
+        assert_close(power, 4.0 * PI - 2.0 * PI);
+    }
+
+    #[test]
+    fn both_reads_errors_are_collected() {
+        let torque_error = PortError::Disconnected { port: 1 };
+        let velocity_error = PortError::Disconnected { port: 3 };
+        let error = net_mechanical_power(
+            vec![(0, Err(torque_error)), (1, Ok(2.0)), (2, Ok(1.0))],
+            vec![
+                (0, Err(torque_error)),
+                (1, Ok(60.0)),
+                (2, Err(velocity_error)),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![torque_error, torque_error, velocity_error]
+        );
+        // Only the motor with both readings counts
+        assert_close(error.result.unwrap(), 4.0 * PI);
+
+        let group = MotorGroup::new(mock_motors(1, Gearset::Green));
+        let error = group.mechanical_power().unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+    }
+}
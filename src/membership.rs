@@ -0,0 +1,361 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::Motor;
+
+use crate::{MotorGroup, check_invariant, meta::MotorMeta, read_cache::Change};
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Swaps the group's motors for `motors`, returning the old ones.
+    ///
+    /// The group's configuration (see [`MotorGroup::current_config`]) and
+    /// subscribers (see [`MotorGroup::events`]) are kept, so a group can be
+    /// rebuilt around new motor handles without setting it up again. Nothing
+    /// is written to the new motors, so apply the configuration again if they
+    /// weren't configured the same way (see [`MotorGroup::apply_config`]).
+    ///
+    /// Since the new motors may not be the old ones, each starts enabled,
+    /// with no label and an output scale of `1.0`, and the last command is
+    /// forgotten.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group.write_error_strategy(WriteErrorStrategy::Stop);
+    ///
+    ///     // Move the mechanism to a spare port, keeping its settings
+    ///     let old = motor_group.replace_motors(vec![Motor::new(
+    ///         peripherals.port_2,
+    ///         Gearset::Green,
+    ///         Direction::Forward,
+    ///     )]);
+    ///     drop(old);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no motors in `motors`.
+    ///
+    /// With the `no-panic` feature, this is only checked in debug builds, the
+    /// same way as in [`MotorGroup::new`].
+    pub fn replace_motors(&mut self, motors: M) -> M {
+        check_invariant(
+            !motors.as_ref().is_empty(),
+            "Cannot create a motor group with no motors",
+        );
+        self.meta = motors.as_ref().iter().map(MotorMeta::for_motor).collect();
+        self.last_command = None;
+        self.read_cache.invalidate(Change::Aggregation);
+        core::mem::replace(&mut self.motors, motors)
+    }
+}
+
+impl MotorGroup<Vec<Motor>> {
+    /// Adds a motor to the end of the group and returns its index.
+    ///
+    /// The motor starts enabled, with no label and an output scale of `1.0`.
+    /// Nothing is written to it, so it keeps its own gearset, direction, and
+    /// limits until the group's hardware settings are applied again (see
+    /// [`MotorGroup::apply_config`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     let index = motor_group.add_motor(Motor::new(
+    ///         peripherals.port_2,
+    ///         Gearset::Green,
+    ///         Direction::Forward,
+    ///     ));
+    ///     motor_group.set_label(index, "booster");
+    /// }
+    /// ```
+    pub fn add_motor(&mut self, motor: Motor) -> usize {
+        self.meta.push(MotorMeta::for_motor(&motor));
+        self.motors.push(motor);
+        self.read_cache.invalidate(Change::Aggregation);
+        self.motors.len() - 1
+    }
+
+    /// Removes the motor at `index` from the group and returns it.
+    ///
+    /// Every motor after it shifts down by one index, along with its label,
+    /// scale, and enabled flag.
+    ///
+    /// Returns `None` if `index` is out of bounds or if removing the motor
+    /// would leave the group without an enabled motor.
+    pub fn remove_motor(&mut self, index: usize) -> Option<Motor> {
+        let meta = self.meta.get(index)?;
+        if meta.is_active() && self.meta.iter().filter(|meta| meta.is_active()).count() <= 1 {
+            return None;
+        }
+        self.meta.remove(index);
+        self.read_cache.invalidate(Change::Aggregation);
+        Some(self.motors.remove(index))
+    }
+
+    /// Splits the group in two at `at`, moving the motors from `at` onwards
+    /// into a new group.
+    ///
+    /// Each motor keeps its label, scale, and enabled flag. The new group
+    /// starts with the same configuration (see [`MotorGroup::current_config`]),
+    /// hard voltage cap (see [`MotorGroup::set_hard_voltage_cap`]) and last
+    /// command as this one, since its motors were last written by this group.
+    ///
+    /// Returns `None` and leaves the group alone if either half would be left
+    /// without an enabled motor, including when `at` is `0` or out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Reverse),
+    ///         Motor::new(peripherals.port_4, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     // Drive each side separately for a turn...
+    ///     let mut right = drive.split(2).unwrap();
+    ///     _ = drive.set_voltage(6.0);
+    ///     _ = right.set_voltage(-6.0);
+    ///
+    ///     // ...then put the drivetrain back together.
+    ///     drive.merge(right);
+    /// }
+    /// ```
+    pub fn split(&mut self, at: usize) -> Option<Self> {
+        if at > self.meta.len() {
+            return None;
+        }
+        let (front, back) = self.meta.split_at(at);
+        if !front.iter().any(MotorMeta::is_active) || !back.iter().any(MotorMeta::is_active) {
+            return None;
+        }
+
+        let mut group = Self::new(self.motors.split_off(at));
+        group.meta = self.meta.split_off(at);
+        group.config = self.config;
+        group.hard_voltage_cap = self.hard_voltage_cap;
+        group.last_command = self.last_command;
+        self.read_cache.invalidate(Change::Aggregation);
+        Some(group)
+    }
+
+    /// Moves every motor from `other` to the end of this group.
+    ///
+    /// Each motor keeps its label, scale, and enabled flag. This group's
+    /// configuration is kept and `other`'s is discarded; nothing is written to
+    /// the motors, so apply the configuration again if the groups were
+    /// configured differently.
+    ///
+    /// The merged group keeps the lower of the two groups' hard voltage caps
+    /// (see [`MotorGroup::set_hard_voltage_cap`]), so that no motor is written
+    /// with more than its old group allowed.
+    pub fn merge(&mut self, mut other: Self) {
+        self.hard_voltage_cap = match (self.hard_voltage_cap, other.hard_voltage_cap) {
+            (Some(cap), Some(other_cap)) => Some(cap.min(other_cap)),
+            (cap, other_cap) => cap.or(other_cap),
+        };
+        // Taken rather than moved, since `other` still runs its stop-on-drop
+        // brake (on no motors) when it's dropped
+        self.motors.append(&mut other.motors);
+        self.meta.append(&mut other.meta);
+        self.read_cache.invalidate(Change::Aggregation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use crate::{MotorGroup, PositionFallback, WriteErrorStrategy, tests::v5_motor};
+
+    /// Builds a group of motors on ports `1..=size`, each labelled with its
+    /// port and scaled by a tenth of it.
+    fn labelled_group(size: u8) -> MotorGroup {
+        let mut group = MotorGroup::new((1..=size).map(v5_motor).collect::<Vec<_>>());
+        for port in 1..=size {
+            let index = usize::from(port - 1);
+            group.set_label(index, format!("port {port}"));
+            group.set_scale(index, f64::from(port) / 10.0);
+        }
+        group
+    }
+
+    /// Returns the port, label, scale, and enabled flag of every motor.
+    fn layout(group: &MotorGroup) -> Vec<(u8, Option<&str>, f64, bool)> {
+        (0..group.motors.len())
+            .map(|index| {
+                (
+                    group.motors[index].port_number(),
+                    group.label(index),
+                    group.scale(index),
+                    group.is_enabled(index),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn metadata_follows_added_and_removed_motors() {
+        let mut group = labelled_group(3);
+        assert!(group.set_enabled(1, false));
+
+        let index = group.add_motor(v5_motor(4));
+        assert_eq!(index, 3);
+        group.set_label(index, "port 4");
+
+        let removed = group.remove_motor(0).unwrap();
+        assert_eq!(removed.port_number(), 1);
+        assert_eq!(
+            layout(&group),
+            vec![
+                (2, Some("port 2"), 0.2, false),
+                (3, Some("port 3"), 0.3, true),
+                (4, Some("port 4"), 1.0, true),
+            ]
+        );
+
+        assert!(group.remove_motor(3).is_none());
+        assert!(group.remove_motor(2).is_some());
+        // The disabled motor can go, but the last enabled one can't
+        assert!(group.remove_motor(0).is_some());
+        assert!(group.remove_motor(0).is_none());
+        assert_eq!(layout(&group), vec![(3, Some("port 3"), 0.3, true)]);
+    }
+
+    #[test]
+    fn metadata_follows_split_and_merged_motors() {
+        let mut group = labelled_group(4);
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        assert!(group.set_enabled(3, false));
+
+        assert!(group.split(0).is_none());
+        assert!(group.split(5).is_none());
+        // The back half would only have the disabled motor
+        assert!(group.split(3).is_none());
+
+        let back = group.split(2).unwrap();
+        assert_eq!(
+            back.current_config().write_error_strategy,
+            WriteErrorStrategy::Stop
+        );
+        assert_eq!(
+            layout(&back),
+            vec![
+                (3, Some("port 3"), 0.3, true),
+                (4, Some("port 4"), 0.4, false),
+            ]
+        );
+        assert_eq!(layout(&group).len(), 2);
+
+        group.merge(back);
+        assert_eq!(
+            layout(&group),
+            vec![
+                (1, Some("port 1"), 0.1, true),
+                (2, Some("port 2"), 0.2, true),
+                (3, Some("port 3"), 0.3, true),
+                (4, Some("port 4"), 0.4, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn disabled_motors_are_skipped() {
+        let mut group = labelled_group(3);
+        assert!(group.set_enabled(0, false));
+        assert!(group.set_enabled(2, false));
+        // The last enabled motor can't be disabled
+        assert!(!group.set_enabled(1, false));
+
+        // Every mock motor fails, so the error count shows which motors were
+        // visited
+        assert_eq!(group.set_voltage(6.0).unwrap_err().errors.len(), 1);
+        assert_eq!(group.velocity().unwrap_err().errors.len(), 1);
+        assert!(group.checkout(1).is_none());
+
+        assert!(group.set_enabled(0, true));
+        assert_eq!(group.set_voltage(6.0).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn scale_applies_to_voltage_and_velocity() {
+        let group = labelled_group(2);
+        assert_eq!(
+            group.scaled_targets(MotorControl::Voltage(10.0)),
+            vec![MotorControl::Voltage(1.0), MotorControl::Voltage(2.0)]
+        );
+        assert_eq!(
+            group.scaled_targets(MotorControl::Velocity(-15)),
+            vec![MotorControl::Velocity(-2), MotorControl::Velocity(-3)]
+        );
+        let brake = MotorControl::Brake(BrakeMode::Hold);
+        assert_eq!(group.scaled_targets(brake), vec![brake; 2]);
+    }
+
+    #[test]
+    fn replacing_motors_keeps_the_configuration() {
+        let mut group = labelled_group(2);
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        group.position_fallback(Some(PositionFallback {
+            failures_to_enter: 2,
+            gain: 0.05,
+            max_voltage: 6.0,
+        }));
+        assert!(group.set_enabled(0, false));
+        _ = group.set_voltage(6.0);
+        let config = group.current_config();
+
+        let old = group.replace_motors((5..=7).map(v5_motor).collect());
+        assert_eq!(
+            old.iter().map(Motor::port_number).collect::<Vec<_>>(),
+            [1, 2]
+        );
+        assert_eq!(group.current_config(), config);
+        // The new motors don't inherit the old ones' settings
+        assert_eq!(
+            layout(&group),
+            vec![
+                (5, None, 1.0, true),
+                (6, None, 1.0, true),
+                (7, None, 1.0, true),
+            ]
+        );
+        assert_eq!(group.last_command, None);
+        // With the stop strategy, only the first failing write is reported
+        assert_eq!(group.set_voltage(6.0).unwrap_err().errors.len(), 1);
+    }
+
+    #[cfg(any(not(feature = "no-panic"), debug_assertions))]
+    #[test]
+    #[should_panic(expected = "Cannot create a motor group with no motors")]
+    fn replacing_with_no_motors_panics() {
+        let mut group = labelled_group(1);
+        _ = group.replace_motors(Vec::new());
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "events")]
+use core::cell::Cell;
+
+use vexide::{
+    prelude::Direction,
+    smart::motor::{Motor, MotorControl},
+};
+
+#[cfg(feature = "diagnostics")]
+use crate::wear::WearHistory;
+use crate::{MotorGroup, read_cache::Change, velocity};
+
+/// Software state the group keeps about each of its motors.
+///
+/// The group stores one of these for every motor, at the same index as the
+/// motor itself. Anything that moves motors around (such as
+/// [`MotorGroup::remove_motor`] or [`MotorGroup::split`]) moves their metadata
+/// along with them.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MotorMeta {
+    /// Whether the motor missed the last position reference change that other
+    /// motors in the group received, so its position can't be compared with
+    /// theirs.
+    pub(crate) reference_stale: bool,
+    /// The direction the motor should have. See [`MotorGroup::directions`].
+    pub(crate) direction: Option<Direction>,
+    /// Whether the motor missed the last direction set on the group, so it
+    /// has to be re-sent before the motor's next target.
+    pub(crate) direction_stale: bool,
+    /// Whether the motor is currently checked out of the group with
+    /// [`MotorGroup::checkout`](crate::MotorGroup::checkout).
+    pub(crate) checked_out: bool,
+    /// See [`MotorGroup::set_enabled`].
+    pub(crate) enabled: bool,
+    /// See [`MotorGroup::set_label`].
+    pub(crate) label: Option<String>,
+    /// See [`MotorGroup::set_scale`].
+    pub(crate) scale: f64,
+    /// See [`MotorGroup::update_wear`].
+    #[cfg(feature = "diagnostics")]
+    pub(crate) wear: WearHistory,
+    /// How many position targets in a row the motor has rejected.
+    pub(crate) position_failures: u32,
+    /// See [`MotorGroup::position_fallback`].
+    pub(crate) in_fallback: bool,
+    /// Whether the motor could be reached by the last read or target write,
+    /// or `None` if it hasn't been tried. See [`MotorGroup::events`].
+    #[cfg(feature = "events")]
+    pub(crate) connected: Cell<Option<bool>>,
+    /// Whether the motor was over temperature when last read. See
+    /// [`MotorGroup::events`].
+    #[cfg(feature = "events")]
+    pub(crate) over_temperature: Cell<bool>,
+}
+
+impl Default for MotorMeta {
+    fn default() -> Self {
+        Self {
+            reference_stale: false,
+            direction: None,
+            direction_stale: false,
+            checked_out: false,
+            enabled: true,
+            label: None,
+            scale: 1.0,
+            #[cfg(feature = "diagnostics")]
+            wear: WearHistory::default(),
+            position_failures: 0,
+            in_fallback: false,
+            #[cfg(feature = "events")]
+            connected: Cell::new(None),
+            #[cfg(feature = "events")]
+            over_temperature: Cell::new(false),
+        }
+    }
+}
+
+impl MotorMeta {
+    /// Returns the metadata of a motor joining a group, with the direction
+    /// it's configured with if it can be read.
+    pub(crate) fn for_motor(motor: &Motor) -> Self {
+        Self {
+            direction: motor.direction().ok(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns `true` if the group's reads and writes should include the
+    /// motor.
+    pub(crate) fn is_active(&self) -> bool {
+        self.enabled && !self.checked_out
+    }
+
+    /// Returns the direction that has to be sent to the motor before its
+    /// next target, if it missed its last direction write.
+    pub(crate) fn pending_direction(&self) -> Option<Direction> {
+        self.direction.filter(|_| self.direction_stale)
+    }
+
+    /// Returns `target` with the motor's output scale applied.
+    pub(crate) fn scale_target(&self, target: MotorControl) -> MotorControl {
+        match target {
+            MotorControl::Voltage(volts) => MotorControl::Voltage(volts * self.scale),
+            MotorControl::Velocity(rpm) => {
+                MotorControl::Velocity(velocity::round_rpm(f64::from(rpm) * self.scale))
+            }
+            other => other,
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns `target` scaled for each motor in the group, in order.
+    pub(crate) fn scaled_targets(&self, target: MotorControl) -> Vec<MotorControl> {
+        self.meta
+            .iter()
+            .map(|meta| meta.scale_target(target))
+            .collect()
+    }
+
+    /// Enables or disables the motor at `index`.
+    ///
+    /// A disabled motor stays in the group, but every read and write skips it
+    /// until it's enabled again, just like a checked out motor (see
+    /// [`MotorGroup::checkout`]). This is useful for taking a motor that is
+    /// known to be broken out of a subsystem without rebuilding the group.
+    /// Disabling a motor doesn't stop it, so brake it first if it shouldn't
+    /// keep its last target.
+    ///
+    /// Returns `false` and leaves the motor alone if disabling it would leave
+    /// the group without an active motor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // The motor on port 2 has a stripped gearbox.
+    ///     if let Some(mut motor) = motor_group.checkout(1) {
+    ///         _ = motor.brake(BrakeMode::Coast);
+    ///     }
+    ///     motor_group.set_enabled(1, false);
+    /// }
+    /// ```
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) -> bool {
+        let active = self.meta.iter().filter(|meta| meta.is_active()).count();
+        let meta = &mut self.meta[index];
+        if !enabled && meta.is_active() && active <= 1 {
+            return false;
+        }
+        meta.enabled = enabled;
+        self.read_cache.invalidate(Change::Aggregation);
+        true
+    }
+
+    /// Returns `true` if the motor at `index` is enabled.
+    ///
+    /// See [`MotorGroup::set_enabled`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_enabled(&self, index: usize) -> bool {
+        self.meta[index].enabled
+    }
+
+    /// Sets a human-readable label for the motor at `index`, such as
+    /// `"front left"`, for use in diagnostics.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_label(&mut self, index: usize, label: impl Into<String>) -> &mut Self {
+        self.meta[index].label = Some(label.into());
+        self
+    }
+
+    /// Returns the label of the motor at `index`, or `None` if it has none.
+    ///
+    /// See [`MotorGroup::set_label`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.meta[index].label.as_deref()
+    }
+
+    /// Sets the output scale of the motor at `index`.
+    ///
+    /// Every voltage and velocity target written to the group is multiplied
+    /// by the scale before reaching this motor, which can trim a motor that
+    /// runs faster than the others it shares a load with. Position targets and
+    /// brakes aren't scaled, and neither are readings. The default scale is
+    /// `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_scale(&mut self, index: usize, scale: f64) -> &mut Self {
+        self.meta[index].scale = scale;
+        self
+    }
+
+    /// Returns the output scale of the motor at `index`.
+    ///
+    /// See [`MotorGroup::set_scale`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn scale(&self, index: usize) -> f64 {
+        self.meta[index].scale
+    }
+}
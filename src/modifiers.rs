@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use vexide::{math::Angle, smart::motor::Motor};
+
+use crate::{MotorGroup, SharedMotors, TargetingMode};
+
+/// The settings that shape a motor group's commands on their way to the
+/// motors, in the order they're applied.
+///
+/// It's returned by [`MotorGroup::modifiers`]. With the `serde` feature, it
+/// implements `serde::Serialize`, with the position limits in degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputModifiers {
+    /// Keeps voltage and velocity targets from driving further past the
+    /// limits, and clamps position targets into them. See
+    /// [`MotorGroup::set_position_limits`].
+    pub position_limits: Option<(Angle, Angle)>,
+    /// How a position target is split between the motors. See
+    /// [`MotorGroup::set_targeting_mode`].
+    pub targeting_mode: TargetingMode,
+    /// What each motor's voltage and velocity targets are multiplied by. See
+    /// [`MotorGroup::set_scale`].
+    pub scales: Vec<f64>,
+    /// The voltage no motor is driven past, in volts. See
+    /// [`MotorGroup::set_hard_voltage_cap`].
+    pub hard_voltage_cap: Option<f64>,
+    /// Whether each motor is written to at all. See
+    /// [`MotorGroup::set_enabled`].
+    pub enabled: Vec<bool>,
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns every setting that changes a command between
+    /// [`MotorGroup::set_target`] and the motors, without reading them.
+    ///
+    /// A command is first limited to the group's position limits, then split
+    /// between the motors by the targeting mode. Each motor's share is
+    /// multiplied by its scale and held under the hard voltage cap, and is
+    /// only written if the motor is enabled. The motors' directions are left
+    /// to the motors themselves (see [`MotorGroup::set_direction`]), so
+    /// they aren't part of this.
+    ///
+    /// This is meant for dashboards that show why the motors aren't doing
+    /// exactly what they were told. See [`MotorGroup::config_snapshot`] for
+    /// the rest of the group's configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     drive.set_scale(1, 0.95);
+    ///     drive.set_hard_voltage_cap(8.0);
+    ///
+    ///     println!("{:?}", drive.modifiers());
+    /// }
+    /// ```
+    pub fn modifiers(&self) -> OutputModifiers {
+        OutputModifiers {
+            position_limits: self.config.position_limits,
+            targeting_mode: self.config.targeting_mode,
+            scales: self.meta.iter().map(|meta| meta.scale).collect(),
+            hard_voltage_cap: self.hard_voltage_cap,
+            enabled: self.meta.iter().map(|meta| meta.enabled).collect(),
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::modifiers`].
+    pub fn modifiers(&self) -> OutputModifiers {
+        self.0.borrow().modifiers()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::math::Angle;
+
+    use super::OutputModifiers;
+    use crate::{TargetingMode, tests::two_motor_group};
+
+    #[test]
+    fn modifiers_match_the_configured_group() {
+        let mut group = two_motor_group();
+        assert_eq!(
+            group.modifiers(),
+            OutputModifiers {
+                position_limits: None,
+                targeting_mode: TargetingMode::Absolute,
+                scales: vec![1.0, 1.0],
+                hard_voltage_cap: None,
+                enabled: vec![true, true],
+            }
+        );
+
+        let limits = (Angle::from_degrees(-10.0), Angle::from_degrees(90.0));
+        group
+            .set_targeting_mode(TargetingMode::RelativeDelta)
+            .set_scale(0, 0.8);
+        group.set_position_limits(limits.0, limits.1);
+        group.set_hard_voltage_cap(6.0);
+        group.set_enabled(1, false);
+        assert_eq!(
+            group.modifiers(),
+            OutputModifiers {
+                position_limits: Some(limits),
+                targeting_mode: TargetingMode::RelativeDelta,
+                scales: vec![0.8, 1.0],
+                hard_voltage_cap: Some(6.0),
+                enabled: vec![true, false],
+            }
+        );
+    }
+}
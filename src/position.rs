@@ -0,0 +1,571 @@
+use alloc::vec::Vec;
+
+use vexide::{
+    math::Angle,
+    smart::{
+        PortError, SmartDevice,
+        motor::{Motor, MotorControl},
+    },
+};
+
+use crate::{
+    GetterResult, MotorGroup, MotorGroupError,
+    readings::{self, Reading},
+    reference,
+};
+
+/// The average position of a motor group, returned by
+/// [`MotorGroup::group_position`].
+///
+/// # Precision
+///
+/// The position is stored as an `f64` number of degrees, and every operation
+/// on it is done in degrees. The group's average is taken in degrees too, so
+/// a reading is only converted once, from each motor's [`Angle`]. An `f64`
+/// represents whole degrees exactly up to 2^53, and keeps better than a
+/// millionth of a degree of precision up to about 10^9 degrees (over two
+/// million revolutions), far beyond what a motor travels in a match.
+///
+/// [`Angle`] itself stores radians, so converting to and from it with
+/// [`GroupPosition::to_position`] can change the last bit or so of the value.
+/// Comparisons against limits should be done on a `GroupPosition` (or its
+/// [`degrees`](GroupPosition::degrees)) rather than on an [`Angle`] that has
+/// been converted back and forth.
+///
+/// Positions are never NaN or infinite. Operations that would produce such a
+/// position, such as [`GroupPosition::add_degrees`] with an infinite delta,
+/// return `None` instead.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct GroupPosition {
+    degrees: f64,
+}
+
+impl GroupPosition {
+    /// Creates a position from a number of degrees, or returns `None` if
+    /// `degrees` isn't finite.
+    pub fn from_degrees(degrees: f64) -> Option<Self> {
+        degrees.is_finite().then_some(Self { degrees })
+    }
+
+    /// Returns the position in degrees.
+    pub fn degrees(self) -> f64 {
+        self.degrees
+    }
+
+    /// Returns the position in revolutions of the motor's output shaft.
+    pub fn revolutions(self) -> f64 {
+        self.degrees / 360.0
+    }
+
+    /// Converts the position to an [`Angle`], such as for
+    /// [`MotorGroup::set_position_target`].
+    ///
+    /// See the [precision model](GroupPosition#precision) for how this
+    /// conversion rounds.
+    pub fn to_position(self) -> Angle {
+        Angle::from_degrees(self.degrees)
+    }
+
+    /// Returns the position moved by `delta` degrees, or `None` if the result
+    /// isn't finite.
+    pub fn add_degrees(self, delta: f64) -> Option<Self> {
+        Self::from_degrees(self.degrees + delta)
+    }
+}
+
+/// Error returned by [`MotorGroup::max_distance_to_target`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TargetDistanceError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// A motor isn't being moved to a position target, so it has no distance
+    /// to one.
+    NotPositionTarget {
+        /// The index of the motor in the group.
+        index: usize,
+        /// The motor's current target.
+        target: MotorControl,
+    },
+}
+
+impl From<PortError> for TargetDistanceError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for TargetDistanceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::NotPositionTarget { index, target } => {
+                write!(
+                    f,
+                    "motor {index} has no position target, its target is {target:?}"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for TargetDistanceError {}
+
+/// Returns the largest distance in degrees between a motor's position and its
+/// target, from `(target, position)` readings.
+///
+/// Motors without a position target are reported as errors, and are left out
+/// of the result along with those that couldn't be read.
+pub(crate) fn max_distance(
+    readings: Vec<Reading<(MotorControl, f64)>>,
+) -> Result<f64, MotorGroupError<TargetDistanceError, f64>> {
+    let mut errors = Vec::new();
+    let mut max: Option<f64> = None;
+    for (index, reading) in readings {
+        match reading {
+            Ok((MotorControl::Position(target, _), position)) => {
+                let distance = (target.as_degrees() - position).abs();
+                max = Some(max.map_or(distance, |max| max.max(distance)));
+            }
+            Ok((target, _)) => {
+                errors.push(TargetDistanceError::NotPositionTarget { index, target });
+            }
+            Err(error) => errors.push(error.into()),
+        }
+    }
+    readings::finish(max, errors)
+}
+
+/// Converts a motor position to the position of the mechanism it drives
+/// through `external_ratio` (motor revolutions per mechanism revolution).
+pub(crate) fn output_position(position: GroupPosition, external_ratio: f64) -> Angle {
+    Angle::from_degrees(position.degrees() / external_ratio)
+}
+
+/// Returns the distance in degrees between the furthest apart positions.
+pub(crate) fn spread(positions: Vec<f64>, errors: Vec<PortError>) -> GetterResult<f64> {
+    let spread = positions
+        .iter()
+        .copied()
+        .reduce(f64::max)
+        .zip(positions.iter().copied().reduce(f64::min))
+        .map(|(max, min)| max - min);
+    readings::finish(spread, errors)
+}
+
+/// Returns whether every target read is a position target.
+pub(crate) fn all_position_targets(
+    targets: impl IntoIterator<Item = Reading<MotorControl>>,
+) -> GetterResult<bool> {
+    let (targets, errors) = readings::partition(targets);
+    let all = (!targets.is_empty()).then(|| {
+        targets
+            .iter()
+            .all(|(_, target)| matches!(target, MotorControl::Position(..)))
+    });
+    readings::finish(all, errors)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the motor group's average position as a [`GroupPosition`].
+    ///
+    /// This is the same average as [`MotorGroup::position`], including
+    /// leaving out motors with a stale position reference, but kept in
+    /// degrees so that further math on it doesn't round through radians. See
+    /// the [precision model](GroupPosition#precision).
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is the average of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if let Ok(position) = motor_group.group_position() {
+    ///         println!("Lift is {:.2} revolutions up", position.revolutions());
+    ///     }
+    /// }
+    /// ```
+    pub fn group_position(&self) -> GetterResult<GroupPosition> {
+        reference::average_position(self.read_each(Motor::position), &self.meta)
+    }
+
+    /// Returns how far apart the group's motors are, in degrees, from the
+    /// motor that has turned the least to the one that has turned the most.
+    ///
+    /// Motors sharing a gear train turn together, so this should stay near
+    /// zero; a growing spread means their encoders have fallen out of sync
+    /// (or a motor is slipping). Like [`MotorGroup::group_position`], motors
+    /// with a stale position reference are left out, since their positions
+    /// can't be compared with the rest.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is the spread of the motors that
+    ///   could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     if lift.position_spread_degrees().is_ok_and(|spread| spread > 10.0) {
+    ///         println!("Lift encoders are out of sync");
+    ///     }
+    /// }
+    /// ```
+    pub fn position_spread_degrees(&self) -> GetterResult<f64> {
+        let (positions, errors) =
+            reference::comparable_positions(self.read_each(Motor::position), &self.meta);
+        spread(positions, errors)
+    }
+
+    /// Returns the average position of the mechanism the group drives, after
+    /// the external gear ratio.
+    ///
+    /// A motor's position is already measured at the output of its
+    /// cartridge, since [`Motor::position`] accounts for the [`Gearset`]
+    /// (for example, one revolution of a blue cartridge's output is 300
+    /// encoder ticks). What's left between the motors and the mechanism is
+    /// the external ratio set with [`MotorGroup::shift_ratio`], so the
+    /// average position (see [`MotorGroup::group_position`]) is divided by
+    /// it. This assumes every motor drives the mechanism through the same
+    /// ratio, which is the case for motors sharing a gear train.
+    ///
+    /// The division is done in degrees, and only the result is converted to
+    /// an [`Angle`], so it rounds once. See the
+    /// [precision model](GroupPosition#precision).
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is computed from the motors that
+    ///   could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     // 36:48 from the motors to the wheels
+    ///     _ = drive.shift_ratio(48.0 / 36.0);
+    ///
+    ///     if let Ok(wheel) = drive.output_position() {
+    ///         println!("Wheels have turned {:.1} times", wheel.as_turns());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`Gearset`]: vexide::smart::motor::Gearset
+    pub fn output_position(&self) -> GetterResult<Angle> {
+        let external_ratio = self.config.external_ratio;
+        readings::map_result(self.group_position(), |position| {
+            output_position(position, external_ratio)
+        })
+    }
+
+    /// Returns how far the motor that is furthest from its position target
+    /// still has to go.
+    ///
+    /// This is the largest distance between any motor's position and its own
+    /// position target (see [`Motor::target`]), not the average. When motors
+    /// fall out of sync during a move, the average can reach the target
+    /// while the slowest motor is still short of it, so this is the right
+    /// check for whether the whole group has arrived.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error, or with a
+    ///   [`TargetDistanceError::NotPositionTarget`] error for each motor that
+    ///   isn't being moved to a position target. Its result is the largest
+    ///   distance of the other motors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///
+    ///     _ = lift.set_position_target(Angle::from_degrees(720.0), 100);
+    ///     while lift
+    ///         .max_distance_to_target()
+    ///         .is_ok_and(|distance| distance.as_degrees() > 5.0)
+    ///     {
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn max_distance_to_target(
+        &self,
+    ) -> Result<Angle, MotorGroupError<TargetDistanceError, Angle>> {
+        let distance = max_distance(self.read_each(|motor| {
+            let target = motor.target();
+            Ok((target, motor.position()?.as_degrees()))
+        }));
+        match distance {
+            Ok(distance) => Ok(Angle::from_degrees(distance)),
+            Err(error) => Err(match error.result {
+                Some(distance) => {
+                    MotorGroupError::with_result(error.errors, Angle::from_degrees(distance))
+                }
+                None => MotorGroupError::with_empty_result(error.errors),
+            }),
+        }
+    }
+
+    /// Returns whether every motor in the group is under a position target,
+    /// such as holding its position after a move.
+    ///
+    /// Each motor's target is the last one it accepted, so a motor whose last
+    /// write failed still reports the target before it. A motor given a
+    /// position target counts even after reaching it, since it keeps holding
+    /// the position. A brake, velocity, or voltage target on any motor makes
+    /// this `false`.
+    ///
+    /// The targets themselves don't need to be read from the motors, but a
+    /// motor that isn't connected to its port can't be holding anything, so it
+    /// counts as a read error.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor device is not
+    ///   currently connected to the Smart Port. Its result is whether the
+    ///   other motors are all under position targets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     _ = lift.set_position_target(Angle::from_degrees(120.0), 100);
+    ///     // ...later in the routine...
+    ///     if matches!(lift.is_position_controlled(), Ok(false)) {
+    ///         println!("The lift was given another command; it isn't holding");
+    ///     }
+    /// }
+    /// ```
+    pub fn is_position_controlled(&self) -> GetterResult<bool> {
+        all_position_targets(self.read_each(|motor| {
+            motor.validate_port()?;
+            Ok(motor.target())
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::{
+            PortError,
+            motor::{BrakeMode, MotorControl},
+        },
+    };
+
+    use super::{
+        GroupPosition, TargetDistanceError, all_position_targets, max_distance, output_position,
+        spread,
+    };
+    use crate::{MotorGroup, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    fn position(degrees: f64) -> MotorControl {
+        MotorControl::Position(Angle::from_degrees(degrees), 100)
+    }
+
+    #[test]
+    fn conversions_agree() {
+        let position = GroupPosition::from_degrees(-540.0).unwrap();
+        assert_eq!(position.degrees(), -540.0);
+        assert_eq!(position.revolutions(), -1.5);
+        assert!((position.to_position().as_degrees() + 540.0).abs() < 1e-9);
+        assert_eq!(
+            position.add_degrees(90.0),
+            GroupPosition::from_degrees(-450.0)
+        );
+        assert!(position < GroupPosition::default());
+    }
+
+    #[test]
+    fn extreme_positions_stay_finite_and_precise() {
+        assert_eq!(GroupPosition::from_degrees(f64::NAN), None);
+        assert_eq!(GroupPosition::from_degrees(f64::NEG_INFINITY), None);
+
+        let max = GroupPosition::from_degrees(f64::MAX).unwrap();
+        assert_eq!(max.add_degrees(f64::MAX), None);
+        assert_eq!(max.add_degrees(f64::INFINITY), None);
+        assert_eq!(max.add_degrees(-f64::MAX), GroupPosition::from_degrees(0.0));
+
+        // Two million revolutions in, small moves are still exact to well
+        // under a millionth of a degree
+        let far = GroupPosition::from_degrees(720_000_000.0).unwrap();
+        let moved = far.add_degrees(0.001).unwrap();
+        assert!((moved.degrees() - far.degrees() - 0.001).abs() < 1e-6);
+        // The round trip through `Angle` is only off by rounding
+        let angle = far.to_position();
+        assert!((angle.as_degrees() - far.degrees()).abs() <= far.degrees() * 1e-15);
+        assert_eq!(Angle::from_degrees(far.degrees()), angle);
+    }
+
+    #[test]
+    fn slowest_motor_decides_the_distance() {
+        // The average is 5° short, but one motor is still 30° short
+        let readings = vec![
+            (0, Ok((position(90.0), 110.0))),
+            (1, Ok((position(90.0), 60.0))),
+            (2, Ok((position(-90.0), -85.0))),
+        ];
+        let distance = max_distance(readings).unwrap();
+        assert!((distance - 30.0).abs() < 1e-9);
+
+        // A motor past its target counts as far as one short of it
+        let readings = vec![
+            (0, Ok((position(0.0), 45.0))),
+            (1, Ok((position(0.0), -10.0))),
+        ];
+        assert!((max_distance(readings).unwrap() - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn motors_without_position_targets_are_errors() {
+        let readings = vec![
+            (0, Ok((position(90.0), 80.0))),
+            (1, Ok((MotorControl::Velocity(200), 0.0))),
+            (2, Err(DISCONNECTED)),
+        ];
+        let error = max_distance(readings).unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                TargetDistanceError::NotPositionTarget {
+                    index: 1,
+                    target: MotorControl::Velocity(200),
+                },
+                TargetDistanceError::Port {
+                    source: DISCONNECTED
+                },
+            ]
+        );
+        assert!((error.result.unwrap() - 10.0).abs() < 1e-9);
+
+        // The mock motor can't be read, so nothing is known
+        let group = MotorGroup::new(mock_motors(1, Gearset::Red));
+        let error = group.max_distance_to_target().unwrap_err();
+        assert_eq!(error.result, None);
+        assert_eq!(error.errors.len(), 1);
+    }
+
+    #[test]
+    fn only_uniform_position_targets_are_position_controlled() {
+        let uniform = vec![(0, Ok(position(90.0))), (1, Ok(position(-30.0)))];
+        assert!(all_position_targets(uniform).unwrap());
+        let uniform = vec![
+            (0, Ok(MotorControl::Velocity(200))),
+            (1, Ok(MotorControl::Velocity(200))),
+        ];
+        assert!(!all_position_targets(uniform).unwrap());
+        let mixed = vec![
+            (0, Ok(position(90.0))),
+            (1, Ok(MotorControl::Brake(BrakeMode::Hold))),
+        ];
+        assert!(!all_position_targets(mixed).unwrap());
+
+        // Read errors are aggregated, keeping what the other motors say
+        let error = all_position_targets(vec![
+            (0, Ok(position(90.0))),
+            (1, Err(DISCONNECTED)),
+            (2, Ok(position(90.0))),
+        ])
+        .unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(true));
+
+        // The mock motor isn't connected, so nothing is known
+        let group = MotorGroup::new(mock_motors(1, Gearset::Red));
+        let error = group.is_position_controlled().unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn output_position_divides_by_external_ratio() {
+        // Two revolutions of a blue cartridge's output, read from its encoder
+        let motor = Angle::from_turns(600.0 / f64::from(Gearset::Blue.ticks_per_revolution()));
+        let position = GroupPosition::from_degrees(motor.as_degrees()).unwrap();
+        assert!((position.degrees() - 720.0).abs() < 1e-9);
+
+        // The cartridge is already accounted for, so without an external
+        // ratio the output is the motor position
+        assert!((output_position(position, 1.0).as_degrees() - 720.0).abs() < 1e-9);
+        // Geared down 2:1, the mechanism has turned once
+        assert!((output_position(position, 2.0).as_turns() - 1.0).abs() < 1e-12);
+        // Geared up 3:5
+        assert!((output_position(position, 0.6).as_degrees() - 1200.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn spread_is_measured_in_degrees() {
+        // A green cartridge's output turns once every 900 ticks, so motors
+        // 225 ticks apart are a quarter turn apart
+        let ticks = f64::from(Gearset::Green.ticks_per_revolution());
+        let positions = [100.0, 325.0, 200.0]
+            .map(|tick| Angle::from_turns(tick / ticks).as_degrees())
+            .to_vec();
+        assert!((spread(positions, vec![]).unwrap() - 90.0).abs() < 1e-9);
+        assert_eq!(spread(vec![-40.0], vec![]).unwrap(), 0.0);
+
+        // Read errors are aggregated, keeping the spread of the others
+        let error = spread(vec![10.0, -20.0], vec![DISCONNECTED]).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(30.0));
+        let error = spread(vec![], vec![DISCONNECTED]).unwrap_err();
+        assert_eq!(error.result, None);
+    }
+}
@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+
+use vexide::smart::{
+    PortError, SmartDevice,
+    motor::{Motor, MotorControl},
+};
+
+use crate::{
+    MotorGroup,
+    readings::{self, Reading},
+};
+
+/// How motors that can't be read are counted by group predicates such as
+/// [`MotorGroup::all_satisfy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PredicateErrorStrategy {
+    /// A motor that can't be read doesn't satisfy the predicate, so the group
+    /// as a whole doesn't either.
+    ///
+    /// This is the default, since a readiness check shouldn't pass on a motor
+    /// it knows nothing about.
+    #[default]
+    Unsatisfied,
+    /// Motors that can't be read are left out, and the predicate is satisfied
+    /// if every motor that could be read satisfies it.
+    ///
+    /// If no motor could be read, the predicate is still not satisfied.
+    Ignore,
+}
+
+/// The outcome of checking a predicate against every motor in a group,
+/// returned by [`MotorGroup::all_satisfy`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupPredicateResult {
+    /// Whether the group as a whole satisfies the predicate, with errors
+    /// counted according to the group's [`PredicateErrorStrategy`].
+    pub satisfied: bool,
+    /// The outcome for each motor, as `(index, outcome)` pairs in the order
+    /// of the group. Disabled motors (see [`MotorGroup::set_enabled`]) and
+    /// checked out motors (see [`MotorGroup::checkout`]) have no entry.
+    pub outcomes: Vec<(usize, Result<bool, PortError>)>,
+    /// The errors of the motors that couldn't be read.
+    pub errors: Vec<PortError>,
+}
+
+impl GroupPredicateResult {
+    /// Returns the indices of the motors that didn't satisfy the predicate,
+    /// including those that couldn't be read.
+    pub fn unsatisfied(&self) -> Vec<usize> {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome != Ok(true))
+            .map(|(index, _)| *index)
+            .collect()
+    }
+}
+
+/// Combines per-motor predicate outcomes into a group result.
+pub(crate) fn evaluate(
+    outcomes: Vec<Reading<bool>>,
+    strategy: PredicateErrorStrategy,
+) -> GroupPredicateResult {
+    let (values, errors) = readings::partition(outcomes.iter().cloned());
+    let all_readable = values.iter().all(|(_, value)| *value);
+    let satisfied = match strategy {
+        PredicateErrorStrategy::Unsatisfied => all_readable && errors.is_empty(),
+        PredicateErrorStrategy::Ignore => all_readable && !values.is_empty(),
+    };
+    GroupPredicateResult {
+        satisfied,
+        outcomes,
+        errors,
+    }
+}
+
+/// Returns whether `motor` is within `tolerance` of its target.
+///
+/// See [`MotorGroup::all_at_target`].
+fn at_target(motor: &Motor, tolerance: f64) -> Result<bool, PortError> {
+    Ok(match motor.target() {
+        MotorControl::Velocity(rpm) => (motor.velocity()? - f64::from(rpm)).abs() <= tolerance,
+        MotorControl::Brake(_) => motor.velocity()?.abs() <= tolerance,
+        MotorControl::Position(position, _) => {
+            (motor.position()?.as_degrees() - position.as_degrees()).abs() <= tolerance
+        }
+        // A voltage isn't a closed-loop target, so it's reached immediately
+        _ => true,
+    })
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets how motors that can't be read are counted by group predicates
+    /// such as [`MotorGroup::all_satisfy`].
+    ///
+    /// By default, a motor that can't be read doesn't satisfy any predicate.
+    /// See [`PredicateErrorStrategy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group.predicate_error_strategy(PredicateErrorStrategy::Ignore);
+    /// }
+    /// ```
+    pub fn predicate_error_strategy(&mut self, strategy: PredicateErrorStrategy) -> &mut Self {
+        self.config.predicate_error_strategy = strategy;
+        self
+    }
+
+    /// Checks `predicate` against every motor in the group.
+    ///
+    /// This is the dual of flag getters like [`MotorGroup::is_over_current`]:
+    /// rather than asking whether any motor has a problem, it asks whether
+    /// every motor is ready. The group satisfies the predicate only if every
+    /// motor does. Every motor is checked even after one fails, so the result
+    /// also reports the outcome for each motor.
+    ///
+    /// Motors that can't be read are counted according to the group's
+    /// [`PredicateErrorStrategy`] (see
+    /// [`MotorGroup::predicate_error_strategy`]). Disabled motors (see
+    /// [`MotorGroup::set_enabled`]) and checked out motors (see
+    /// [`MotorGroup::checkout`]) are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let checks = [
+    ///         ("connected", motor_group.all_connected()),
+    ///         ("cool", motor_group.all_below_temperature(45.0)),
+    ///         ("no faults", motor_group.all_satisfy(|motor| Ok(!motor.is_driver_fault()?))),
+    ///     ];
+    ///     for (name, check) in checks {
+    ///         if !check.satisfied {
+    ///             println!("Drive isn't {name}: motors {:?}", check.unsatisfied());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn all_satisfy(
+        &self,
+        predicate: impl Fn(&Motor) -> Result<bool, PortError>,
+    ) -> GroupPredicateResult {
+        evaluate(
+            self.read_each(predicate),
+            self.config.predicate_error_strategy,
+        )
+    }
+
+    /// Checks whether every motor in the group is below `threshold` degrees
+    /// Celsius.
+    ///
+    /// See [`MotorGroup::all_satisfy`].
+    pub fn all_below_temperature(&self, threshold: f64) -> GroupPredicateResult {
+        self.all_satisfy(|motor| Ok(motor.temperature()? < threshold))
+    }
+
+    /// Checks whether every motor in the group is connected to its port.
+    ///
+    /// Checking the connection doesn't fail, so no errors are reported.
+    ///
+    /// See [`MotorGroup::all_satisfy`].
+    pub fn all_connected(&self) -> GroupPredicateResult {
+        self.all_satisfy(|motor| Ok(motor.is_connected()))
+    }
+
+    /// Checks whether every motor in the group is within `tolerance` of its
+    /// target.
+    ///
+    /// The tolerance is in RPM for velocity targets and in degrees for
+    /// position targets. A braking motor is at its target once its velocity is
+    /// within `tolerance` RPM of zero, and a motor given a voltage is always at
+    /// its target.
+    ///
+    /// Each motor is compared with its own last target, so a motor whose last
+    /// write failed is compared with the target before it.
+    ///
+    /// See [`MotorGroup::all_satisfy`].
+    pub fn all_at_target(&self, tolerance: f64) -> GroupPredicateResult {
+        self.all_satisfy(|motor| at_target(motor, tolerance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{PredicateErrorStrategy, evaluate};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    #[test]
+    fn errors_are_unsatisfied_by_default() {
+        let outcomes = vec![(0, Ok(true)), (1, Err(DISCONNECTED)), (2, Ok(true))];
+        let result = evaluate(outcomes.clone(), PredicateErrorStrategy::default());
+        assert!(!result.satisfied);
+        assert_eq!(result.outcomes, outcomes);
+        assert_eq!(result.errors, vec![DISCONNECTED]);
+        assert_eq!(result.unsatisfied(), vec![1]);
+
+        // Ignoring errors, the motors that could be read decide
+        let result = evaluate(outcomes, PredicateErrorStrategy::Ignore);
+        assert!(result.satisfied);
+        assert_eq!(result.errors, vec![DISCONNECTED]);
+
+        let outcomes = vec![(0, Ok(false)), (1, Err(DISCONNECTED))];
+        assert!(!evaluate(outcomes, PredicateErrorStrategy::Ignore).satisfied);
+        // Nothing could be read, so nothing is known to be satisfied
+        let outcomes = vec![(0, Err(DISCONNECTED))];
+        assert!(!evaluate(outcomes, PredicateErrorStrategy::Ignore).satisfied);
+    }
+
+    #[test]
+    fn every_motor_is_checked() {
+        let mut group = MotorGroup::new(mock_motors(3, Gearset::Green));
+        // No mock motor is plugged in, but checking doesn't fail
+        let result = group.all_connected();
+        assert!(!result.satisfied);
+        assert_eq!(result.unsatisfied(), vec![0, 1, 2]);
+        assert!(result.errors.is_empty());
+
+        group.predicate_error_strategy(PredicateErrorStrategy::Ignore);
+        let result = group.all_below_temperature(45.0);
+        assert!(!result.satisfied);
+        assert_eq!(result.errors.len(), 3);
+
+        let mut checkout = group.checkout(1).unwrap();
+        let result = checkout.group().all_satisfy(|_| Ok(true));
+        assert!(result.satisfied);
+        assert_eq!(result.outcomes, vec![(0, Ok(true)), (2, Ok(true))]);
+    }
+}
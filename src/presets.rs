@@ -0,0 +1,689 @@
+use alloc::vec::Vec;
+#[cfg(feature = "control")]
+use core::ops::ControlFlow;
+use core::time::Duration;
+
+use vexide::{
+    math::Angle,
+    smart::{
+        PortError,
+        motor::{Motor, MotorControl},
+    },
+};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError, SharedMotors, limits, readings};
+#[cfg(feature = "control")]
+use crate::{TargetDistanceError, tick::tick_loop};
+
+/// A named position of a motor group, added with [`MotorGroup::add_preset`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Preset {
+    /// The name the preset is moved to with.
+    pub name: &'static str,
+    /// The position of the motors, in the same frame as
+    /// [`MotorGroup::position`].
+    pub position: Angle,
+    /// The velocity to move to the position at, in RPM.
+    pub velocity: i32,
+}
+
+/// Error returned by the preset methods, such as [`MotorGroup::goto_preset`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PresetError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The group has no preset with the given name.
+    Unknown,
+    /// The preset's position is outside the group's position limits (see
+    /// [`MotorGroup::set_position_limits`]).
+    OutsideLimits {
+        /// The preset's position.
+        position: Angle,
+        /// The lower position limit.
+        min: Angle,
+        /// The upper position limit.
+        max: Angle,
+    },
+    /// A motor was given another command while the group was moving to the
+    /// preset, so it has no position target to arrive at.
+    NotPositionTarget {
+        /// The index of the motor in the group.
+        index: usize,
+        /// The motor's current target.
+        target: MotorControl,
+    },
+    /// The group hadn't arrived at the preset when the timeout ran out.
+    Timeout {
+        /// The timeout that ran out.
+        timeout: Duration,
+        /// The last distance of the furthest motor from its target, or `None`
+        /// if no motor could be read.
+        distance: Option<Angle>,
+    },
+}
+
+impl From<PortError> for PresetError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+#[cfg(feature = "control")]
+impl From<TargetDistanceError> for PresetError {
+    fn from(error: TargetDistanceError) -> Self {
+        match error {
+            TargetDistanceError::Port { source } => Self::Port { source },
+            TargetDistanceError::NotPositionTarget { index, target } => {
+                Self::NotPositionTarget { index, target }
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for PresetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::Unknown => write!(f, "no preset has that name"),
+            Self::OutsideLimits { position, min, max } => write!(
+                f,
+                "preset at {}° is outside the position limits {}° to {}°",
+                position.as_degrees(),
+                min.as_degrees(),
+                max.as_degrees()
+            ),
+            Self::NotPositionTarget { index, target } => write!(
+                f,
+                "motor {index} has no position target, its target is {target:?}"
+            ),
+            Self::Timeout {
+                timeout,
+                distance: Some(distance),
+            } => write!(
+                f,
+                "still {}° from the preset after {timeout:?}",
+                distance.as_degrees()
+            ),
+            Self::Timeout {
+                timeout,
+                distance: None,
+            } => write!(f, "distance couldn't be read within {timeout:?}"),
+        }
+    }
+}
+
+impl core::error::Error for PresetError {}
+
+/// Returns the preset named `name`.
+fn find(presets: &[Preset], name: &str) -> Result<Preset, MotorGroupError<PresetError>> {
+    presets
+        .iter()
+        .find(|preset| preset.name == name)
+        .copied()
+        .ok_or_else(|| MotorGroupError::new(vec![PresetError::Unknown]))
+}
+
+/// Returns the name of the preset closest to `position` out of `first` and
+/// `rest`, preferring the one added first on a tie.
+fn nearest(first: &Preset, rest: &[Preset], position: f64) -> &'static str {
+    let distance = |preset: &Preset| (preset.position.as_degrees() - position).abs();
+    rest.iter()
+        .fold(first, |nearest, preset| {
+            if distance(preset) < distance(nearest) {
+                preset
+            } else {
+                nearest
+            }
+        })
+        .name
+}
+
+/// Converts the motors' errors to [`PresetError`]s.
+fn port_errors<T>(error: MotorGroupError<PortError, T>) -> MotorGroupError<PresetError> {
+    MotorGroupError::new(error.errors.into_iter().map(PresetError::from).collect())
+}
+
+/// Returns the errors of a preset's target write to report once the group
+/// arrives, or the error to give up with if no motor accepted the target.
+#[cfg(feature = "control")]
+fn target_errors(
+    result: Result<(), MotorGroupError>,
+    written: &[(usize, bool)],
+) -> Result<Vec<PresetError>, MotorGroupError<PresetError, Duration>> {
+    let Err(error) = result else {
+        return Ok(Vec::new());
+    };
+    let errors = error.errors.into_iter().map(Into::into).collect();
+    if written.iter().any(|(_, accepted)| *accepted) {
+        Ok(errors)
+    } else {
+        Err(MotorGroupError::with_empty_result(errors))
+    }
+}
+
+/// Reads the furthest motor's distance to its target with `read` every
+/// `interval` until it's within `tolerance`, returning how long that took.
+#[cfg(feature = "control")]
+async fn arrive(
+    interval: Duration,
+    tolerance: Angle,
+    timeout: Duration,
+    mut target_errors: Vec<PresetError>,
+    mut read: impl FnMut() -> Result<Angle, MotorGroupError<TargetDistanceError, Angle>>,
+) -> Result<Duration, MotorGroupError<PresetError, Duration>> {
+    tick_loop(interval, Some(timeout), |elapsed| {
+        let distance = read();
+        let (distance, read_errors) = match distance {
+            Ok(distance) => (Some(distance), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        if distance.is_some_and(|distance| distance <= tolerance) {
+            let errors = core::mem::take(&mut target_errors);
+            return ControlFlow::Break(readings::finish(Some(elapsed), errors));
+        }
+        if elapsed >= timeout {
+            let mut errors = core::mem::take(&mut target_errors);
+            errors.extend(read_errors.into_iter().map(PresetError::from));
+            errors.push(PresetError::Timeout { timeout, distance });
+            return ControlFlow::Break(readings::finish(None, errors));
+        }
+        ControlFlow::Continue(())
+    })
+    .await
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Adds a named position that the group can be moved to with
+    /// [`MotorGroup::goto_preset`], such as a lift's intake, carry and
+    /// scoring heights.
+    ///
+    /// `position` is in the same frame as [`MotorGroup::position`] and
+    /// [`MotorGroup::set_position_target`], and `velocity` is the velocity
+    /// in RPM to move there at. Adding a name again replaces its preset in
+    /// place, so [`MotorGroup::presets`] keeps the order names were first
+    /// added in.
+    ///
+    /// If the group has position limits (see
+    /// [`MotorGroup::set_position_limits`]), the preset is checked against
+    /// them, including the limits themselves. Presets already added aren't
+    /// checked again when the limits change; a preset outside the new limits
+    /// is clamped into them like any other position target.
+    ///
+    /// Nothing is read from or written to the motors.
+    ///
+    /// # Errors
+    ///
+    /// - A [`PresetError::OutsideLimits`] error is returned, and the preset
+    ///   isn't added, if `position` is outside the group's position limits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     lift.set_position_limits(Angle::ZERO, Angle::from_turns(2.4));
+    ///
+    ///     _ = lift.add_preset("intake", Angle::ZERO, 100);
+    ///     _ = lift.add_preset("carry", Angle::from_degrees(90.0), 100);
+    ///     _ = lift.add_preset("score_high", Angle::from_turns(2.2), 60);
+    ///
+    ///     _ = lift.goto_preset("score_high");
+    /// }
+    /// ```
+    pub fn add_preset(
+        &mut self,
+        name: &'static str,
+        position: Angle,
+        velocity: i32,
+    ) -> Result<(), PresetError> {
+        if let Some((min, max)) = self.config.position_limits
+            && !limits::is_within((min, max), position)
+        {
+            return Err(PresetError::OutsideLimits { position, min, max });
+        }
+        let preset = Preset {
+            name,
+            position,
+            velocity,
+        };
+        match self.presets.iter_mut().find(|other| other.name == name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        Ok(())
+    }
+
+    /// Removes the preset named `name`, returning `false` if there wasn't
+    /// one.
+    pub fn remove_preset(&mut self, name: &str) -> bool {
+        let count = self.presets.len();
+        self.presets.retain(|preset| preset.name != name);
+        self.presets.len() != count
+    }
+
+    /// Returns the group's presets, in the order they were added. See
+    /// [`MotorGroup::add_preset`].
+    pub fn presets(&self) -> &[Preset] {
+        &self.presets
+    }
+
+    /// Moves the group to the preset named `name` by setting its position
+    /// target (see [`MotorGroup::set_position_target`]).
+    ///
+    /// This returns as soon as the target is written. To wait for the group
+    /// to arrive, use [`MotorGroup::goto_preset_settled`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`PresetError::Unknown`] error is returned, and nothing is
+    ///   written, if the group has no preset named `name`.
+    /// - A [`PresetError::Port`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port.
+    pub fn goto_preset(&mut self, name: &str) -> Result<(), MotorGroupError<PresetError>> {
+        let preset = find(&self.presets, name)?;
+        self.set_position_target(preset.position, preset.velocity)
+            .map_err(port_errors)
+    }
+
+    /// Returns the name of the preset closest to the group's average position
+    /// (see [`MotorGroup::position`]), such as to show which one a mechanism
+    /// is at on a screen, or `None` without reading the motors if there are
+    /// no presets.
+    ///
+    /// On a tie, the preset added first is returned.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is the preset closest to the average
+    ///   of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut arm = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     _ = arm.add_preset("stow", Angle::ZERO, 100);
+    ///     _ = arm.add_preset("score", Angle::from_degrees(120.0), 100);
+    ///
+    ///     if let Some(Ok(name)) = arm.nearest_preset() {
+    ///         println!("Arm is near {name}");
+    ///     }
+    /// }
+    /// ```
+    pub fn nearest_preset(&self) -> Option<GetterResult<&'static str>> {
+        let (first, rest) = self.presets.split_first()?;
+        Some(readings::map_result(self.group_position(), |position| {
+            nearest(first, rest, position.degrees())
+        }))
+    }
+
+    /// Moves the group to the preset named `name`, then waits until every
+    /// motor is within `tolerance` of its position target, returning how long
+    /// that took.
+    ///
+    /// The distance is that of the furthest motor from its own target (see
+    /// [`MotorGroup::max_distance_to_target`]), so the group has only arrived
+    /// once its slowest motor has. It's read every tick (see
+    /// [`MotorGroup::tick_interval`], 5ms by default), starting right after
+    /// the target is written, from the motors that could be read.
+    ///
+    /// # Errors
+    ///
+    /// - A [`PresetError::Unknown`] error is returned straight away if the
+    ///   group has no preset named `name`.
+    /// - If no motor accepted the target, a [`MotorGroupError`] error with
+    ///   the target's [`PresetError::Port`] errors is returned straight away.
+    /// - If only some motors accepted the target, the group is still waited
+    ///   on. Once it arrives, a [`MotorGroupError`] error with the target's
+    ///   [`PresetError::Port`] errors is returned, whose result is how long
+    ///   the move took.
+    /// - If the group hasn't arrived after `timeout`, a [`MotorGroupError`]
+    ///   error is returned. It contains any target errors and the errors of
+    ///   the last read, followed by a [`PresetError::Timeout`] error with the
+    ///   last distance of the furthest motor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     _ = lift.add_preset("score_high", Angle::from_turns(2.2), 60);
+    ///
+    ///     match lift
+    ///         .goto_preset_settled("score_high", Angle::from_degrees(5.0), Duration::from_secs(2))
+    ///         .await
+    ///     {
+    ///         Ok(took) => println!("At scoring height in {took:?}"),
+    ///         Err(error) => println!("Didn't reach scoring height: {error}"),
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "control")]
+    pub async fn goto_preset_settled(
+        &mut self,
+        name: &str,
+        tolerance: Angle,
+        timeout: Duration,
+    ) -> Result<Duration, MotorGroupError<PresetError, Duration>> {
+        let preset = find(&self.presets, name)
+            .map_err(|error| MotorGroupError::with_empty_result(error.errors))?;
+        let (result, written) =
+            self.write_command(MotorControl::Position(preset.position, preset.velocity));
+        let errors = target_errors(result, &written)?;
+        arrive(
+            self.config.tick_interval,
+            tolerance,
+            timeout,
+            errors,
+            || self.max_distance_to_target(),
+        )
+        .await
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::add_preset`].
+    pub fn add_preset(
+        &mut self,
+        name: &'static str,
+        position: Angle,
+        velocity: i32,
+    ) -> Result<(), PresetError> {
+        self.0.borrow_mut().add_preset(name, position, velocity)
+    }
+
+    /// See [`MotorGroup::remove_preset`].
+    pub fn remove_preset(&mut self, name: &str) -> bool {
+        self.0.borrow_mut().remove_preset(name)
+    }
+
+    /// See [`MotorGroup::presets`].
+    pub fn presets(&self) -> Vec<Preset> {
+        self.0.borrow().presets().to_vec()
+    }
+
+    /// See [`MotorGroup::goto_preset`].
+    pub fn goto_preset(&mut self, name: &str) -> Result<(), MotorGroupError<PresetError>> {
+        let preset = find(&self.0.borrow().presets, name)?;
+        self.set_position_target(preset.position, preset.velocity)
+            .map_err(port_errors)
+    }
+
+    /// See [`MotorGroup::nearest_preset`].
+    pub fn nearest_preset(&self) -> Option<GetterResult<&'static str>> {
+        self.0.borrow().nearest_preset()
+    }
+
+    /// Moves the motor group to a preset, then waits until it arrives,
+    /// borrowing the group only while writing and reading it.
+    ///
+    /// Since the group is free between reads, other tasks can use it while
+    /// this waits. See [`MotorGroup::goto_preset_settled`].
+    ///
+    /// The shadow (see [`SharedMotors::shadow`]) is given the preset's
+    /// position target with [`SharedMotors::set_position_target`], but isn't
+    /// waited for.
+    #[cfg(feature = "control")]
+    pub async fn goto_preset_settled(
+        &mut self,
+        name: &str,
+        tolerance: Angle,
+        timeout: Duration,
+    ) -> Result<Duration, MotorGroupError<PresetError, Duration>> {
+        let (interval, preset, errors) = {
+            let mut group = self.0.borrow_mut();
+            let preset = find(&group.presets, name)
+                .map_err(|error| MotorGroupError::with_empty_result(error.errors))?;
+            let (result, written) =
+                group.write_command(MotorControl::Position(preset.position, preset.velocity));
+            (
+                group.config.tick_interval,
+                preset,
+                target_errors(result, &written),
+            )
+        };
+        self.mirror(|shadow| shadow.set_position_target(preset.position, preset.velocity));
+        let errors = errors?;
+        arrive(interval, tolerance, timeout, errors, || {
+            self.0.borrow().max_distance_to_target()
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::{PortError, motor::MotorControl},
+    };
+
+    use super::{Preset, PresetError, nearest};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(1, Gearset::Red))
+    }
+
+    fn preset(name: &'static str, degrees: f64) -> Preset {
+        Preset {
+            name,
+            position: Angle::from_degrees(degrees),
+            velocity: 100,
+        }
+    }
+
+    #[test]
+    fn presets_are_checked_against_limits() {
+        let mut group = group();
+        let (min, max) = (Angle::ZERO, Angle::from_degrees(720.0));
+        group.set_position_limits(min, max);
+
+        assert_eq!(group.add_preset("intake", min, 100), Ok(()));
+        assert_eq!(group.add_preset("score_high", max, 60), Ok(()));
+        let position = Angle::from_degrees(-10.0);
+        assert_eq!(
+            group.add_preset("below", position, 100),
+            Err(PresetError::OutsideLimits { position, min, max })
+        );
+        assert_eq!(
+            group.presets(),
+            [
+                Preset {
+                    name: "intake",
+                    position: min,
+                    velocity: 100
+                },
+                Preset {
+                    name: "score_high",
+                    position: max,
+                    velocity: 60
+                },
+            ]
+        );
+
+        // Adding a name again replaces its preset in place
+        _ = group.add_preset("intake", Angle::from_degrees(10.0), 80);
+        assert_eq!(group.presets()[0].velocity, 80);
+        assert_eq!(group.presets().len(), 2);
+
+        assert!(group.remove_preset("intake"));
+        assert!(!group.remove_preset("intake"));
+        assert_eq!(group.presets()[0].name, "score_high");
+    }
+
+    #[test]
+    fn goto_preset_sets_the_position_target() {
+        let mut group = group();
+        _ = group.add_preset("carry", Angle::from_degrees(90.0), 50);
+
+        let generation = group.command_generation();
+        let error = group.goto_preset("score").unwrap_err();
+        assert_eq!(error.errors, vec![PresetError::Unknown]);
+        // Nothing was written
+        assert_eq!(group.command_generation(), generation);
+
+        // Every write to the mock motors fails, but the target is still set
+        let error = group.goto_preset("carry").unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![PresetError::Port {
+                source: PortError::Disconnected { port: 1 }
+            }]
+        );
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Position(Angle::from_degrees(90.0), 50))
+        );
+    }
+
+    #[test]
+    fn nearest_preset_prefers_the_first_on_a_tie() {
+        let presets = [
+            preset("intake", 0.0),
+            preset("carry", 90.0),
+            preset("score", 180.0),
+        ];
+        let (first, rest) = presets.split_first().unwrap();
+        assert_eq!(nearest(first, rest, -40.0), "intake");
+        assert_eq!(nearest(first, rest, 100.0), "carry");
+        assert_eq!(nearest(first, rest, 1000.0), "score");
+        assert_eq!(nearest(first, rest, 45.0), "intake");
+        assert_eq!(nearest(first, rest, 135.0), "carry");
+    }
+
+    #[test]
+    fn nearest_preset_reads_only_with_presets() {
+        let mut group = group();
+        assert!(group.nearest_preset().is_none());
+
+        _ = group.add_preset("intake", Angle::ZERO, 100);
+        let error = group.nearest_preset().unwrap().unwrap_err();
+        assert_eq!(error.errors, vec![PortError::Disconnected { port: 1 }]);
+        assert_eq!(error.result, None);
+    }
+
+    #[cfg(feature = "control")]
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        let mut future = core::pin::pin!(future);
+        let mut cx = core::task::Context::from_waker(core::task::Waker::noop());
+        loop {
+            if let core::task::Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+                return value;
+            }
+        }
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn arrival_is_the_furthest_motor_within_tolerance() {
+        use core::time::Duration;
+
+        use super::arrive;
+        use crate::{MotorGroupError, TargetDistanceError};
+
+        let tolerance = Angle::from_degrees(5.0);
+        let write_error = PresetError::Port {
+            source: PortError::Disconnected { port: 2 },
+        };
+
+        // A partial reading counts once the motors that could be read arrive
+        let mut distances = [30.0, 10.0, 4.0].into_iter();
+        let error = run(arrive(
+            Duration::ZERO,
+            tolerance,
+            Duration::from_secs(1),
+            vec![write_error],
+            || {
+                let distance = Angle::from_degrees(distances.next().unwrap());
+                Err(MotorGroupError::with_result(
+                    vec![TargetDistanceError::Port {
+                        source: PortError::Disconnected { port: 2 },
+                    }],
+                    distance,
+                ))
+            },
+        ))
+        .unwrap_err();
+        assert_eq!(error.errors, vec![write_error]);
+        assert!(error.result.is_some());
+
+        // Otherwise the last reading is reported once the timeout runs out
+        let target = MotorControl::Velocity(100);
+        let error = run(arrive(
+            Duration::ZERO,
+            tolerance,
+            Duration::ZERO,
+            Vec::new(),
+            || {
+                Err(MotorGroupError::with_result(
+                    vec![TargetDistanceError::NotPositionTarget { index: 1, target }],
+                    Angle::from_degrees(30.0),
+                ))
+            },
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                PresetError::NotPositionTarget { index: 1, target },
+                PresetError::Timeout {
+                    timeout: Duration::ZERO,
+                    distance: Some(Angle::from_degrees(30.0))
+                },
+            ]
+        );
+        assert_eq!(error.result, None);
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn goto_preset_settled_gives_up_without_a_target() {
+        use core::time::Duration;
+
+        let mut group = group();
+        group.tick_interval(Duration::ZERO);
+        _ = group.add_preset("carry", Angle::from_degrees(90.0), 50);
+        let tolerance = Angle::from_degrees(5.0);
+
+        let error = run(group.goto_preset_settled("score", tolerance, Duration::ZERO)).unwrap_err();
+        assert_eq!(error.errors, vec![PresetError::Unknown]);
+        assert_eq!(error.result, None);
+
+        // No mock motor accepts the target, so there's nothing to wait for
+        let error =
+            run(group.goto_preset_settled("carry", tolerance, Duration::from_secs(1))).unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![PresetError::Port {
+                source: PortError::Disconnected { port: 1 }
+            }]
+        );
+        assert_eq!(error.result, None);
+    }
+}
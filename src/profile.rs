@@ -0,0 +1,395 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::{
+    math::Angle,
+    smart::{
+        PortError,
+        motor::{BrakeMode, Motor, MotorControl},
+    },
+};
+
+use crate::{
+    MotorGroup, MotorGroupError, WriteErrorStrategy, control::EndCommandGuard, tick::tick_loop,
+    velocity,
+};
+
+/// Degrees per second in one RPM.
+const DEGREES_PER_SEC_PER_RPM: f64 = 6.0;
+
+/// A trapezoidal velocity profile over a fixed distance: a constant
+/// acceleration up to a peak velocity, a cruise at it, and a constant
+/// deceleration back to zero.
+///
+/// Moves too short to reach the maximum velocity are triangular, peaking
+/// where the acceleration meets the deceleration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TrapezoidalProfile {
+    /// `1.0` to move forwards, `-1.0` to move backwards.
+    sign: f64,
+    /// The length of the move in degrees.
+    distance: f64,
+    /// The highest velocity reached in degrees per second.
+    peak: f64,
+    /// The acceleration and deceleration in degrees per second squared.
+    accel: f64,
+}
+
+impl TrapezoidalProfile {
+    /// Returns the profile of a move of `distance` degrees with at most
+    /// `max_rpm` and `accel` RPM per second, or `None` if nothing would move:
+    /// the distance, the velocity or the acceleration is zero, negative or
+    /// not finite.
+    pub(crate) fn new(distance: f64, max_rpm: f64, accel: f64) -> Option<Self> {
+        let max = max_rpm * DEGREES_PER_SEC_PER_RPM;
+        let accel = accel * DEGREES_PER_SEC_PER_RPM;
+        let valid = |value: f64| value.is_finite() && value > 0.0;
+        if !valid(distance.abs()) || !valid(max) || !valid(accel) {
+            return None;
+        }
+        // Ramping up to `max` and back down covers `max² / accel`
+        let peak = max.min((distance.abs() * accel).sqrt());
+        Some(Self {
+            sign: distance.signum(),
+            distance: distance.abs(),
+            peak,
+            accel,
+        })
+    }
+
+    /// How long the acceleration, and the deceleration, take in seconds.
+    fn ramp_secs(&self) -> f64 {
+        self.peak / self.accel
+    }
+
+    /// How long the cruise at the peak velocity takes in seconds.
+    fn cruise_secs(&self) -> f64 {
+        let ramps = self.peak * self.ramp_secs();
+        ((self.distance - ramps) / self.peak).max(0.0)
+    }
+
+    /// How long the whole move takes.
+    pub(crate) fn duration(&self) -> Duration {
+        Duration::from_secs_f64(2.0 * self.ramp_secs() + self.cruise_secs())
+    }
+
+    /// Returns the velocity `elapsed` into the move in RPM, which is zero
+    /// before it starts and after it ends.
+    pub(crate) fn velocity_at(&self, elapsed: Duration) -> f64 {
+        let t = elapsed.as_secs_f64();
+        let ramp = self.ramp_secs();
+        let end = 2.0 * ramp + self.cruise_secs();
+        let velocity = if t <= 0.0 || t >= end {
+            0.0
+        } else if t < ramp {
+            self.accel * t
+        } else if t > end - ramp {
+            self.accel * (end - t)
+        } else {
+            self.peak
+        };
+        self.sign * velocity / DEGREES_PER_SEC_PER_RPM
+    }
+}
+
+/// Runs `profile` every `interval`, giving `command` the velocity for each
+/// tick in RPM, and returns every distinct error of those commands in the
+/// order they first happened.
+///
+/// Each tick commands the velocity halfway through it, so that holding that
+/// velocity for the tick covers the same distance as the profile does. If
+/// `stop_on_error` is set, the first failed command cuts the profile short.
+/// Returns whether the profile ran to its end along with the errors.
+async fn run_profile(
+    interval: Duration,
+    profile: TrapezoidalProfile,
+    stop_on_error: bool,
+    mut command: impl FnMut(i32) -> Result<(), MotorGroupError>,
+) -> (bool, Vec<PortError>) {
+    let duration = profile.duration();
+    let mut errors: Vec<PortError> = Vec::new();
+    tick_loop(interval, Some(duration), |elapsed| {
+        if elapsed >= duration {
+            return ControlFlow::Break((true, core::mem::take(&mut errors)));
+        }
+        let rpm = profile.velocity_at(elapsed + interval / 2);
+        if let Err(error) = command(velocity::round_rpm(rpm)) {
+            for error in error.errors {
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+            if stop_on_error {
+                return ControlFlow::Break((false, core::mem::take(&mut errors)));
+            }
+        }
+        ControlFlow::Continue(())
+    })
+    .await
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Moves the motor group to the position `target` along a trapezoidal
+    /// motion profile, accelerating at `accel` RPM per second up to at most
+    /// `max_velocity` RPM and decelerating back down to arrive at `target`.
+    ///
+    /// A plain [`MotorGroup::set_position_target`] starts and stops as hard as
+    /// the motors' internal controller allows, which jerks a drivetrain or
+    /// tips a stack. Instead, the profile is planned from the group's position
+    /// (see [`MotorGroup::position`]) when this is called:
+    ///
+    /// - The velocity ramps up from zero by `accel` RPM every second, cruises
+    ///   at `max_velocity`, and ramps down by `accel` RPM every second, so
+    ///   that the area under it is the distance to `target`.
+    /// - Moves too short to reach `max_velocity` ramp straight from
+    ///   accelerating into decelerating, peaking at
+    ///   `√(distance × accel / 6)` RPM, with the distance in degrees.
+    ///
+    /// The profile's velocity is written with [`MotorGroup::set_velocity`]
+    /// once every tick (see [`MotorGroup::tick_interval`], 5ms by default),
+    /// rounded to the nearest RPM. Each tick commands the velocity halfway
+    /// through it, so that the distance covered matches the profile however
+    /// long the ticks are, and writes once to every motor. A longer tick
+    /// interval makes the ramps coarser steps. The profile isn't corrected by
+    /// the position along the way, so once it ends, the group is given the
+    /// position target `target` at `max_velocity` to take up any remaining
+    /// error and hold the position.
+    ///
+    /// This future completes when the profile ends, which is when the group
+    /// should arrive if the motors tracked it. If it's dropped before then,
+    /// the group is braked with [`BrakeMode::Hold`]. Under
+    /// [`WriteErrorStrategy::Stop`], the first failed write ends the move and
+    /// brakes the group the same way instead of giving it the final target.
+    /// If `target` is the current position, or `max_velocity` or `accel`
+    /// isn't positive, there's no profile, and the group is only given the
+    /// final target.
+    ///
+    /// # Errors
+    ///
+    /// - If the group's position can't be read, a [`MotorGroupError`] error
+    ///   with the read errors is returned before anything is written.
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port, containing every distinct
+    ///   error of the profile's writes in the order they first happened,
+    ///   followed by the errors of the final command.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Up by two rotations, reaching 80 RPM after half a second
+    ///     _ = lift
+    ///         .move_profiled(Angle::from_degrees(720.0), 80, 160.0)
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn move_profiled(
+        &mut self,
+        target: Angle,
+        max_velocity: i32,
+        accel: f64,
+    ) -> Result<(), MotorGroupError> {
+        let start = self
+            .position()
+            .map_err(|error| MotorGroupError::new(error.errors))?;
+        let profile = TrapezoidalProfile::new(
+            target.as_degrees() - start.as_degrees(),
+            f64::from(max_velocity),
+            accel,
+        );
+        let interval = self.config.tick_interval;
+        let stop_on_error = self.config.write_error_strategy == WriteErrorStrategy::Stop;
+        let mut guard = EndCommandGuard {
+            group: self,
+            end: Some(MotorControl::Brake(BrakeMode::Hold)),
+        };
+
+        let (completed, mut errors) = match profile {
+            Some(profile) => {
+                run_profile(interval, profile, stop_on_error, |rpm| {
+                    guard.group.set_velocity(rpm)
+                })
+                .await
+            }
+            None => (true, Vec::new()),
+        };
+        if completed {
+            guard.end = Some(MotorControl::Position(target, max_velocity));
+        }
+        if let Err(error) = guard.finish() {
+            errors.extend(error.errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use vexide::{math::Angle, prelude::*, smart::PortError};
+
+    use super::{TrapezoidalProfile, run_profile};
+    use crate::{MotorGroup, MotorGroupError, tests::mock_motors};
+
+    const TICK: Duration = Duration::from_millis(5);
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} != {expected}"
+        );
+    }
+
+    /// Integrates the velocity `profile` commands every `tick`, like a group
+    /// tracking it perfectly would, returning the distance covered in degrees
+    /// and the commanded velocities.
+    fn integrate(profile: &TrapezoidalProfile, tick: Duration) -> (f64, Vec<f64>) {
+        let mut position = 0.0;
+        let mut velocities = Vec::new();
+        let mut elapsed = Duration::ZERO;
+        while elapsed < profile.duration() {
+            let rpm = profile.velocity_at(elapsed + tick / 2);
+            velocities.push(rpm);
+            position += rpm * 6.0 * tick.as_secs_f64();
+            elapsed += tick;
+        }
+        (position, velocities)
+    }
+
+    #[test]
+    fn long_moves_ramp_cruise_and_ramp() {
+        // 200 RPM reached after a second, covering 600 degrees
+        let profile = TrapezoidalProfile::new(3600.0, 200.0, 200.0).unwrap();
+        // 2400 degrees of cruise take 2 seconds
+        assert_close(profile.duration().as_secs_f64(), 4.0, 1e-9);
+
+        assert_eq!(profile.velocity_at(Duration::ZERO), 0.0);
+        assert_close(profile.velocity_at(Duration::from_millis(500)), 100.0, 1e-9);
+        assert_close(profile.velocity_at(Duration::from_secs(2)), 200.0, 1e-9);
+        assert_close(profile.velocity_at(Duration::from_millis(3750)), 50.0, 1e-9);
+        assert_eq!(profile.velocity_at(Duration::from_secs(5)), 0.0);
+
+        let (distance, velocities) = integrate(&profile, TICK);
+        assert_close(distance, 3600.0, 1e-6);
+        // The velocity changes by at most accel × tick between ticks
+        for pair in velocities.windows(2) {
+            assert!((pair[1] - pair[0]).abs() <= 200.0 * 0.005 + 1e-9);
+        }
+        assert!(velocities.iter().all(|&rpm| rpm <= 200.0));
+    }
+
+    #[test]
+    fn short_moves_are_triangular() {
+        // Reaching 200 RPM would take 600 degrees each way
+        let profile = TrapezoidalProfile::new(-600.0, 200.0, 200.0).unwrap();
+        // Peaks at √(600 × 200 / 6) ≈ 141 RPM, halfway through
+        let peak = 20000.0_f64.sqrt();
+        let duration = profile.duration().as_secs_f64();
+        assert_close(duration, 2.0 * peak / 200.0, 1e-9);
+        assert_close(
+            profile.velocity_at(Duration::from_secs_f64(duration / 2.0)),
+            -peak,
+            1e-6,
+        );
+
+        let (distance, velocities) = integrate(&profile, TICK);
+        assert_close(distance, -600.0, 0.1);
+        assert!(velocities.iter().all(|&rpm| rpm <= 0.0 && rpm >= -peak));
+        // Longer ticks still cover the same distance
+        assert_close(
+            integrate(&profile, Duration::from_millis(20)).0,
+            -600.0,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn moves_that_go_nowhere_have_no_profile() {
+        assert_eq!(TrapezoidalProfile::new(0.0, 200.0, 200.0), None);
+        assert_eq!(TrapezoidalProfile::new(90.0, 0.0, 200.0), None);
+        assert_eq!(TrapezoidalProfile::new(90.0, -200.0, 200.0), None);
+        assert_eq!(TrapezoidalProfile::new(90.0, 200.0, 0.0), None);
+        assert_eq!(TrapezoidalProfile::new(f64::NAN, 200.0, 200.0), None);
+    }
+
+    /// Runs `profile` on real ticks, answering every command with `result`,
+    /// and returns what [`run_profile`] did along with the commands.
+    fn run(
+        profile: TrapezoidalProfile,
+        stop_on_error: bool,
+        result: impl Fn() -> Result<(), MotorGroupError> + 'static,
+    ) -> (bool, Vec<PortError>, Vec<i32>) {
+        vexide::runtime::block_on(async move {
+            let mut commands = Vec::new();
+            let (completed, errors) = run_profile(TICK, profile, stop_on_error, |rpm| {
+                commands.push(rpm);
+                result()
+            })
+            .await;
+            (completed, errors, commands)
+        })
+    }
+
+    #[test]
+    fn the_profile_is_commanded_every_tick() {
+        // Half a second up to 60 RPM and back down
+        let profile = TrapezoidalProfile::new(90.0, 60.0, 240.0).unwrap();
+        let (completed, errors, commands) = run(profile, false, || Ok(()));
+        assert!(completed);
+        assert!(errors.is_empty());
+        // Real ticks can run late, so there are at most 100 of them
+        assert!(commands.len() > 1 && commands.len() <= 100);
+        assert!(commands.iter().all(|rpm| (0..=60).contains(rpm)));
+        assert!(commands.iter().max() >= Some(&50));
+    }
+
+    #[test]
+    fn failed_commands_are_collected_or_stop_the_profile() {
+        let profile = TrapezoidalProfile::new(30.0, 60.0, 600.0).unwrap();
+        let failed = || {
+            Err(MotorGroupError::new(vec![PortError::Disconnected {
+                port: 1,
+            }]))
+        };
+
+        let (completed, errors, commands) = run(profile, false, failed);
+        assert!(completed);
+        assert!(commands.len() > 1);
+        // Repeated errors are only reported once
+        assert_eq!(errors, vec![PortError::Disconnected { port: 1 }]);
+
+        let (completed, errors, commands) = run(profile, true, failed);
+        assert!(!completed);
+        assert_eq!(commands.len(), 1);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn an_unreadable_start_writes_nothing() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Red));
+        let (group, error) = vexide::runtime::block_on(async move {
+            let error = group
+                .move_profiled(Angle::from_degrees(360.0), 100, 200.0)
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        assert_eq!(error.errors.len(), 1);
+        assert_eq!(group.command_generation(), 0);
+    }
+}
@@ -0,0 +1,366 @@
+//! The opt-in read cache of [`MotorGroup`], which lets several callers share
+//! one read of the motors.
+//!
+//! Like the [`last_known`](crate::last_known) values, cached readings are kept
+//! in cells, so getters can fill the cache through a shared reference.
+
+use core::time::Duration;
+use std::time::Instant;
+
+use vexide::{math::Angle, smart::motor::Motor};
+
+use crate::{GetterResult, MotorGroup, last_known::LastKnownValue};
+
+/// A getter whose reading is cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CachedMetric {
+    Velocity,
+    Position,
+    Voltage,
+    Current,
+    Power,
+    Torque,
+    Efficiency,
+    Temperature,
+}
+
+impl CachedMetric {
+    /// Every cached metric.
+    pub(crate) const ALL: [Self; 8] = [
+        Self::Velocity,
+        Self::Position,
+        Self::Voltage,
+        Self::Current,
+        Self::Power,
+        Self::Torque,
+        Self::Efficiency,
+        Self::Temperature,
+    ];
+}
+
+/// A change to a motor group that can make cached readings wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Change {
+    /// A write to the motors, such as a new target or limit, which changes
+    /// what they output.
+    Output,
+    /// A write to the motors' position reference.
+    Reference,
+    /// A change to what the readings mean or which motors they're combined
+    /// from, such as a new direction, gearset, or set of active motors.
+    Aggregation,
+}
+
+impl Change {
+    /// Returns whether a change of this kind can change the reading of
+    /// `metric`.
+    ///
+    /// Writes change everything the motors output, but only a reference write
+    /// moves the position, and nothing written changes the temperature
+    /// straight away.
+    pub(crate) fn invalidates(self, metric: CachedMetric) -> bool {
+        match (self, metric) {
+            (Self::Aggregation, _) => true,
+            (_, CachedMetric::Temperature) => false,
+            (Self::Reference, metric) => metric == CachedMetric::Position,
+            (Self::Output, metric) => metric != CachedMetric::Position,
+        }
+    }
+}
+
+/// The last successful reading of each cached getter of a motor group.
+#[derive(Debug, Default)]
+pub(crate) struct ReadCache {
+    pub(crate) velocity: LastKnownValue<f64>,
+    pub(crate) position: LastKnownValue<Angle>,
+    pub(crate) voltage: LastKnownValue<f64>,
+    pub(crate) current: LastKnownValue<f64>,
+    pub(crate) power: LastKnownValue<f64>,
+    pub(crate) torque: LastKnownValue<f64>,
+    pub(crate) efficiency: LastKnownValue<f64>,
+    pub(crate) temperature: LastKnownValue<f64>,
+}
+
+impl ReadCache {
+    /// Forgets the reading of `metric`.
+    fn clear(&self, metric: CachedMetric) {
+        match metric {
+            CachedMetric::Velocity => self.velocity.clear(),
+            CachedMetric::Position => self.position.clear(),
+            CachedMetric::Voltage => self.voltage.clear(),
+            CachedMetric::Current => self.current.clear(),
+            CachedMetric::Power => self.power.clear(),
+            CachedMetric::Torque => self.torque.clear(),
+            CachedMetric::Efficiency => self.efficiency.clear(),
+            CachedMetric::Temperature => self.temperature.clear(),
+        }
+    }
+
+    /// Forgets every reading that `change` can make wrong.
+    pub(crate) fn invalidate(&self, change: Change) {
+        for metric in CachedMetric::ALL {
+            if change.invalidates(metric) {
+                self.clear(metric);
+            }
+        }
+    }
+
+    /// Returns the reading in `slot` if it's younger than `max_age`, or
+    /// otherwise reads it with `read`, storing it if every motor was read.
+    pub(crate) fn read<T: Copy>(
+        slot: &LastKnownValue<T>,
+        max_age: Duration,
+        read: impl FnOnce() -> GetterResult<T>,
+    ) -> GetterResult<T> {
+        let now = Instant::now();
+        if let Some((value, age)) = slot.get(now)
+            && age < max_age
+        {
+            return Ok(value);
+        }
+        let result = read();
+        if let Ok(value) = &result {
+            slot.record(*value, now);
+        }
+        result
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets how old a cached reading can be for a getter to return it instead
+    /// of reading the motors, or turns the cache off with `None`.
+    ///
+    /// Subsystems sharing a group often read the same values within a single
+    /// loop iteration, and every read of a group reads each of its motors.
+    /// With a cache, the first read within `max_staleness` reads the motors,
+    /// and the others return the same value. Only readings where every motor
+    /// was read are cached. These getters are cached:
+    ///
+    /// - [`MotorGroup::velocity`]
+    /// - [`MotorGroup::position`]
+    /// - [`MotorGroup::voltage`]
+    /// - [`MotorGroup::current`]
+    /// - [`MotorGroup::power`]
+    /// - [`MotorGroup::torque`]
+    /// - [`MotorGroup::efficiency`]
+    /// - [`MotorGroup::temperature`]
+    ///
+    /// Cached readings are forgotten when the group changes in a way that
+    /// affects them, even if they aren't stale yet:
+    ///
+    /// - Any write to the motors, such as a new target or limit, forgets
+    ///   everything but the position and temperature.
+    /// - Writing the position reference, such as with
+    ///   [`MotorGroup::reset_position`], also forgets the position.
+    /// - Changing the direction or gearset, or which motors are read (such as
+    ///   with [`MotorGroup::set_enabled`]), forgets everything.
+    ///
+    /// Changes made to a motor outside of the group can't be seen, so call
+    /// [`MotorGroup::invalidate_read_cache`] after them. The cache is off by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     drive.set_read_cache(Some(Duration::from_millis(5)));
+    ///
+    ///     loop {
+    ///         // Only the first of these reads the motors
+    ///         let odometry = drive.position();
+    ///         let telemetry = drive.position();
+    ///         sleep(Duration::from_millis(10)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_read_cache(&mut self, max_staleness: Option<Duration>) -> &mut Self {
+        self.config.read_cache = max_staleness;
+        self.invalidate_read_cache();
+        self
+    }
+
+    /// Forgets every cached reading, so that the next read of each getter
+    /// reads the motors.
+    ///
+    /// See [`MotorGroup::set_read_cache`].
+    pub fn invalidate_read_cache(&self) {
+        self.read_cache.invalidate(Change::Aggregation);
+    }
+
+    /// Returns `read` through the read cache, if it's on.
+    pub(crate) fn cached<T: Copy>(
+        &self,
+        slot: impl FnOnce(&ReadCache) -> &LastKnownValue<T>,
+        read: impl FnOnce() -> GetterResult<T>,
+    ) -> GetterResult<T> {
+        match self.config.read_cache {
+            Some(max_age) => ReadCache::read(slot(&self.read_cache), max_age, read),
+            None => read(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, time::Duration};
+
+    use vexide::{math::Angle, prelude::*, smart::PortError};
+
+    use super::{CachedMetric, Change, ReadCache};
+    use crate::{MotorGroup, MotorGroupError, last_known::LastKnownValue, tests::two_motor_group};
+
+    const MAX_AGE: Duration = Duration::from_secs(60);
+
+    fn group() -> MotorGroup {
+        two_motor_group()
+    }
+
+    /// Fills every slot of `cache` with a reading.
+    fn fill(cache: &ReadCache) {
+        let read = || Ok(1.0);
+        for slot in [
+            &cache.velocity,
+            &cache.voltage,
+            &cache.current,
+            &cache.power,
+            &cache.torque,
+            &cache.efficiency,
+            &cache.temperature,
+        ] {
+            _ = ReadCache::read(slot, MAX_AGE, read);
+        }
+        _ = ReadCache::read(&cache.position, MAX_AGE, || Ok(Angle::from_degrees(1.0)));
+    }
+
+    /// Returns which metrics `cache` still has a reading for.
+    fn cached(cache: &ReadCache) -> Vec<CachedMetric> {
+        let now = std::time::Instant::now();
+        CachedMetric::ALL
+            .into_iter()
+            .filter(|metric| match metric {
+                CachedMetric::Velocity => cache.velocity.get(now).is_some(),
+                CachedMetric::Position => cache.position.get(now).is_some(),
+                CachedMetric::Voltage => cache.voltage.get(now).is_some(),
+                CachedMetric::Current => cache.current.get(now).is_some(),
+                CachedMetric::Power => cache.power.get(now).is_some(),
+                CachedMetric::Torque => cache.torque.get(now).is_some(),
+                CachedMetric::Efficiency => cache.efficiency.get(now).is_some(),
+                CachedMetric::Temperature => cache.temperature.get(now).is_some(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fresh_readings_are_reused() {
+        let slot = LastKnownValue::default();
+        let reads = Cell::new(0);
+        let read = || {
+            reads.set(reads.get() + 1);
+            Ok(100.0)
+        };
+        assert_eq!(ReadCache::read(&slot, MAX_AGE, read).unwrap(), 100.0);
+        assert_eq!(ReadCache::read(&slot, MAX_AGE, read).unwrap(), 100.0);
+        assert_eq!(reads.get(), 1);
+
+        // A stale reading is read again
+        assert_eq!(ReadCache::read(&slot, Duration::ZERO, read).unwrap(), 100.0);
+        assert_eq!(reads.get(), 2);
+    }
+
+    #[test]
+    fn partial_readings_are_not_cached() {
+        let slot = LastKnownValue::default();
+        let disconnected = PortError::Disconnected { port: 1 };
+        let error = ReadCache::read(&slot, MAX_AGE, || {
+            Err(MotorGroupError::with_result(vec![disconnected], 50.0))
+        })
+        .unwrap_err();
+        assert_eq!(error.result, Some(50.0));
+        // The next read still goes to the motors
+        assert_eq!(ReadCache::read(&slot, MAX_AGE, || Ok(60.0)).unwrap(), 60.0);
+    }
+
+    #[test]
+    fn changes_invalidate_what_they_affect() {
+        let cache = ReadCache::default();
+
+        fill(&cache);
+        cache.invalidate(Change::Output);
+        assert_eq!(
+            cached(&cache),
+            [CachedMetric::Position, CachedMetric::Temperature]
+        );
+
+        fill(&cache);
+        cache.invalidate(Change::Reference);
+        assert!(!cached(&cache).contains(&CachedMetric::Position));
+        assert_eq!(cached(&cache).len(), CachedMetric::ALL.len() - 1);
+
+        fill(&cache);
+        cache.invalidate(Change::Aggregation);
+        assert_eq!(cached(&cache), []);
+    }
+
+    #[test]
+    fn group_changes_invalidate_the_cache() {
+        let mut group = group();
+        assert_eq!(group.current_config().read_cache, None);
+        group.set_read_cache(Some(MAX_AGE));
+
+        // Every write forgets the outputs
+        fill(&group.read_cache);
+        _ = group.set_voltage(6.0);
+        assert_eq!(
+            cached(&group.read_cache),
+            [CachedMetric::Position, CachedMetric::Temperature]
+        );
+
+        // Position writes forget the position too, even if they fail
+        fill(&group.read_cache);
+        _ = group.reset_position();
+        assert_eq!(cached(&group.read_cache), [CachedMetric::Temperature]);
+
+        // Direction changes and different sets of motors forget everything
+        fill(&group.read_cache);
+        _ = group.set_direction(Direction::Reverse);
+        assert_eq!(cached(&group.read_cache), []);
+        fill(&group.read_cache);
+        let mut checkout = group.checkout(0).unwrap();
+        assert_eq!(cached(&checkout.group().read_cache), []);
+        fill(&checkout.group().read_cache);
+        drop(checkout);
+        assert_eq!(cached(&group.read_cache), []);
+        fill(&group.read_cache);
+        group.set_enabled(1, false);
+        assert_eq!(cached(&group.read_cache), []);
+
+        fill(&group.read_cache);
+        group.invalidate_read_cache();
+        assert_eq!(cached(&group.read_cache), []);
+    }
+
+    #[test]
+    fn cached_getters_skip_the_motors() {
+        let mut group = group();
+        // The mock motors can't be read
+        assert!(group.temperature().is_err());
+
+        // A cached reading is returned instead, until the cache is turned off
+        group.set_read_cache(Some(MAX_AGE));
+        group
+            .read_cache
+            .temperature
+            .record(35.0, std::time::Instant::now());
+        assert_eq!(group.temperature().unwrap(), 35.0);
+        group.set_read_cache(None);
+        assert!(group.temperature().is_err());
+    }
+}
@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+
+use vexide::smart::{
+    PortError,
+    motor::{Gearset, Motor},
+};
+
+use crate::{
+    GroupPredicateResult, MotorGroup, current_limit::configured_current_limits,
+    predicates::evaluate, readings::Reading,
+};
+
+/// How far a motor's limit can be from the configured one and still be
+/// verified, in Volts or Amperes.
+const LIMIT_TOLERANCE: f64 = 0.01;
+
+/// Which checks [`MotorGroup::readiness`] runs, and their thresholds.
+///
+/// A check is skipped if it's turned off or its threshold is `None`. The
+/// default runs every check, with a pre-match checklist's thresholds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadinessCriteria {
+    /// Whether every motor has to be connected to its port.
+    pub connected: bool,
+    /// The temperature every motor has to be below, in °C.
+    pub max_temperature: Option<f64>,
+    /// How close to zero every motor's position has to be, in degrees.
+    pub zero_tolerance: Option<f64>,
+    /// Whether every motor's voltage and current limits have to match the
+    /// group's configuration.
+    ///
+    /// The voltage limit is only checked if one was set on the group. The
+    /// current limit is compared with the per-motor or total limit set on the
+    /// group, or with the motor's hardware maximum if neither was.
+    pub verify_limits: bool,
+    /// Whether every motor has to use the same gearset: the group's
+    /// configured gearset if one was set, or the first motor's otherwise.
+    pub uniform_gearsets: bool,
+    /// Whether every motor has to be free of over temperature, over current,
+    /// and driver faults.
+    pub no_faults: bool,
+}
+
+impl ReadinessCriteria {
+    /// Runs every check, requiring motors to be below 40 °C and within 1
+    /// degree of zero.
+    pub const DEFAULT: Self = Self {
+        connected: true,
+        max_temperature: Some(40.0),
+        zero_tolerance: Some(1.0),
+        verify_limits: true,
+        uniform_gearsets: true,
+        no_faults: true,
+    };
+}
+
+impl Default for ReadinessCriteria {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A check run by [`MotorGroup::readiness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessCheck {
+    /// See [`ReadinessCriteria::connected`].
+    Connected,
+    /// See [`ReadinessCriteria::max_temperature`].
+    Temperature,
+    /// See [`ReadinessCriteria::zero_tolerance`].
+    Zeroed,
+    /// See [`ReadinessCriteria::verify_limits`].
+    Limits,
+    /// See [`ReadinessCriteria::uniform_gearsets`].
+    UniformGearsets,
+    /// See [`ReadinessCriteria::no_faults`].
+    NoFaults,
+}
+
+/// The outcome of every check run by [`MotorGroup::readiness`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadinessReport {
+    /// Each check that was run with its outcome, in the order of
+    /// [`ReadinessCheck`]. The outcome says which motors failed the check
+    /// and which couldn't be read.
+    pub checks: Vec<(ReadinessCheck, GroupPredicateResult)>,
+}
+
+impl ReadinessReport {
+    /// Returns whether every check that was run passed.
+    pub fn is_ready(&self) -> bool {
+        self.checks.iter().all(|(_, result)| result.satisfied)
+    }
+
+    /// Returns the outcome of `check`, or `None` if it wasn't run.
+    pub fn get(&self, check: ReadinessCheck) -> Option<&GroupPredicateResult> {
+        self.checks
+            .iter()
+            .find(|(candidate, _)| *candidate == check)
+            .map(|(_, result)| result)
+    }
+
+    /// Returns each check that failed with the indices of the motors that
+    /// failed it, including those that couldn't be read.
+    pub fn failures(&self) -> Vec<(ReadinessCheck, Vec<usize>)> {
+        self.checks
+            .iter()
+            .filter(|(_, result)| !result.satisfied)
+            .map(|(check, result)| (*check, result.unsatisfied()))
+            .collect()
+    }
+}
+
+/// Compares each motor's gearset with `expected`, or with the first gearset
+/// that could be read if there is none.
+pub(crate) fn uniform_gearsets(
+    gearsets: Vec<Reading<Gearset>>,
+    expected: Option<Gearset>,
+) -> Vec<Reading<bool>> {
+    let expected = expected.or_else(|| {
+        gearsets
+            .iter()
+            .find_map(|(_, gearset)| gearset.as_ref().ok().copied())
+    });
+    gearsets
+        .into_iter()
+        .map(|(index, gearset)| (index, gearset.map(|gearset| Some(gearset) == expected)))
+        .collect()
+}
+
+/// Returns whether `motor`'s limits are within [`LIMIT_TOLERANCE`] of
+/// `voltage_limit`, if set, and `current_limit`.
+fn limits_match(
+    motor: &Motor,
+    voltage_limit: Option<f64>,
+    current_limit: f64,
+) -> Result<bool, PortError> {
+    let voltage_matches = match voltage_limit {
+        Some(limit) => (motor.voltage_limit()? - limit).abs() <= LIMIT_TOLERANCE,
+        None => true,
+    };
+    Ok(voltage_matches && (motor.current_limit()? - current_limit).abs() <= LIMIT_TOLERANCE)
+}
+
+/// Returns whether `motor` has no fault flags set.
+fn fault_free(motor: &Motor) -> Result<bool, PortError> {
+    Ok(!(motor.is_over_temperature()?
+        || motor.is_over_current()?
+        || motor.is_driver_fault()?
+        || motor.is_driver_over_current()?))
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Runs a pre-match checklist on the group.
+    ///
+    /// Each check enabled in `criteria` is run against every motor, the same
+    /// way as [`MotorGroup::all_satisfy`]: every motor is checked even after
+    /// one fails, motors that can't be read are counted according to the
+    /// group's [`PredicateErrorStrategy`](crate::PredicateErrorStrategy), and
+    /// disabled and checked out motors are skipped. The report lists every
+    /// check that was run and which motors failed it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let report = motor_group.readiness(&ReadinessCriteria::DEFAULT);
+    ///     for (check, motors) in report.failures() {
+    ///         println!("Drive failed {check:?}: motors {motors:?}");
+    ///     }
+    /// }
+    /// ```
+    pub fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport {
+        let strategy = self.config.predicate_error_strategy;
+        let mut checks = Vec::new();
+        if criteria.connected {
+            checks.push((ReadinessCheck::Connected, self.all_connected()));
+        }
+        if let Some(threshold) = criteria.max_temperature {
+            checks.push((
+                ReadinessCheck::Temperature,
+                self.all_below_temperature(threshold),
+            ));
+        }
+        if let Some(tolerance) = criteria.zero_tolerance {
+            checks.push((
+                ReadinessCheck::Zeroed,
+                self.all_satisfy(|motor| Ok(motor.position()?.as_degrees().abs() <= tolerance)),
+            ));
+        }
+        if criteria.verify_limits {
+            let current_limits =
+                configured_current_limits(&self.config, &self.max_current_per_motor());
+            let outcomes = self
+                .motors
+                .as_ref()
+                .iter()
+                .zip(&self.meta)
+                .zip(current_limits)
+                .enumerate()
+                .filter(|(_, ((_, meta), _))| meta.is_active())
+                .map(|(index, ((motor, _), current_limit))| {
+                    (
+                        index,
+                        limits_match(motor, self.config.voltage_limit, current_limit),
+                    )
+                })
+                .collect();
+            checks.push((ReadinessCheck::Limits, evaluate(outcomes, strategy)));
+        }
+        if criteria.uniform_gearsets {
+            let outcomes = uniform_gearsets(self.read_each(Motor::gearset), self.config.gearset);
+            checks.push((
+                ReadinessCheck::UniformGearsets,
+                evaluate(outcomes, strategy),
+            ));
+        }
+        if criteria.no_faults {
+            checks.push((ReadinessCheck::NoFaults, self.all_satisfy(fault_free)));
+        }
+        ReadinessReport { checks }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{ReadinessCheck, ReadinessCriteria, uniform_gearsets};
+    use crate::{MotorGroup, PredicateErrorStrategy, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 2 };
+
+    /// Criteria that run no checks.
+    const NOTHING: ReadinessCriteria = ReadinessCriteria {
+        connected: false,
+        max_temperature: None,
+        zero_tolerance: None,
+        verify_limits: false,
+        uniform_gearsets: false,
+        no_faults: false,
+    };
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(3, Gearset::Blue))
+    }
+
+    #[test]
+    fn gearsets_are_compared_with_the_expected_one() {
+        let gearsets = vec![
+            (0, Err(DISCONNECTED)),
+            (1, Ok(Gearset::Blue)),
+            (2, Ok(Gearset::Green)),
+        ];
+        // Without a configured gearset, the first readable one is expected
+        assert_eq!(
+            uniform_gearsets(gearsets.clone(), None),
+            vec![(0, Err(DISCONNECTED)), (1, Ok(true)), (2, Ok(false))]
+        );
+        assert_eq!(
+            uniform_gearsets(gearsets, Some(Gearset::Green)),
+            vec![(0, Err(DISCONNECTED)), (1, Ok(false)), (2, Ok(true))]
+        );
+        assert_eq!(
+            uniform_gearsets(vec![(0, Ok(Gearset::Red)), (3, Ok(Gearset::Red))], None),
+            vec![(0, Ok(true)), (3, Ok(true))]
+        );
+    }
+
+    #[test]
+    fn every_enabled_check_is_reported() {
+        let mut group = group();
+        let report = group.readiness(&ReadinessCriteria::DEFAULT);
+        assert_eq!(
+            report
+                .checks
+                .iter()
+                .map(|(check, _)| *check)
+                .collect::<Vec<_>>(),
+            vec![
+                ReadinessCheck::Connected,
+                ReadinessCheck::Temperature,
+                ReadinessCheck::Zeroed,
+                ReadinessCheck::Limits,
+                ReadinessCheck::UniformGearsets,
+                ReadinessCheck::NoFaults,
+            ]
+        );
+        // No mock motor is plugged in, so every check fails on every motor
+        assert!(!report.is_ready());
+        assert_eq!(report.failures().len(), 6);
+        assert!(
+            report
+                .failures()
+                .iter()
+                .all(|(_, motors)| *motors == vec![0, 1, 2])
+        );
+        // Checking the connection doesn't fail, but reading does
+        assert!(
+            report
+                .get(ReadinessCheck::Connected)
+                .unwrap()
+                .errors
+                .is_empty()
+        );
+        assert_eq!(report.get(ReadinessCheck::Limits).unwrap().errors.len(), 3);
+
+        // Disabled motors are skipped
+        group.set_enabled(1, false);
+        let criteria = ReadinessCriteria {
+            connected: true,
+            ..NOTHING
+        };
+        let report = group.readiness(&criteria);
+        assert_eq!(
+            report.failures(),
+            vec![(ReadinessCheck::Connected, vec![0, 2])]
+        );
+        assert_eq!(report.get(ReadinessCheck::Zeroed), None);
+    }
+
+    #[test]
+    fn skipped_checks_pass() {
+        let mut group = group();
+        let report = group.readiness(&NOTHING);
+        assert!(report.checks.is_empty());
+        assert!(report.is_ready());
+
+        // Ignoring read errors doesn't pass a check nothing could be read for
+        group.predicate_error_strategy(PredicateErrorStrategy::Ignore);
+        let criteria = ReadinessCriteria {
+            uniform_gearsets: true,
+            ..NOTHING
+        };
+        let report = group.readiness(&criteria);
+        assert_eq!(
+            report.failures(),
+            vec![(ReadinessCheck::UniformGearsets, vec![0, 1, 2])]
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Helpers for turning per-motor readings into group-level getter results.
+//!
+//! These are kept free of any hardware access so that the aggregation logic
+//! can be tested on the host with made-up readings.
+
+use alloc::vec::Vec;
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError, read_cache::Change};
+
+/// Builds a getter result out of a value computed from the successful
+/// readings and the errors from the failed ones.
+///
+/// `value` should only be `None` if no motor could be read.
+pub(crate) fn finish<T, E>(value: Option<T>, errors: Vec<E>) -> Result<T, MotorGroupError<E, T>> {
+    match (value, errors.is_empty()) {
+        (Some(value), true) => Ok(value),
+        (Some(value), false) => Err(MotorGroupError::with_result(errors, value)),
+        (None, _) => Err(MotorGroupError::with_empty_result(errors)),
+    }
+}
+
+/// A reading from a single motor, along with the motor's index in the group.
+pub(crate) type Reading<T> = (usize, Result<T, PortError>);
+
+/// Splits per-motor readings into `(index, value)` pairs for the successful
+/// ones and the errors of the failed ones.
+pub(crate) fn partition<T>(
+    readings: impl IntoIterator<Item = Reading<T>>,
+) -> (Vec<(usize, T)>, Vec<PortError>) {
+    let readings = readings.into_iter();
+    let mut values = Vec::with_capacity(readings.size_hint().0);
+    let mut errors = Vec::new();
+    for (index, reading) in readings {
+        match reading {
+            Ok(value) => values.push((index, value)),
+            Err(error) => errors.push(error),
+        }
+    }
+    (values, errors)
+}
+
+/// Returns the mean of `values`, or `None` if there are none.
+///
+/// The values are summed with Neumaier's compensated summation, so the mean
+/// of a large group doesn't drift with the order of its motors or lose the
+/// small differences between large readings such as positions late in a
+/// match.
+pub(crate) fn mean(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = sum(values);
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Returns the sum of `values` and how many there are, using the same
+/// compensated summation as [`mean`].
+fn sum(values: impl IntoIterator<Item = f64>) -> (f64, usize) {
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+    let mut count = 0usize;
+    for value in values {
+        let total = sum + value;
+        compensation += if sum.abs() >= value.abs() {
+            (sum - total) + value
+        } else {
+            (value - total) + sum
+        };
+        sum = total;
+        count += 1;
+    }
+    (sum + compensation, count)
+}
+
+/// Averages per-motor readings.
+///
+/// The partial result of an error is the average of the motors that could be
+/// read, computed the same way as a fully successful result.
+pub(crate) fn average(readings: impl IntoIterator<Item = Reading<f64>>) -> GetterResult<f64> {
+    let (values, errors) = partition(readings);
+    finish(mean(values.into_iter().map(|(_, value)| value)), errors)
+}
+
+/// Averages per-motor readings over a fixed `divisor`, so that motors
+/// without a reading count as zero.
+///
+/// The partial result of an error is computed the same way, as long as at
+/// least one motor could be read.
+pub(crate) fn average_over(
+    readings: impl IntoIterator<Item = Reading<f64>>,
+    divisor: usize,
+) -> GetterResult<f64> {
+    let (values, errors) = partition(readings);
+    let (sum, count) = sum(values.into_iter().map(|(_, value)| value));
+    finish((count > 0).then(|| sum / divisor as f64), errors)
+}
+
+/// Returns `Ok(true)` if any reading is `true`, even if other motors couldn't
+/// be read.
+///
+/// Otherwise, an error is returned if any motor couldn't be read. Its result is
+/// `false` unless no motor could be read at all.
+pub(crate) fn any(readings: impl IntoIterator<Item = Reading<bool>>) -> GetterResult<bool> {
+    let (values, errors) = partition(readings);
+    if values.iter().any(|(_, value)| *value) {
+        Ok(true)
+    } else {
+        finish((!values.is_empty()).then_some(false), errors)
+    }
+}
+
+/// Returns the index and value of the reading with the smallest magnitude.
+pub(crate) fn min_magnitude(
+    readings: impl IntoIterator<Item = Reading<f64>>,
+) -> GetterResult<(usize, f64)> {
+    let (values, errors) = partition(readings);
+    let min = values
+        .into_iter()
+        .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()));
+    finish(min, errors)
+}
+
+/// Maps the value of a getter result, including the partial result of an
+/// error.
+pub(crate) fn map_result<T, U>(result: GetterResult<T>, f: impl FnOnce(T) -> U) -> GetterResult<U> {
+    match result {
+        Ok(value) => Ok(f(value)),
+        Err(error) => finish(error.result.map(f), error.errors),
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets whether disabled motors and motors that can't be read count
+    /// towards the divisor of the group's averaged getters.
+    ///
+    /// By default (`false`), a getter such as [`MotorGroup::velocity`]
+    /// averages only the motors it could read, so disabling a motor (see
+    /// [`MotorGroup::set_enabled`]) or losing one to an error changes the
+    /// divisor and can make the reading jump. With `true`, the divisor is
+    /// every motor in the group, and each disabled or failed motor
+    /// contributes zero to the sum:
+    ///
+    /// - With every motor enabled and readable, both settings give the same
+    ///   average.
+    /// - A disabled motor is never read, so it always counts as zero.
+    /// - A motor that fails to read counts as zero, and its error is still
+    ///   returned. The error's partial result is the average over the full
+    ///   divisor.
+    /// - If no motor could be read, there is no partial result under either
+    ///   setting.
+    ///
+    /// Checked out motors (see [`MotorGroup::checkout`]) are being
+    /// controlled separately, so they are never counted. This only affects
+    /// the getters that average plain readings: [`MotorGroup::velocity`],
+    /// [`MotorGroup::power`], [`MotorGroup::torque`],
+    /// [`MotorGroup::voltage`], [`MotorGroup::current`],
+    /// [`MotorGroup::efficiency`], and [`MotorGroup::temperature`]. The
+    /// group's position is never averaged with zeros, since a zero position
+    /// isn't a neutral value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     // Report the output of the whole group, rather than the average of
+    ///     // the motors still working.
+    ///     motor_group.count_disabled_in_average(true);
+    /// }
+    /// ```
+    pub fn count_disabled_in_average(&mut self, enabled: bool) -> &mut Self {
+        self.config.count_disabled_in_average = enabled;
+        self.read_cache.invalidate(Change::Aggregation);
+        self
+    }
+
+    /// Reads a value from every active motor and averages it according to
+    /// [`MotorGroup::count_disabled_in_average`].
+    pub(crate) fn average_each(
+        &self,
+        read: impl FnMut(&Motor) -> Result<f64, PortError>,
+    ) -> GetterResult<f64> {
+        self.average_readings(self.read_each(read))
+    }
+
+    /// Averages readings from the active motors according to
+    /// [`MotorGroup::count_disabled_in_average`].
+    pub(crate) fn average_readings(&self, readings: Vec<Reading<f64>>) -> GetterResult<f64> {
+        if self.config.count_disabled_in_average {
+            let divisor = self.meta.iter().filter(|meta| !meta.checked_out).count();
+            average_over(readings, divisor)
+        } else {
+            average(readings)
+        }
+    }
+
+    /// Reads a value from every active motor in the group, in order.
+    ///
+    /// Disabled motors (see [`MotorGroup::set_enabled`]) and checked out
+    /// motors (see [`MotorGroup::checkout`]) are skipped.
+    pub(crate) fn read_each<T>(
+        &self,
+        read: impl FnMut(&Motor) -> Result<T, PortError>,
+    ) -> Vec<Reading<T>> {
+        self.read_where(|_| true, read)
+    }
+
+    /// [`MotorGroup::read_each`], only reading the motors whose index
+    /// `include` returns `true` for.
+    pub(crate) fn read_where<T>(
+        &self,
+        include: impl Fn(usize) -> bool,
+        mut read: impl FnMut(&Motor) -> Result<T, PortError>,
+    ) -> Vec<Reading<T>> {
+        self.motors
+            .as_ref()
+            .iter()
+            .zip(&self.meta)
+            .enumerate()
+            .filter(|(index, (_, meta))| include(*index) && meta.is_active())
+            .map(|(index, (motor, _))| {
+                let reading = read(motor);
+                self.observe_connection(index, reading.is_ok());
+                (index, reading)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,581 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{
+    ConfigureError, GroupEvent, MotorGroup, MotorGroupError, SharedMotors, read_cache::Change,
+    readings::Reading, startup::wait_ready,
+};
+
+/// Which steps [`MotorGroup::recover`] takes, and how long it waits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecoveryOptions {
+    /// How long to wait for the motors to respond. This is the only step
+    /// that waits, so it bounds how long recovery takes.
+    pub timeout: Duration,
+    /// Wait for every motor to respond, like
+    /// [`MotorGroup::wait_until_ready`].
+    pub check_connectivity: bool,
+    /// Write the group's configuration to the motors again, like
+    /// [`MotorGroup::apply_config`] with [`MotorGroup::current_config`].
+    pub reapply_config: bool,
+    /// Re-sync the position of motors that dropped out with the rest of the
+    /// group, like [`MotorGroup::rezero`].
+    pub resync_positions: bool,
+    /// Take every motor out of the
+    /// [position fallback](MotorGroup::position_fallback).
+    pub clear_protection: bool,
+    /// Give the motors the group's last command again. Nothing that moves the
+    /// motors is written unless this is set.
+    pub reapply_motion: bool,
+}
+
+impl RecoveryOptions {
+    /// Every step except re-issuing the last command, with a timeout of one
+    /// second.
+    pub const DEFAULT: Self = Self {
+        timeout: Duration::from_secs(1),
+        check_connectivity: true,
+        reapply_config: true,
+        resync_positions: true,
+        clear_protection: true,
+        reapply_motion: false,
+    };
+}
+
+impl Default for RecoveryOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The outcome of a single step of [`MotorGroup::recover`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryStep<E = PortError> {
+    /// Whether the step was taken. Skipped steps haven't succeeded either.
+    pub attempted: bool,
+    /// Whether the step was taken and had no errors.
+    pub succeeded: bool,
+    /// The errors of the step.
+    pub errors: Vec<E>,
+}
+
+impl<E> RecoveryStep<E> {
+    /// A step that wasn't taken.
+    const fn skipped() -> Self {
+        Self {
+            attempted: false,
+            succeeded: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// A step that was taken with `result`.
+    fn taken(result: Result<(), MotorGroupError<E>>) -> Self {
+        let errors = result.map_or_else(|error| error.errors, |()| Vec::new());
+        Self {
+            attempted: true,
+            succeeded: errors.is_empty(),
+            errors,
+        }
+    }
+}
+
+/// What [`MotorGroup::recover`] did, step by step, in the order the steps are
+/// taken.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryReport {
+    /// See [`RecoveryOptions::check_connectivity`]. Its errors are those of
+    /// the motors that still didn't respond at the timeout.
+    pub connectivity: RecoveryStep,
+    /// See [`RecoveryOptions::reapply_config`].
+    pub config: RecoveryStep<ConfigureError>,
+    /// See [`RecoveryOptions::resync_positions`].
+    pub positions: RecoveryStep,
+    /// See [`RecoveryOptions::clear_protection`].
+    pub protection: RecoveryStep,
+    /// See [`RecoveryOptions::reapply_motion`]. This isn't attempted if the
+    /// group hasn't been given a command yet.
+    pub motion: RecoveryStep,
+}
+
+impl RecoveryReport {
+    /// Returns `true` if every step that was attempted succeeded.
+    pub fn is_recovered(&self) -> bool {
+        let steps = [
+            (self.connectivity.attempted, self.connectivity.succeeded),
+            (self.config.attempted, self.config.succeeded),
+            (self.positions.attempted, self.positions.succeeded),
+            (self.protection.attempted, self.protection.succeeded),
+            (self.motion.attempted, self.motion.succeeded),
+        ];
+        steps
+            .iter()
+            .all(|&(attempted, succeeded)| !attempted || succeeded)
+    }
+}
+
+/// The primitives a recovery is built from, so that the sequence can be run on
+/// both kinds of group and tested against scripted ones.
+trait Recover {
+    /// The interval to poll the motors at.
+    fn poll_interval(&self) -> Duration;
+    /// Reads the status of every active motor.
+    fn status(&self) -> Vec<Reading<()>>;
+    /// Writes the group's configuration again.
+    fn reapply_config(&mut self) -> Result<(), MotorGroupError<ConfigureError>>;
+    /// Re-syncs the motors at the indices in `returned`, and any other stale
+    /// motors, with the rest of the group.
+    fn resync_positions(&mut self, returned: &[usize]) -> Result<(), MotorGroupError>;
+    /// Takes every motor out of the position fallback.
+    fn clear_protection(&mut self);
+    /// Gives the motors the last command again, or returns `None` if there
+    /// isn't one.
+    fn reapply_motion(&mut self) -> Option<Result<(), MotorGroupError>>;
+}
+
+/// Takes the steps of a recovery chosen by `options` on `group`.
+async fn recover_with<G: Recover>(group: &mut G, options: &RecoveryOptions) -> RecoveryReport {
+    let mut report = RecoveryReport {
+        connectivity: RecoveryStep::skipped(),
+        config: RecoveryStep::skipped(),
+        positions: RecoveryStep::skipped(),
+        protection: RecoveryStep::skipped(),
+        motion: RecoveryStep::skipped(),
+    };
+
+    // The motors that didn't respond at first may have lost their position
+    // while they were away, unless none responded, in which case there's no
+    // position left to sync them with.
+    let mut returned = Vec::new();
+    if options.check_connectivity {
+        let mut first_poll = true;
+        let interval = group.poll_interval();
+        let result = wait_ready(interval, options.timeout, || {
+            let readings = group.status();
+            if core::mem::take(&mut first_poll) && readings.iter().any(|(_, status)| status.is_ok())
+            {
+                returned = readings
+                    .iter()
+                    .filter(|(_, status)| status.is_err())
+                    .map(|(index, _)| *index)
+                    .collect();
+            }
+            readings
+        })
+        .await;
+        report.connectivity = RecoveryStep::taken(result);
+    }
+    if options.reapply_config {
+        report.config = RecoveryStep::taken(group.reapply_config());
+    }
+    if options.resync_positions {
+        report.positions = RecoveryStep::taken(group.resync_positions(&returned));
+    }
+    if options.clear_protection {
+        group.clear_protection();
+        report.protection = RecoveryStep::taken(Ok(()));
+    }
+    if options.reapply_motion
+        && let Some(result) = group.reapply_motion()
+    {
+        report.motion = RecoveryStep::taken(result);
+    }
+    report
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Recover for MotorGroup<M> {
+    fn poll_interval(&self) -> Duration {
+        self.config.tick_interval
+    }
+
+    fn status(&self) -> Vec<Reading<()>> {
+        self.status_readings()
+    }
+
+    fn reapply_config(&mut self) -> Result<(), MotorGroupError<ConfigureError>> {
+        // A motor that lost power forgot its direction too, so it's sent
+        // again before the motor's next target
+        for meta in &mut self.meta {
+            meta.direction_stale = meta.direction.is_some();
+        }
+        let config = self.config;
+        self.apply_config(&config)
+    }
+
+    fn resync_positions(&mut self, returned: &[usize]) -> Result<(), MotorGroupError> {
+        for &index in returned {
+            self.meta[index].reference_stale = true;
+        }
+        self.rezero()
+    }
+
+    fn clear_protection(&mut self) {
+        for index in self.fallback_motors() {
+            self.meta[index].in_fallback = false;
+            self.emit(GroupEvent::FallbackExited(index));
+        }
+        for meta in &mut self.meta {
+            meta.position_failures = 0;
+        }
+        self.read_cache.invalidate(Change::Aggregation);
+    }
+
+    fn reapply_motion(&mut self) -> Option<Result<(), MotorGroupError>> {
+        let command = self.last_command?;
+        Some(self.set_target(command))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Recover for SharedMotors<M> {
+    fn poll_interval(&self) -> Duration {
+        self.0.borrow().poll_interval()
+    }
+
+    fn status(&self) -> Vec<Reading<()>> {
+        self.0.borrow().status()
+    }
+
+    fn reapply_config(&mut self) -> Result<(), MotorGroupError<ConfigureError>> {
+        self.0.borrow_mut().reapply_config()
+    }
+
+    fn resync_positions(&mut self, returned: &[usize]) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().resync_positions(returned)
+    }
+
+    fn clear_protection(&mut self) {
+        self.0.borrow_mut().clear_protection();
+    }
+
+    fn reapply_motion(&mut self) -> Option<Result<(), MotorGroupError>> {
+        self.0.borrow_mut().reapply_motion()
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Gets the group working again after its motors lost power or were
+    /// unplugged, such as after a brownout or a cable being re-seated.
+    ///
+    /// A motor that comes back has forgotten everything it was told, so this
+    /// takes the steps chosen by `options`, in order:
+    ///
+    /// 1. Connectivity: waits for every motor to respond, like
+    ///    [`MotorGroup::wait_until_ready`], for up to the timeout.
+    /// 2. Configuration: writes the group's configuration to the motors
+    ///    again, like [`MotorGroup::apply_config`] with
+    ///    [`MotorGroup::current_config`]. Motors with their own direction
+    ///    (see [`MotorGroup::set_direction`]) are sent it again before their
+    ///    next target.
+    /// 3. Positions: marks the motors that didn't respond at first as stale,
+    ///    since their position may have been reset, then re-syncs every stale
+    ///    motor with [`MotorGroup::rezero`]. If no motor responded at first,
+    ///    there's nothing to sync them with, so none are marked.
+    /// 4. Protection: takes every motor out of the
+    ///    [position fallback](MotorGroup::position_fallback) and drops any
+    ///    cached readings.
+    /// 5. Motion: gives the motors the group's last command again with
+    ///    [`MotorGroup::set_target`]. This is off by default, and nothing that
+    ///    moves the motors is written unless it's chosen.
+    ///
+    /// Every chosen step is taken even if an earlier one fails, so that the
+    /// motors that did come back are set up. The connectivity check is the
+    /// only step that waits, so recovery takes at most the timeout plus the
+    /// time to write to the motors.
+    ///
+    /// The report says which steps were attempted and succeeded, with their
+    /// errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     _ = lift.set_gearset(Gearset::Red);
+    ///     _ = lift.set_position_target(Angle::from_degrees(90.0), 100);
+    ///
+    ///     // ...a motor browns out...
+    ///
+    ///     let report = lift
+    ///         .recover(RecoveryOptions {
+    ///             reapply_motion: true,
+    ///             ..RecoveryOptions::DEFAULT
+    ///         })
+    ///         .await;
+    ///     if !report.is_recovered() {
+    ///         println!("The lift didn't fully recover: {report:?}");
+    ///     }
+    /// }
+    /// ```
+    pub async fn recover(&mut self, options: RecoveryOptions) -> RecoveryReport {
+        recover_with(self, &options).await
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Gets the group working again after its motors lost power or were
+    /// unplugged, borrowing the group only while taking each step.
+    ///
+    /// The last command is re-issued to this group only, not to its shadow
+    /// (see [`SharedMotors::shadow`]), which has to be recovered on its own.
+    ///
+    /// See [`MotorGroup::recover`].
+    pub async fn recover(&mut self, options: RecoveryOptions) -> RecoveryReport {
+        recover_with(self, &options).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::{PortError, motor::MotorControl},
+    };
+
+    use super::{Recover, RecoveryOptions, RecoveryStep, recover_with};
+    use crate::{
+        ConfigureError, MotorGroup, MotorGroupError, readings::Reading, tests::mock_motors,
+    };
+
+    /// Polls `future` until it completes. With a zero interval, the loops
+    /// only ever yield, so they never need a runtime to wake them.
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+                return value;
+            }
+        }
+    }
+
+    fn disconnected(index: usize) -> PortError {
+        PortError::Disconnected {
+            port: index as u8 + 1,
+        }
+    }
+
+    /// A group whose motors each respond after a scripted number of polls,
+    /// recording the steps taken on it.
+    #[derive(Default)]
+    struct ScriptedGroup {
+        back_after: Vec<usize>,
+        polls: Cell<usize>,
+        steps: Vec<&'static str>,
+        returned: Vec<usize>,
+        last_command: Option<MotorControl>,
+    }
+
+    impl ScriptedGroup {
+        fn new(back_after: Vec<usize>) -> Self {
+            Self {
+                back_after,
+                ..Self::default()
+            }
+        }
+    }
+
+    impl Recover for ScriptedGroup {
+        fn poll_interval(&self) -> Duration {
+            Duration::ZERO
+        }
+
+        fn status(&self) -> Vec<Reading<()>> {
+            let polls = self.polls.get();
+            self.polls.set(polls + 1);
+            self.back_after
+                .iter()
+                .enumerate()
+                .map(|(index, &after)| {
+                    let status = if polls >= after {
+                        Ok(())
+                    } else {
+                        Err(disconnected(index))
+                    };
+                    (index, status)
+                })
+                .collect()
+        }
+
+        fn reapply_config(&mut self) -> Result<(), MotorGroupError<ConfigureError>> {
+            self.steps.push("config");
+            let (_, errors) = crate::readings::partition(self.status());
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(MotorGroupError::new(
+                    errors.into_iter().map(ConfigureError::from).collect(),
+                ))
+            }
+        }
+
+        fn resync_positions(&mut self, returned: &[usize]) -> Result<(), MotorGroupError> {
+            self.steps.push("positions");
+            self.returned = returned.to_vec();
+            Ok(())
+        }
+
+        fn clear_protection(&mut self) {
+            self.steps.push("protection");
+        }
+
+        fn reapply_motion(&mut self) -> Option<Result<(), MotorGroupError>> {
+            self.last_command?;
+            self.steps.push("motion");
+            Some(Ok(()))
+        }
+    }
+
+    const ALL: RecoveryOptions = RecoveryOptions {
+        timeout: Duration::from_secs(5),
+        reapply_motion: true,
+        ..RecoveryOptions::DEFAULT
+    };
+
+    #[test]
+    fn disconnected_motor_is_recovered_once_it_returns() {
+        // Motor 1 is unplugged for the first three polls
+        let mut group = ScriptedGroup::new(vec![0, 3]);
+        group.last_command = Some(MotorControl::Voltage(6.0));
+        let report = run(recover_with(&mut group, &ALL));
+
+        assert!(report.is_recovered());
+        assert_eq!(group.polls.get(), 5);
+        assert_eq!(group.steps, ["config", "positions", "protection", "motion"]);
+        // It may have lost its position while it was away
+        assert_eq!(group.returned, [1]);
+    }
+
+    #[test]
+    fn motors_that_never_return_are_reported() {
+        let mut group = ScriptedGroup::new(vec![0, usize::MAX]);
+        group.last_command = Some(MotorControl::Voltage(6.0));
+        let report = run(recover_with(
+            &mut group,
+            &RecoveryOptions {
+                timeout: Duration::ZERO,
+                ..ALL
+            },
+        ));
+
+        assert!(!report.is_recovered());
+        assert!(report.connectivity.attempted);
+        assert_eq!(report.connectivity.errors, [disconnected(1)]);
+        // The rest of the steps are still taken for the motors that are back
+        assert_eq!(
+            report.config.errors,
+            [ConfigureError::Port {
+                source: disconnected(1)
+            }]
+        );
+        assert!(report.positions.succeeded);
+        assert!(report.motion.succeeded);
+        assert_eq!(group.steps.len(), 4);
+    }
+
+    #[test]
+    fn skipped_steps_are_not_taken() {
+        let mut group = ScriptedGroup::new(vec![0, 0]);
+        group.last_command = Some(MotorControl::Voltage(6.0));
+        let report = run(recover_with(
+            &mut group,
+            &RecoveryOptions {
+                check_connectivity: false,
+                clear_protection: false,
+                ..RecoveryOptions::DEFAULT
+            },
+        ));
+
+        assert_eq!(report.connectivity, RecoveryStep::skipped());
+        assert_eq!(report.protection, RecoveryStep::skipped());
+        assert_eq!(report.motion, RecoveryStep::skipped());
+        assert_eq!(group.steps, ["config", "positions"]);
+        // Nothing was known to have been away
+        assert!(group.returned.is_empty());
+        assert!(report.is_recovered());
+
+        // There's no command to re-issue
+        let mut group = ScriptedGroup::new(vec![0]);
+        let report = run(recover_with(&mut group, &ALL));
+        assert!(!report.motion.attempted);
+        assert!(!group.steps.contains(&"motion"));
+    }
+
+    #[test]
+    fn nothing_is_marked_stale_if_no_motor_responded() {
+        let mut group = ScriptedGroup::new(vec![2, 2]);
+        run(recover_with(&mut group, &ALL));
+        assert!(group.returned.is_empty());
+    }
+
+    fn motors() -> MotorGroup {
+        let mut group = MotorGroup::new(mock_motors(2, Gearset::Green));
+        group.tick_interval(Duration::ZERO);
+        group
+    }
+
+    #[test]
+    fn motion_is_only_reissued_when_asked() {
+        let mut group = motors();
+        _ = group.set_position_target(Angle::from_degrees(90.0), 100);
+        let generation = group.command_generation();
+
+        // The mock motors never respond, so every step that writes fails
+        let options = RecoveryOptions {
+            timeout: Duration::ZERO,
+            ..RecoveryOptions::DEFAULT
+        };
+        let report = run(group.recover(options));
+        assert!(!report.motion.attempted);
+        assert_eq!(report.connectivity.errors.len(), 2);
+        assert_eq!(group.command_generation(), generation);
+
+        let report = run(group.recover(RecoveryOptions {
+            reapply_motion: true,
+            ..options
+        }));
+        assert!(report.motion.attempted);
+        assert_eq!(report.motion.errors.len(), 2);
+        assert_eq!(group.command_generation(), generation + 1);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Position(Angle::from_degrees(90.0), 100))
+        );
+    }
+
+    #[test]
+    fn latched_fallback_is_cleared() {
+        let mut group = motors();
+        for meta in &mut group.meta {
+            meta.in_fallback = true;
+            meta.position_failures = 3;
+        }
+        let report = run(group.recover(RecoveryOptions {
+            timeout: Duration::ZERO,
+            check_connectivity: false,
+            reapply_config: false,
+            resync_positions: false,
+            ..RecoveryOptions::DEFAULT
+        }));
+        assert!(report.protection.succeeded);
+        assert!(!group.is_in_fallback());
+        assert!(group.meta.iter().all(|meta| meta.position_failures == 0));
+    }
+}
@@ -0,0 +1,351 @@
+use alloc::vec::Vec;
+use vexide::{
+    math::Angle,
+    smart::{PortError, motor::Motor},
+};
+
+use crate::{
+    GetterResult, GroupPosition, MotorGroup, MotorGroupError, WriteErrorStrategy, WriteKind,
+    meta::MotorMeta,
+    read_cache::Change,
+    readings::{self, Reading},
+};
+
+/// Updates each motor's reference state after a write that changes the
+/// position reference (such as [`MotorGroup::reset_position`]).
+///
+/// `succeeded[i]` is whether the write reached motor `i`. If no write
+/// succeeded, every motor still has the same reference as before, so nothing
+/// changes. Otherwise, the motors that didn't receive the write now disagree
+/// with the ones that did.
+pub(crate) fn record_reference_writes(meta: &mut [MotorMeta], succeeded: &[bool]) {
+    if !succeeded.iter().any(|succeeded| *succeeded) {
+        return;
+    }
+    for (meta, succeeded) in meta.iter_mut().zip(succeeded) {
+        meta.reference_stale = !succeeded;
+    }
+}
+
+/// Splits position readings into the positions in degrees of the motors that
+/// share the group's reference, and the errors.
+///
+/// If every motor is stale, they all share the same (stale) reference, so
+/// every position is kept.
+pub(crate) fn comparable_positions(
+    readings: impl IntoIterator<Item = Reading<Angle>>,
+    meta: &[MotorMeta],
+) -> (Vec<f64>, Vec<PortError>) {
+    let all_stale = meta
+        .iter()
+        .filter(|meta| meta.is_active())
+        .all(|meta| meta.reference_stale);
+    let (values, errors) = readings::partition(readings);
+    let positions = values
+        .into_iter()
+        .filter(|(index, _)| all_stale || !meta[*index].reference_stale)
+        .map(|(_, position)| position.as_degrees())
+        .collect();
+    (positions, errors)
+}
+
+/// Averages position readings, ignoring the motors marked as stale.
+///
+/// If every motor is stale, they are all averaged instead. The average is
+/// taken in degrees; see [`GroupPosition`].
+pub(crate) fn average_position(
+    readings: impl IntoIterator<Item = Reading<Angle>>,
+    meta: &[MotorMeta],
+) -> GetterResult<GroupPosition> {
+    let (positions, errors) = comparable_positions(readings, meta);
+    let average = readings::mean(positions);
+    readings::finish(average.and_then(GroupPosition::from_degrees), errors)
+}
+
+/// Returns the absolute target `delta` away from a position reading, along
+/// with the errors from the reading.
+///
+/// The target is `None` if no motor could be read, or if it wouldn't be
+/// finite.
+pub(crate) fn relative_target(
+    position: GetterResult<GroupPosition>,
+    delta: Angle,
+) -> (Option<Angle>, Vec<PortError>) {
+    let (position, errors) = match position {
+        Ok(position) => (Some(position), Vec::new()),
+        Err(error) => (error.result, error.errors),
+    };
+    let target = position
+        .and_then(|position| position.add_degrees(delta.as_degrees()))
+        .map(GroupPosition::to_position);
+    (target, errors)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets a position target relative to the group's current position.
+    ///
+    /// This reads the group's average position with [`MotorGroup::position`],
+    /// adds `delta` to it, and commands the result as an absolute target with
+    /// [`MotorGroup::set_position_target`]. Every motor is given the same
+    /// target, so a motor that has drifted from the group average moves by
+    /// more or less than `delta` to catch up. The position is read once, so
+    /// calling this repeatedly while the group moves sets a new target from
+    /// wherever the group is each time.
+    ///
+    /// If only some motors can be read, the average of the others is used as
+    /// the baseline, unless the group's write error strategy is
+    /// [`WriteErrorStrategy::Stop`](crate::WriteErrorStrategy::Stop), in
+    /// which case nothing is commanded.
+    ///
+    /// The target is computed in degrees with [`GroupPosition::add_degrees`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor couldn't be read
+    ///   or written to. It contains the read errors followed by the write
+    ///   errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delta` isn't finite, or is so large that the target isn't.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Turn the output shaft another half turn from wherever it is.
+    ///     _ = motor_group.set_relative_position_target(Angle::from_turns(0.5), 200);
+    /// }
+    /// ```
+    pub fn set_relative_position_target(
+        &mut self,
+        delta: Angle,
+        velocity: i32,
+    ) -> Result<(), MotorGroupError> {
+        let (target, mut errors) = relative_target(self.group_position(), delta);
+        crate::check_invariant(
+            target.is_some() || !errors.is_empty(),
+            "relative position target isn't finite",
+        );
+        let stop = self.config.write_error_strategy == WriteErrorStrategy::Stop;
+        let Some(target) = target.filter(|_| errors.is_empty() || !stop) else {
+            return Err(MotorGroupError::new(errors));
+        };
+
+        if let Err(error) = self.set_position_target(target, velocity) {
+            errors.extend(error.errors);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Returns the indices of motors whose position reference is inconsistent
+    /// with the rest of the group.
+    ///
+    /// This happens when [`MotorGroup::reset_position`] or
+    /// [`MotorGroup::set_position`] reaches some motors but not others. Those
+    /// motors are left out of [`MotorGroup::position`] until they are re-synced
+    /// with [`MotorGroup::rezero`] or a later reset succeeds on every motor.
+    pub fn stale_motors(&self) -> Vec<usize> {
+        self.meta
+            .iter()
+            .enumerate()
+            .filter(|(_, meta)| meta.reference_stale)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns `true` if any motor's position reference is inconsistent with
+    /// the rest of the group.
+    ///
+    /// See [`MotorGroup::stale_motors`].
+    pub fn has_inconsistent_reference(&self) -> bool {
+        self.meta.iter().any(|meta| meta.reference_stale)
+    }
+
+    /// Runs a write that changes the position reference on every motor,
+    /// keeping track of which motors received it.
+    pub(crate) fn write_reference(
+        &mut self,
+        mut write: impl FnMut(&mut Motor) -> Result<(), PortError>,
+    ) -> Result<(), MotorGroupError> {
+        let mut succeeded = alloc::vec![false; self.motors.as_ref().len()];
+        let result = self.write_each(WriteKind::Position, |index, motor| {
+            write(motor)?;
+            succeeded[index] = true;
+            Ok(())
+        });
+        record_reference_writes(&mut self.meta, &succeeded);
+        self.read_cache.invalidate(Change::Reference);
+        result
+    }
+
+    /// Re-syncs the position reference of the motors listed by
+    /// [`MotorGroup::stale_motors`] with the rest of the group.
+    ///
+    /// The average position of the healthy motors is read, then written to
+    /// each stale motor with [`Motor::set_position`]. Healthy motors aren't
+    /// written to. Motors that accept the write are no longer stale.
+    ///
+    /// This does nothing if no motors are stale.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if no healthy motor could be
+    ///   read, or if a stale motor couldn't be written to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if motor_group.reset_position().is_err() && motor_group.has_inconsistent_reference() {
+    ///         _ = motor_group.rezero();
+    ///     }
+    /// }
+    /// ```
+    pub fn rezero(&mut self) -> Result<(), MotorGroupError> {
+        if !self.has_inconsistent_reference() {
+            return Ok(());
+        }
+        let position = match self.position() {
+            Ok(position) => position,
+            Err(MotorGroupError {
+                result: Some(position),
+                ..
+            }) => position,
+            Err(error) => return Err(MotorGroupError::new(error.errors)),
+        };
+
+        let mut stale: Vec<bool> = self.meta.iter().map(|meta| meta.reference_stale).collect();
+        let result = self.write_each(WriteKind::Position, |index, motor| {
+            if stale[index] {
+                motor.set_position(position)?;
+                stale[index] = false;
+            }
+            Ok(())
+        });
+        for (meta, stale) in self.meta.iter_mut().zip(stale) {
+            meta.reference_stale = stale;
+        }
+        self.read_cache.invalidate(Change::Reference);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{math::Angle, smart::PortError};
+
+    use super::{average_position, record_reference_writes, relative_target};
+    use crate::{GroupPosition, MotorGroupError, meta::MotorMeta};
+
+    fn stale(meta: &[MotorMeta]) -> Vec<bool> {
+        meta.iter().map(|meta| meta.reference_stale).collect()
+    }
+
+    #[test]
+    fn partial_reset_sequence() {
+        let mut meta = vec![MotorMeta::default(); 3];
+
+        // The second motor misses a reset
+        record_reference_writes(&mut meta, &[true, false, true]);
+        assert_eq!(stale(&meta), [false, true, false]);
+
+        // A reset that reaches nobody changes nothing
+        record_reference_writes(&mut meta, &[false, false, false]);
+        assert_eq!(stale(&meta), [false, true, false]);
+
+        // Now the third motor misses one, and the second catches up
+        record_reference_writes(&mut meta, &[true, true, false]);
+        assert_eq!(stale(&meta), [false, false, true]);
+
+        // A complete reset clears everything
+        record_reference_writes(&mut meta, &[true, true, true]);
+        assert_eq!(stale(&meta), [false, false, false]);
+    }
+
+    #[test]
+    fn stale_motors_are_excluded_from_position() {
+        let mut meta = vec![MotorMeta::default(); 3];
+        record_reference_writes(&mut meta, &[true, false, true]);
+
+        let readings = [
+            Ok(Angle::from_degrees(10.0)),
+            Ok(Angle::from_degrees(5000.0)),
+            Ok(Angle::from_degrees(20.0)),
+        ];
+        let position = average_position(readings.into_iter().enumerate(), &meta).unwrap();
+        assert_eq!(position.degrees(), 15.0);
+
+        // Errors are still reported alongside the partial result
+        let disconnected = PortError::Disconnected { port: 3 };
+        let readings = [
+            Ok(Angle::from_degrees(10.0)),
+            Ok(Angle::from_degrees(5000.0)),
+            Err(disconnected),
+        ];
+        let error = average_position(readings.into_iter().enumerate(), &meta).unwrap_err();
+        assert_eq!(error.errors, vec![disconnected]);
+        assert_eq!(error.result().unwrap().degrees(), 10.0);
+    }
+
+    fn degrees(degrees: f64) -> GroupPosition {
+        GroupPosition::from_degrees(degrees).unwrap()
+    }
+
+    /// Asserts that `angle` is `degrees`, up to rounding from the conversion
+    /// through radians.
+    fn assert_degrees(angle: Option<Angle>, degrees: f64) {
+        assert!((angle.unwrap().as_degrees() - degrees).abs() < 1e-9);
+    }
+
+    #[test]
+    fn relative_target_is_offset_from_average() {
+        let delta = Angle::from_degrees(90.0);
+        let (target, errors) = relative_target(Ok(degrees(360.0)), delta);
+        assert_degrees(target, 450.0);
+        assert!(errors.is_empty());
+
+        // Moving backwards from a negative position
+        let (target, _) = relative_target(Ok(degrees(-30.0)), -delta);
+        assert_degrees(target, -120.0);
+
+        // The motors that could be read still give a baseline
+        let disconnected = PortError::Disconnected { port: 1 };
+        let position = Err(MotorGroupError::with_result(
+            vec![disconnected],
+            degrees(10.0),
+        ));
+        let (target, errors) = relative_target(position, delta);
+        assert_degrees(target, 100.0);
+        assert_eq!(errors, vec![disconnected]);
+
+        let position = Err(MotorGroupError::with_empty_result(vec![disconnected]));
+        assert_eq!(relative_target(position, delta), (None, vec![disconnected]));
+
+        // A target that isn't finite can't be commanded
+        let infinite = Angle::from_degrees(f64::INFINITY);
+        assert_eq!(relative_target(Ok(degrees(0.0)), infinite), (None, vec![]));
+    }
+}
@@ -0,0 +1,275 @@
+use core::fmt::{self, Display, Write};
+
+use vexide::smart::{PortError, SmartDevice, motor::Motor};
+
+use crate::{GetterResult, GroupPosition, MotorGroup, readings};
+
+/// Writes `value` if it's set, or `not set` otherwise.
+fn write_option<T: fmt::Debug>(out: &mut impl Write, value: Option<T>) -> fmt::Result {
+    match value {
+        Some(value) => write!(out, "{value:?}"),
+        None => out.write_str("not set"),
+    }
+}
+
+/// Writes a single motor's reading followed by `unit`, or `error` if it
+/// couldn't be read.
+fn write_reading<T: Display>(
+    out: &mut impl Write,
+    reading: Result<T, PortError>,
+    unit: &str,
+) -> fmt::Result {
+    match reading {
+        Ok(value) => write!(out, "{value:.2}{unit}"),
+        Err(_) => out.write_str("error"),
+    }
+}
+
+/// Writes a group getter's result followed by `unit`, marking partial results
+/// with how many motors failed.
+fn write_result(out: &mut impl Write, result: GetterResult<f64>, unit: &str) -> fmt::Result {
+    match result {
+        Ok(value) => write!(out, "{value:.2}{unit}"),
+        Err(error) => {
+            match error.result {
+                Some(value) => write!(out, "{value:.2}{unit}")?,
+                None => out.write_str("error")?,
+            }
+            write!(out, " ({} failed)", error.errors.len())
+        }
+    }
+}
+
+/// Writes a group flag getter's result the same way as [`write_result`].
+fn write_flag(out: &mut impl Write, result: GetterResult<bool>) -> fmt::Result {
+    match result {
+        Ok(value) => write!(out, "{value}"),
+        Err(error) => {
+            match error.result {
+                Some(value) => write!(out, "{value}")?,
+                None => out.write_str("error")?,
+            }
+            write!(out, " ({} failed)", error.errors.len())
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Writes a human-readable report of everything about the group to `out`.
+    ///
+    /// The report covers the group's configuration, its last command, its
+    /// averaged readings and fault flags, and then each motor's port, label,
+    /// type, gearset, direction, and readings. It's meant for printing to the
+    /// serial console while debugging, so every value is read fresh and a
+    /// read that fails is shown as `error` rather than stopping the report.
+    /// Group values that were only read from some motors are followed by how
+    /// many motors failed.
+    ///
+    /// The layout is one `key: value` per line, indented by section, and is
+    /// kept stable between releases as much as possible, but it isn't meant
+    /// to be parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if writing to `out` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let mut report = String::new();
+    ///     motor_group.diagnostic_report(&mut report).unwrap();
+    ///     println!("{report}");
+    /// }
+    /// ```
+    pub fn diagnostic_report(&self, out: &mut impl Write) -> fmt::Result {
+        let motors = self.motors.as_ref();
+        let active = self.meta.iter().filter(|meta| meta.is_active()).count();
+        writeln!(out, "Motor group: {} motors, {active} active", motors.len())?;
+
+        let config = &self.config;
+        writeln!(out, "Config:")?;
+        writeln!(out, "  write errors: {:?}", config.write_error_strategy)?;
+        writeln!(
+            out,
+            "  predicate errors: {:?}",
+            config.predicate_error_strategy
+        )?;
+        writeln!(
+            out,
+            "  current limit policy: {:?}",
+            config.current_limit_policy
+        )?;
+        writeln!(
+            out,
+            "  max current: V5 {}A, EXP {}A",
+            config.max_current_table.v5, config.max_current_table.exp
+        )?;
+        writeln!(out, "  validation: {:?}", config.validation)?;
+        writeln!(
+            out,
+            "  count disabled in average: {}",
+            config.count_disabled_in_average
+        )?;
+        writeln!(out, "  external ratio: {}", config.external_ratio)?;
+        out.write_str("  stop on drop: ")?;
+        write_option(out, config.stop_on_drop)?;
+        out.write_str("\n  position fallback: ")?;
+        write_option(out, config.position_fallback)?;
+        write!(out, "\n  tick interval: {:?}", config.tick_interval)?;
+        out.write_str("\n  gearset: ")?;
+        write_option(out, config.gearset)?;
+        out.write_str("\n  direction: ")?;
+        write_option(out, config.direction)?;
+        out.write_str("\n  voltage limit: ")?;
+        write_option(out, config.voltage_limit)?;
+        out.write_str("\n  current limit: ")?;
+        write_option(out, config.current_limit)?;
+        out.write_str("\n  total current limit: ")?;
+        write_option(out, config.total_current_limit)?;
+        out.write_str("\nLast command: ")?;
+        write_option(out, self.last_command)?;
+
+        out.write_str("\nGroup:\n  velocity: ")?;
+        write_result(out, self.velocity(), " RPM")?;
+        out.write_str("\n  position: ")?;
+        write_result(
+            out,
+            readings::map_result(self.group_position(), GroupPosition::degrees),
+            "°",
+        )?;
+        out.write_str("\n  current: ")?;
+        write_result(out, self.current(), " A")?;
+        out.write_str("\n  temperature: ")?;
+        write_result(out, self.temperature(), " °C")?;
+        out.write_str("\n  over temperature: ")?;
+        write_flag(out, self.is_over_temperature())?;
+        out.write_str("\n  over current: ")?;
+        write_flag(out, self.is_over_current())?;
+        out.write_str("\n  driver fault: ")?;
+        write_flag(out, self.is_driver_fault())?;
+        out.write_str("\n  driver over current: ")?;
+        write_flag(out, self.is_driver_over_current())?;
+        writeln!(out, "\n  stale motors: {:?}", self.stale_motors())?;
+
+        writeln!(out, "Motors:")?;
+        for (index, (motor, meta)) in motors.iter().zip(&self.meta).enumerate() {
+            write!(out, "  [{index}] port {}", motor.port_number())?;
+            if let Some(label) = &meta.label {
+                write!(out, " \"{label}\"")?;
+            }
+            write!(out, " {:?}", motor.motor_type())?;
+            if !meta.enabled {
+                out.write_str(" (disabled)")?;
+            }
+            if meta.checked_out {
+                out.write_str(" (checked out)")?;
+            }
+            if meta.scale != 1.0 {
+                write!(out, " (scale {})", meta.scale)?;
+            }
+            out.write_str("\n      gearset: ")?;
+            match motor.gearset() {
+                Ok(gearset) => write!(out, "{gearset:?}")?,
+                Err(_) => out.write_str("error")?,
+            }
+            out.write_str(", direction: ")?;
+            match (motor.direction(), meta.direction) {
+                // A motor facing the other way than the group set it to is
+                // worth calling out, unlike one that's meant to be reversed
+                (Ok(direction), Some(intent)) if direction != intent => {
+                    write!(out, "{direction:?} (expected {intent:?})")?;
+                }
+                (Ok(direction), _) => write!(out, "{direction:?}")?,
+                (Err(_), _) => out.write_str("error")?,
+            }
+            out.write_str("\n      velocity: ")?;
+            write_reading(out, motor.velocity(), " RPM")?;
+            out.write_str(", position: ")?;
+            write_reading(
+                out,
+                motor.position().map(|position| position.as_degrees()),
+                "°",
+            )?;
+            out.write_str(", current: ")?;
+            write_reading(out, motor.current(), " A")?;
+            out.write_str(", temperature: ")?;
+            write_reading(out, motor.temperature(), " °C")?;
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::motor::BrakeMode};
+
+    use crate::{MotorGroup, tests::mock_motors};
+
+    #[test]
+    fn report_matches_snapshot() {
+        let mut group = MotorGroup::new(mock_motors(3, Gearset::Green));
+        group.set_label(0, "front left").set_scale(1, 0.9);
+        group.set_enabled(2, false);
+        group.stop_on_drop(Some(BrakeMode::Coast));
+        _ = group.set_voltage_limit(10.0);
+        _ = group.set_voltage(6.0);
+
+        let mut report = String::new();
+        group.diagnostic_report(&mut report).unwrap();
+        // No mock motor can be read, but the report still covers every field
+        assert_eq!(
+            report,
+            "\
+Motor group: 3 motors, 2 active
+Config:
+  write errors: Ignore
+  predicate errors: Unsatisfied
+  current limit policy: Clamp
+  max current: V5 2.5A, EXP 1.25A
+  validation: Lenient
+  count disabled in average: false
+  external ratio: 1
+  stop on drop: Coast
+  position fallback: not set
+  tick interval: 5ms
+  gearset: not set
+  direction: not set
+  voltage limit: 10.0
+  current limit: not set
+  total current limit: not set
+Last command: Voltage(6.0)
+Group:
+  velocity: error (2 failed)
+  position: error (2 failed)
+  current: error (2 failed)
+  temperature: error (2 failed)
+  over temperature: error (2 failed)
+  over current: error (2 failed)
+  driver fault: error (2 failed)
+  driver over current: error (2 failed)
+  stale motors: []
+Motors:
+  [0] port 1 \"front left\" V5
+      gearset: error, direction: error
+      velocity: error, position: error, current: error, temperature: error
+  [1] port 2 V5 (scale 0.9)
+      gearset: error, direction: error
+      velocity: error, position: error, current: error, temperature: error
+  [2] port 3 V5 (disabled)
+      gearset: error, direction: error
+      velocity: error, position: error, current: error, temperature: error
+"
+        );
+    }
+}
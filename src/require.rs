@@ -0,0 +1,179 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError, tick::tick_loop};
+
+/// Error returned by [`MotorGroup::require_velocity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequireVelocityError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The group didn't reach the velocity band before the deadline.
+    Timeout {
+        /// The deadline that was missed.
+        deadline: Duration,
+        /// The group's last velocity reading in RPM, or `None` if no motor
+        /// could be read.
+        velocity: Option<f64>,
+    },
+}
+
+impl From<PortError> for RequireVelocityError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for RequireVelocityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::Timeout {
+                deadline,
+                velocity: Some(velocity),
+            } => write!(
+                f,
+                "velocity was {velocity} RPM after {deadline:?}, outside the required band"
+            ),
+            Self::Timeout {
+                deadline,
+                velocity: None,
+            } => write!(f, "velocity couldn't be read within {deadline:?}"),
+        }
+    }
+}
+
+impl core::error::Error for RequireVelocityError {}
+
+/// Returns the velocity from a group reading, including a partial one, and
+/// whether it's within `tolerance` of `target_rpm`.
+pub(crate) fn check_velocity(
+    reading: &GetterResult<f64>,
+    target_rpm: f64,
+    tolerance: f64,
+) -> (Option<f64>, bool) {
+    let velocity = match reading {
+        Ok(velocity) => Some(*velocity),
+        Err(error) => error.result,
+    };
+    let in_band = velocity.is_some_and(|velocity| (velocity - target_rpm).abs() <= tolerance);
+    (velocity, in_band)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Waits until the group's average velocity is within `tolerance` RPM of
+    /// `target_rpm`, failing if that takes longer than `deadline`.
+    ///
+    /// This doesn't command anything; set the velocity first. The velocity is
+    /// read every tick (see [`MotorGroup::tick_interval`], 5ms by default),
+    /// using the average of the motors that could be read. Each tick reads
+    /// every motor's velocity once.
+    ///
+    /// Unlike a check such as [`MotorGroup::all_at_target`], which reports
+    /// whether the group is there right now as a `bool`, a missed deadline is
+    /// an error. That makes this suited to fail-fast autonomous routines,
+    /// where a flywheel that never spins up should abort the routine with `?`
+    /// instead of being checked by hand.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if the band isn't reached by
+    ///   the deadline. It contains the errors of the last read, if any,
+    ///   followed by a [`RequireVelocityError::Timeout`] error with the last
+    ///   velocity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// async fn shoot(flywheel: &mut MotorGroup) -> Result<(), MotorGroupError<RequireVelocityError>> {
+    ///     _ = flywheel.set_velocity(550);
+    ///     flywheel
+    ///         .require_velocity(550.0, 15.0, Duration::from_secs(2))
+    ///         .await?;
+    ///     // ...feed the game piece...
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn require_velocity(
+        &self,
+        target_rpm: f64,
+        tolerance: f64,
+        deadline: Duration,
+    ) -> Result<(), MotorGroupError<RequireVelocityError>> {
+        tick_loop(self.config.tick_interval, Some(deadline), |elapsed| {
+            let reading = self.velocity();
+            let (velocity, in_band) = check_velocity(&reading, target_rpm, tolerance);
+            if in_band {
+                return ControlFlow::Break(Ok(()));
+            }
+            if elapsed >= deadline {
+                let mut errors: Vec<RequireVelocityError> = match reading {
+                    Ok(_) => Vec::new(),
+                    Err(error) => error.errors.into_iter().map(Into::into).collect(),
+                };
+                errors.push(RequireVelocityError::Timeout { deadline, velocity });
+                return ControlFlow::Break(Err(MotorGroupError::new(errors)));
+            }
+            ControlFlow::Continue(())
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{RequireVelocityError, check_velocity};
+    use crate::{MotorGroup, MotorGroupError, tests::mock_motors};
+
+    #[test]
+    fn band_uses_partial_readings() {
+        assert_eq!(check_velocity(&Ok(540.0), 550.0, 15.0), (Some(540.0), true));
+        assert_eq!(
+            check_velocity(&Ok(-540.0), 550.0, 15.0),
+            (Some(-540.0), false)
+        );
+
+        let disconnected = PortError::Disconnected { port: 1 };
+        let reading = Err(MotorGroupError::with_result(vec![disconnected], 560.0));
+        assert_eq!(check_velocity(&reading, 550.0, 15.0), (Some(560.0), true));
+        let reading = Err(MotorGroupError::with_empty_result(vec![disconnected]));
+        assert_eq!(check_velocity(&reading, 550.0, 15.0), (None, false));
+    }
+
+    #[test]
+    fn missed_deadline_is_an_error() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Blue));
+        // The mock motor never spins up
+        _ = group.set_velocity(550);
+
+        let deadline = Duration::from_millis(20);
+        let start = Instant::now();
+        let error = vexide::runtime::block_on(async move {
+            group
+                .require_velocity(550.0, 15.0, deadline)
+                .await
+                .unwrap_err()
+        });
+        assert!(start.elapsed() >= deadline);
+        assert!(matches!(
+            error.errors[..],
+            [
+                RequireVelocityError::Port { .. },
+                RequireVelocityError::Timeout { velocity: None, .. }
+            ]
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+use vexide::smart::motor::Motor;
+
+use crate::{GroupConfig, MotorGroup, MotorGroupError, SharedMotors, WriteKind};
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Puts the group back the way it was when it was created, and writes the
+    /// motors' default limits, for a clean re-initialization of a subsystem.
+    ///
+    /// In software, this resets:
+    ///
+    /// - every setting in [`GroupConfig`] except the gearset and direction to
+    ///   [`GroupConfig::DEFAULT`], which clears the position limits, the
+    ///   position fallback, the read cache and the external gear ratio, and
+    ///   sets the strategies and the targeting mode back to their defaults;
+    /// - the custom write policy (see [`MotorGroup::set_custom_write_policy`]);
+    /// - each motor's scale to `1.0` (see [`MotorGroup::set_scale`]), and
+    ///   re-enables every disabled motor (see [`MotorGroup::set_enabled`]);
+    /// - the software zeros (see [`MotorGroup::set_zero_here`] and
+    ///   [`MotorGroup::set_named_zero`]) and the presets (see
+    ///   [`MotorGroup::add_preset`]);
+    /// - with the `diagnostics` feature, the samples behind
+    ///   [`MotorGroup::averaged_current`], [`MotorGroup::rms_current`],
+    ///   [`MotorGroup::efficiency_trend`] and [`MotorGroup::time_to_cutout`].
+    ///
+    /// In hardware, every motor is given:
+    ///
+    /// - a voltage limit of its [maximum voltage](Motor::max_voltage);
+    /// - a current limit of its hardware maximum in
+    ///   [`MaxCurrentTable::DEFAULT`](crate::MaxCurrentTable::DEFAULT). VEXos
+    ///   lowers this on robots with more than eight 11W motors, whatever is
+    ///   written.
+    ///
+    /// Afterwards, [`MotorGroup::current_config`] has no voltage or current
+    /// limit, as on a new group.
+    ///
+    /// The gearset and the motors' directions are left alone, since they
+    /// describe how the mechanism is built, and so are the labels, the last
+    /// command, the position reference and the wear records. The
+    /// [hard voltage cap](MotorGroup::set_hard_voltage_cap) is kept too,
+    /// since it can only be raised on purpose. The velocity PID constants
+    /// (with the `vexide-unstable` feature) are forgotten but not written,
+    /// because vexide doesn't expose the motors' defaults. Checked out motors
+    /// are written to like any other.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port. The software state is reset
+    ///   regardless.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///     intake.set_scale(1, 0.9);
+    ///     _ = intake.set_voltage_limit(8.0);
+    ///
+    ///     // Start the next routine from a clean slate
+    ///     _ = intake.reset_all();
+    /// }
+    /// ```
+    pub fn reset_all(&mut self) -> Result<(), MotorGroupError> {
+        let (gearset, direction) = (self.config.gearset, self.config.direction);
+        // With no hardware settings and lenient validation, this only
+        // changes the software settings, so it can't fail
+        _ = self.apply_config(&GroupConfig::DEFAULT);
+        self.config = GroupConfig {
+            gearset,
+            direction,
+            ..GroupConfig::DEFAULT
+        };
+        self.write_policy = None;
+        self.zeros = crate::zero::Zeros::default();
+        self.presets.clear();
+        for meta in &mut self.meta {
+            meta.scale = 1.0;
+            meta.enabled = true;
+        }
+        #[cfg(feature = "diagnostics")]
+        {
+            self.efficiency_history = crate::diagnostics::SampleHistory::default();
+            self.current_history = crate::diagnostics::SampleHistory::default();
+            self.average_current_history = crate::diagnostics::SampleHistory::default();
+            self.thermal_history = crate::thermal::ThermalHistory::default();
+        }
+        self.read_cache
+            .invalidate(crate::read_cache::Change::Aggregation);
+
+        let table = self.config.max_current_table;
+        self.write_each(WriteKind::Configuration, |_, motor| {
+            motor.set_voltage_limit(motor.max_voltage())?;
+            motor.set_current_limit(table.max_current(motor.motor_type()))
+        })
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::reset_all`].
+    pub fn reset_all(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().reset_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{math::Angle, prelude::*, smart::PortError};
+
+    use crate::{
+        GroupConfig, OutputModifiers, PositionFallback, TargetingMode, WriteErrorStrategy,
+        tests::two_motor_group,
+    };
+
+    #[test]
+    fn modifiers_are_cleared() {
+        let mut group = two_motor_group();
+        group
+            .set_targeting_mode(TargetingMode::RelativeDelta)
+            .set_scale(0, 0.5);
+        _ = group.shift_ratio(2.0);
+        group.set_position_limits(Angle::ZERO, Angle::from_degrees(90.0));
+        group.set_enabled(1, false);
+        group.set_custom_write_policy(|_, _, _| crate::PolicyDecision::Continue);
+        group.position_fallback(Some(PositionFallback {
+            failures_to_enter: 3,
+            gain: 0.01,
+            max_voltage: 6.0,
+        }));
+        _ = group.set_gearset(Gearset::Red);
+        _ = group.set_voltage_limit(6.0);
+        _ = group.set_current_limit(1.0);
+        group.zeros.here = crate::GroupPosition::from_degrees(90.0);
+        _ = group.add_preset("stow", Angle::ZERO, 100);
+
+        // Every write to the mock motors fails, but the software is still reset
+        let error = group.reset_all().unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                PortError::Disconnected { port: 1 },
+                PortError::Disconnected { port: 2 }
+            ]
+        );
+        assert_eq!(
+            group.modifiers(),
+            OutputModifiers {
+                position_limits: None,
+                targeting_mode: TargetingMode::Absolute,
+                scales: vec![1.0, 1.0],
+                hard_voltage_cap: None,
+                enabled: vec![true, true],
+            }
+        );
+        assert!(group.write_policy.is_none());
+        assert_eq!(group.zeros, crate::zero::Zeros::default());
+        assert!(group.presets().is_empty());
+        assert_eq!(
+            group.current_config(),
+            GroupConfig {
+                gearset: Some(Gearset::Red),
+                ..GroupConfig::DEFAULT
+            }
+        );
+        assert_eq!(
+            group.current_config().write_error_strategy,
+            WriteErrorStrategy::Ignore
+        );
+    }
+
+    #[test]
+    fn hard_voltage_cap_is_kept() {
+        let mut group = two_motor_group();
+        group.set_hard_voltage_cap(6.0);
+        _ = group.reset_all();
+        assert_eq!(group.hard_voltage_cap(), Some(6.0));
+    }
+}
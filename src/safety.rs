@@ -0,0 +1,125 @@
+use vexide::smart::motor::{Motor, MotorControl};
+
+use crate::{MotorGroup, hard_cap};
+
+/// Gives the motors of `group` the command `end` from a path with no one to
+/// report errors to, such as a `Drop` impl or a cancelled future.
+///
+/// This never panics and never fails: every error is ignored. Normally the
+/// command goes through [`MotorGroup::set_target`], so the group records it
+/// like any other. If the thread is already panicking, where a second panic
+/// would abort the program, or if `include_disabled` is set, `end` is instead
+/// written straight to each motor with only the hard voltage cap applied,
+/// skipping the group's bookkeeping. Disabled motors are only written to with
+/// `include_disabled`.
+pub(crate) fn best_effort_stop<M: AsRef<[Motor]> + AsMut<[Motor]>>(
+    group: &mut MotorGroup<M>,
+    end: MotorControl,
+    include_disabled: bool,
+) {
+    if !include_disabled && !std::thread::panicking() {
+        _ = group.set_target(end);
+        return;
+    }
+    let cap = group.hard_voltage_cap;
+    let gearset = group.config.gearset;
+    for (motor, meta) in group.motors.as_mut().iter_mut().zip(&group.meta) {
+        if include_disabled || meta.is_active() {
+            _ = hard_cap::capped_target(motor, end, cap, gearset)
+                .and_then(|end| motor.set_target(end));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use vexide::smart::motor::{BrakeMode, MotorControl};
+
+    use super::best_effort_stop;
+    use crate::{SharedMotors, tests::two_motor_group};
+
+    /// Panics with "boom" while `guard` is alive, and returns the panic's
+    /// message once it has been caught.
+    fn panic_while_held<T>(guard: T) -> String {
+        let payload = panic::catch_unwind(AssertUnwindSafe(move || {
+            let _guard = guard;
+            panic!("boom");
+        }))
+        .unwrap_err();
+        payload.downcast_ref::<&str>().unwrap().to_string()
+    }
+
+    #[test]
+    fn failed_writes_are_swallowed() {
+        let mut group = two_motor_group();
+
+        // Every write to the mock motors fails
+        best_effort_stop(&mut group, MotorControl::Brake(BrakeMode::Brake), false);
+        assert_eq!(group.command_generation, 1);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Brake))
+        );
+
+        best_effort_stop(&mut group, MotorControl::Velocity(100), true);
+        assert_eq!(group.command_generation, 1);
+    }
+
+    #[test]
+    fn dropping_a_group_while_panicking_does_not_abort() {
+        let mut group = two_motor_group();
+        group.set_enabled(0, false);
+        group.stop_on_drop(Some(BrakeMode::Hold));
+
+        // A second panic in the drop would abort the test process instead of
+        // returning the first one
+        assert_eq!(panic_while_held(group), "boom");
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn guards_dropped_while_panicking_skip_bookkeeping() {
+        let mut group = two_motor_group();
+        _ = group.set_voltage(6.0);
+        let message = panic_while_held(crate::load::StopOnCancel {
+            group: &mut group,
+            armed: true,
+        });
+        assert_eq!(message, "boom");
+
+        // The brake was written to the motors directly
+        assert_eq!(group.command_generation, 1);
+        assert_eq!(group.last_command, Some(MotorControl::Voltage(6.0)));
+
+        // Outside of a panic, the brake goes through the group as usual
+        drop(crate::load::StopOnCancel {
+            group: &mut group,
+            armed: true,
+        });
+        assert_eq!(group.command_generation, 2);
+    }
+
+    #[test]
+    fn handles_dropped_while_the_group_is_locked_do_not_panic() {
+        let mut group = two_motor_group();
+        group.stop_on_drop(Some(BrakeMode::Brake));
+        let shared = SharedMotors::new(group);
+        let handle = shared.clone();
+        let weak = shared.downgrade();
+
+        {
+            let mut locked = shared.lock();
+            _ = locked.set_voltage(6.0);
+            // Dropping another handle while the group is borrowed neither
+            // touches the group nor panics
+            drop(handle);
+            assert_eq!(locked.command_generation, 1);
+        }
+
+        // The last handle drops the group, which brakes without a borrow
+        drop(shared);
+        assert!(weak.upgrade().is_none());
+    }
+}
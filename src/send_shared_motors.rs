@@ -0,0 +1,158 @@
+use alloc::{sync::Arc, vec::Vec};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use vexide::smart::motor::Motor;
+
+use crate::MotorGroup;
+
+/// Motors that can be cloned and shared between threads.
+///
+/// This is the `Send` and `Sync` counterpart of [`SharedMotors`]: clones
+/// share the same inner motor group through an `Arc<Mutex<MotorGroup>>`, so
+/// they can be moved into anything that requires `Send`, such as a
+/// `std::thread` on the host with the `mock` feature. On the brain, where
+/// every vexide task runs on the same thread, prefer [`SharedMotors`], which
+/// doesn't need a lock and comes with the last known values cache and
+/// shadows. See [Tasks and threads](crate#tasks-and-threads).
+///
+/// Every method of the group is reached through [`SendSharedMotors::lock`].
+///
+/// ```
+/// # use vexide::{prelude::*, smart::SmartPort};
+/// # use vexide_motorgroup::*;
+/// # let port_1 = unsafe { SmartPort::new(1) };
+/// # let port_2 = unsafe { SmartPort::new(2) };
+/// let drive = SendSharedMotors::from_motors(vec![
+///     Motor::new(port_1, Gearset::Green, Direction::Forward),
+///     Motor::new(port_2, Gearset::Green, Direction::Forward),
+/// ]);
+/// let handle = drive.clone();
+///
+/// std::thread::spawn(move || {
+///     handle.lock().write_error_strategy(WriteErrorStrategy::Stop);
+/// })
+/// .join()
+/// .unwrap();
+/// assert_eq!(
+///     drive.lock().current_config().write_error_strategy,
+///     WriteErrorStrategy::Stop,
+/// );
+/// ```
+///
+/// [`SharedMotors`] itself can't be sent to another thread:
+///
+/// ```compile_fail,E0277
+/// # use vexide::{prelude::*, smart::SmartPort};
+/// # use vexide_motorgroup::*;
+/// # let port_1 = unsafe { SmartPort::new(1) };
+/// let drive = SharedMotors::from_motors(vec![
+///     Motor::new(port_1, Gearset::Green, Direction::Forward),
+/// ]);
+/// std::thread::spawn(move || drop(drive));
+/// ```
+///
+/// [`SharedMotors`]: crate::SharedMotors
+#[derive(Debug)]
+pub struct SendSharedMotors<M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>>(
+    Arc<Mutex<MotorGroup<M>>>,
+);
+
+// Not derived, since that would require `M: Clone` even though only the `Arc`
+// is cloned.
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Clone for SendSharedMotors<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SendSharedMotors<M> {
+    /// Create a new SendSharedMotors from a MotorGroup.
+    pub fn new(motors: MotorGroup<M>) -> Self {
+        Self(Arc::new(Mutex::new(motors)))
+    }
+
+    /// Create a new SendSharedMotors from motors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no motors in the vector.
+    pub fn from_motors(motors: M) -> Self {
+        Self::new(MotorGroup::new(motors))
+    }
+
+    /// Locks the motor group until the returned guard is dropped, blocking
+    /// the current thread until no other handle holds the lock.
+    ///
+    /// A thread that panicked while holding the lock doesn't poison the
+    /// group: the motors are still there to command, so the lock is taken
+    /// anyway.
+    ///
+    /// Locking again from the same thread while the guard is held
+    /// deadlocks, so drop the guard before the next call, and before
+    /// yielding to other vexide tasks.
+    pub fn lock(&self) -> MutexGuard<'_, MotorGroup<M>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the motor group if no other handle holds the lock, or returns
+    /// `None` without blocking.
+    ///
+    /// As with [`SendSharedMotors::lock`], a poisoned lock is taken anyway.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, MotorGroup<M>>> {
+        match self.0.try_lock() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(error)) => Some(error.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::SendSharedMotors;
+    use crate::{PolicyDecision, tests::two_motor_group};
+
+    #[test]
+    fn clones_share_the_group_across_threads() {
+        let motors = SendSharedMotors::new(two_motor_group());
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let motors = motors.clone();
+                thread::spawn(move || _ = motors.lock().set_voltage(6.0))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(motors.lock().command_generation(), 2);
+    }
+
+    #[test]
+    fn held_locks_are_not_waited_for() {
+        let motors = SendSharedMotors::new(two_motor_group());
+        let guard = motors.lock();
+        assert!(motors.clone().try_lock().is_none());
+        drop(guard);
+        assert!(motors.try_lock().is_some());
+    }
+
+    #[test]
+    fn panics_while_locked_do_not_poison_the_group() {
+        let motors = SendSharedMotors::new(two_motor_group());
+        let handle = motors.clone();
+        thread::spawn(move || {
+            let mut group = handle.lock();
+            group.set_custom_write_policy(|_, _, _| PolicyDecision::StopRemaining);
+            panic!("the lock is held");
+        })
+        .join()
+        .unwrap_err();
+
+        // Only the first motor is written to before the policy stops
+        let error = motors.lock().set_voltage(6.0).unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        assert!(motors.try_lock().is_some());
+    }
+}
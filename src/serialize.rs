@@ -0,0 +1,90 @@
+//! `serde::Serialize` implementations for the `serde` feature.
+//!
+//! These are written out by hand so that the feature doesn't pull in
+//! `serde_derive` and its proc macro dependencies, which noticeably slow down
+//! builds for the brain. Enums serialize as unit variants named like their
+//! Rust variants, and structs with their Rust field names.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::{
+    ConfigSnapshot, CurrentLimitPolicy, OutputModifiers, PredicateErrorStrategy, TargetingMode,
+    WriteErrorStrategy,
+};
+
+impl Serialize for WriteErrorStrategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Ignore => serializer.serialize_unit_variant("WriteErrorStrategy", 0, "Ignore"),
+            Self::Stop => serializer.serialize_unit_variant("WriteErrorStrategy", 1, "Stop"),
+            Self::Custom => serializer.serialize_unit_variant("WriteErrorStrategy", 2, "Custom"),
+        }
+    }
+}
+
+impl Serialize for PredicateErrorStrategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unsatisfied => {
+                serializer.serialize_unit_variant("PredicateErrorStrategy", 0, "Unsatisfied")
+            }
+            Self::Ignore => {
+                serializer.serialize_unit_variant("PredicateErrorStrategy", 1, "Ignore")
+            }
+        }
+    }
+}
+
+impl Serialize for CurrentLimitPolicy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Clamp => serializer.serialize_unit_variant("CurrentLimitPolicy", 0, "Clamp"),
+            Self::Error => serializer.serialize_unit_variant("CurrentLimitPolicy", 1, "Error"),
+        }
+    }
+}
+
+impl Serialize for ConfigSnapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ConfigSnapshot", 12)?;
+        state.serialize_field("motor_count", &self.motor_count)?;
+        state.serialize_field("write_error_strategy", &self.write_error_strategy)?;
+        state.serialize_field("predicate_error_strategy", &self.predicate_error_strategy)?;
+        state.serialize_field("count_disabled_in_average", &self.count_disabled_in_average)?;
+        state.serialize_field("scales", &self.scales)?;
+        state.serialize_field("enabled", &self.enabled)?;
+        state.serialize_field("labels", &self.labels)?;
+        state.serialize_field("external_ratio", &self.external_ratio)?;
+        state.serialize_field("voltage_limit", &self.voltage_limit)?;
+        state.serialize_field("current_limit", &self.current_limit)?;
+        state.serialize_field("total_current_limit", &self.total_current_limit)?;
+        state.serialize_field("current_limit_policy", &self.current_limit_policy)?;
+        state.end()
+    }
+}
+
+impl Serialize for TargetingMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Absolute => serializer.serialize_unit_variant("TargetingMode", 0, "Absolute"),
+            Self::RelativeDelta => {
+                serializer.serialize_unit_variant("TargetingMode", 1, "RelativeDelta")
+            }
+        }
+    }
+}
+
+impl Serialize for OutputModifiers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let position_limits = self
+            .position_limits
+            .map(|(min, max)| (min.as_degrees(), max.as_degrees()));
+        let mut state = serializer.serialize_struct("OutputModifiers", 5)?;
+        state.serialize_field("position_limits", &position_limits)?;
+        state.serialize_field("targeting_mode", &self.targeting_mode)?;
+        state.serialize_field("scales", &self.scales)?;
+        state.serialize_field("hard_voltage_cap", &self.hard_voltage_cap)?;
+        state.serialize_field("enabled", &self.enabled)?;
+        state.end()
+    }
+}
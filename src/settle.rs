@@ -0,0 +1,388 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::{
+    PortError,
+    motor::{BrakeMode, Motor, MotorControl},
+};
+
+use crate::{
+    MotorGroup, MotorGroupError, SharedMotors,
+    readings::{self, Reading},
+    tick::tick_loop,
+};
+
+/// Error returned by [`MotorGroup::stop_and_settle`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StopError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The group was still moving when the timeout ran out.
+    Timeout {
+        /// The timeout that ran out.
+        timeout: Duration,
+        /// The last velocity of the fastest motor in RPM, or `None` if no
+        /// motor could be read.
+        velocity: Option<f64>,
+    },
+}
+
+impl From<PortError> for StopError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for StopError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::Timeout {
+                timeout,
+                velocity: Some(velocity),
+            } => write!(f, "still moving at {velocity} RPM after {timeout:?}"),
+            Self::Timeout {
+                timeout,
+                velocity: None,
+            } => write!(f, "velocity couldn't be read within {timeout:?}"),
+        }
+    }
+}
+
+impl core::error::Error for StopError {}
+
+/// Returns the velocity of the fastest motor that could be read, and whether
+/// it's within `epsilon_rpm` of zero.
+pub(crate) fn check_stopped(readings: &[Reading<f64>], epsilon_rpm: f64) -> (Option<f64>, bool) {
+    let fastest = readings
+        .iter()
+        .filter_map(|(_, reading)| reading.as_ref().ok().copied())
+        .max_by(|a, b| a.abs().total_cmp(&b.abs()));
+    (
+        fastest,
+        fastest.is_some_and(|velocity| velocity.abs() <= epsilon_rpm),
+    )
+}
+
+/// Returns the errors of a brake write to report once the group settles, or
+/// the error to give up with if no motor accepted the brake.
+pub(crate) fn brake_errors(
+    result: Result<(), MotorGroupError>,
+    written: &[(usize, bool)],
+) -> Result<Vec<StopError>, MotorGroupError<StopError, Duration>> {
+    let Err(error) = result else {
+        return Ok(Vec::new());
+    };
+    let errors = error.errors.into_iter().map(Into::into).collect();
+    if written.iter().any(|(_, accepted)| *accepted) {
+        Ok(errors)
+    } else {
+        Err(MotorGroupError::with_empty_result(errors))
+    }
+}
+
+/// Reads velocities with `read` every `interval` until they're all within
+/// `epsilon_rpm` of zero, returning how long that took.
+async fn settle(
+    interval: Duration,
+    epsilon_rpm: f64,
+    timeout: Duration,
+    mut brake_errors: Vec<StopError>,
+    mut read: impl FnMut() -> Vec<Reading<f64>>,
+) -> Result<Duration, MotorGroupError<StopError, Duration>> {
+    tick_loop(interval, Some(timeout), |elapsed| {
+        let velocities = read();
+        let (velocity, stopped) = check_stopped(&velocities, epsilon_rpm);
+        if stopped {
+            let errors = core::mem::take(&mut brake_errors);
+            return ControlFlow::Break(readings::finish(Some(elapsed), errors));
+        }
+        if elapsed >= timeout {
+            let mut errors = core::mem::take(&mut brake_errors);
+            let (_, read_errors) = readings::partition(velocities);
+            errors.extend(read_errors.into_iter().map(StopError::from));
+            errors.push(StopError::Timeout { timeout, velocity });
+            return ControlFlow::Break(readings::finish(None, errors));
+        }
+        ControlFlow::Continue(())
+    })
+    .await
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Brakes the motor group with `mode`, then waits until it has stopped,
+    /// returning how long that took.
+    ///
+    /// The group counts as stopped once every motor that could be read is
+    /// within `epsilon_rpm` of zero. Velocities are read every tick (see
+    /// [`MotorGroup::tick_interval`], 5ms by default), starting right after
+    /// the brake is written, so the returned duration is measured from the
+    /// brake to the first tick that found the group stopped. Each tick reads
+    /// every motor's velocity once.
+    ///
+    /// Heavy mechanisms like flywheels and lifts keep moving for a while after
+    /// a brake, so an autonomous routine can await this instead of guessing a
+    /// delay before its next step.
+    ///
+    /// # Errors
+    ///
+    /// - If no motor accepted the brake, a [`MotorGroupError`] error with the
+    ///   brake's [`StopError::Port`] errors is returned straight away.
+    /// - If only some motors accepted the brake, the group is still waited
+    ///   on. Once it stops, a [`MotorGroupError`] error with the brake's
+    ///   [`StopError::Port`] errors is returned, whose result is how long the
+    ///   stop took.
+    /// - If the group hasn't stopped after `timeout`, a [`MotorGroupError`]
+    ///   error is returned. It contains any brake errors and the errors of the
+    ///   last read, followed by a [`StopError::Timeout`] error with the last
+    ///   velocity of the fastest motor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     _ = flywheel.set_velocity(600);
+    ///     sleep(Duration::from_secs(2)).await;
+    ///
+    ///     match flywheel
+    ///         .stop_and_settle(BrakeMode::Brake, 5.0, Duration::from_secs(2))
+    ///         .await
+    ///     {
+    ///         Ok(took) => println!("Stopped in {took:?}"),
+    ///         Err(error) => println!("Didn't stop cleanly: {error}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn stop_and_settle(
+        &mut self,
+        mode: BrakeMode,
+        epsilon_rpm: f64,
+        timeout: Duration,
+    ) -> Result<Duration, MotorGroupError<StopError, Duration>> {
+        let (result, written) = self.write_command(MotorControl::Brake(mode));
+        let errors = brake_errors(result, &written)?;
+        settle(
+            self.config.tick_interval,
+            epsilon_rpm,
+            timeout,
+            errors,
+            || self.read_each(Motor::velocity),
+        )
+        .await
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Brakes the motor group, then waits until it has stopped, borrowing the
+    /// group only while braking and reading it.
+    ///
+    /// Since the group is free between reads, other tasks can use it while
+    /// this waits. See [`MotorGroup::stop_and_settle`].
+    ///
+    /// The shadow (see [`SharedMotors::shadow`]) is braked with
+    /// [`SharedMotors::brake`], but isn't waited for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     _ = lift
+    ///         .stop_and_settle(BrakeMode::Hold, 2.0, Duration::from_secs(1))
+    ///         .await;
+    /// }
+    /// ```
+    pub async fn stop_and_settle(
+        &mut self,
+        mode: BrakeMode,
+        epsilon_rpm: f64,
+        timeout: Duration,
+    ) -> Result<Duration, MotorGroupError<StopError, Duration>> {
+        let (interval, errors) = {
+            let mut group = self.0.borrow_mut();
+            let (result, written) = group.write_command(MotorControl::Brake(mode));
+            (group.config.tick_interval, brake_errors(result, &written))
+        };
+        self.mirror(|shadow| shadow.brake(mode));
+        let errors = errors?;
+        settle(interval, epsilon_rpm, timeout, errors, || {
+            self.0.borrow().read_each(Motor::velocity)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use vexide::{
+        prelude::*,
+        smart::{
+            PortError,
+            motor::{BrakeMode, MotorControl},
+        },
+    };
+
+    use super::{StopError, brake_errors, check_stopped, settle};
+    use crate::{MotorGroup, MotorGroupError, SharedMotors, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    fn motors() -> Vec<Motor> {
+        mock_motors(2, Gearset::Blue)
+    }
+
+    #[test]
+    fn the_fastest_motor_decides() {
+        assert_eq!(
+            check_stopped(&[(0, Ok(1.0)), (1, Ok(-4.0))], 5.0),
+            (Some(-4.0), true)
+        );
+        assert_eq!(
+            check_stopped(&[(0, Ok(1.0)), (1, Ok(-6.0))], 5.0),
+            (Some(-6.0), false)
+        );
+        // Motors that can't be read are left out, but someone has to be read
+        assert_eq!(
+            check_stopped(&[(0, Err(DISCONNECTED)), (1, Ok(2.0))], 5.0),
+            (Some(2.0), true)
+        );
+        assert_eq!(check_stopped(&[(0, Err(DISCONNECTED))], 5.0), (None, false));
+    }
+
+    #[test]
+    fn a_partial_brake_is_reported_but_waited_on() {
+        assert_eq!(brake_errors(Ok(()), &[(0, true)]).unwrap(), vec![]);
+
+        let failed = || Err(MotorGroupError::new(vec![DISCONNECTED]));
+        assert_eq!(
+            brake_errors(failed(), &[(0, true), (1, false)]).unwrap(),
+            vec![StopError::Port {
+                source: DISCONNECTED
+            }]
+        );
+        let error = brake_errors(failed(), &[(0, false)]).unwrap_err();
+        assert_eq!(error.result, None);
+
+        // The brake errors come back with how long the stop took
+        let errors = vec![StopError::Port {
+            source: DISCONNECTED,
+        }];
+        let error = vexide::runtime::block_on(settle(
+            Duration::from_millis(5),
+            5.0,
+            Duration::from_secs(1),
+            errors.clone(),
+            || vec![(0, Ok(0.0))],
+        ))
+        .unwrap_err();
+        assert_eq!(error.errors, errors);
+        // Stopped on the first read, before any wait
+        assert!(error.result.unwrap() < Duration::from_millis(5));
+    }
+
+    #[test]
+    fn the_stop_is_timed_from_the_brake() {
+        // The group slows down by 30 RPM every read
+        let mut velocity = 100.0;
+        let start = Instant::now();
+        let took = vexide::runtime::block_on(settle(
+            Duration::from_millis(5),
+            15.0,
+            Duration::from_secs(1),
+            Vec::new(),
+            move || {
+                let reading = velocity;
+                velocity -= 30.0;
+                vec![(0, Ok(reading))]
+            },
+        ))
+        .unwrap();
+        // 100, 70, 40, then 10 RPM on the fourth read, three ticks in
+        assert!(took >= Duration::from_millis(15));
+        assert!(took < Duration::from_secs(1));
+        assert!(start.elapsed() >= took);
+    }
+
+    #[test]
+    fn a_group_still_moving_times_out() {
+        let timeout = Duration::from_millis(20);
+        let start = Instant::now();
+        let error = vexide::runtime::block_on(settle(
+            Duration::from_millis(5),
+            5.0,
+            timeout,
+            Vec::new(),
+            || vec![(0, Ok(50.0)), (1, Err(DISCONNECTED))],
+        ))
+        .unwrap_err();
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(
+            error.errors,
+            vec![
+                StopError::Port {
+                    source: DISCONNECTED
+                },
+                StopError::Timeout {
+                    timeout,
+                    velocity: Some(50.0)
+                },
+            ]
+        );
+        assert_eq!(error.result, None);
+    }
+
+    #[test]
+    fn an_unacknowledged_brake_gives_up_straight_away() {
+        let timeout = Duration::from_secs(1);
+        let mut group = MotorGroup::new(motors());
+        let start = Instant::now();
+        let (group, error) = vexide::runtime::block_on(async move {
+            let error = group
+                .stop_and_settle(BrakeMode::Brake, 5.0, timeout)
+                .await
+                .unwrap_err();
+            (group, error)
+        });
+        assert!(start.elapsed() < timeout);
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Brake(BrakeMode::Brake))
+        );
+
+        let shared = SharedMotors::from_motors(motors());
+        let mut task = shared.clone();
+        let error = vexide::runtime::block_on(async move {
+            task.stop_and_settle(BrakeMode::Hold, 5.0, timeout)
+                .await
+                .unwrap_err()
+        });
+        assert_eq!(error.errors.len(), 2);
+        // The group isn't left borrowed
+        assert!(shared.0.try_borrow_mut().is_ok());
+    }
+}
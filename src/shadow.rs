@@ -0,0 +1,399 @@
+use alloc::{rc::Rc, vec::Vec};
+use core::cell::RefCell;
+
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{MotorGroupError, SharedMotors};
+
+/// The shadow of a shared motor group, registered with
+/// [`SharedMotors::shadow`] and shared by all of its clones.
+#[derive(Debug, Default)]
+pub(crate) struct ShadowLink {
+    /// The group that motion commands are mirrored to.
+    shadow: RefCell<Option<SharedMotors>>,
+    /// The errors of the shadow's last mirrored command.
+    errors: RefCell<Vec<PortError>>,
+}
+
+/// Returns whether `a` and `b` are handles to the same motor group.
+fn same_group<A, B>(a: &SharedMotors<A>, b: &SharedMotors<B>) -> bool
+where
+    A: AsRef<[Motor]> + AsMut<[Motor]>,
+    B: AsRef<[Motor]> + AsMut<[Motor]>,
+{
+    core::ptr::addr_eq(Rc::as_ptr(&a.0), Rc::as_ptr(&b.0))
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Registers `other` as this group's shadow, which is given a copy of
+    /// every motion command given to this group from then on.
+    ///
+    /// This is for mechanisms built twice, such as two identical catapults,
+    /// that should always be commanded the same way: command one of them and
+    /// the other follows. The commands mirrored are
+    /// [`SharedMotors::set_target`], [`SharedMotors::brake`],
+    /// [`SharedMotors::set_voltage`], [`SharedMotors::set_voltage_compensated`],
+    /// [`SharedMotors::set_velocity`], [`SharedMotors::set_velocity_f64`],
+    /// [`SharedMotors::set_position_target`],
+    /// [`SharedMotors::set_relative_position_target`],
+    /// [`SharedMotors::set_profiled_velocity`] and
+    /// [`SharedMotors::goto_preset`], along with the steps of the software
+    /// velocity controllers and of the position fallback
+    /// ([`SharedMotors::update_fallback`]). Each is given to this group first,
+    /// then to the shadow through the same method, so the shadow applies its
+    /// own configuration to it (limits, scales, write error strategy and so
+    /// on), and a shadow with a shadow of its own passes the command on in
+    /// turn. The shadow is commanded even if this group's write failed.
+    ///
+    /// A few commands are given to the shadow through a simpler method: the
+    /// voltage of [`SharedMotors::set_voltage_verified`] and
+    /// [`SharedMotors::set_voltage_if_healthy`] (only once it's written) goes
+    /// through [`SharedMotors::set_voltage`], the brake of
+    /// [`SharedMotors::stop_and_settle`] through [`SharedMotors::brake`], and
+    /// the target of [`SharedMotors::goto_preset_settled`] through
+    /// [`SharedMotors::set_position_target`]. Only this group is verified or
+    /// waited for.
+    ///
+    /// Configuration isn't mirrored, and neither is
+    /// [`SharedMotors::set_tank`], whose indices only make sense for this
+    /// group, or [`SharedMotors::recover`], which restores this group's last
+    /// command rather than giving a new one. Nothing done through
+    /// [`SharedMotors::lock`] or the inner [`MotorGroup`](crate::MotorGroup)
+    /// is mirrored either, including the commands only found there, such as
+    /// [`MotorGroup::transition`](crate::MotorGroup::transition), so set both
+    /// groups up the same way before linking them and command them through
+    /// their handles.
+    ///
+    /// The shadow's errors never show up in this group's results. Read them
+    /// with [`SharedMotors::shadow_errors`] instead.
+    ///
+    /// A group has at most one shadow, so this replaces any previous one.
+    /// The shadow is shared by every clone of this group, and is kept alive
+    /// until it's removed with [`SharedMotors::clear_shadow`].
+    ///
+    /// Returns `false` and leaves the shadow alone if `other` is this group
+    /// or is already shadowed by it, directly or through other shadows, since
+    /// every command would then go round in a loop.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut left_catapult = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///     let right_catapult = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_3, Gearset::Red, Direction::Reverse),
+    ///         Motor::new(peripherals.port_4, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///     left_catapult.shadow(right_catapult);
+    ///
+    ///     // Both catapults wind up
+    ///     _ = left_catapult.set_voltage(12.0);
+    ///     if !left_catapult.shadow_errors().is_empty() {
+    ///         println!("The right catapult didn't follow");
+    ///     }
+    /// }
+    /// ```
+    pub fn shadow(&mut self, other: SharedMotors) -> bool {
+        let mut next = Some(other.clone());
+        while let Some(group) = next {
+            if same_group(self, &group) {
+                return false;
+            }
+            next = group.2.shadow.borrow().clone();
+        }
+        *self.2.shadow.borrow_mut() = Some(other);
+        self.2.errors.borrow_mut().clear();
+        true
+    }
+
+    /// Removes this group's shadow, if it has one, and returns it. See
+    /// [`SharedMotors::shadow`].
+    pub fn clear_shadow(&mut self) -> Option<SharedMotors> {
+        self.2.errors.borrow_mut().clear();
+        self.2.shadow.borrow_mut().take()
+    }
+
+    /// Returns the errors of the last command mirrored to this group's
+    /// shadow (see [`SharedMotors::shadow`]).
+    ///
+    /// This is empty if the shadow accepted the last command, or if nothing
+    /// has been mirrored since the shadow was registered.
+    pub fn shadow_errors(&self) -> Vec<PortError> {
+        self.2.errors.borrow().clone()
+    }
+
+    /// Gives this group's shadow, if it has one, a copy of a motion command
+    /// with `command`, and records its errors.
+    ///
+    /// The handle is cloned out first so that nothing stays borrowed while
+    /// the shadow runs the command.
+    pub(crate) fn mirror(
+        &self,
+        command: impl FnOnce(&mut SharedMotors) -> Result<(), MotorGroupError>,
+    ) {
+        let Some(mut shadow) = self.2.shadow.borrow().clone() else {
+            return;
+        };
+        let errors = command(&mut shadow).map_or_else(|error| error.errors, |()| Vec::new());
+        *self.2.errors.borrow_mut() = errors;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "control")]
+    use core::time::Duration;
+    use std::sync::{Arc, Mutex};
+
+    use vexide::{
+        math::Angle,
+        smart::{
+            PortError,
+            motor::{BrakeMode, MotorControl},
+        },
+    };
+
+    use crate::{
+        PolicyDecision, PositionFallback, SharedMotors, WriteErrorStrategy, tests::v5_motor,
+    };
+
+    fn group(ports: &[u8]) -> SharedMotors {
+        SharedMotors::from_motors(ports.iter().copied().map(v5_motor).collect())
+    }
+
+    #[test]
+    fn motion_commands_are_mirrored() {
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3]);
+        assert!(primary.shadow(shadow.clone()));
+
+        _ = primary.set_voltage(6.0);
+        _ = primary.brake(BrakeMode::Hold);
+        _ = primary.set_position_target(Angle::from_degrees(90.0), 50);
+        let shadow = shadow.lock();
+        assert_eq!(shadow.command_generation(), 3);
+        assert_eq!(
+            shadow.last_command,
+            Some(MotorControl::Position(Angle::from_degrees(90.0), 50))
+        );
+    }
+
+    #[test]
+    fn configuration_is_not_mirrored() {
+        let mut primary = group(&[1]);
+        let shadow = group(&[2]);
+        primary.shadow(shadow.clone());
+
+        primary.write_error_strategy(WriteErrorStrategy::Stop);
+        primary.set_voltage_limit(6.0).ok();
+        assert_eq!(
+            shadow.current_config().write_error_strategy,
+            WriteErrorStrategy::Ignore
+        );
+        assert_eq!(shadow.command_generation(), 0);
+
+        // Neither is anything done through the lock
+        _ = primary.lock().set_voltage(6.0);
+        assert_eq!(shadow.command_generation(), 0);
+    }
+
+    #[test]
+    fn shadow_errors_are_kept_apart() {
+        let mut primary = group(&[1, 2]);
+        primary.shadow(group(&[3]));
+        assert!(primary.shadow_errors().is_empty());
+
+        // Every write to the mock motors fails
+        let error = primary.set_velocity(100).unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                PortError::Disconnected { port: 1 },
+                PortError::Disconnected { port: 2 }
+            ]
+        );
+        assert_eq!(
+            primary.shadow_errors(),
+            vec![PortError::Disconnected { port: 3 }]
+        );
+
+        // Clones share the shadow and its errors
+        let clone = primary.clone();
+        assert_eq!(clone.shadow_errors().len(), 1);
+
+        let removed = primary.clear_shadow().unwrap();
+        assert!(primary.shadow_errors().is_empty());
+        _ = primary.set_velocity(100);
+        assert!(primary.shadow_errors().is_empty());
+        assert_eq!(removed.command_generation(), 1);
+        assert!(clone.clone().clear_shadow().is_none());
+    }
+
+    #[test]
+    fn the_primary_is_commanded_first() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut primary = group(&[1]);
+        let mut shadow = group(&[2]);
+        let mut shadows_shadow = group(&[3]);
+        for (name, group) in [
+            ("primary", &mut primary),
+            ("shadow", &mut shadow),
+            ("shadow's shadow", &mut shadows_shadow),
+        ] {
+            let order = order.clone();
+            group.set_custom_write_policy(move |_, _, _| {
+                order.lock().unwrap().push(name);
+                PolicyDecision::Continue
+            });
+        }
+        shadow.shadow(shadows_shadow);
+        primary.shadow(shadow.clone());
+
+        _ = primary.set_voltage(3.0);
+        assert_eq!(
+            *order.lock().unwrap(),
+            ["primary", "shadow", "shadow's shadow"]
+        );
+        // Each shadow keeps the errors of its own shadow
+        assert_eq!(
+            primary.shadow_errors(),
+            vec![PortError::Disconnected { port: 2 }]
+        );
+        assert_eq!(
+            shadow.shadow_errors(),
+            vec![PortError::Disconnected { port: 3 }]
+        );
+    }
+
+    #[test]
+    fn shadow_cycles_are_refused() {
+        let mut a = group(&[1]);
+        let mut b = group(&[2]);
+        let mut c = group(&[3]);
+
+        // A group can't shadow itself, even through a clone
+        assert!(!a.shadow(a.clone()));
+        assert!(a.clear_shadow().is_none());
+
+        assert!(a.shadow(b.clone()));
+        assert!(!b.shadow(a.clone()));
+        assert!(b.shadow(c.clone()));
+        // A longer loop is caught too
+        assert!(!c.shadow(a.clone()));
+
+        // Once the loop is broken, the link is allowed
+        b.clear_shadow();
+        assert!(c.shadow(a.clone()));
+        _ = a.set_voltage(1.0);
+        assert_eq!(b.command_generation(), 1);
+        assert_eq!(a.command_generation(), 1);
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn verified_voltages_are_mirrored() {
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3]);
+        primary.shadow(shadow.clone());
+
+        _ = primary.set_voltage_verified(6.0);
+        assert_eq!(shadow.command_generation(), 1);
+        assert_eq!(shadow.lock().last_command, Some(MotorControl::Voltage(6.0)));
+        assert_eq!(
+            primary.shadow_errors(),
+            vec![PortError::Disconnected { port: 3 }]
+        );
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn settling_stops_brake_the_shadow() {
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3]);
+        primary.shadow(shadow.clone());
+
+        // The primary's brake is refused, which ends the wait straight away,
+        // but the shadow is still braked
+        let mut task = primary.clone();
+        vexide::runtime::block_on(async move {
+            task.stop_and_settle(BrakeMode::Hold, 5.0, Duration::from_secs(1))
+                .await
+                .unwrap_err();
+        });
+        assert_eq!(
+            shadow.lock().last_command,
+            Some(MotorControl::Brake(BrakeMode::Hold))
+        );
+        assert_eq!(primary.shadow_errors().len(), 1);
+    }
+
+    #[cfg(feature = "control")]
+    #[test]
+    fn settled_presets_are_mirrored() {
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3]);
+        primary.shadow(shadow.clone());
+        primary
+            .add_preset("score", Angle::from_degrees(90.0), 50)
+            .unwrap();
+
+        let mut task = primary.clone();
+        vexide::runtime::block_on(async move {
+            task.goto_preset_settled("score", Angle::from_degrees(1.0), Duration::from_secs(1))
+                .await
+                .unwrap_err();
+            // An unknown preset gives no command at all
+            task.goto_preset_settled("park", Angle::from_degrees(1.0), Duration::from_secs(1))
+                .await
+                .unwrap_err();
+        });
+        assert_eq!(shadow.command_generation(), 1);
+        assert_eq!(
+            shadow.lock().last_command,
+            Some(MotorControl::Position(Angle::from_degrees(90.0), 50))
+        );
+    }
+
+    #[test]
+    fn fallback_steps_are_mirrored() {
+        const FALLBACK: PositionFallback = PositionFallback {
+            failures_to_enter: 1,
+            gain: 0.05,
+            max_voltage: 10.0,
+        };
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3, 4]);
+        primary.shadow(shadow.clone());
+
+        // Only the shadow is following
+        {
+            let mut shadow = shadow.lock();
+            shadow.position_fallback(Some(FALLBACK));
+            let target = MotorControl::Position(Angle::from_degrees(90.0), 50);
+            shadow.track_fallback(target, &[(0, true), (1, false)]);
+            assert!(shadow.is_in_fallback());
+        }
+        assert!(primary.update_fallback().is_ok());
+        // The leader's read and the follower's write fail
+        assert_eq!(primary.shadow_errors().len(), 2);
+    }
+
+    #[cfg(feature = "drivetrain")]
+    #[test]
+    fn tank_commands_are_not_mirrored() {
+        let mut primary = group(&[1, 2]);
+        let shadow = group(&[3, 4]);
+        primary.shadow(shadow.clone());
+
+        _ = primary.set_tank(6.0, -6.0, &[0], &[1]);
+        assert_eq!(shadow.command_generation(), 0);
+        assert!(primary.shadow_errors().is_empty());
+    }
+}
@@ -1,27 +1,200 @@
-use core::cell::RefCell;
+use core::{
+    cell::{RefCell, RefMut},
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use std::time::Instant;
 
-use alloc::{rc::Rc, vec::Vec};
+use alloc::{rc::Rc, string::String, vec::Vec};
 use vexide::{
     math::Angle,
     prelude::*,
-    smart::motor::{BrakeMode, MotorControl, SetGearsetError},
+    smart::{
+        PortError,
+        motor::{BrakeMode, MotorControl, SetGearsetError},
+    },
 };
 
-use crate::{GetterResult, MotorGroup, MotorGroupError, WriteErrorStrategy};
+#[cfg(feature = "events")]
+use crate::EventReceiver;
+#[cfg(feature = "drivetrain")]
+use crate::TankError;
+use crate::{
+    ConfigSnapshot, ConfigureError, GetterResult, GroupConfig, GroupPosition, GroupPredicateResult,
+    GroupSnapshot, MotorGroup, MotorGroupError, PositionFallback, PredicateErrorStrategy,
+    SetCurrentLimitError, Sign, TargetDistanceError, WriteErrorStrategy,
+    last_known::LastKnownCache, shadow::ShadowLink,
+};
+#[cfg(feature = "telemetry")]
+use crate::{HistoryConfig, Metric, WriteTiming, WriteTimingStats};
+#[cfg(feature = "diagnostics")]
+use crate::{ReadinessCriteria, ReadinessReport};
 
 /// Motors that can be cloned with interior mutability.
 ///
 /// This simply wraps MotorGroups with a newtype while adding some traits useful
 /// for using them.
-#[derive(Clone, Debug)]
+///
+/// Clones share the same inner motor group, so configuration changed through
+/// one clone is observed by all of the others:
+///
+/// ```
+/// # use vexide::{prelude::*, smart::SmartPort};
+/// # use vexide_motorgroup::*;
+/// # let port_1 = unsafe { SmartPort::new(1) };
+/// # let port_2 = unsafe { SmartPort::new(2) };
+/// let mut drive = SharedMotors::from_motors(vec![
+///     Motor::new(port_1, Gearset::Green, Direction::Forward),
+///     Motor::new(port_2, Gearset::Green, Direction::Forward),
+/// ]);
+/// let autonomous_drive = drive.clone();
+///
+/// drive.write_error_strategy(WriteErrorStrategy::Stop);
+/// assert_eq!(
+///     autonomous_drive.current_config().write_error_strategy,
+///     WriteErrorStrategy::Stop,
+/// );
+/// ```
+///
+/// Clones share an optional cache of the last known value of each getter. See
+/// [`SharedMotors::set_last_known_cache`].
+///
+/// This is the handle to share a group between vexide tasks. It isn't `Send`
+/// or `Sync`: see [Tasks and threads](crate#tasks-and-threads).
+///
+/// The handle is opaque, since the cache and shadow (see
+/// [`SharedMotors::shadow`]) have to be shared by its clones alongside the
+/// group. Create one with [`SharedMotors::new`] rather than from an
+/// `Rc<RefCell<MotorGroup>>`, and borrow the group with
+/// [`SharedMotors::lock`] rather than through the `Rc`.
+#[derive(Debug)]
 pub struct SharedMotors<M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>>(
-    pub Rc<RefCell<MotorGroup<M>>>,
+    pub(crate) Rc<RefCell<MotorGroup<M>>>,
+    pub(crate) Rc<LastKnownCache>,
+    pub(crate) Rc<ShadowLink>,
 );
 
+// Not derived, since that would require `M: Clone` even though only the `Rc`s
+// are cloned.
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Clone for SharedMotors<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone(), self.2.clone())
+    }
+}
+
 impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
     /// Create a new SharedMotors from a MotorGroup.
     pub fn new(motors: MotorGroup<M>) -> Self {
-        Self(Rc::new(RefCell::new(motors)))
+        Self(
+            Rc::new(RefCell::new(motors)),
+            Rc::new(LastKnownCache::default()),
+            Rc::new(ShadowLink::default()),
+        )
+    }
+
+    /// Mutably borrows the motor group until the returned guard is dropped.
+    ///
+    /// The guard gives access to the full [`MotorGroup`] API, so a control
+    /// loop can borrow the group once per iteration instead of once per call.
+    /// The borrow is released when the guard is dropped, which should happen
+    /// before the loop yields to other tasks.
+    ///
+    /// Getters called through the guard don't update the last known values
+    /// cache (see [`SharedMotors::set_last_known_cache`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the motor group is currently borrowed, for example by a guard
+    /// held by another task.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         {
+    ///             let mut motors = drive.lock();
+    ///             let velocity = motors.velocity().unwrap_or_default();
+    ///             _ = motors.set_voltage((100.0 - velocity) * 0.05);
+    ///         }
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn lock(&self) -> MotorGroupGuard<'_, M> {
+        MotorGroupGuard(self.0.borrow_mut())
+    }
+
+    #[cfg(feature = "events")]
+    /// Subscribes to the group's state changes.
+    ///
+    /// Every clone of this group delivers to the same receivers, so the
+    /// receiver can be handed to a separate monitoring task. See
+    /// [`MotorGroup::events`].
+    pub fn events(&self) -> EventReceiver {
+        self.0.borrow_mut().events()
+    }
+
+    /// Create a new SharedMotors directly from motors, without building a
+    /// [`MotorGroup`] first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no motors. See [`MotorGroup::new`].
+    pub fn from_motors(motors: M) -> Self {
+        Self::new(MotorGroup::new(motors))
+    }
+
+    /// Create a new SharedMotors directly from motors with the given
+    /// [`WriteErrorStrategy`].
+    ///
+    /// ```
+    /// # use vexide::{prelude::*, smart::SmartPort};
+    /// # use vexide_motorgroup::*;
+    /// # let port_1 = unsafe { SmartPort::new(1) };
+    /// let lift = SharedMotors::from_motors_with(
+    ///     vec![Motor::new(port_1, Gearset::Red, Direction::Forward)],
+    ///     WriteErrorStrategy::Stop,
+    /// );
+    /// let clone = lift.clone();
+    /// assert_eq!(
+    ///     clone.current_config().write_error_strategy,
+    ///     WriteErrorStrategy::Stop,
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no motors. See [`MotorGroup::new`].
+    pub fn from_motors_with(motors: M, strategy: WriteErrorStrategy) -> Self {
+        let mut group = MotorGroup::new(motors);
+        group.write_error_strategy(strategy);
+        Self::new(group)
+    }
+
+    /// Enables or disables the last known values cache.
+    ///
+    /// While enabled, every getter on these shared motors (and their clones)
+    /// that succeeds for every motor stores its result. The results can then
+    /// be read with the `last_known_*` methods, such as
+    /// [`SharedMotors::last_known_velocity`], without borrowing the motor
+    /// group. This makes them safe to call while the group is mutably borrowed
+    /// elsewhere.
+    ///
+    /// The cache is disabled by default. Disabling it forgets every stored
+    /// value.
+    pub fn set_last_known_cache(&self, enabled: bool) -> &Self {
+        self.1.set_enabled(enabled);
+        self
     }
 
     /// See [`MotorGroup::write_error_strategy`].
@@ -30,24 +203,276 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
         self
     }
 
+    /// See [`MotorGroup::predicate_error_strategy`].
+    pub fn predicate_error_strategy(&mut self, strategy: PredicateErrorStrategy) -> &Self {
+        self.0.borrow_mut().predicate_error_strategy(strategy);
+        self
+    }
+
+    /// See [`MotorGroup::count_disabled_in_average`].
+    pub fn count_disabled_in_average(&mut self, enabled: bool) -> &Self {
+        self.0.borrow_mut().count_disabled_in_average(enabled);
+        self
+    }
+
+    /// See [`MotorGroup::set_read_cache`].
+    pub fn set_read_cache(&mut self, max_staleness: Option<Duration>) -> &Self {
+        self.0.borrow_mut().set_read_cache(max_staleness);
+        self
+    }
+
+    /// See [`MotorGroup::invalidate_read_cache`].
+    pub fn invalidate_read_cache(&self) {
+        self.0.borrow().invalidate_read_cache();
+    }
+
+    /// See [`MotorGroup::shift_ratio`].
+    pub fn shift_ratio(&mut self, new_external_ratio: f64) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().shift_ratio(new_external_ratio)
+    }
+
+    /// See [`MotorGroup::external_ratio`].
+    pub fn external_ratio(&self) -> f64 {
+        self.0.borrow().external_ratio()
+    }
+
+    /// See [`MotorGroup::position_fallback`].
+    pub fn position_fallback(&mut self, fallback: Option<PositionFallback>) -> &Self {
+        self.0.borrow_mut().position_fallback(fallback);
+        self
+    }
+
+    /// See [`MotorGroup::tick_interval`].
+    pub fn tick_interval(&mut self, interval: Duration) -> &Self {
+        self.0.borrow_mut().tick_interval(interval);
+        self
+    }
+
+    /// See [`MotorGroup::is_in_fallback`].
+    pub fn is_in_fallback(&self) -> bool {
+        self.0.borrow().is_in_fallback()
+    }
+
+    /// See [`MotorGroup::fallback_motors`].
+    pub fn fallback_motors(&self) -> Vec<usize> {
+        self.0.borrow().fallback_motors()
+    }
+
+    /// See [`MotorGroup::update_fallback`].
+    pub fn update_fallback(&mut self) -> Result<(), MotorGroupError> {
+        let result = self.0.borrow_mut().update_fallback();
+        self.mirror(SharedMotors::update_fallback);
+        result
+    }
+
+    /// See [`MotorGroup::stop_on_drop`].
+    pub fn stop_on_drop(&mut self, mode: Option<BrakeMode>) -> &Self {
+        self.0.borrow_mut().stop_on_drop(mode);
+        self
+    }
+
+    /// See [`MotorGroup::set_velocity_pid_constants`].
+    #[cfg(feature = "vexide-unstable")]
+    pub fn set_velocity_pid_constants(
+        &mut self,
+        constants: vexide::smart::motor::MotorTuningConstants,
+    ) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().set_velocity_pid_constants(constants)
+    }
+
+    /// See [`MotorGroup::velocity_pid_constants`].
+    #[cfg(feature = "vexide-unstable")]
+    pub fn velocity_pid_constants(&self) -> Option<vexide::smart::motor::MotorTuningConstants> {
+        self.0.borrow().velocity_pid_constants()
+    }
+
+    /// See [`MotorGroup::set_enabled`].
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) -> bool {
+        self.0.borrow_mut().set_enabled(index, enabled)
+    }
+
+    /// See [`MotorGroup::is_enabled`].
+    pub fn is_enabled(&self, index: usize) -> bool {
+        self.0.borrow().is_enabled(index)
+    }
+
+    /// See [`MotorGroup::set_label`].
+    pub fn set_label(&mut self, index: usize, label: impl Into<String>) -> &Self {
+        self.0.borrow_mut().set_label(index, label);
+        self
+    }
+
+    /// See [`MotorGroup::label`].
+    ///
+    /// The label is cloned, since it can't be borrowed out of the shared
+    /// group.
+    pub fn label(&self, index: usize) -> Option<String> {
+        self.0.borrow().label(index).map(String::from)
+    }
+
+    /// See [`MotorGroup::set_scale`].
+    pub fn set_scale(&mut self, index: usize, scale: f64) -> &Self {
+        self.0.borrow_mut().set_scale(index, scale);
+        self
+    }
+
+    /// See [`MotorGroup::scale`].
+    pub fn scale(&self, index: usize) -> f64 {
+        self.0.borrow().scale(index)
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::enable_write_timing`].
+    pub fn enable_write_timing(&mut self, enabled: bool) -> &Self {
+        self.0.borrow_mut().enable_write_timing(enabled);
+        self
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::last_write_timing`].
+    pub fn last_write_timing(&self) -> Option<WriteTiming> {
+        self.0.borrow().last_write_timing()
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::write_timing_stats`].
+    pub fn write_timing_stats(&self) -> Option<WriteTimingStats> {
+        self.0.borrow().write_timing_stats()
+    }
+
+    /// See [`MotorGroup::replace_motors`].
+    pub fn replace_motors(&mut self, motors: M) -> M {
+        self.0.borrow_mut().replace_motors(motors)
+    }
+
+    /// See [`MotorGroup::command_generation`].
+    pub fn command_generation(&self) -> u64 {
+        self.0.borrow().command_generation()
+    }
+
+    /// See [`MotorGroup::snapshot`].
+    pub fn snapshot(&self) -> GroupSnapshot {
+        self.0.borrow().snapshot()
+    }
+
+    /// See [`MotorGroup::current_config`].
+    pub fn current_config(&self) -> GroupConfig {
+        self.0.borrow().current_config()
+    }
+
+    /// See [`MotorGroup::config_snapshot`].
+    pub fn config_snapshot(&self) -> ConfigSnapshot {
+        self.0.borrow().config_snapshot()
+    }
+
+    /// See [`MotorGroup::apply_config`].
+    pub fn apply_config(
+        &mut self,
+        config: &GroupConfig,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        self.0.borrow_mut().apply_config(config)
+    }
+
+    /// See [`MotorGroup::configure`].
+    pub fn configure(
+        &mut self,
+        gearset: Option<Gearset>,
+        current_limit: Option<f64>,
+        voltage_limit: Option<f64>,
+        direction: Option<Direction>,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        self.0
+            .borrow_mut()
+            .configure(gearset, current_limit, voltage_limit, direction)
+    }
+
     /// See [`MotorGroup::set_target`].
     pub fn set_target(&mut self, target: MotorControl) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().set_target(target)
+        let result = self.0.borrow_mut().set_target(target);
+        self.mirror(|shadow| shadow.set_target(target));
+        result
     }
 
     /// See [`MotorGroup::brake`].
     pub fn brake(&mut self, mode: BrakeMode) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().brake(mode)
+        let result = self.0.borrow_mut().brake(mode);
+        self.mirror(|shadow| shadow.brake(mode));
+        result
     }
 
     /// See [`MotorGroup::set_velocity`].
     pub fn set_velocity(&mut self, rpm: i32) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().set_velocity(rpm)
+        let result = self.0.borrow_mut().set_velocity(rpm);
+        self.mirror(|shadow| shadow.set_velocity(rpm));
+        result
+    }
+
+    #[cfg(feature = "control")]
+    /// See [`MotorGroup::approach_velocity`].
+    pub fn approach_velocity(
+        &mut self,
+        target_rpm: f64,
+        kp: f64,
+        max_volts: f64,
+    ) -> Result<(), MotorGroupError> {
+        let result = self
+            .0
+            .borrow_mut()
+            .approach_velocity(target_rpm, kp, max_volts);
+        self.mirror(|shadow| shadow.approach_velocity(target_rpm, kp, max_volts));
+        result
+    }
+
+    #[cfg(feature = "control")]
+    /// See [`MotorGroup::set_velocity_fp`].
+    pub fn set_velocity_fp(
+        &mut self,
+        target_rpm: f64,
+        kv: f64,
+        kp: f64,
+        max_volts: f64,
+    ) -> Result<(), MotorGroupError> {
+        let result = self
+            .0
+            .borrow_mut()
+            .set_velocity_fp(target_rpm, kv, kp, max_volts);
+        self.mirror(|shadow| shadow.set_velocity_fp(target_rpm, kv, kp, max_volts));
+        result
     }
 
     /// See [`MotorGroup::set_voltage`].
     pub fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().set_voltage(volts)
+        let result = self.0.borrow_mut().set_voltage(volts);
+        self.mirror(|shadow| shadow.set_voltage(volts));
+        result
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::set_voltage_verified`].
+    ///
+    /// The shadow (see [`SharedMotors::shadow`]) is given
+    /// [`SharedMotors::set_voltage`], since only this group is verified.
+    pub fn set_voltage_verified(&mut self, volts: f64) -> GetterResult<Vec<bool>> {
+        let result = self.0.borrow_mut().set_voltage_verified(volts);
+        self.mirror(|shadow| shadow.set_voltage(volts));
+        result
+    }
+
+    #[cfg(feature = "drivetrain")]
+    /// See [`MotorGroup::set_tank`].
+    ///
+    /// This isn't mirrored to the shadow (see [`SharedMotors::shadow`]),
+    /// since the indices only make sense for this group.
+    pub fn set_tank(
+        &mut self,
+        left: f64,
+        right: f64,
+        left_indices: &[usize],
+        right_indices: &[usize],
+    ) -> Result<(), MotorGroupError<TankError>> {
+        self.0
+            .borrow_mut()
+            .set_tank(left, right, left_indices, right_indices)
     }
 
     /// See [`MotorGroup::set_position_target`].
@@ -56,12 +481,30 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
         position: Angle,
         velocity: i32,
     ) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().set_position_target(position, velocity)
+        let result = self.0.borrow_mut().set_position_target(position, velocity);
+        self.mirror(|shadow| shadow.set_position_target(position, velocity));
+        result
+    }
+
+    /// See [`MotorGroup::set_relative_position_target`].
+    pub fn set_relative_position_target(
+        &mut self,
+        delta: Angle,
+        velocity: i32,
+    ) -> Result<(), MotorGroupError> {
+        let result = self
+            .0
+            .borrow_mut()
+            .set_relative_position_target(delta, velocity);
+        self.mirror(|shadow| shadow.set_relative_position_target(delta, velocity));
+        result
     }
 
     /// See [`MotorGroup::set_profiled_velocity`].
     pub fn set_profiled_velocity(&mut self, velocity: i32) -> Result<(), MotorGroupError> {
-        self.0.borrow_mut().set_profiled_velocity(velocity)
+        let result = self.0.borrow_mut().set_profiled_velocity(velocity);
+        self.mirror(|shadow| shadow.set_profiled_velocity(velocity));
+        result
     }
 
     /// See [`MotorGroup::set_gearset`].
@@ -72,6 +515,11 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
         self.0.borrow_mut().set_gearset(gearset)
     }
 
+    /// See [`MotorGroup::max_rpm`].
+    pub fn max_rpm(&self) -> Option<f64> {
+        self.0.borrow().max_rpm()
+    }
+
     /// See [`MotorGroup::has_exp`].
     pub fn has_exp(&self) -> bool {
         self.0.borrow().has_exp()
@@ -82,6 +530,16 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
         self.0.borrow().has_v5()
     }
 
+    /// See [`MotorGroup::exp_motors`].
+    pub fn exp_motors(&self) -> Vec<usize> {
+        self.0.borrow().exp_motors()
+    }
+
+    /// See [`MotorGroup::v5_motors`].
+    pub fn v5_motors(&self) -> Vec<usize> {
+        self.0.borrow().v5_motors()
+    }
+
     /// See [`MotorGroup::max_voltage`].
     pub fn max_voltage(&self) -> f64 {
         self.0.borrow().max_voltage()
@@ -89,37 +547,297 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
 
     /// See [`MotorGroup::velocity`].
     pub fn velocity(&self) -> GetterResult<f64> {
-        self.0.borrow().velocity()
+        let result = self.0.borrow().velocity();
+        self.1.remember(&self.1.velocity, &result);
+        result
+    }
+
+    #[cfg(feature = "drivetrain")]
+    /// See [`MotorGroup::weighted_output_velocity`].
+    pub fn weighted_output_velocity(&self, wheel_diameters: &[f64]) -> GetterResult<f64> {
+        self.0.borrow().weighted_output_velocity(wheel_diameters)
+    }
+
+    /// Returns the last successfully read [`SharedMotors::velocity`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_velocity(&self) -> Option<(f64, Duration)> {
+        self.1.velocity.get(Instant::now())
+    }
+
+    /// See [`MotorGroup::min_velocity`].
+    pub fn min_velocity(&self) -> GetterResult<f64> {
+        self.0.borrow().min_velocity()
+    }
+
+    /// See [`MotorGroup::slowest_motor`].
+    pub fn slowest_motor(&self) -> GetterResult<usize> {
+        self.0.borrow().slowest_motor()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::dead_motors`].
+    pub fn dead_motors(
+        &self,
+        min_group_velocity: f64,
+        dead_threshold: f64,
+    ) -> GetterResult<Vec<usize>> {
+        self.0
+            .borrow()
+            .dead_motors(min_group_velocity, dead_threshold)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::motors_above_temperature`].
+    pub fn motors_above_temperature(&self, celsius: f64) -> GetterResult<usize> {
+        self.0.borrow().motors_above_temperature(celsius)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::time_to_cutout`].
+    pub fn time_to_cutout(&mut self, cutout_celsius: f64) -> GetterResult<Option<Duration>> {
+        self.0.borrow_mut().time_to_cutout(cutout_celsius)
+    }
+
+    /// See [`MotorGroup::group_position`].
+    pub fn group_position(&self) -> GetterResult<GroupPosition> {
+        self.0.borrow().group_position()
+    }
+
+    /// See [`MotorGroup::position_spread_degrees`].
+    pub fn position_spread_degrees(&self) -> GetterResult<f64> {
+        self.0.borrow().position_spread_degrees()
+    }
+
+    /// See [`MotorGroup::output_position`].
+    pub fn output_position(&self) -> GetterResult<Angle> {
+        self.0.borrow().output_position()
+    }
+
+    /// See [`MotorGroup::max_distance_to_target`].
+    pub fn max_distance_to_target(
+        &self,
+    ) -> Result<Angle, MotorGroupError<TargetDistanceError, Angle>> {
+        self.0.borrow().max_distance_to_target()
+    }
+
+    /// See [`MotorGroup::is_position_controlled`].
+    pub fn is_position_controlled(&self) -> GetterResult<bool> {
+        self.0.borrow().is_position_controlled()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::diagnostic_report`].
+    pub fn diagnostic_report(&self, out: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.0.borrow().diagnostic_report(out)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::telemetry_tuples`].
+    pub fn telemetry_tuples(&self) -> Vec<(f64, f64, f64)> {
+        self.0.borrow().telemetry_tuples()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::current_distribution`].
+    pub fn current_distribution(&self) -> GetterResult<Vec<f64>> {
+        self.0.borrow().current_distribution()
+    }
+
+    /// See [`MotorGroup::all_satisfy`].
+    pub fn all_satisfy(
+        &self,
+        predicate: impl Fn(&Motor) -> Result<bool, PortError>,
+    ) -> GroupPredicateResult {
+        self.0.borrow().all_satisfy(predicate)
+    }
+
+    /// See [`MotorGroup::all_below_temperature`].
+    pub fn all_below_temperature(&self, threshold: f64) -> GroupPredicateResult {
+        self.0.borrow().all_below_temperature(threshold)
+    }
+
+    /// See [`MotorGroup::all_connected`].
+    pub fn all_connected(&self) -> GroupPredicateResult {
+        self.0.borrow().all_connected()
+    }
+
+    /// See [`MotorGroup::all_at_target`].
+    pub fn all_at_target(&self, tolerance: f64) -> GroupPredicateResult {
+        self.0.borrow().all_at_target(tolerance)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::readiness`].
+    pub fn readiness(&self, criteria: &ReadinessCriteria) -> ReadinessReport {
+        self.0.borrow().readiness(criteria)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::efficiency_trend`].
+    pub fn efficiency_trend(&mut self, window: usize) -> GetterResult<f64> {
+        self.0.borrow_mut().efficiency_trend(window)
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::enable_metric_history`].
+    pub fn enable_metric_history(&mut self, config: HistoryConfig) {
+        self.0.borrow_mut().enable_metric_history(config);
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::disable_metric_history`].
+    pub fn disable_metric_history(&mut self) {
+        self.0.borrow_mut().disable_metric_history();
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::record_metrics`].
+    pub fn record_metrics(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().record_metrics()
+    }
+
+    #[cfg(feature = "telemetry")]
+    /// See [`MotorGroup::metric_range`].
+    ///
+    /// The samples themselves borrow the group, so read them through
+    /// [`SharedMotors::lock`].
+    pub fn metric_range(&self, metric: Metric) -> Option<(f64, f64)> {
+        self.0.borrow().metric_range(metric)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::rms_current`].
+    pub fn rms_current(&mut self, window: usize) -> GetterResult<f64> {
+        self.0.borrow_mut().rms_current(window)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::averaged_current`].
+    pub fn averaged_current(&mut self, window: usize) -> GetterResult<f64> {
+        self.0.borrow_mut().averaged_current(window)
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::reset_current_average`].
+    pub fn reset_current_average(&mut self) {
+        self.0.borrow_mut().reset_current_average();
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::update_wear`].
+    pub fn update_wear(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().update_wear()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::wear_score`].
+    pub fn wear_score(&self) -> GetterResult<f64> {
+        self.0.borrow().wear_score()
+    }
+
+    #[cfg(feature = "diagnostics")]
+    /// See [`MotorGroup::wear_scores`].
+    pub fn wear_scores(&self) -> Vec<f64> {
+        self.0.borrow().wear_scores()
     }
 
     /// See [`MotorGroup::power`].
     pub fn power(&self) -> GetterResult<f64> {
-        self.0.borrow().power()
+        let result = self.0.borrow().power();
+        self.1.remember(&self.1.power, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::power`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_power(&self) -> Option<(f64, Duration)> {
+        self.1.power.get(Instant::now())
     }
 
     /// See [`MotorGroup::torque`].
     pub fn torque(&self) -> GetterResult<f64> {
-        self.0.borrow().torque()
+        let result = self.0.borrow().torque();
+        self.1.remember(&self.1.torque, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::torque`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_torque(&self) -> Option<(f64, Duration)> {
+        self.1.torque.get(Instant::now())
     }
 
     /// See [`MotorGroup::voltage`].
     pub fn voltage(&self) -> GetterResult<f64> {
-        self.0.borrow().voltage()
+        let result = self.0.borrow().voltage();
+        self.1.remember(&self.1.voltage, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::voltage`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_voltage(&self) -> Option<(f64, Duration)> {
+        self.1.voltage.get(Instant::now())
     }
 
     /// See [`MotorGroup::position`].
     pub fn position(&self) -> GetterResult<Angle> {
-        self.0.borrow().position()
+        let result = self.0.borrow().position();
+        self.1.remember(&self.1.position, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::position`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_position(&self) -> Option<(Angle, Duration)> {
+        self.1.position.get(Instant::now())
     }
 
     /// See [`MotorGroup::current`].
     pub fn current(&self) -> GetterResult<f64> {
-        self.0.borrow().current()
+        let result = self.0.borrow().current();
+        self.1.remember(&self.1.current, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::current`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_current(&self) -> Option<(f64, Duration)> {
+        self.1.current.get(Instant::now())
     }
 
     /// See [`MotorGroup::efficiency`].
     pub fn efficiency(&self) -> GetterResult<f64> {
-        self.0.borrow().efficiency()
+        let result = self.0.borrow().efficiency();
+        self.1.remember(&self.1.efficiency, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::efficiency`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_efficiency(&self) -> Option<(f64, Duration)> {
+        self.1.efficiency.get(Instant::now())
     }
 
     /// See [`MotorGroup::reset_position`].
@@ -132,38 +850,152 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
         self.0.borrow_mut().set_position(position)
     }
 
+    /// See [`MotorGroup::stale_motors`].
+    pub fn stale_motors(&self) -> Vec<usize> {
+        self.0.borrow().stale_motors()
+    }
+
+    /// See [`MotorGroup::has_inconsistent_reference`].
+    pub fn has_inconsistent_reference(&self) -> bool {
+        self.0.borrow().has_inconsistent_reference()
+    }
+
+    /// See [`MotorGroup::rezero`].
+    pub fn rezero(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().rezero()
+    }
+
     /// See [`MotorGroup::set_current_limit`].
-    pub fn set_current_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
+    pub fn set_current_limit(
+        &mut self,
+        limit: f64,
+    ) -> Result<(), MotorGroupError<SetCurrentLimitError>> {
         self.0.borrow_mut().set_current_limit(limit)
     }
 
+    /// See [`MotorGroup::set_total_current_limit`].
+    pub fn set_total_current_limit(
+        &mut self,
+        total: f64,
+    ) -> Result<(), MotorGroupError<SetCurrentLimitError>> {
+        self.0.borrow_mut().set_total_current_limit(total)
+    }
+
+    /// See [`MotorGroup::apply_torque_limit`].
+    pub fn apply_torque_limit(&mut self, max_torque: f64, current_floor: f64) -> GetterResult<()> {
+        self.0
+            .borrow_mut()
+            .apply_torque_limit(max_torque, current_floor)
+    }
+
+    /// See [`MotorGroup::max_current`].
+    pub fn max_current(&self) -> f64 {
+        self.0.borrow().max_current()
+    }
+
+    /// See [`MotorGroup::max_current_per_motor`].
+    pub fn max_current_per_motor(&self) -> Vec<f64> {
+        self.0.borrow().max_current_per_motor()
+    }
+
     /// See [`MotorGroup::set_voltage_limit`].
     pub fn set_voltage_limit(&mut self, limit: f64) -> Result<(), MotorGroupError> {
         self.0.borrow_mut().set_voltage_limit(limit)
     }
 
+    /// See [`MotorGroup::set_hard_voltage_cap`].
+    pub fn set_hard_voltage_cap(&mut self, volts: f64) -> bool {
+        self.0.borrow_mut().set_hard_voltage_cap(volts)
+    }
+
+    /// See [`MotorGroup::override_hard_voltage_cap`].
+    pub fn override_hard_voltage_cap(&mut self, volts: Option<f64>) -> bool {
+        self.0.borrow_mut().override_hard_voltage_cap(volts)
+    }
+
+    /// See [`MotorGroup::hard_voltage_cap`].
+    pub fn hard_voltage_cap(&self) -> Option<f64> {
+        self.0.borrow().hard_voltage_cap()
+    }
+
+    /// See [`MotorGroup::set_position_limits`].
+    pub fn set_position_limits(&mut self, min: Angle, max: Angle) {
+        self.0.borrow_mut().set_position_limits(min, max);
+    }
+
+    /// See [`MotorGroup::clear_position_limits`].
+    pub fn clear_position_limits(&mut self) {
+        self.0.borrow_mut().clear_position_limits();
+    }
+
+    /// See [`MotorGroup::position_limits`].
+    pub fn position_limits(&self) -> Option<(Angle, Angle)> {
+        self.0.borrow().position_limits()
+    }
+
+    /// See [`MotorGroup::within_position_limits`].
+    pub fn within_position_limits(&self) -> GetterResult<bool> {
+        self.0.borrow().within_position_limits()
+    }
+
     /// See [`MotorGroup::temperature`].
     pub fn temperature(&self) -> GetterResult<f64> {
-        self.0.borrow().temperature()
+        let result = self.0.borrow().temperature();
+        self.1.remember(&self.1.temperature, &result);
+        result
+    }
+
+    /// Returns the last successfully read [`SharedMotors::temperature`] and how long
+    /// ago it was read, without borrowing the motor group.
+    ///
+    /// Returns `None` if it hasn't been read since the last known values cache
+    /// was enabled. See [`SharedMotors::set_last_known_cache`].
+    pub fn last_known_temperature(&self) -> Option<(f64, Duration)> {
+        self.1.temperature.get(Instant::now())
+    }
+
+    /// See [`MotorGroup::temperature_fraction`].
+    pub fn temperature_fraction(&self) -> GetterResult<f64> {
+        self.0.borrow().temperature_fraction()
+    }
+
+    /// See [`MotorGroup::current_fraction`].
+    pub fn current_fraction(&self) -> GetterResult<f64> {
+        self.0.borrow().current_fraction()
+    }
+
+    /// See [`MotorGroup::output_fraction`].
+    pub fn output_fraction(&self) -> GetterResult<f64> {
+        self.0.borrow().output_fraction()
+    }
+
+    /// See [`MotorGroup::commanded_direction`].
+    pub fn commanded_direction(&self) -> Option<Sign> {
+        self.0.borrow().commanded_direction()
+    }
+
+    /// See [`MotorGroup::direction_mismatch`].
+    pub fn direction_mismatch(&self, min_velocity: f64) -> GetterResult<Vec<usize>> {
+        self.0.borrow().direction_mismatch(min_velocity)
     }
 
     /// See [`MotorGroup::is_over_temperature`].
-    pub fn is_over_temperature(&self) -> Result<bool, MotorGroupError> {
+    pub fn is_over_temperature(&self) -> GetterResult<bool> {
         self.0.borrow().is_over_temperature()
     }
 
     /// See [`MotorGroup::is_over_current`].
-    pub fn is_over_current(&self) -> Result<bool, MotorGroupError> {
+    pub fn is_over_current(&self) -> GetterResult<bool> {
         self.0.borrow().is_over_current()
     }
 
     /// See [`MotorGroup::is_driver_fault`].
-    pub fn is_driver_fault(&self) -> Result<bool, MotorGroupError> {
+    pub fn is_driver_fault(&self) -> GetterResult<bool> {
         self.0.borrow().is_driver_fault()
     }
 
     /// See [`MotorGroup::is_driver_over_current`].
-    pub fn is_driver_over_current(&self) -> Result<bool, MotorGroupError> {
+    pub fn is_driver_over_current(&self) -> GetterResult<bool> {
         self.0.borrow().is_driver_over_current()
     }
 
@@ -171,4 +1003,92 @@ impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
     pub fn set_direction(&mut self, direction: Direction) -> Result<(), MotorGroupError> {
         self.0.borrow_mut().set_direction(direction)
     }
+
+    /// See [`MotorGroup::flip_all`].
+    pub fn flip_all(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().flip_all()
+    }
+
+    /// See [`MotorGroup::directions`].
+    pub fn directions(&self) -> Vec<Option<Direction>> {
+        self.0.borrow().directions()
+    }
+
+    /// See [`MotorGroup::misdirected_motors`].
+    pub fn misdirected_motors(&self) -> GetterResult<Vec<usize>> {
+        self.0.borrow().misdirected_motors()
+    }
+}
+
+impl SharedMotors<Vec<Motor>> {
+    /// See [`MotorGroup::add_motor`].
+    pub fn add_motor(&mut self, motor: Motor) -> usize {
+        self.0.borrow_mut().add_motor(motor)
+    }
+
+    /// See [`MotorGroup::remove_motor`].
+    pub fn remove_motor(&mut self, index: usize) -> Option<Motor> {
+        self.0.borrow_mut().remove_motor(index)
+    }
+}
+
+/// A mutable borrow of the motor group inside [`SharedMotors`], returned by
+/// [`SharedMotors::lock`].
+///
+/// The borrow is released when the guard is dropped.
+#[derive(Debug)]
+pub struct MotorGroupGuard<'a, M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>>(
+    RefMut<'a, MotorGroup<M>>,
+);
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Deref for MotorGroupGuard<'_, M> {
+    type Target = MotorGroup<M>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> DerefMut for MotorGroupGuard<'_, M> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::prelude::*;
+
+    use super::SharedMotors;
+    use crate::{WriteErrorStrategy, tests::mock_motors};
+
+    fn shared() -> SharedMotors {
+        SharedMotors::from_motors(mock_motors(2, Gearset::Green))
+    }
+
+    #[test]
+    fn guard_holds_borrow_until_dropped() {
+        let shared = shared();
+        let clone = shared.clone();
+        {
+            let mut motors = shared.lock();
+            motors.write_error_strategy(WriteErrorStrategy::Stop);
+            assert_eq!(motors.set_voltage(6.0).unwrap_err().errors.len(), 1);
+            assert!(motors.velocity().is_err());
+            // Nothing else can borrow the group while the guard is held
+            assert!(clone.0.try_borrow().is_err());
+        }
+        assert_eq!(
+            clone.current_config().write_error_strategy,
+            WriteErrorStrategy::Stop
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn locking_twice_panics() {
+        let shared = shared();
+        let _guard = shared.lock();
+        let _ = shared.clone().lock();
+    }
 }
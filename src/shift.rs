@@ -0,0 +1,242 @@
+use alloc::vec::Vec;
+
+use vexide::{
+    math::Angle,
+    smart::motor::{BrakeMode, Motor, MotorControl},
+};
+
+use crate::{MotorGroup, MotorGroupError, readings, velocity};
+
+/// Rewrites a target so that the mechanism keeps chasing the same target
+/// after its gear ratio is multiplied by `factor`.
+///
+/// `position` is the motor's position in degrees, which is only needed for
+/// position targets: the remaining distance to the target is scaled, so the
+/// motor doesn't jump. Returns `None` for targets that don't depend on the
+/// ratio (voltages and brakes).
+pub(crate) fn rescale_target(
+    target: MotorControl,
+    position: Option<f64>,
+    factor: f64,
+) -> Option<MotorControl> {
+    let rescale_velocity = |rpm: i32| velocity::round_rpm(f64::from(rpm) * factor);
+    match target {
+        MotorControl::Velocity(rpm) => Some(MotorControl::Velocity(rescale_velocity(rpm))),
+        MotorControl::Position(target, velocity) => {
+            let position = position?;
+            let target = position + (target.as_degrees() - position) * factor;
+            Some(MotorControl::Position(
+                Angle::from_degrees(target),
+                rescale_velocity(velocity),
+            ))
+        }
+        _ => None,
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Changes the external gear ratio between the motors and the mechanism
+    /// they drive, such as when a two-speed transmission shifts.
+    ///
+    /// The ratio is the number of motor revolutions per revolution of the
+    /// mechanism, and starts at `1.0`. Shifting multiplies it by
+    /// `new_external_ratio / old_ratio`, and every motor's active target is
+    /// rewritten so the mechanism keeps doing the same thing:
+    ///
+    /// - A velocity target is multiplied by the factor, so the mechanism
+    ///   keeps its speed.
+    /// - A position target is moved so the *remaining* distance is
+    ///   multiplied by the factor, measured from each motor's own position,
+    ///   so the mechanism still stops in the same place. Its profile velocity
+    ///   is multiplied by the factor too.
+    /// - Voltages and brakes don't depend on the ratio, so they are left
+    ///   alone.
+    ///
+    /// Each motor's own last target (see [`Motor::target`]) is rewritten, so
+    /// per-motor scales (see [`MotorGroup::set_scale`]) are kept. As with
+    /// [`MotorGroup::set_target`], a motor that missed the last
+    /// [`MotorGroup::set_direction`] is sent the direction again before its
+    /// rewritten target. Shift the transmission itself (for example, fire the
+    /// pneumatics) right before calling this.
+    ///
+    /// The new ratio is recorded even if some motors fail. Since a mix of
+    /// old-scale and new-scale targets would make the motors fight each
+    /// other, if any motor can't be read or rewritten, every motor is braked
+    /// with [`BrakeMode::Hold`] instead, leaving the group stopped until it's
+    /// given a new command.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing the errors of the
+    ///   rewrite followed by those of the brake if a motor device is not
+    ///   currently connected to the Smart Port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_external_ratio` isn't finite and positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     let mut shifter = AdiDigitalOut::new(peripherals.adi_a);
+    ///
+    ///     _ = drive.set_velocity(400);
+    ///     sleep(Duration::from_secs(1)).await;
+    ///
+    ///     // Shift into the low gear, which is twice as slow.
+    ///     _ = shifter.set_high();
+    ///     _ = drive.shift_ratio(2.0);
+    /// }
+    /// ```
+    pub fn shift_ratio(&mut self, new_external_ratio: f64) -> Result<(), MotorGroupError> {
+        crate::check_invariant(
+            new_external_ratio.is_finite() && new_external_ratio > 0.0,
+            "external gear ratio must be finite and positive",
+        );
+        let factor = new_external_ratio / self.config.external_ratio;
+        self.config.external_ratio = new_external_ratio;
+
+        // Only position targets need the motor's position to be rewritten
+        let current: Vec<MotorControl> = self.motors.as_ref().iter().map(Motor::target).collect();
+        let (positions, mut errors) = readings::partition(self.read_where(
+            |index| matches!(current[index], MotorControl::Position(..)),
+            Motor::position,
+        ));
+        let rescaled: Vec<Option<MotorControl>> = current
+            .iter()
+            .enumerate()
+            .map(|(index, &target)| {
+                let position = positions
+                    .iter()
+                    .find(|(read, _)| *read == index)
+                    .map(|(_, position)| position.as_degrees());
+                rescale_target(target, position, factor)
+            })
+            .collect();
+        let targets: Vec<MotorControl> = rescaled
+            .iter()
+            .zip(&current)
+            .map(|(rescaled, current)| rescaled.unwrap_or(*current))
+            .collect();
+        let (result, _) = self.write_targets_where(&targets, |index| rescaled[index].is_some());
+        if let Err(error) = result {
+            errors.extend(error.errors);
+        }
+
+        if errors.is_empty() {
+            let position =
+                readings::mean(positions.iter().map(|(_, position)| position.as_degrees()));
+            self.last_command = self
+                .last_command
+                .and_then(|command| rescale_target(command, position, factor))
+                .or(self.last_command);
+            return Ok(());
+        }
+        if let Err(error) = self.brake(BrakeMode::Hold) {
+            errors.extend(error.errors);
+        }
+        Err(MotorGroupError::new(errors))
+    }
+
+    /// Returns the external gear ratio set with [`MotorGroup::shift_ratio`].
+    pub fn external_ratio(&self) -> f64 {
+        self.config.external_ratio
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::motor::{BrakeMode, MotorControl},
+    };
+
+    use super::rescale_target;
+    use crate::{GroupConfig, MotorGroup, tests::mock_motors};
+
+    /// Asserts that `target` is a position target of `degrees` at `velocity`,
+    /// up to rounding from the conversion through radians.
+    fn assert_position(target: Option<MotorControl>, degrees: f64, velocity: i32) {
+        match target {
+            Some(MotorControl::Position(target, target_velocity)) => {
+                assert!((target.as_degrees() - degrees).abs() < 1e-9);
+                assert_eq!(target_velocity, velocity);
+            }
+            other => panic!("expected a position target, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn position_targets_keep_their_mechanism_target() {
+        let target = MotorControl::Position(Angle::from_degrees(900.0), 200);
+        // 180° from the target at 1:1 is 360° from it at 2:1
+        assert_position(rescale_target(target, Some(720.0), 2.0), 1080.0, 400);
+        // Shifting back undoes it
+        let shifted = rescale_target(target, Some(720.0), 2.0).unwrap();
+        assert_position(rescale_target(shifted, Some(720.0), 0.5), 900.0, 200);
+        // Moving backwards, from a motor past its target
+        assert_position(rescale_target(target, Some(1000.0), 0.5), 950.0, 100);
+        // A motor already at its target stays there
+        assert_position(rescale_target(target, Some(900.0), 3.0), 900.0, 600);
+        // Without the motor's position, the target can't be rewritten
+        assert_eq!(rescale_target(target, None, 2.0), None);
+    }
+
+    #[test]
+    fn velocity_targets_keep_their_mechanism_speed() {
+        assert_eq!(
+            rescale_target(MotorControl::Velocity(400), None, 0.5),
+            Some(MotorControl::Velocity(200))
+        );
+        assert_eq!(
+            rescale_target(MotorControl::Velocity(-101), None, 1.5),
+            Some(MotorControl::Velocity(-152))
+        );
+        // Voltages and brakes don't depend on the ratio
+        assert_eq!(rescale_target(MotorControl::Voltage(6.0), None, 2.0), None);
+        let brake = MotorControl::Brake(BrakeMode::Hold);
+        assert_eq!(rescale_target(brake, Some(10.0), 2.0), None);
+    }
+
+    #[test]
+    fn shifting_records_the_ratio() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Blue));
+        assert_eq!(group.external_ratio(), 1.0);
+        // The mock motor never accepted a target, so it's still on its
+        // initial one and there is nothing to rewrite
+        assert!(group.shift_ratio(2.0).is_ok());
+        assert_eq!(group.external_ratio(), 2.0);
+        assert_eq!(group.current_config().external_ratio, 2.0);
+        assert_eq!(group.last_command, None);
+
+        // The group's own last command is rescaled with it
+        _ = group.set_velocity(300);
+        assert!(group.shift_ratio(3.0).is_ok());
+        assert_eq!(group.last_command, Some(MotorControl::Velocity(450)));
+
+        let config = GroupConfig {
+            external_ratio: 0.5,
+            ..GroupConfig::DEFAULT
+        };
+        _ = group.apply_config(&config);
+        assert_eq!(group.external_ratio(), 0.5);
+    }
+
+    #[cfg(any(not(feature = "no-panic"), debug_assertions))]
+    #[test]
+    #[should_panic]
+    fn zero_ratio_panics() {
+        let mut group = MotorGroup::new(mock_motors(1, Gearset::Blue));
+        _ = group.shift_ratio(0.0);
+    }
+}
@@ -0,0 +1,202 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::{Motor, MotorControl};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError, SharedMotors, TankError, readings, tank};
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Runs the motors at `indices` at `volts` Volts, leaving the others
+    /// alone. See [`DriveSide::set_voltage`].
+    fn set_side_voltage(&mut self, indices: &[usize], volts: f64) -> Result<(), MotorGroupError> {
+        self.last_command = None;
+        let targets: Vec<MotorControl> = self
+            .meta
+            .iter()
+            .map(|meta| meta.scale_target(MotorControl::Voltage(volts)))
+            .collect();
+        let (result, written) =
+            self.write_targets_where(&targets, |index| indices.contains(&index));
+        self.track_fallback(MotorControl::Voltage(volts), &written);
+        result
+    }
+}
+
+/// One side of a skid-steer drivetrain kept in a single group, returned by
+/// [`SharedMotors::sides`].
+///
+/// A side is a handle to the whole group that only commands and reads the
+/// motors it was given. Like any other [`SharedMotors`] handle, it borrows the
+/// group only for the length of each call, so both sides and the group itself
+/// can be used in any order, such as from a chassis library that expects
+/// separate left and right motors. Calling a side while the group is borrowed
+/// elsewhere, such as through [`SharedMotors::lock`], panics.
+///
+/// The indices are fixed when the sides are made. If motors are added to or
+/// removed from the group afterwards (see [`MotorGroup::remove_motor`]),
+/// make the sides again.
+#[derive(Debug)]
+pub struct DriveSide<M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>> {
+    group: SharedMotors<M>,
+    indices: Vec<usize>,
+}
+
+// Not derived, since that would require `M: Clone`.
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Clone for DriveSide<M> {
+    fn clone(&self) -> Self {
+        Self {
+            group: self.group.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> DriveSide<M> {
+    /// Returns the indices in the group of the motors on this side.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Runs the motors on this side at `volts` Volts, leaving the other side
+    /// alone.
+    ///
+    /// This writes like [`MotorGroup::set_tank`] does for one side: each
+    /// motor's output scale and the hard voltage cap are applied, disabled
+    /// and checked out motors are skipped, and afterwards the group has no
+    /// single command (see [`MotorGroup::commanded_direction`]).
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device on this
+    ///   side is not currently connected to the Smart Port.
+    pub fn set_voltage(&mut self, volts: f64) -> Result<(), MotorGroupError> {
+        self.group
+            .0
+            .borrow_mut()
+            .set_side_voltage(&self.indices, volts)
+    }
+
+    /// Returns the average velocity of the motors on this side, in RPM.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor on this side
+    ///   encounters an error, or without a result if the side has no motors
+    ///   that can be read. Its result is the average of the motors that could
+    ///   be read.
+    pub fn velocity(&self) -> GetterResult<f64> {
+        let group = self.group.0.borrow();
+        readings::average(group.read_where(|index| self.indices.contains(&index), Motor::velocity))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Splits a skid-steer drivetrain kept in a single group into its left
+    /// and right sides, without splitting the group.
+    ///
+    /// Each [`DriveSide`] holds a clone of this handle along with its indices,
+    /// so the group stays whole: its settings, its other methods and
+    /// [`MotorGroup::set_tank`] keep working on every motor, and the sides can
+    /// be handed to a library that drives each side on its own.
+    ///
+    /// Together, the two sides must list every motor in the group exactly
+    /// once, as with [`MotorGroup::set_tank`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`TankError::OutOfRange`], [`TankError::Overlap`], or
+    ///   [`TankError::Uncovered`] error is returned for every problem with the
+    ///   partition, and no sides are made.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Reverse),
+    ///         Motor::new(peripherals.port_4, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///     let (mut left, mut right) = drive.sides(&[0, 1], &[2, 3]).unwrap();
+    ///
+    ///     // Turn in place
+    ///     _ = left.set_voltage(6.0);
+    ///     _ = right.set_voltage(-6.0);
+    /// }
+    /// ```
+    pub fn sides(
+        &self,
+        left: &[usize],
+        right: &[usize],
+    ) -> Result<(DriveSide<M>, DriveSide<M>), MotorGroupError<TankError>> {
+        let len = self.0.borrow().motors.as_ref().len();
+        tank::partition(len, left, right).map_err(MotorGroupError::new)?;
+        let side = |indices: &[usize]| DriveSide {
+            group: self.clone(),
+            indices: indices.to_vec(),
+        };
+        Ok((side(left), side(right)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use crate::{SharedMotors, TankError, tests::mock_motors};
+
+    fn drive() -> SharedMotors {
+        SharedMotors::from_motors(mock_motors(4, Gearset::Blue))
+    }
+
+    fn disconnected(ports: &[u8]) -> Vec<PortError> {
+        ports
+            .iter()
+            .map(|&port| PortError::Disconnected { port })
+            .collect()
+    }
+
+    #[test]
+    fn sides_are_driven_independently() {
+        let drive = drive();
+        let (mut left, mut right) = drive.sides(&[0, 3], &[1, 2]).unwrap();
+        assert_eq!(left.indices(), [0, 3]);
+
+        // Every write to the mock motors fails, so the errors show which
+        // motors each side reached
+        let error = left.set_voltage(6.0).unwrap_err();
+        assert_eq!(error.errors, disconnected(&[1, 4]));
+        let error = right.set_voltage(-6.0).unwrap_err();
+        assert_eq!(error.errors, disconnected(&[2, 3]));
+        assert_eq!(drive.command_generation(), 2);
+        assert_eq!(drive.0.borrow().last_command, None);
+
+        let error = right.velocity().unwrap_err();
+        assert_eq!(error.errors, disconnected(&[2, 3]));
+        assert_eq!(error.result, None);
+
+        // The group itself still reaches every motor
+        assert_eq!(
+            drive.velocity().unwrap_err().errors,
+            disconnected(&[1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn wrong_partitions_make_no_sides() {
+        let drive = drive();
+        let error = drive.sides(&[0, 1], &[1, 2]).unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                TankError::Overlap { index: 1 },
+                TankError::Uncovered { index: 3 },
+            ]
+        );
+        assert!(drive.sides(&[0, 1], &[2, 3, 4]).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use core::{future::Future, time::Duration};
+
+use vexide::{math::Angle, smart::motor::Motor};
+
+use crate::{GetterResult, MotorGroup, SharedMotors, tick};
+
+/// The main measurements of a motor group, read together and tagged with the
+/// command they were read under.
+///
+/// Returned by [`MotorGroup::snapshot`] and
+/// [`SharedMotors::snapshot_consistent`].
+#[derive(Debug)]
+pub struct GroupSnapshot {
+    /// See [`MotorGroup::velocity`].
+    pub velocity: GetterResult<f64>,
+    /// See [`MotorGroup::position`].
+    pub position: GetterResult<Angle>,
+    /// See [`MotorGroup::voltage`].
+    pub voltage: GetterResult<f64>,
+    /// See [`MotorGroup::current`].
+    pub current: GetterResult<f64>,
+    /// The [`MotorGroup::command_generation`] every value was read under, or
+    /// `None` if the group was written to while the snapshot was being read.
+    pub command_generation: Option<u64>,
+}
+
+/// Runs `read`, and runs it once more if `generation` changed while it did.
+///
+/// Returns the last value read, along with the generation it was read under
+/// if it didn't change.
+async fn read_consistent<T, F: Future<Output = T>>(
+    generation: impl Fn() -> u64,
+    mut read: impl FnMut() -> F,
+) -> (T, Option<u64>) {
+    let before = generation();
+    let value = read().await;
+    if generation() == before {
+        return (value, Some(before));
+    }
+
+    let before = generation();
+    let value = read().await;
+    (value, (generation() == before).then_some(before))
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns how many times the group has been written to.
+    ///
+    /// Every write to the group, including one that failed on some or all of
+    /// the motors, advances the generation by one. A reading taken under one
+    /// generation may disagree with one taken under another: while a
+    /// direction flip reaches each motor in turn, for example, the average
+    /// velocity can briefly spike. Comparing generations lets a consumer
+    /// discard values that straddle a command change.
+    ///
+    /// The generation wraps around after [`u64::MAX`] writes.
+    pub fn command_generation(&self) -> u64 {
+        self.command_generation
+    }
+
+    /// Reads the group's velocity, position, voltage, and current at once.
+    ///
+    /// The group can't be written to while it's borrowed, so every value is
+    /// read under the same [`MotorGroup::command_generation`]. To take a
+    /// snapshot of [`SharedMotors`] without holding the borrow throughout,
+    /// see [`SharedMotors::snapshot_consistent`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///
+    ///     let snapshot = motor_group.snapshot();
+    ///     if let (Ok(velocity), Ok(current)) = (snapshot.velocity, snapshot.current) {
+    ///         println!("{velocity} RPM at {current} A");
+    ///     }
+    /// }
+    /// ```
+    pub fn snapshot(&self) -> GroupSnapshot {
+        GroupSnapshot {
+            velocity: self.velocity(),
+            position: self.position(),
+            voltage: self.voltage(),
+            current: self.current(),
+            command_generation: Some(self.command_generation),
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Reads the group's velocity, position, voltage, and current, yielding
+    /// to other tasks between each of them.
+    ///
+    /// Each value is read through its own borrow, like the getter of the same
+    /// name (which also updates the last known values cache), so a task that
+    /// writes to the group can run in between. If the
+    /// [`MotorGroup::command_generation`] changed while reading, the snapshot
+    /// is read once more. If it changed again, the second snapshot is
+    /// returned with a [`GroupSnapshot::command_generation`] of `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         let snapshot = drive.snapshot_consistent().await;
+    ///         // Skip samples taken across a command change
+    ///         if snapshot.command_generation.is_some() {
+    ///             println!("{:?} RPM", snapshot.velocity);
+    ///         }
+    ///         sleep(Duration::from_millis(50)).await;
+    ///     }
+    /// }
+    /// ```
+    pub async fn snapshot_consistent(&self) -> GroupSnapshot {
+        let read = || async {
+            let velocity = self.velocity();
+            tick::pause(Duration::ZERO).await;
+            let position = self.position();
+            tick::pause(Duration::ZERO).await;
+            let voltage = self.voltage();
+            tick::pause(Duration::ZERO).await;
+            let current = self.current();
+            (velocity, position, voltage, current)
+        };
+        let ((velocity, position, voltage, current), command_generation) =
+            read_consistent(|| self.command_generation(), read).await;
+        GroupSnapshot {
+            velocity,
+            position,
+            voltage,
+            current,
+            command_generation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        future::{self, Future},
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use vexide::prelude::*;
+
+    use super::read_consistent;
+    use crate::{MotorGroup, SharedMotors, tests::mock_motors};
+
+    fn motors() -> Vec<Motor> {
+        mock_motors(2, Gearset::Green)
+    }
+
+    /// Polls `future` until it completes, returning how many times it was
+    /// pending.
+    fn run<T>(future: impl Future<Output = T>) -> (T, usize) {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(future);
+        let mut pending = 0;
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(value) => return (value, pending),
+                Poll::Pending => pending += 1,
+            }
+        }
+    }
+
+    /// Reads with a generation that advances during each read listed in
+    /// `writes_during`, returning the result and how many reads happened.
+    fn read_with_writes(writes_during: &[usize]) -> ((usize, Option<u64>), usize) {
+        let generation = Cell::new(7);
+        let reads = Cell::new(0);
+        let (result, _) = run(read_consistent(
+            || generation.get(),
+            || {
+                reads.set(reads.get() + 1);
+                if writes_during.contains(&reads.get()) {
+                    generation.set(generation.get() + 1);
+                }
+                future::ready(reads.get())
+            },
+        ));
+        (result, reads.get())
+    }
+
+    #[test]
+    fn every_write_advances_the_generation() {
+        let mut group = MotorGroup::new(motors());
+        assert_eq!(group.command_generation(), 0);
+        // Failed writes still count
+        _ = group.set_voltage(6.0);
+        _ = group.set_gearset(Gearset::Blue);
+        assert_eq!(group.command_generation(), 2);
+        // Reads don't
+        _ = group.velocity();
+        assert_eq!(group.snapshot().command_generation, Some(2));
+    }
+
+    #[test]
+    fn reads_straddling_a_write_are_retried_once() {
+        // Nothing written: the first read is kept
+        assert_eq!(read_with_writes(&[]), ((1, Some(7)), 1));
+        // Written during the first read: the retry is consistent
+        assert_eq!(read_with_writes(&[1]), ((2, Some(8)), 2));
+        // Written during both: the second read is returned, untagged
+        assert_eq!(read_with_writes(&[1, 2]), ((2, None), 2));
+    }
+
+    #[test]
+    fn shared_snapshots_yield_between_reads() {
+        let drive = SharedMotors::from_motors(motors());
+        let (snapshot, pending) = run(drive.snapshot_consistent());
+        assert_eq!(pending, 3);
+        assert_eq!(snapshot.command_generation, Some(0));
+        assert_eq!(snapshot.velocity.unwrap_err().errors.len(), 2);
+    }
+}
@@ -0,0 +1,421 @@
+use alloc::vec::Vec;
+use core::{ops::ControlFlow, time::Duration};
+
+use vexide::smart::motor::{Motor, SetGearsetError};
+
+use crate::{
+    ConfigureError, GroupConfig, MotorGroup, MotorGroupError, SetCurrentLimitError, SharedMotors,
+    readings::{self, Reading},
+    tick::tick_loop,
+};
+
+/// Returns whether applying a configuration again could fix `error`, which is
+/// the case for motors that couldn't be reached.
+fn is_retryable(error: &ConfigureError) -> bool {
+    matches!(
+        error,
+        ConfigureError::Port { .. }
+            | ConfigureError::Gearset {
+                source: SetGearsetError::Port { .. }
+            }
+            | ConfigureError::CurrentLimit {
+                source: SetCurrentLimitError::Port { .. }
+            }
+    )
+}
+
+/// Polls every `interval` until `poll` has no errors, or returns the errors
+/// of the last poll at `timeout`.
+pub(crate) async fn wait_ready(
+    interval: Duration,
+    timeout: Duration,
+    mut poll: impl FnMut() -> Vec<Reading<()>>,
+) -> Result<(), MotorGroupError> {
+    tick_loop(interval, Some(timeout), |elapsed| {
+        let (_, errors) = readings::partition(poll());
+        if errors.is_empty() {
+            ControlFlow::Break(Ok(()))
+        } else if elapsed >= timeout {
+            ControlFlow::Break(Err(MotorGroupError::new(errors)))
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .await
+}
+
+/// Polls `group` with `ready` every `interval` until it has no errors, then
+/// applies its configuration with `apply`.
+///
+/// Applying is retried every tick, waiting for readiness again first, for as
+/// long as it only fails with retryable errors (see [`is_retryable`]). At
+/// `timeout`, the configuration is applied one last time whether the group is
+/// ready or not, and the result is returned.
+async fn initialize_with<G>(
+    interval: Duration,
+    timeout: Duration,
+    group: &mut G,
+    ready: impl Fn(&G) -> Vec<Reading<()>>,
+    mut apply: impl FnMut(&mut G) -> Result<(), MotorGroupError<ConfigureError>>,
+) -> Result<(), MotorGroupError<ConfigureError>> {
+    tick_loop(interval, Some(timeout), |elapsed| {
+        let timed_out = elapsed >= timeout;
+        let (_, not_ready) = readings::partition(ready(group));
+        if !not_ready.is_empty() && !timed_out {
+            return ControlFlow::Continue(());
+        }
+        match apply(group) {
+            Ok(()) => ControlFlow::Break(Ok(())),
+            Err(error) if timed_out || !error.errors.iter().all(is_retryable) => {
+                ControlFlow::Break(Err(error))
+            }
+            Err(_) => ControlFlow::Continue(()),
+        }
+    })
+    .await
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Reads the status flags of every active motor, which only succeeds once
+    /// the motor is connected and responding.
+    pub(crate) fn status_readings(&self) -> Vec<Reading<()>> {
+        self.read_each(|motor| motor.status().map(drop))
+    }
+
+    /// Waits until every motor in the group responds, or until `timeout` has
+    /// passed.
+    ///
+    /// For the first few hundred milliseconds after the program starts, the
+    /// brain hasn't finished detecting its devices, so reads and writes fail
+    /// with port errors, and any configuration written then is lost. This
+    /// reads each motor's status flags (see [`Motor::status`]), a single cheap
+    /// read, every [tick](MotorGroup::tick_interval) until none fail.
+    /// Disabled and checked out motors aren't waited on.
+    ///
+    /// To also apply the group's configuration once it's ready, use
+    /// [`MotorGroup::initialize`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if some motors still don't
+    ///   respond at `timeout`, containing their errors from the last poll.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if let Err(error) = lift.wait_until_ready(Duration::from_secs(1)).await {
+    ///         println!("Some lift motors never came up: {error}");
+    ///     }
+    ///     _ = lift.set_voltage_limit(10.0);
+    /// }
+    /// ```
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<(), MotorGroupError> {
+        wait_ready(self.config.tick_interval, timeout, || {
+            self.status_readings()
+        })
+        .await
+    }
+
+    /// Waits until every motor in the group responds, then applies `config`
+    /// to it, retrying until it sticks or `timeout` has passed.
+    ///
+    /// This waits like [`MotorGroup::wait_until_ready`], then applies the
+    /// configuration like [`MotorGroup::apply_config`]. If that fails only
+    /// because some motors couldn't be reached, those motors have dropped out
+    /// again, so this waits for them and applies the configuration again on
+    /// the next tick. Retrying rewrites the hardware settings of every motor,
+    /// which doesn't change the motors that already have them. Errors that
+    /// retrying can't fix, such as a current limit above the hardware maximum
+    /// or a failed validation, are returned straight away.
+    ///
+    /// If some motors still don't respond at `timeout`, the configuration is
+    /// applied once more anyway, so that at least the motors that do respond
+    /// are configured.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned containing every error from
+    ///   the last attempt to apply the configuration, if it failed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// const LIFT_CONFIG: GroupConfig = GroupConfig {
+    ///     gearset: Some(Gearset::Red),
+    ///     voltage_limit: Some(10.0),
+    ///     ..GroupConfig::DEFAULT
+    /// };
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     lift.initialize(&LIFT_CONFIG, Duration::from_secs(1))
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn initialize(
+        &mut self,
+        config: &GroupConfig,
+        timeout: Duration,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        initialize_with(
+            self.config.tick_interval,
+            timeout,
+            self,
+            Self::status_readings,
+            |group| group.apply_config(config),
+        )
+        .await
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Waits until every motor in the group responds, borrowing the group
+    /// only while polling it.
+    ///
+    /// See [`MotorGroup::wait_until_ready`].
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<(), MotorGroupError> {
+        let interval = self.0.borrow().config.tick_interval;
+        wait_ready(interval, timeout, || self.0.borrow().status_readings()).await
+    }
+
+    /// Waits until every motor in the group responds, then applies `config`
+    /// to it, borrowing the group only while polling and applying.
+    ///
+    /// See [`MotorGroup::initialize`].
+    pub async fn initialize(
+        &mut self,
+        config: &GroupConfig,
+        timeout: Duration,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        let interval = self.0.borrow().config.tick_interval;
+        initialize_with(
+            interval,
+            timeout,
+            self,
+            |shared| shared.0.borrow().status_readings(),
+            |shared| shared.0.borrow_mut().apply_config(config),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    use vexide::{
+        prelude::*,
+        smart::{PortError, motor::SetGearsetError},
+    };
+
+    use super::{initialize_with, wait_ready};
+    use crate::{
+        ConfigureError, GroupConfig, MotorGroup, MotorGroupError, SetCurrentLimitError,
+        readings::Reading, tests::mock_motors,
+    };
+
+    const LONG: Duration = Duration::from_secs(5);
+
+    /// Polls `future` until it completes. With a zero interval, the loops
+    /// only ever yield, so they never need a runtime to wake them.
+    fn run<T>(future: impl Future<Output = T>) -> T {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+                return value;
+            }
+        }
+    }
+
+    fn disconnected(index: usize) -> PortError {
+        PortError::Disconnected {
+            port: index as u8 + 1,
+        }
+    }
+
+    /// Motors that each start responding after a scripted number of polls,
+    /// and a configuration that fails to apply a scripted number of times.
+    struct ScriptedGroup {
+        ready_after: Vec<usize>,
+        polls: Cell<usize>,
+        failed_applies: usize,
+        apply_error: ConfigureError,
+        applies: usize,
+    }
+
+    impl ScriptedGroup {
+        fn new(ready_after: Vec<usize>) -> Self {
+            Self {
+                ready_after,
+                polls: Cell::new(0),
+                failed_applies: 0,
+                apply_error: ConfigureError::Port {
+                    source: disconnected(0),
+                },
+                applies: 0,
+            }
+        }
+
+        fn status(&self) -> Vec<Reading<()>> {
+            let polls = self.polls.get();
+            self.polls.set(polls + 1);
+            self.ready_after
+                .iter()
+                .enumerate()
+                .map(|(index, &after)| {
+                    (
+                        index,
+                        if polls >= after {
+                            Ok(())
+                        } else {
+                            Err(disconnected(index))
+                        },
+                    )
+                })
+                .collect()
+        }
+
+        fn apply(&mut self) -> Result<(), MotorGroupError<ConfigureError>> {
+            self.applies += 1;
+            if self.applies > self.failed_applies {
+                Ok(())
+            } else {
+                Err(MotorGroupError::new(vec![self.apply_error]))
+            }
+        }
+    }
+
+    fn initialize(
+        group: &mut ScriptedGroup,
+        timeout: Duration,
+    ) -> Result<(), MotorGroupError<ConfigureError>> {
+        run(initialize_with(
+            Duration::ZERO,
+            timeout,
+            group,
+            ScriptedGroup::status,
+            ScriptedGroup::apply,
+        ))
+    }
+
+    fn motors() -> Vec<Motor> {
+        mock_motors(2, Gearset::Green)
+    }
+
+    #[test]
+    fn waits_for_the_slowest_motor() {
+        let group = ScriptedGroup::new(vec![0, 3]);
+        run(wait_ready(Duration::ZERO, LONG, || group.status())).unwrap();
+        assert_eq!(group.polls.get(), 4);
+    }
+
+    #[test]
+    fn stragglers_are_reported_at_the_timeout() {
+        let group = ScriptedGroup::new(vec![0, usize::MAX]);
+        let error = run(wait_ready(Duration::ZERO, Duration::ZERO, || {
+            group.status()
+        }))
+        .unwrap_err();
+        assert_eq!(error.errors, vec![disconnected(1)]);
+        assert_eq!(group.polls.get(), 1);
+    }
+
+    #[test]
+    fn configuration_is_applied_once_ready() {
+        let mut group = ScriptedGroup::new(vec![2, 0]);
+        initialize(&mut group, LONG).unwrap();
+        assert_eq!(group.polls.get(), 3);
+        assert_eq!(group.applies, 1);
+    }
+
+    #[test]
+    fn unreachable_motors_are_retried() {
+        let mut group = ScriptedGroup::new(vec![0, 0]);
+        group.failed_applies = 2;
+        group.apply_error = ConfigureError::Gearset {
+            source: SetGearsetError::Port {
+                source: disconnected(1),
+            },
+        };
+        initialize(&mut group, LONG).unwrap();
+        assert_eq!(group.applies, 3);
+    }
+
+    #[test]
+    fn other_errors_are_not_retried() {
+        let mut group = ScriptedGroup::new(vec![0, 0]);
+        group.failed_applies = usize::MAX;
+        group.apply_error = ConfigureError::CurrentLimit {
+            source: SetCurrentLimitError::ExceedsMaximum {
+                index: None,
+                requested: 100.0,
+                maximum: 5.0,
+            },
+        };
+        let error = initialize(&mut group, LONG).unwrap_err();
+        assert_eq!(error.errors, vec![group.apply_error]);
+        assert_eq!(group.applies, 1);
+    }
+
+    #[test]
+    fn configuration_is_applied_at_the_timeout_anyway() {
+        let mut group = ScriptedGroup::new(vec![0, usize::MAX]);
+        group.failed_applies = usize::MAX;
+        let error = initialize(&mut group, Duration::ZERO).unwrap_err();
+        assert_eq!(error.errors, vec![group.apply_error]);
+        assert_eq!(group.applies, 1);
+    }
+
+    #[test]
+    fn disconnected_motors_time_out() {
+        let mut group = MotorGroup::new(motors());
+        let error = vexide::runtime::block_on(async move {
+            group
+                .wait_until_ready(Duration::from_millis(10))
+                .await
+                .unwrap_err();
+            // Software settings apply without the motors, hardware ones don't
+            group
+                .initialize(&GroupConfig::DEFAULT, Duration::ZERO)
+                .await
+                .unwrap();
+            group
+                .initialize(
+                    &GroupConfig {
+                        gearset: Some(Gearset::Blue),
+                        ..GroupConfig::DEFAULT
+                    },
+                    Duration::ZERO,
+                )
+                .await
+                .unwrap_err()
+        });
+        assert_eq!(error.errors.len(), 2);
+    }
+}
@@ -0,0 +1,340 @@
+use vexide::smart::motor::Motor;
+
+use crate::{
+    GetterResult, MotorGroup, SharedMotors,
+    readings::{self, Reading},
+    reference,
+};
+
+/// Simple statistics of one measurement across the motors of a group that
+/// could be read, returned by getters such as [`MotorGroup::velocity_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Stats {
+    /// The mean of the readings.
+    pub mean: f64,
+    /// The smallest reading.
+    pub min: f64,
+    /// The largest reading.
+    pub max: f64,
+    /// The population standard deviation of the readings: how far they
+    /// typically are from [`Stats::mean`]. This is `0.0` for a single
+    /// reading.
+    pub stddev: f64,
+    /// The number of readings, which is the number of motors that could be
+    /// read.
+    pub count: usize,
+}
+
+impl Stats {
+    /// Computes the statistics of `values` in a single pass, or returns
+    /// `None` if there are none.
+    ///
+    /// The mean and standard deviation are updated with Welford's method, so
+    /// they stay accurate for readings that are large compared to their
+    /// spread, such as positions late in a match.
+    pub(crate) fn of(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut stats: Option<Self> = None;
+        // The sum of squared differences from the running mean
+        let mut squares = 0.0;
+        for value in values {
+            let Some(stats) = &mut stats else {
+                stats = Some(Self {
+                    mean: value,
+                    min: value,
+                    max: value,
+                    stddev: 0.0,
+                    count: 1,
+                });
+                continue;
+            };
+            stats.count += 1;
+            let delta = value - stats.mean;
+            stats.mean += delta / stats.count as f64;
+            squares += delta * (value - stats.mean);
+            stats.min = stats.min.min(value);
+            stats.max = stats.max.max(value);
+        }
+        stats.map(|stats| Self {
+            stddev: (squares / stats.count as f64).sqrt(),
+            ..stats
+        })
+    }
+}
+
+/// Computes the statistics of per-motor readings.
+///
+/// The partial result of an error is the statistics of the motors that could
+/// be read.
+pub(crate) fn summarize(readings: impl IntoIterator<Item = Reading<f64>>) -> GetterResult<Stats> {
+    let (values, errors) = readings::partition(readings);
+    readings::finish(
+        Stats::of(values.into_iter().map(|(_, value)| value)),
+        errors,
+    )
+}
+
+/// Returns the smallest and largest of per-motor readings as `(min, max)`.
+///
+/// The partial result of an error is the range of the motors that could be
+/// read.
+pub(crate) fn range(readings: impl IntoIterator<Item = Reading<f64>>) -> GetterResult<(f64, f64)> {
+    let (values, errors) = readings::partition(readings);
+    let range = values
+        .into_iter()
+        .map(|(_, value)| (value, value))
+        .reduce(|(min, max), (value, _)| (min.min(value), max.max(value)));
+    readings::finish(range, errors)
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the mean, minimum, maximum and standard deviation of the
+    /// measured velocities of the motors in the group in rotations per minute
+    /// (RPM), read in one pass.
+    ///
+    /// Unlike [`MotorGroup::velocity`], only the motors that could be read
+    /// count, whatever [`MotorGroup::count_disabled_in_average`] is set to,
+    /// and the result isn't cached.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the
+    ///   statistics of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if let Ok(stats) = drive.velocity_stats()
+    ///         && stats.stddev > 20.0
+    ///     {
+    ///         println!("Motors are fighting: {:.0} to {:.0} RPM", stats.min, stats.max);
+    ///     }
+    /// }
+    /// ```
+    pub fn velocity_stats(&self) -> GetterResult<Stats> {
+        summarize(self.read_each(Motor::velocity))
+    }
+
+    /// Returns the statistics of the temperatures of the motors in the group
+    /// in degrees Celsius.
+    ///
+    /// See [`MotorGroup::velocity_stats`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the
+    ///   statistics of the motors that could be read.
+    pub fn temperature_stats(&self) -> GetterResult<Stats> {
+        summarize(self.read_each(Motor::temperature))
+    }
+
+    /// Returns the coolest and hottest temperatures of the motors in the
+    /// group in degrees Celsius, as `(min, max)`, read in one pass.
+    ///
+    /// This is the pair of bounds a thermal gauge needs, without the rest of
+    /// [`MotorGroup::temperature_stats`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the range
+    ///   of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     if let Ok((coolest, hottest)) = drive.temperature_range() {
+    ///         println!("Drive motors are {coolest:.0}-{hottest:.0} °C");
+    ///     }
+    /// }
+    /// ```
+    pub fn temperature_range(&self) -> GetterResult<(f64, f64)> {
+        range(self.read_each(Motor::temperature))
+    }
+
+    /// Returns the statistics of the currents drawn by the motors in the
+    /// group in Amperes.
+    ///
+    /// See [`MotorGroup::velocity_stats`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the
+    ///   statistics of the motors that could be read.
+    pub fn current_stats(&self) -> GetterResult<Stats> {
+        summarize(self.read_each(Motor::current))
+    }
+
+    /// Returns the statistics of the positions of the motors in the group in
+    /// degrees.
+    ///
+    /// Like [`MotorGroup::position`], motors whose position reference is stale
+    /// are left out unless every motor is (see
+    /// [`MotorGroup::position_spread_degrees`]). See also
+    /// [`MotorGroup::velocity_stats`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the
+    ///   statistics of the motors that could be read.
+    pub fn position_stats(&self) -> GetterResult<Stats> {
+        let (positions, errors) =
+            reference::comparable_positions(self.read_each(Motor::position), &self.meta);
+        readings::finish(Stats::of(positions), errors)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::velocity_stats`].
+    pub fn velocity_stats(&self) -> GetterResult<Stats> {
+        self.0.borrow().velocity_stats()
+    }
+
+    /// See [`MotorGroup::temperature_stats`].
+    pub fn temperature_stats(&self) -> GetterResult<Stats> {
+        self.0.borrow().temperature_stats()
+    }
+
+    /// See [`MotorGroup::temperature_range`].
+    pub fn temperature_range(&self) -> GetterResult<(f64, f64)> {
+        self.0.borrow().temperature_range()
+    }
+
+    /// See [`MotorGroup::current_stats`].
+    pub fn current_stats(&self) -> GetterResult<Stats> {
+        self.0.borrow().current_stats()
+    }
+
+    /// See [`MotorGroup::position_stats`].
+    pub fn position_stats(&self) -> GetterResult<Stats> {
+        self.0.borrow().position_stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{Stats, range, summarize};
+    use crate::{MotorGroup, SharedMotors, tests::mock_motors};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn statistics_match_hand_computed_values() {
+        let stats = Stats::of([2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(stats.count, 8);
+        assert_close(stats.mean, 5.0);
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 9.0);
+        assert_close(stats.stddev, 2.0);
+
+        // 100 and 300 are both 100 from their mean
+        let stats = Stats::of([300.0, 100.0]).unwrap();
+        assert_close(stats.mean, 200.0);
+        assert_close(stats.stddev, 100.0);
+    }
+
+    #[test]
+    fn small_samples_are_handled() {
+        assert_eq!(Stats::of([]), None);
+        assert_eq!(
+            Stats::of([-42.5]),
+            Some(Stats {
+                mean: -42.5,
+                min: -42.5,
+                max: -42.5,
+                stddev: 0.0,
+                count: 1,
+            })
+        );
+        // Identical readings have no spread at all
+        assert_eq!(Stats::of([3.0; 4]).unwrap().stddev, 0.0);
+    }
+
+    #[test]
+    fn large_readings_keep_their_spread() {
+        // Positions late in a match, a degree apart
+        let stats = Stats::of([1e9, 1e9 + 1.0, 1e9 + 2.0]).unwrap();
+        assert_close(stats.mean, 1e9 + 1.0);
+        assert_close(stats.stddev, (2.0f64 / 3.0).sqrt());
+    }
+
+    #[test]
+    fn partial_reads_summarize_what_could_be_read() {
+        let error = PortError::Disconnected { port: 2 };
+        let error = summarize([(0, Ok(100.0)), (1, Err(error)), (2, Ok(200.0))]).unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+        let stats = error.result.unwrap();
+        assert_eq!(stats.count, 2);
+        assert_close(stats.mean, 150.0);
+        assert_close(stats.stddev, 50.0);
+
+        assert!(matches!(
+            summarize([(0, Ok(100.0)), (1, Ok(100.0))]),
+            Ok(Stats { count: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn range_spans_the_readings() {
+        assert_eq!(
+            range([(0, Ok(41.5)), (1, Ok(55.0)), (2, Ok(30.0)), (3, Ok(55.0))]).unwrap(),
+            (30.0, 55.0)
+        );
+        assert_eq!(range([(0, Ok(45.0))]).unwrap(), (45.0, 45.0));
+
+        let error = PortError::Disconnected { port: 2 };
+        let error = range([(0, Ok(50.0)), (1, Err(error)), (2, Ok(35.0))]).unwrap_err();
+        assert_eq!(error.errors, vec![PortError::Disconnected { port: 2 }]);
+        assert_eq!(error.result, Some((35.0, 50.0)));
+    }
+
+    #[test]
+    fn unreadable_groups_have_no_statistics() {
+        let mut group = MotorGroup::new(mock_motors(3, Gearset::Green));
+        group.set_enabled(2, false);
+
+        for result in [
+            group.velocity_stats(),
+            group.temperature_stats(),
+            group.current_stats(),
+            group.position_stats(),
+        ] {
+            let error = result.unwrap_err();
+            assert_eq!(error.errors.len(), 2);
+            assert_eq!(error.result, None);
+        }
+        assert_eq!(group.temperature_range().unwrap_err().result, None);
+
+        let shared = SharedMotors::new(group);
+        assert_eq!(shared.velocity_stats().unwrap_err().errors.len(), 2);
+    }
+}
@@ -0,0 +1,231 @@
+use alloc::vec::Vec;
+
+use vexide::smart::{
+    PortError,
+    motor::{Motor, MotorControl},
+};
+
+use crate::{MotorGroup, MotorGroupError};
+
+/// Error returned by [`MotorGroup::set_tank`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TankError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// An index is out of bounds for the group.
+    OutOfRange {
+        /// The index given.
+        index: usize,
+    },
+    /// A motor was given more than once, on the same side or on both.
+    Overlap {
+        /// The index of the motor.
+        index: usize,
+    },
+    /// A motor wasn't given on either side.
+    Uncovered {
+        /// The index of the motor.
+        index: usize,
+    },
+}
+
+impl From<PortError> for TankError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for TankError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::OutOfRange { index } => write!(f, "motor {index} isn't in the group"),
+            Self::Overlap { index } => write!(f, "motor {index} is given more than once"),
+            Self::Uncovered { index } => write!(f, "motor {index} isn't on either side"),
+        }
+    }
+}
+
+impl core::error::Error for TankError {}
+
+/// Returns, for each of the `len` motors of a group, whether it's on the left
+/// side, or every problem with the partition.
+pub(crate) fn partition(
+    len: usize,
+    left_indices: &[usize],
+    right_indices: &[usize],
+) -> Result<Vec<bool>, Vec<TankError>> {
+    let mut sides: Vec<Option<bool>> = alloc::vec![None; len];
+    let mut errors = Vec::new();
+    let given = left_indices
+        .iter()
+        .map(|index| (*index, true))
+        .chain(right_indices.iter().map(|index| (*index, false)));
+    for (index, left) in given {
+        match sides.get_mut(index) {
+            None => errors.push(TankError::OutOfRange { index }),
+            Some(Some(_)) => errors.push(TankError::Overlap { index }),
+            Some(side) => *side = Some(left),
+        }
+    }
+    for (index, side) in sides.iter().enumerate() {
+        if side.is_none() {
+            errors.push(TankError::Uncovered { index });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(sides.into_iter().flatten().collect())
+    } else {
+        Err(errors)
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Drives a skid-steer drivetrain kept in a single group, running the
+    /// motors at `left_indices` at `left` Volts and those at `right_indices`
+    /// at `right` Volts.
+    ///
+    /// Together, the two sides must list every motor in the group exactly
+    /// once. This is checked before anything is written, so a wrong partition
+    /// never drives only part of the drivetrain. As with
+    /// [`MotorGroup::set_voltage`], each motor's output scale is applied and
+    /// disabled and checked out motors are skipped.
+    ///
+    /// Since the sides run at different voltages, the group no longer has a
+    /// single command afterwards, so methods that depend on the last command
+    /// (such as [`MotorGroup::commanded_direction`]) treat the group as if it
+    /// was never commanded.
+    ///
+    /// # Errors
+    ///
+    /// - A [`TankError::OutOfRange`], [`TankError::Overlap`], or
+    ///   [`TankError::Uncovered`] error is returned for every problem with the
+    ///   partition, and nothing is written.
+    /// - A [`TankError::Port`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Reverse),
+    ///         Motor::new(peripherals.port_4, Gearset::Blue, Direction::Reverse),
+    ///     ]);
+    ///     let controller = peripherals.primary_controller;
+    ///
+    ///     loop {
+    ///         let state = controller.state().unwrap_or_default();
+    ///         _ = drive.set_tank(
+    ///             state.left_stick.y() * 12.0,
+    ///             state.right_stick.y() * 12.0,
+    ///             &[0, 1],
+    ///             &[2, 3],
+    ///         );
+    ///         sleep(Motor::WRITE_INTERVAL).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_tank(
+        &mut self,
+        left: f64,
+        right: f64,
+        left_indices: &[usize],
+        right_indices: &[usize],
+    ) -> Result<(), MotorGroupError<TankError>> {
+        let sides = partition(self.motors.as_ref().len(), left_indices, right_indices)
+            .map_err(MotorGroupError::new)?;
+
+        self.last_command = None;
+        let targets: Vec<MotorControl> = sides
+            .iter()
+            .zip(&self.meta)
+            .map(|(left_side, meta)| {
+                meta.scale_target(MotorControl::Voltage(if *left_side { left } else { right }))
+            })
+            .collect();
+        let (result, written) = self.write_targets(&targets);
+        self.track_fallback(MotorControl::Voltage(left), &written);
+        result.map_err(|error| {
+            MotorGroupError::new(error.errors.into_iter().map(TankError::from).collect())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{TankError, partition};
+    use crate::{MotorGroup, Sign, tests::mock_motors};
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(4, Gearset::Blue))
+    }
+
+    #[test]
+    fn sides_are_assigned_by_index() {
+        assert_eq!(
+            partition(4, &[0, 1], &[2, 3]),
+            Ok(vec![true, true, false, false])
+        );
+        // The sides don't have to be contiguous or in order
+        assert_eq!(
+            partition(4, &[3, 0], &[1, 2]),
+            Ok(vec![true, false, false, true])
+        );
+        // One side may be empty
+        assert_eq!(partition(2, &[], &[1, 0]), Ok(vec![false, false]));
+    }
+
+    #[test]
+    fn wrong_partitions_are_rejected() {
+        assert_eq!(
+            partition(4, &[0, 1], &[1, 2]),
+            Err(vec![
+                TankError::Overlap { index: 1 },
+                TankError::Uncovered { index: 3 },
+            ])
+        );
+        assert_eq!(
+            partition(2, &[0, 0], &[1, 2]),
+            Err(vec![
+                TankError::Overlap { index: 0 },
+                TankError::OutOfRange { index: 2 },
+            ])
+        );
+
+        // Nothing is written, so the group keeps its last command
+        let mut group = group();
+        _ = group.set_voltage(6.0);
+        let error = group.set_tank(6.0, -6.0, &[0, 1], &[2]).unwrap_err();
+        assert_eq!(error.errors, vec![TankError::Uncovered { index: 3 }]);
+        assert_eq!(group.commanded_direction(), Some(Sign::Forward));
+    }
+
+    #[test]
+    fn every_motor_is_written() {
+        let mut group = group();
+        _ = group.set_voltage(6.0);
+        let error = group.set_tank(6.0, -6.0, &[0, 1], &[2, 3]).unwrap_err();
+        assert_eq!(
+            error.errors,
+            (1..=4)
+                .map(|port| TankError::Port {
+                    source: PortError::Disconnected { port },
+                })
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(group.last_command, None);
+    }
+}
@@ -0,0 +1,258 @@
+use alloc::vec::Vec;
+
+use vexide::{
+    math::Angle,
+    smart::{
+        PortError,
+        motor::{Motor, MotorControl},
+    },
+};
+
+use crate::{
+    MotorGroup, MotorGroupError, SharedMotors, WriteErrorStrategy, readings::Reading, reference,
+};
+
+/// How a motor group turns a position target into a target for each motor,
+/// set with [`MotorGroup::set_targeting_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TargetingMode {
+    /// Every motor is given the same absolute position target.
+    ///
+    /// This is the default.
+    #[default]
+    Absolute,
+    /// Every motor is moved by the same distance: each is given its own
+    /// position plus the distance from the group's position to the target.
+    ///
+    /// See [`MotorGroup::set_targeting_mode`].
+    RelativeDelta,
+}
+
+/// Returns the position target of each of `count` motors for a
+/// [`TargetingMode::RelativeDelta`] move of the group from `average` degrees
+/// to `target`.
+///
+/// Each motor in `readings` is given its own position plus the group's
+/// distance to the target. Motors that couldn't be read, or that aren't in
+/// `readings`, are given `target` itself.
+pub(crate) fn relative_delta_targets(
+    target: Angle,
+    average: f64,
+    readings: &[Reading<Angle>],
+    count: usize,
+) -> Vec<Angle> {
+    let delta = target.as_degrees() - average;
+    let mut targets = alloc::vec![target; count];
+    for (index, reading) in readings {
+        if let Ok(position) = reading {
+            targets[*index] = Angle::from_degrees(position.as_degrees() + delta);
+        }
+    }
+    targets
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets how position targets are handed out to the motors of the group.
+    ///
+    /// With [`TargetingMode::Absolute`] (the default), every motor is given
+    /// the same position target. When the encoders of a linked mechanism
+    /// disagree by a few degrees, the motors then fight each other at the
+    /// end of every move, each trying to reach the target by its own count.
+    ///
+    /// With [`TargetingMode::RelativeDelta`], every position target (through
+    /// [`MotorGroup::set_position_target`] or [`MotorGroup::set_target`]) is
+    /// turned into a move of the same distance on every motor: each motor is
+    /// given its own position plus the distance from the group's position
+    /// (see [`MotorGroup::position`]) to the target. This costs a read of
+    /// every motor's position before each position target:
+    ///
+    /// - If no motor's position can be read, the read errors are returned
+    ///   and nothing is written.
+    /// - If only some can be read, the others are given the absolute target,
+    ///   and the read errors are returned along with any write errors. Under
+    ///   [`WriteErrorStrategy::Stop`], nothing is written instead.
+    ///
+    /// [`MotorGroup::position`] keeps averaging the motors, so it still
+    /// reports the group target once every motor has arrived. Other targets
+    /// aren't affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Reverse),
+    ///     ]);
+    ///     lift.set_targeting_mode(TargetingMode::RelativeDelta);
+    ///
+    ///     // Both motors move by the same amount, wherever their encoders are
+    ///     _ = lift.set_position_target(Angle::from_degrees(720.0), 100);
+    /// }
+    /// ```
+    pub fn set_targeting_mode(&mut self, mode: TargetingMode) -> &mut Self {
+        self.config.targeting_mode = mode;
+        self
+    }
+
+    /// Returns how position targets are handed out to the motors of the
+    /// group. See [`MotorGroup::set_targeting_mode`].
+    pub fn targeting_mode(&self) -> TargetingMode {
+        self.config.targeting_mode
+    }
+
+    /// Returns `target` for each motor in the group, in order, scaled and
+    /// handed out according to the group's [`TargetingMode`], along with the
+    /// errors of any position reads it took.
+    ///
+    /// # Errors
+    ///
+    /// Returns the read errors if the targets can't be worked out, in which
+    /// case nothing should be written.
+    pub(crate) fn fanned_out_targets(
+        &self,
+        target: MotorControl,
+    ) -> Result<(Vec<MotorControl>, Vec<PortError>), MotorGroupError> {
+        let MotorControl::Position(position, velocity) = target else {
+            return Ok((self.scaled_targets(target), Vec::new()));
+        };
+        if self.config.targeting_mode == TargetingMode::Absolute {
+            return Ok((self.scaled_targets(target), Vec::new()));
+        }
+
+        let readings = self.read_each(Motor::position);
+        let (average, errors) = match reference::average_position(readings.clone(), &self.meta) {
+            Ok(average) => (Some(average), Vec::new()),
+            Err(error) => (error.result, error.errors),
+        };
+        let stop = self.config.write_error_strategy == WriteErrorStrategy::Stop;
+        let Some(average) = average.filter(|_| errors.is_empty() || !stop) else {
+            return Err(MotorGroupError::new(errors));
+        };
+        let targets = relative_delta_targets(
+            position,
+            average.degrees(),
+            &readings,
+            self.motors.as_ref().len(),
+        )
+        .into_iter()
+        .map(|position| MotorControl::Position(position, velocity))
+        .collect();
+        Ok((targets, errors))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_targeting_mode`].
+    pub fn set_targeting_mode(&mut self, mode: TargetingMode) -> &Self {
+        self.0.borrow_mut().set_targeting_mode(mode);
+        self
+    }
+
+    /// See [`MotorGroup::targeting_mode`].
+    pub fn targeting_mode(&self) -> TargetingMode {
+        self.0.borrow().targeting_mode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        math::Angle,
+        prelude::*,
+        smart::{PortError, motor::MotorControl},
+    };
+
+    use super::{TargetingMode, relative_delta_targets};
+    use crate::{GroupConfig, MotorGroup, WriteErrorStrategy, tests::mock_motors};
+
+    fn degrees(degrees: f64) -> Angle {
+        Angle::from_degrees(degrees)
+    }
+
+    fn assert_degrees(actual: &[Angle], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual.as_degrees() - expected).abs() < 1e-9,
+                "{} != {expected}",
+                actual.as_degrees()
+            );
+        }
+    }
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(2, Gearset::Red))
+    }
+
+    #[test]
+    fn every_motor_moves_by_the_same_distance() {
+        // The encoders disagree by 6 degrees around an average of 100
+        let readings = [(0, Ok(degrees(97.0))), (1, Ok(degrees(103.0)))];
+        let targets = relative_delta_targets(degrees(460.0), 100.0, &readings, 2);
+        assert_degrees(&targets, &[457.0, 463.0]);
+
+        // Moving backwards works the same way
+        let targets = relative_delta_targets(degrees(-20.0), 100.0, &readings, 2);
+        assert_degrees(&targets, &[-23.0, -17.0]);
+    }
+
+    #[test]
+    fn unread_motors_get_the_absolute_target() {
+        let readings = [
+            (0, Ok(degrees(95.0))),
+            (1, Err(PortError::Disconnected { port: 2 })),
+            (3, Ok(degrees(105.0))),
+        ];
+        // Motor 2 is disabled, so it isn't in the readings at all
+        let targets = relative_delta_targets(degrees(200.0), 100.0, &readings, 4);
+        assert_degrees(&targets, &[195.0, 200.0, 200.0, 205.0]);
+    }
+
+    #[test]
+    fn the_mode_is_part_of_the_configuration() {
+        let mut group = group();
+        assert_eq!(group.targeting_mode(), TargetingMode::Absolute);
+        group.set_targeting_mode(TargetingMode::RelativeDelta);
+        assert_eq!(
+            group.current_config().targeting_mode,
+            TargetingMode::RelativeDelta
+        );
+        assert_eq!(
+            MotorGroup::new_like(mock_motors(1, Gearset::Red), &group).targeting_mode(),
+            TargetingMode::RelativeDelta
+        );
+
+        _ = group.apply_config(&GroupConfig::DEFAULT);
+        assert_eq!(group.targeting_mode(), TargetingMode::Absolute);
+    }
+
+    #[test]
+    fn nothing_is_written_without_a_position() {
+        let mut group = group();
+        group.set_targeting_mode(TargetingMode::RelativeDelta);
+
+        // The mock motors' positions can't be read
+        let error = group.set_position_target(degrees(90.0), 100).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(group.command_generation(), 0);
+        assert_eq!(group.last_command, None);
+
+        // Other targets don't need a position
+        _ = group.set_voltage(6.0);
+        assert_eq!(group.command_generation(), 1);
+
+        // Absolute targets don't either
+        group.set_targeting_mode(TargetingMode::Absolute);
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        _ = group.set_position_target(degrees(90.0), 100);
+        assert_eq!(
+            group.last_command,
+            Some(MotorControl::Position(degrees(90.0), 100))
+        );
+    }
+}
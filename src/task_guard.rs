@@ -0,0 +1,210 @@
+use alloc::{
+    rc::{Rc, Weak},
+    vec::Vec,
+};
+use core::{cell::RefCell, future::Future};
+
+use vexide::{
+    smart::motor::{BrakeMode, Motor, MotorControl},
+    task::{self, Task},
+};
+
+use crate::{MotorGroup, SharedMotors, last_known::LastKnownCache, safety, shadow::ShadowLink};
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets whether the group brakes its motors when it's dropped, and with
+    /// which [`BrakeMode`].
+    ///
+    /// With `None` (the default), dropping the group leaves each motor running
+    /// its last target, since the motors themselves outlive the group. For a
+    /// group inside [`SharedMotors`], the group is dropped along with the last
+    /// strong handle, so together with [`SharedMotors::spawn_helper`] this
+    /// makes dropping every handle a user holds stop both the helpers and the
+    /// motors.
+    ///
+    /// Every motor is braked, including disabled ones (see
+    /// [`MotorGroup::set_enabled`]). Errors are ignored, since there is no one
+    /// left to report them to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     intake.stop_on_drop(Some(BrakeMode::Coast));
+    ///     _ = intake.set_voltage(12.0);
+    ///
+    ///     // The intake coasts to a stop here.
+    ///     drop(intake);
+    /// }
+    /// ```
+    pub fn stop_on_drop(&mut self, mode: Option<BrakeMode>) -> &mut Self {
+        self.config.stop_on_drop = mode;
+        self
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Drop for MotorGroup<M> {
+    fn drop(&mut self) {
+        if let Some(mode) = self.config.stop_on_drop {
+            safety::best_effort_stop(self, MotorControl::Brake(mode), true);
+        }
+    }
+}
+
+/// A handle to [`SharedMotors`] that doesn't keep the motor group alive,
+/// returned by [`SharedMotors::downgrade`].
+#[derive(Debug)]
+pub struct WeakSharedMotors<M: AsRef<[Motor]> + AsMut<[Motor]> = Vec<Motor>>(
+    Weak<RefCell<MotorGroup<M>>>,
+    Weak<LastKnownCache>,
+    Weak<ShadowLink>,
+);
+
+// Not derived, for the same reason as `SharedMotors`.
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> Clone for WeakSharedMotors<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1.clone(), self.2.clone())
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> WeakSharedMotors<M> {
+    /// Returns a strong handle to the motors, or `None` if every strong
+    /// handle has been dropped.
+    ///
+    /// Code holding a weak handle should only keep the strong handle for as
+    /// long as it needs it, such as one iteration of a loop, so that it
+    /// doesn't keep the group alive in the meantime.
+    pub fn upgrade(&self) -> Option<SharedMotors<M>> {
+        Some(SharedMotors(
+            self.0.upgrade()?,
+            self.1.upgrade()?,
+            self.2.upgrade()?,
+        ))
+    }
+}
+
+/// A helper task spawned on a motor group, returned by
+/// [`SharedMotors::spawn_helper`].
+///
+/// Dropping the guard cancels the task, so its future won't be polled again.
+#[derive(Debug)]
+#[must_use = "dropping a `TaskGuard` cancels its task"]
+pub struct TaskGuard(Task<()>);
+
+impl TaskGuard {
+    /// Returns `true` if the task has run to completion.
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// Creates a [`WeakSharedMotors`] handle to these motors.
+    pub fn downgrade(&self) -> WeakSharedMotors<M> {
+        WeakSharedMotors(
+            Rc::downgrade(&self.0),
+            Rc::downgrade(&self.1),
+            Rc::downgrade(&self.2),
+        )
+    }
+
+    /// Spawns a helper task that works on these motors in the background.
+    ///
+    /// `helper` is given a [`WeakSharedMotors`] handle rather than a clone of
+    /// these motors, so the task never keeps the group alive. Once every
+    /// strong handle is dropped, [`WeakSharedMotors::upgrade`] returns `None`
+    /// and the helper should return. With [`MotorGroup::stop_on_drop`], the
+    /// motors are also braked at that point, rather than being left driving
+    /// by a task nobody can reach.
+    ///
+    /// The task runs until it returns or the returned [`TaskGuard`] is
+    /// dropped, whichever comes first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = SharedMotors::from_motors(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     drive.stop_on_drop(Some(BrakeMode::Brake));
+    ///
+    ///     let _logger = drive.spawn_helper(|drive| async move {
+    ///         while let Some(drive) = drive.upgrade() {
+    ///             if let Ok(temperature) = drive.temperature() {
+    ///                 println!("Drive is at {temperature}°C");
+    ///             }
+    ///             drop(drive);
+    ///             sleep(Duration::from_secs(1)).await;
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn spawn_helper<F, Fut>(&self, helper: F) -> TaskGuard
+    where
+        F: FnOnce(WeakSharedMotors<M>) -> Fut,
+        Fut: Future<Output = ()> + 'static,
+    {
+        TaskGuard(task::spawn(helper(self.downgrade())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::rc::Rc;
+
+    use vexide::{prelude::*, smart::motor::BrakeMode};
+
+    use crate::{SharedMotors, tests::mock_motors};
+
+    fn shared() -> SharedMotors {
+        SharedMotors::from_motors(mock_motors(1, Gearset::Green))
+    }
+
+    #[test]
+    fn helpers_hold_only_weak_handles() {
+        let shared = shared();
+        let guard = shared.spawn_helper(|motors| async move {
+            if let Some(mut motors) = motors.upgrade() {
+                _ = motors.set_voltage(6.0);
+            }
+        });
+        // The task hasn't run, so it only holds the weak handle it was given
+        assert_eq!(Rc::strong_count(&shared.0), 1);
+        assert_eq!(Rc::weak_count(&shared.0), 1);
+        assert!(!guard.is_finished());
+
+        // Cancelling the task drops its future, along with the handle, the
+        // next time the executor reaches it
+        drop(guard);
+        vexide::runtime::block_on(async {});
+        assert_eq!(Rc::weak_count(&shared.0), 0);
+    }
+
+    #[test]
+    fn weak_handles_dont_keep_the_group_alive() {
+        let mut shared = shared();
+        shared.stop_on_drop(Some(BrakeMode::Coast));
+        let weak = shared.downgrade();
+        let clone = weak.upgrade().unwrap();
+        assert_eq!(clone.current_config().stop_on_drop, Some(BrakeMode::Coast));
+
+        drop(clone);
+        assert!(weak.upgrade().is_some());
+        // Dropping the last strong handle drops (and brakes) the group
+        drop(shared);
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -1,13 +1,26 @@
 // Unit tests for `lib.rs` covering MotorGroupError helpers and error strategy
 // These tests avoid hardware-specific APIs and focus on pure-data helpers
 
-#![cfg(test)]
+use vexide::{
+    math::Angle,
+    prelude::*,
+    smart::{
+        PortError, SmartPort,
+        motor::{Motor, MotorControl},
+    },
+};
 
-use crate::{MotorGroupError, WriteErrorStrategy};
+use crate::{
+    CurrentLimitPolicy, EmptyGroupError, MaxCurrentTable, MotorGroup, MotorGroupError,
+    SetCurrentLimitError, SharedMotors, WriteErrorStrategy, WriteKind,
+    current_limit::distribute_current_budget, readings, write_policy::WriteFailure,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct FakeErr(&'static str);
 
+// With the `no-panic` feature, this is only checked in debug builds
+#[cfg(any(not(feature = "no-panic"), debug_assertions))]
 #[test]
 fn motor_group_error_new_panics_on_empty() {
     // MotorGroupError::new should panic when given an empty errors vector
@@ -19,6 +32,33 @@ fn motor_group_error_new_panics_on_empty() {
     );
 }
 
+#[cfg(any(not(feature = "no-panic"), debug_assertions))]
+#[test]
+#[should_panic(expected = "Cannot create a motor group with no motors")]
+fn new_panics_on_empty_group() {
+    MotorGroup::new(Vec::new());
+}
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+#[test]
+fn new_allows_empty_group_in_release() {
+    let group = MotorGroup::new(Vec::new());
+    assert!(group.velocity().unwrap_err().errors.is_empty());
+}
+
+#[test]
+fn try_from_rejects_empty_group() {
+    assert_eq!(
+        MotorGroup::try_from(Vec::new()).unwrap_err(),
+        EmptyGroupError
+    );
+    assert_eq!(MotorGroup::try_from([]).unwrap_err(), EmptyGroupError);
+
+    let group = MotorGroup::try_from([v5_motor(1), v5_motor(2)]).unwrap();
+    assert_eq!(group.velocity().unwrap_err().errors.len(), 2);
+    assert!(MotorGroup::try_from(vec![v5_motor(1)]).is_ok());
+}
+
 #[test]
 fn motor_group_error_with_result_and_first() {
     let errors = vec![FakeErr("a"), FakeErr("b")];
@@ -55,3 +95,574 @@ fn write_error_strategy_default_is_ignore() {
     // Default strategy should be Ignore
     assert_eq!(WriteErrorStrategy::default(), WriteErrorStrategy::Ignore);
 }
+
+// Motors on the host are backed by the mock SDK, so every read and write
+// returns a port error. They're still useful for testing anything that doesn't
+// touch the hardware, such as motor types.
+pub(crate) fn v5_motor(port: u8) -> Motor {
+    Motor::new(
+        unsafe { SmartPort::new(port) },
+        Gearset::Green,
+        Direction::Forward,
+    )
+}
+
+pub(crate) fn exp_motor(port: u8) -> Motor {
+    Motor::new_exp(unsafe { SmartPort::new(port) }, Direction::Forward)
+}
+
+/// Mock V5 motors on ports `1..=count`, all with the same gearset.
+pub(crate) fn mock_motors(count: u8, gearset: Gearset) -> Vec<Motor> {
+    (1..=count)
+        .map(|port| Motor::new(unsafe { SmartPort::new(port) }, gearset, Direction::Forward))
+        .collect()
+}
+
+/// The group used by tests that only need some motors to command: two mock
+/// V5 motors with the green gearset.
+pub(crate) fn two_motor_group() -> MotorGroup {
+    MotorGroup::new(mock_motors(2, Gearset::Green))
+}
+
+#[test]
+fn motors_are_partitioned_by_type() {
+    let group = MotorGroup::new(vec![
+        exp_motor(1),
+        v5_motor(2),
+        v5_motor(3),
+        exp_motor(4),
+        v5_motor(5),
+    ]);
+    assert_eq!(group.exp_motors(), vec![0, 3]);
+    assert_eq!(group.v5_motors(), vec![1, 2, 4]);
+
+    let v5_group = SharedMotors::new(MotorGroup::new(vec![v5_motor(1), v5_motor(2)]));
+    assert!(v5_group.exp_motors().is_empty());
+    assert_eq!(v5_group.v5_motors(), vec![0, 1]);
+}
+
+#[test]
+fn max_current_homogeneous_and_mixed() {
+    let v5_group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    assert_eq!(v5_group.max_current(), 5.0);
+    assert_eq!(v5_group.max_current_per_motor(), vec![2.5, 2.5]);
+
+    let mixed_group = MotorGroup::new(vec![v5_motor(1), exp_motor(2)]);
+    assert_eq!(mixed_group.max_current(), 3.75);
+    assert_eq!(mixed_group.max_current_per_motor(), vec![2.5, 1.25]);
+
+    let mut custom_group = MotorGroup::new(vec![v5_motor(1), exp_motor(2)]);
+    custom_group.set_max_current_table(MaxCurrentTable { v5: 3.0, exp: 1.0 });
+    assert_eq!(custom_group.max_current_per_motor(), vec![3.0, 1.0]);
+}
+
+#[test]
+fn current_limit_clamp_policy() {
+    let group = MotorGroup::new(vec![v5_motor(1), exp_motor(2)]);
+    // Clamp is the default policy
+    assert_eq!(group.checked_current_limits(2.0).unwrap(), vec![2.0, 1.25]);
+    assert_eq!(group.checked_current_limits(1.0).unwrap(), vec![1.0, 1.0]);
+}
+
+#[test]
+fn current_limit_error_policy() {
+    let mut group = MotorGroup::new(vec![v5_motor(1), exp_motor(2), v5_motor(3)]);
+    group.set_current_limit_policy(CurrentLimitPolicy::Error);
+    assert_eq!(group.checked_current_limits(1.0).unwrap(), vec![1.0; 3]);
+
+    // Only the EXP motor is exceeded
+    let error = group.checked_current_limits(2.0).unwrap_err();
+    assert_eq!(
+        error.errors,
+        vec![SetCurrentLimitError::ExceedsMaximum {
+            index: Some(1),
+            requested: 2.0,
+            maximum: 1.25,
+        }]
+    );
+
+    // Rejected limits never reach the hardware, so no port errors show up
+    let error = group.set_current_limit(3.0).unwrap_err();
+    assert_eq!(error.errors.len(), 3);
+    assert!(
+        error
+            .errors
+            .iter()
+            .all(|error| matches!(error, SetCurrentLimitError::ExceedsMaximum { .. }))
+    );
+}
+
+#[test]
+fn current_budget_distribution() {
+    assert_eq!(distribute_current_budget(3.0, &[2.5, 2.5]), vec![1.5, 1.5]);
+    assert_eq!(distribute_current_budget(3.0, &[2.5, 1.25]), vec![2.0, 1.0]);
+
+    let mut group = MotorGroup::new(vec![v5_motor(1), exp_motor(2)]);
+    group.set_current_limit_policy(CurrentLimitPolicy::Error);
+    let error = group.set_total_current_limit(4.0).unwrap_err();
+    assert_eq!(
+        error.errors,
+        vec![SetCurrentLimitError::ExceedsMaximum {
+            index: None,
+            requested: 4.0,
+            maximum: 3.75,
+        }]
+    );
+
+    // Under the clamp policy the budget is written, failing on the mock ports
+    group.set_current_limit_policy(CurrentLimitPolicy::Clamp);
+    let error = group.set_total_current_limit(4.0).unwrap_err();
+    assert!(
+        error
+            .errors
+            .iter()
+            .all(|error| matches!(error, SetCurrentLimitError::Port { .. }))
+    );
+}
+
+const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+#[test]
+fn slowest_motor_by_magnitude() {
+    let readings = [Ok(190.0), Ok(-120.0), Ok(200.0)];
+    assert_eq!(
+        readings::min_magnitude(readings.into_iter().enumerate()).unwrap(),
+        (1, -120.0)
+    );
+
+    let readings = [Ok(-190.0), Ok(-201.0), Ok(-185.5)];
+    assert_eq!(
+        readings::min_magnitude(readings.into_iter().enumerate()).unwrap(),
+        (2, -185.5)
+    );
+}
+
+#[test]
+fn slowest_motor_partial_result() {
+    let readings = [Ok(190.0), Err(DISCONNECTED), Ok(150.0)];
+    let error = readings::min_magnitude(readings.into_iter().enumerate()).unwrap_err();
+    assert_eq!(error.result(), &Some((2, 150.0)));
+    assert_eq!(error.errors, vec![DISCONNECTED]);
+
+    // The mock motors can't be read at all, so there's no partial result
+    let group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    let error = group.slowest_motor().unwrap_err();
+    assert_eq!(error.result(), &None);
+    assert_eq!(error.errors.len(), 2);
+    assert!(group.min_velocity().is_err());
+}
+
+// Property tests over the aggregation and write paths for a range of group
+// sizes. The mock motors can't be read, so readings and write failures are
+// generated from a fixed seed and fed through the same helpers the getters
+// use.
+
+const GROUP_SIZES: [usize; 6] = [1, 2, 3, 8, 16, 21];
+const TRIALS: usize = 200;
+
+/// A small xorshift generator, so the property tests are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in `-range..range`.
+    fn value(&mut self, range: f64) -> f64 {
+        (self.next() as f64 / u64::MAX as f64 * 2.0 - 1.0) * range
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+/// Generates readings for a group of `size` motors, with each one failing
+/// with a probability of `fail_percent`.
+fn random_readings(
+    rng: &mut Rng,
+    size: usize,
+    offset: f64,
+    fail_percent: u64,
+) -> Vec<Result<f64, PortError>> {
+    (0..size)
+        .map(|port| {
+            if rng.chance(fail_percent) {
+                Err(PortError::Disconnected {
+                    port: port as u8 + 1,
+                })
+            } else {
+                Ok(offset + rng.value(200.0))
+            }
+        })
+        .collect()
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!(
+        (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.0),
+        "{a} != {b}"
+    );
+}
+
+#[test]
+fn average_is_consistent_across_group_sizes() {
+    let mut rng = Rng(0x5eed);
+    for size in GROUP_SIZES {
+        for _ in 0..TRIALS {
+            let readings = random_readings(&mut rng, size, 0.0, 30);
+            let successes: Vec<f64> = readings.iter().filter_map(|r| r.ok()).collect();
+            let failures = size - successes.len();
+
+            match readings::average(readings.clone().into_iter().enumerate()) {
+                Ok(average) => {
+                    assert_eq!(failures, 0);
+                    assert_close(average, successes.iter().sum::<f64>() / size as f64);
+                }
+                Err(error) => {
+                    // Exactly one error per failed motor, and the partial
+                    // result is averaged like a full one
+                    assert_eq!(error.errors.len(), failures);
+                    match error.result {
+                        Some(average) => assert_close(
+                            average,
+                            successes.iter().sum::<f64>() / successes.len() as f64,
+                        ),
+                        None => assert!(successes.is_empty()),
+                    }
+                }
+            }
+
+            // Reordering the motors doesn't change the average
+            let mut reversed = readings.clone();
+            reversed.reverse();
+            assert_eq!(
+                readings::average(readings.into_iter().enumerate()).ok(),
+                readings::average(reversed.into_iter().enumerate()).ok()
+            );
+        }
+    }
+}
+
+#[test]
+fn average_keeps_precision_for_large_readings() {
+    // Identical readings with a large offset. Summing these naively rounds
+    // away part of the fraction and gives an average that isn't any of them.
+    let readings = vec![Ok(1.0e15 + 0.375); 16];
+    assert_eq!(
+        readings::average(readings.into_iter().enumerate()).unwrap(),
+        1.0e15 + 0.375
+    );
+}
+
+#[test]
+fn average_divisor_policies() {
+    let disconnected = PortError::Disconnected { port: 2 };
+    // Four motors, one of them disabled and one failing
+    let readings = vec![(0, Ok(100.0)), (1, Err(disconnected)), (3, Ok(50.0))];
+
+    // By default, only the readable motors are averaged
+    let error = readings::average(readings.clone()).unwrap_err();
+    assert_eq!(error.result, Some(75.0));
+    // Counting every motor, the disabled and failed ones are zeros
+    let error = readings::average_over(readings, 4).unwrap_err();
+    assert_eq!(error.errors, vec![disconnected]);
+    assert_eq!(error.result, Some(37.5));
+
+    // Both agree when every motor is read
+    let readings = vec![(0, Ok(100.0)), (1, Ok(50.0))];
+    assert_eq!(readings::average(readings.clone()).unwrap(), 75.0);
+    assert_eq!(readings::average_over(readings, 2).unwrap(), 75.0);
+
+    // Nothing is known if no motor could be read
+    let error = readings::average_over([(0, Err(disconnected))], 2).unwrap_err();
+    assert_eq!(error.result, None);
+
+    let mut group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    group.count_disabled_in_average(true);
+    assert!(group.current_config().count_disabled_in_average);
+    assert_eq!(group.velocity().unwrap_err().result, None);
+}
+
+#[test]
+fn position_average_skips_stale_motors_across_group_sizes() {
+    let mut rng = Rng(0xa11);
+    for size in GROUP_SIZES {
+        for _ in 0..TRIALS {
+            let readings = random_readings(&mut rng, size, 1.0e6, 20);
+            let meta: Vec<_> = (0..size)
+                .map(|_| crate::meta::MotorMeta {
+                    reference_stale: rng.chance(25),
+                    ..Default::default()
+                })
+                .collect();
+            let all_stale = meta.iter().all(|meta| meta.reference_stale);
+            let included: Vec<f64> = readings
+                .iter()
+                .zip(&meta)
+                .filter(|(_, meta)| all_stale || !meta.reference_stale)
+                .filter_map(|(reading, _)| reading.ok())
+                .collect();
+
+            let result = crate::reference::average_position(
+                readings
+                    .iter()
+                    .map(|reading| reading.map(Angle::from_degrees))
+                    .enumerate(),
+                &meta,
+            );
+            let average = match &result {
+                Ok(average) => Some(*average),
+                Err(error) => error.result,
+            };
+            match average {
+                Some(average) => assert_close(
+                    average.degrees(),
+                    included.iter().sum::<f64>() / included.len() as f64,
+                ),
+                None => assert!(included.is_empty()),
+            }
+            assert_eq!(
+                result.is_ok(),
+                readings.iter().all(|reading| reading.is_ok())
+            );
+        }
+    }
+}
+
+#[test]
+fn boolean_getters_across_group_sizes() {
+    let mut rng = Rng(0xb001);
+    for size in GROUP_SIZES {
+        for _ in 0..TRIALS {
+            let readings: Vec<Result<bool, PortError>> = (0..size)
+                .map(|_| {
+                    if rng.chance(20) {
+                        Err(DISCONNECTED)
+                    } else {
+                        Ok(rng.chance(10))
+                    }
+                })
+                .collect();
+            let any_true = readings.iter().any(|reading| reading == &Ok(true));
+            let failures = readings.iter().filter(|reading| reading.is_err()).count();
+
+            match readings::any(readings.into_iter().enumerate()) {
+                Ok(value) => {
+                    assert_eq!(value, any_true);
+                    assert!(any_true || failures == 0);
+                }
+                Err(error) => {
+                    assert!(!any_true);
+                    assert_eq!(error.errors.len(), failures);
+                    // The partial result is `false` whenever anything was read
+                    let expected = (failures < size).then_some(false);
+                    assert_eq!(error.result, expected);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn boolean_getters_are_order_independent() {
+    // A flagged motor wins over errors whether it's read before or after them
+    let flagged_first = [Ok(true), Err(DISCONNECTED), Ok(false)];
+    let flagged_last = [Err(DISCONNECTED), Ok(false), Ok(true)];
+    assert!(readings::any(flagged_first.into_iter().enumerate()).unwrap());
+    assert!(readings::any(flagged_last.into_iter().enumerate()).unwrap());
+
+    // Without a flagged motor, the same error and partial result are reported
+    // whichever order the motors are in
+    let error_first = [Err(DISCONNECTED), Ok(false)];
+    let error_last = [Ok(false), Err(DISCONNECTED)];
+    for readings in [error_first, error_last] {
+        let error = readings::any(readings.into_iter().enumerate()).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert_eq!(error.result, Some(false));
+    }
+
+    // Nothing could be read, so there's no partial result
+    let group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    let error = group.is_over_temperature().unwrap_err();
+    assert_eq!(error.errors.len(), 2);
+    assert_eq!(error.result, None);
+}
+
+// Lets write errors be stand-in indices, which are never port errors
+impl WriteFailure for usize {
+    fn port_error(&self) -> Option<&PortError> {
+        None
+    }
+}
+
+#[test]
+fn write_strategies_across_group_sizes() {
+    let mut rng = Rng(0x3);
+    for size in GROUP_SIZES {
+        let mut group = MotorGroup::new((1..=size as u8).map(v5_motor).collect::<Vec<_>>());
+        // Without a policy, a custom strategy acts like ignoring errors
+        for strategy in [
+            WriteErrorStrategy::Ignore,
+            WriteErrorStrategy::Stop,
+            WriteErrorStrategy::Custom,
+        ] {
+            group.write_error_strategy(strategy);
+            for _ in 0..TRIALS {
+                let failing: Vec<bool> = (0..size).map(|_| rng.chance(20)).collect();
+                let mut attempted = Vec::new();
+                let result = group.write_each(WriteKind::Target, |index, _| {
+                    attempted.push(index);
+                    if failing[index] { Err(index) } else { Ok(()) }
+                });
+
+                let failed: Vec<usize> = (0..size).filter(|index| failing[*index]).collect();
+                match strategy {
+                    WriteErrorStrategy::Ignore | WriteErrorStrategy::Custom => {
+                        // Every motor is written to, in order
+                        assert_eq!(attempted, (0..size).collect::<Vec<_>>());
+                        match result {
+                            Ok(()) => assert!(failed.is_empty()),
+                            Err(error) => assert_eq!(error.errors, failed),
+                        }
+                    }
+                    WriteErrorStrategy::Stop => match failed.first() {
+                        // Writing stops right after the first failure
+                        Some(first) => {
+                            assert_eq!(attempted, (0..=*first).collect::<Vec<_>>());
+                            assert_eq!(result.unwrap_err().errors, vec![*first]);
+                        }
+                        None => {
+                            assert_eq!(attempted.len(), size);
+                            assert!(result.is_ok());
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn reference_writes_across_group_sizes() {
+    let mut rng = Rng(0x4);
+    for size in GROUP_SIZES {
+        let mut group = MotorGroup::new((1..=size as u8).map(v5_motor).collect::<Vec<_>>());
+        for _ in 0..TRIALS {
+            let failing: Vec<bool> = (0..size).map(|_| rng.chance(15)).collect();
+            let mut index = 0;
+            _ = group.write_reference(|_| {
+                index += 1;
+                if failing[index - 1] {
+                    Err(DISCONNECTED)
+                } else {
+                    Ok(())
+                }
+            });
+
+            if failing.iter().all(|failing| *failing) {
+                // Nothing was written, so nothing can be compared anyway
+                continue;
+            }
+            let expected: Vec<usize> = (0..size).filter(|index| failing[*index]).collect();
+            assert_eq!(group.stale_motors(), expected);
+            assert_eq!(group.has_inconsistent_reference(), !expected.is_empty());
+        }
+    }
+}
+
+#[test]
+fn position_reads_back_as_angle() {
+    // `Angle` is the one position type, re-exported from the crate root
+    let meta = vec![crate::meta::MotorMeta::default(); 2];
+    let position: crate::Angle = crate::reference::average_position(
+        [
+            (0, Ok(Angle::from_degrees(90.0))),
+            (1, Ok(Angle::from_turns(0.75))),
+        ],
+        &meta,
+    )
+    .unwrap()
+    .to_position();
+    assert_eq!(position.as_degrees(), 180.0);
+
+    // The same type is used when a motor group reads its position
+    let group = MotorGroup::new(vec![v5_motor(1)]);
+    let error: MotorGroupError<PortError, crate::Angle> = group.position().unwrap_err();
+    assert_eq!(error.result, None);
+}
+
+#[test]
+fn profile_velocity_changes_always_reach_the_motors() {
+    let mut group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    let target = Angle::from_degrees(90.0);
+
+    // A position target, then the same position at a new velocity, then a
+    // profiled velocity change: each is written and becomes the last command
+    _ = group.set_position_target(target, 200);
+    assert_eq!(group.command_generation(), 1);
+    assert_eq!(
+        group.last_command,
+        Some(MotorControl::Position(target, 200))
+    );
+    _ = group.set_position_target(target, 100);
+    assert_eq!(group.command_generation(), 2);
+    assert_eq!(
+        group.last_command,
+        Some(MotorControl::Position(target, 100))
+    );
+    _ = group.set_profiled_velocity(50);
+    assert_eq!(group.command_generation(), 3);
+    assert_eq!(group.last_command, Some(MotorControl::Position(target, 50)));
+    // Repeating the same velocity is written again too
+    _ = group.set_profiled_velocity(50);
+    assert_eq!(group.command_generation(), 4);
+
+    // Without a position target, only the motors are told
+    _ = group.set_velocity(300);
+    _ = group.set_profiled_velocity(50);
+    assert_eq!(group.command_generation(), 6);
+    assert_eq!(group.last_command, Some(MotorControl::Velocity(300)));
+}
+
+#[test]
+fn gearset_changes_are_reflected_without_rebuilding() {
+    let mut group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    assert_eq!(group.max_rpm(), None);
+
+    // The mock motors reject the write, but the gearset is still recorded
+    assert!(group.set_gearset(Gearset::Blue).is_err());
+    assert_eq!(group.max_rpm(), Some(600.0));
+    assert_eq!(group.current_config().gearset, Some(Gearset::Blue));
+
+    // Applying a configuration goes through the same path
+    let mut config = group.current_config();
+    config.gearset = Some(Gearset::Red);
+    _ = group.apply_config(&config);
+    assert_eq!(group.max_rpm(), Some(100.0));
+}
+
+#[cfg(feature = "events")]
+#[test]
+fn gearset_changes_are_events() {
+    use crate::GroupEvent;
+
+    let mut group = MotorGroup::new(vec![v5_motor(1), v5_motor(2)]);
+    let mut events = group.events();
+
+    _ = group.set_gearset(Gearset::Blue);
+    assert_eq!(events.try_recv(), Some(GroupEvent::GearsetChanged));
+    // Setting the same gearset again isn't a change
+    _ = group.set_gearset(Gearset::Blue);
+    assert_eq!(events.try_recv(), None);
+
+    let mut config = group.current_config();
+    config.gearset = Some(Gearset::Red);
+    _ = group.apply_config(&config);
+    assert_eq!(events.try_recv(), Some(GroupEvent::GearsetChanged));
+    assert_eq!(events.try_recv(), None);
+}
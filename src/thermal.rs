@@ -0,0 +1,219 @@
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::time::Instant;
+
+use vexide::smart::motor::Motor;
+
+use crate::{GetterResult, MotorGroup, readings};
+
+/// Timestamped samples of a group's hottest motor temperature, used to
+/// extrapolate when it will reach a cutout.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ThermalHistory {
+    samples: VecDeque<(Instant, f64)>,
+}
+
+impl ThermalHistory {
+    /// The maximum number of samples kept. Older samples are dropped.
+    pub(crate) const CAPACITY: usize = 64;
+
+    /// Adds a sample, dropping the oldest one if the history is full.
+    pub(crate) fn push(&mut self, at: Instant, temperature: f64) {
+        if self.samples.len() == Self::CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((at, temperature));
+    }
+
+    /// Returns the least-squares slope of the samples against time, in °C per
+    /// second.
+    ///
+    /// Returns `None` if there aren't two samples taken at different times.
+    pub(crate) fn heating_rate(&self) -> Option<f64> {
+        let &(start, _) = self.samples.front()?;
+        let points = self
+            .samples
+            .iter()
+            .map(|&(at, temperature)| ((at - start).as_secs_f64(), temperature));
+        let count = self.samples.len() as f64;
+        let mean_x = points.clone().map(|(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.clone().map(|(_, y)| y).sum::<f64>() / count;
+        let (covariance, variance) = points.fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+            let dx = x - mean_x;
+            (covariance + dx * (y - mean_y), variance + dx * dx)
+        });
+        (variance > 0.0).then(|| covariance / variance)
+    }
+
+    /// Returns how long until the latest temperature reaches `cutout` at the
+    /// current heating rate.
+    ///
+    /// Returns [`Duration::ZERO`] if it's already there, and `None` if the
+    /// temperature isn't rising or there aren't enough samples to tell.
+    pub(crate) fn time_to(&self, cutout: f64) -> Option<Duration> {
+        let &(_, latest) = self.samples.back()?;
+        if latest >= cutout {
+            return Some(Duration::ZERO);
+        }
+        let rate = self.heating_rate().filter(|&rate| rate > 0.0)?;
+        Some(Duration::from_secs_f64((cutout - latest) / rate))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Samples the temperature of the hottest motor in the group and estimates
+    /// how long until it reaches `cutout_celsius`.
+    ///
+    /// Motors start limiting their output at around 55 °C and cut out
+    /// entirely at 70 °C (see [`Motor::is_over_temperature`]), so knowing how
+    /// much time is left helps decide whether a subsystem can keep being
+    /// pushed until the end of a match.
+    ///
+    /// Each call takes one sample, and the group keeps the last 64. The
+    /// heating rate is the least-squares slope of those samples against
+    /// time, and the estimate extrapolates it in a straight line from the
+    /// latest temperature. Call this at a steady interval, such as every
+    /// 500ms from a telemetry task, which makes the rate cover the last 32
+    /// seconds.
+    ///
+    /// The estimate is rough. Motors heat up more slowly as they get hotter,
+    /// and follow their load, so a straight line is only accurate while the
+    /// load stays the same, and usually errs on the side of too little time.
+    /// Motors also report their temperature in coarse steps, so the rate
+    /// needs several samples to settle after a change.
+    ///
+    /// The result is `None` if the temperature is stable or falling, or
+    /// until two samples have been taken, and zero once the hottest motor
+    /// has reached `cutout_celsius`.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   The hottest of the motors that could be read is still sampled, and
+    ///   the result is the estimate including it. If no motor could be read,
+    ///   nothing is sampled and the result is the estimate from the existing
+    ///   samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         if let Ok(Some(left)) = drive.time_to_cutout(55.0) {
+    ///             if left < Duration::from_secs(30) {
+    ///                 println!("The drive will start limiting in {left:?}");
+    ///             }
+    ///         }
+    ///         sleep(Duration::from_millis(500)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn time_to_cutout(&mut self, cutout_celsius: f64) -> GetterResult<Option<Duration>> {
+        let (temperatures, errors) = readings::partition(self.read_each(Motor::temperature));
+        let hottest = temperatures
+            .into_iter()
+            .map(|(_, temperature)| temperature)
+            .reduce(f64::max);
+        if let Some(hottest) = hottest {
+            self.thermal_history.push(Instant::now(), hottest);
+        }
+        readings::finish(Some(self.thermal_history.time_to(cutout_celsius)), errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::ThermalHistory;
+    use crate::tests::two_motor_group;
+
+    /// Builds a history from `(seconds, temperature)` samples.
+    fn history(samples: &[(u64, f64)]) -> ThermalHistory {
+        let start = Instant::now();
+        let mut history = ThermalHistory::default();
+        for &(seconds, temperature) in samples {
+            history.push(start + Duration::from_secs(seconds), temperature);
+        }
+        history
+    }
+
+    #[test]
+    fn rising_temperature_is_extrapolated() {
+        // 0.5 °C/s, 10 °C short of the cutout
+        let rising = history(&[
+            (0, 40.0),
+            (2, 41.0),
+            (4, 42.0),
+            (6, 43.0),
+            (8, 44.0),
+            (10, 45.0),
+        ]);
+        assert!((rising.heating_rate().unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(rising.time_to(55.0), Some(Duration::from_secs(20)));
+
+        // Noise around the trend is smoothed out rather than followed
+        let noisy = history(&[(0, 40.0), (1, 41.0), (2, 40.5), (3, 41.5), (4, 42.0)]);
+        let rate = noisy.heating_rate().unwrap();
+        assert!(rate > 0.0 && rate < 1.0);
+    }
+
+    #[test]
+    fn stable_or_falling_temperature_never_cuts_out() {
+        assert_eq!(ThermalHistory::default().time_to(55.0), None);
+        assert_eq!(history(&[(0, 40.0)]).time_to(55.0), None);
+        assert_eq!(
+            history(&[(0, 40.0), (5, 40.0), (10, 40.0)]).time_to(55.0),
+            None
+        );
+        assert_eq!(
+            history(&[(0, 45.0), (5, 42.0), (10, 40.0)]).time_to(55.0),
+            None
+        );
+    }
+
+    #[test]
+    fn reaching_the_cutout_leaves_no_time() {
+        assert_eq!(
+            history(&[(0, 50.0), (5, 55.0)]).time_to(55.0),
+            Some(Duration::ZERO)
+        );
+        // Even if it's already cooling down
+        assert_eq!(
+            history(&[(0, 60.0), (5, 58.0)]).time_to(55.0),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn only_recent_samples_set_the_rate() {
+        let samples: Vec<_> = (0..ThermalHistory::CAPACITY as u64 * 2)
+            .map(|seconds| {
+                // Heating fast for the first half, then holding steady
+                let temperature = 30.0 + seconds.min(ThermalHistory::CAPACITY as u64) as f64 / 4.0;
+                (seconds, temperature)
+            })
+            .collect();
+        assert_eq!(history(&samples).heating_rate(), Some(0.0));
+        assert_eq!(history(&samples).time_to(55.0), None);
+    }
+
+    #[test]
+    fn unreadable_motors_are_not_sampled() {
+        let mut group = two_motor_group();
+        let error = group.time_to_cutout(55.0).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, Some(None));
+        assert_eq!(group.thermal_history.heating_rate(), None);
+    }
+}
@@ -0,0 +1,272 @@
+use core::ops::ControlFlow;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use std::time::Instant;
+
+use vexide::{smart::motor::Motor, time::sleep};
+
+use crate::MotorGroup;
+
+/// A future that is pending exactly once, handing control back to the
+/// executor before completing.
+#[derive(Debug, Default)]
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Waits for `duration`, or only yields to the executor if it's zero.
+pub(crate) async fn pause(duration: Duration) {
+    if duration.is_zero() {
+        YieldNow::default().await;
+    } else {
+        sleep(duration).await;
+    }
+}
+
+/// Returns how long to wait after a tick at `elapsed`: `interval`, shortened
+/// so that a tick lands exactly on `end` if there is one.
+fn next_wait(interval: Duration, elapsed: Duration, end: Option<Duration>) -> Duration {
+    match end.and_then(|end| end.checked_sub(elapsed)) {
+        Some(remaining) if !remaining.is_zero() => interval.min(remaining),
+        _ => interval,
+    }
+}
+
+/// Runs `tick` every `interval` until it breaks, returning its value.
+///
+/// This is the loop every async method in the crate is built on, so that none
+/// of them can starve the other tasks on the brain's single core: `tick` is
+/// given the time elapsed since the loop started, and between ticks the loop
+/// always awaits, either by sleeping for `interval` or, if it's zero, by
+/// yielding once. If there's an `end`, the wait before it is shortened so
+/// that a tick lands exactly on it. The first tick runs immediately.
+///
+/// Dropping the future cancels the loop at the wait after the last tick;
+/// `tick` is never interrupted midway.
+pub(crate) async fn tick_loop<T>(
+    interval: Duration,
+    end: Option<Duration>,
+    tick: impl FnMut(Duration) -> ControlFlow<T>,
+) -> T {
+    run_ticks(Instant::now, pause, interval, end, tick).await
+}
+
+/// [`tick_loop`] with its clock and wait, so that it can be tested without
+/// real time passing.
+async fn run_ticks<T, F: Future<Output = ()>>(
+    clock: impl Fn() -> Instant,
+    mut wait: impl FnMut(Duration) -> F,
+    interval: Duration,
+    end: Option<Duration>,
+    mut tick: impl FnMut(Duration) -> ControlFlow<T>,
+) -> T {
+    let start = clock();
+    loop {
+        let elapsed = clock().saturating_duration_since(start);
+        if let ControlFlow::Break(value) = tick(elapsed) {
+            return value;
+        }
+        wait(next_wait(interval, elapsed, end)).await;
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets how often the group's async methods, such as
+    /// [`MotorGroup::transition`] and [`MotorGroup::require_velocity`], read
+    /// and write the motors.
+    ///
+    /// The V5 Brain has a single core, so an async method has to give the
+    /// other tasks (including driver control) a turn regularly. Every async
+    /// method in this crate does its work in ticks, and waits for this
+    /// interval between every two of them. Each method documents the most
+    /// work it does in a single tick, which is at most a few reads and one
+    /// write per motor.
+    ///
+    /// The default is [`Motor::WRITE_INTERVAL`] (5ms), which is as often as
+    /// the motors accept new commands and report new readings, so a shorter
+    /// interval only takes time away from other tasks. A longer one trades
+    /// responsiveness for more time for the rest of the program. An interval
+    /// of zero still yields to other tasks once per tick, but is flagged by
+    /// [`GroupConfig::validate`](crate::GroupConfig::validate).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Checking the flywheel every 20ms is plenty
+    ///     flywheel.tick_interval(Duration::from_millis(20));
+    ///     _ = flywheel.set_velocity(550);
+    ///     _ = flywheel
+    ///         .require_velocity(550.0, 15.0, Duration::from_secs(2))
+    ///         .await;
+    /// }
+    /// ```
+    pub fn tick_interval(&mut self, interval: Duration) -> &mut Self {
+        self.config.tick_interval = interval;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::{Cell, RefCell},
+        future::{self, Future},
+        ops::ControlFlow,
+        pin::pin,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+    use std::time::Instant;
+
+    use super::{YieldNow, run_ticks};
+
+    const INTERVAL: Duration = Duration::from_millis(5);
+
+    /// A clock that only moves when the mock wait advances it.
+    struct MockTimer {
+        start: Instant,
+        now: Cell<Duration>,
+        waits: RefCell<Vec<Duration>>,
+    }
+
+    impl MockTimer {
+        fn new() -> Self {
+            Self {
+                start: Instant::now(),
+                now: Cell::new(Duration::ZERO),
+                waits: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn now(&self) -> Instant {
+            self.start + self.now.get()
+        }
+
+        fn wait(&self, duration: Duration) -> future::Ready<()> {
+            self.now.set(self.now.get() + duration);
+            self.waits.borrow_mut().push(duration);
+            future::ready(())
+        }
+    }
+
+    /// Runs ticks on `timer` until `stop_after` ticks, returning when each
+    /// tick happened.
+    fn run(
+        timer: &MockTimer,
+        interval: Duration,
+        end: Option<Duration>,
+        stop_after: usize,
+    ) -> Vec<Duration> {
+        let mut ticks = Vec::new();
+        let future = run_ticks(
+            || timer.now(),
+            |duration| timer.wait(duration),
+            interval,
+            end,
+            |elapsed| {
+                ticks.push(elapsed);
+                if ticks.len() == stop_after {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+        // The mock waits are always ready, so the loop runs to completion
+        let mut cx = Context::from_waker(Waker::noop());
+        assert_eq!(pin!(future).poll(&mut cx), Poll::Ready(()));
+        ticks
+    }
+
+    fn millis(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn every_tick_is_followed_by_a_wait() {
+        let timer = MockTimer::new();
+        assert_eq!(run(&timer, INTERVAL, None, 4), millis(&[0, 5, 10, 15]));
+        // No wait after the tick that breaks
+        assert_eq!(*timer.waits.borrow(), millis(&[5, 5, 5]));
+
+        // A zero interval still waits between ticks, which yields
+        let timer = MockTimer::new();
+        assert_eq!(
+            run(&timer, Duration::ZERO, None, 3),
+            vec![Duration::ZERO; 3]
+        );
+        assert_eq!(*timer.waits.borrow(), vec![Duration::ZERO; 2]);
+    }
+
+    #[test]
+    fn a_tick_lands_on_the_end() {
+        let timer = MockTimer::new();
+        let end = Some(Duration::from_millis(12));
+        assert_eq!(
+            run(&timer, INTERVAL, end, 6),
+            millis(&[0, 5, 10, 12, 17, 22])
+        );
+        assert_eq!(*timer.waits.borrow(), millis(&[5, 5, 2, 5, 5]));
+    }
+
+    #[test]
+    fn yielding_is_pending_once() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut future = pin!(YieldNow::default());
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+
+    #[test]
+    fn dropping_cancels_between_ticks() {
+        let ticks = Cell::new(0);
+        {
+            let mut cx = Context::from_waker(Waker::noop());
+            let mut future = pin!(run_ticks(
+                Instant::now,
+                |_| YieldNow::default(),
+                INTERVAL,
+                None,
+                |_| {
+                    ticks.set(ticks.get() + 1);
+                    ControlFlow::<()>::Continue(())
+                },
+            ));
+            // Each poll runs one tick and stops at the wait after it
+            assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+            assert_eq!(ticks.get(), 1);
+            assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+            assert_eq!(ticks.get(), 2);
+        }
+        // Nothing runs once the future is dropped
+        assert_eq!(ticks.get(), 2);
+    }
+}
@@ -0,0 +1,246 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::time::Instant;
+
+use vexide::smart::motor::Motor;
+
+use crate::{MotorGroup, MotorGroupError, WriteKind, write_policy::WriteFailure};
+
+/// How long a single write to a motor group took.
+///
+/// See [`MotorGroup::enable_write_timing`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteTiming {
+    /// The time taken to write to every motor, including the group's own
+    /// overhead.
+    pub total: Duration,
+    /// The time taken to write to each motor, in the order of the group.
+    /// Disabled motors (see [`MotorGroup::set_enabled`]) and checked out
+    /// motors (see [`MotorGroup::checkout`]) have no entry.
+    ///
+    /// Under [`WriteErrorStrategy::Stop`](crate::WriteErrorStrategy::Stop),
+    /// motors after the first failed write aren't written to and have no
+    /// entry.
+    pub per_motor: Vec<Duration>,
+    /// The group's [`MotorGroup::command_generation`] after this write.
+    pub command_generation: u64,
+}
+
+/// Statistics about the duration of writes to a motor group since write
+/// timing was enabled.
+///
+/// See [`MotorGroup::write_timing_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WriteTimingStats {
+    /// The number of writes timed.
+    pub writes: u32,
+    /// The longest total duration of a write.
+    pub max: Duration,
+    /// The mean total duration of a write.
+    pub mean: Duration,
+}
+
+/// The write timing state of a motor group, present only while timing is
+/// enabled.
+#[derive(Debug, Clone)]
+pub(crate) struct WriteTimer {
+    /// The source of timestamps. This is only replaced in tests.
+    pub(crate) clock: fn() -> Instant,
+    last: Option<WriteTiming>,
+    stats: WriteTimingStats,
+    sum: Duration,
+}
+
+impl WriteTimer {
+    pub(crate) fn new() -> Self {
+        Self {
+            clock: Instant::now,
+            last: None,
+            stats: WriteTimingStats::default(),
+            sum: Duration::ZERO,
+        }
+    }
+
+    /// Records a finished write.
+    pub(crate) fn record(&mut self, timing: WriteTiming) {
+        self.stats.writes = self.stats.writes.saturating_add(1);
+        self.stats.max = self.stats.max.max(timing.total);
+        self.sum = self.sum.saturating_add(timing.total);
+        self.stats.mean = self.sum / self.stats.writes;
+        self.last = Some(timing);
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Enables or disables timing of writes to the motor group.
+    ///
+    /// While enabled, every write (such as [`MotorGroup::set_voltage`])
+    /// measures how long it took to reach each motor and the group as a whole.
+    /// On a congested Smart Port bus this can take long enough to eat into a
+    /// control loop's budget. The measurements are available from
+    /// [`MotorGroup::last_write_timing`] and [`MotorGroup::write_timing_stats`].
+    ///
+    /// Timing is disabled by default, in which case writes don't read the
+    /// clock at all. Disabling it discards every measurement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group.enable_write_timing(true);
+    ///
+    ///     _ = motor_group.set_voltage(6.0);
+    ///     if let Some(timing) = motor_group.last_write_timing() {
+    ///         println!("Writing took {:?}", timing.total);
+    ///     }
+    /// }
+    /// ```
+    pub fn enable_write_timing(&mut self, enabled: bool) -> &mut Self {
+        self.write_timer = match (enabled, self.write_timer.take()) {
+            (true, Some(timer)) => Some(timer),
+            (true, None) => Some(WriteTimer::new()),
+            (false, _) => None,
+        };
+        self
+    }
+
+    /// Returns the timing of the most recent write to the motor group, or
+    /// `None` if write timing is disabled or nothing has been written since it
+    /// was enabled.
+    ///
+    /// See [`MotorGroup::enable_write_timing`].
+    pub fn last_write_timing(&self) -> Option<WriteTiming> {
+        self.write_timer.as_ref()?.last.clone()
+    }
+
+    /// Returns statistics about the duration of writes since write timing was
+    /// enabled, or `None` if it is disabled.
+    ///
+    /// See [`MotorGroup::enable_write_timing`].
+    pub fn write_timing_stats(&self) -> Option<WriteTimingStats> {
+        self.write_timer.as_ref().map(|timer| timer.stats)
+    }
+
+    /// [`MotorGroup::write_active`], timing the write as a whole and on each
+    /// motor with `clock`. A motor that's retried is timed over every attempt.
+    pub(crate) fn timed_write_each<E: WriteFailure>(
+        &mut self,
+        clock: fn() -> Instant,
+        kind: WriteKind,
+        mut write: impl FnMut(usize, &mut Motor) -> Result<(), E>,
+    ) -> Result<(), MotorGroupError<E>> {
+        let start = clock();
+        let mut per_motor = Vec::new();
+        let mut last_index = None;
+        let result = self.write_active(kind, |index, motor| {
+            let motor_start = clock();
+            let result = write(index, motor);
+            let elapsed = clock().saturating_duration_since(motor_start);
+            match per_motor.last_mut() {
+                Some(total) if last_index == Some(index) => *total += elapsed,
+                _ => per_motor.push(elapsed),
+            }
+            last_index = Some(index);
+            result
+        });
+
+        let total = clock().saturating_duration_since(start);
+        let command_generation = self.command_generation;
+        if let Some(timer) = &mut self.write_timer {
+            timer.record(WriteTiming {
+                total,
+                per_motor,
+                command_generation,
+            });
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{cell::Cell, time::Duration};
+    use std::time::Instant;
+
+    use vexide::prelude::*;
+
+    use super::{WriteTiming, WriteTimingStats};
+    use crate::{MotorGroup, WriteErrorStrategy, tests::mock_motors};
+
+    thread_local! {
+        static START: Instant = Instant::now();
+        static TICKS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    /// A clock that advances by exactly 1ms every time it's read.
+    fn fake_clock() -> Instant {
+        let ticks = TICKS.with(|ticks| ticks.replace(ticks.get() + 1));
+        START.with(|start| *start + Duration::from_millis(ticks.into()))
+    }
+
+    fn timed_group() -> MotorGroup {
+        let mut group = MotorGroup::new(mock_motors(3, Gearset::Green));
+        group.enable_write_timing(true);
+        group.write_timer.as_mut().unwrap().clock = fake_clock;
+        group
+    }
+
+    #[test]
+    fn disabled_timing_records_nothing() {
+        let mut group = timed_group();
+        group.enable_write_timing(false);
+        _ = group.set_voltage(6.0);
+        assert_eq!(group.last_write_timing(), None);
+        assert_eq!(group.write_timing_stats(), None);
+    }
+
+    #[test]
+    fn writes_are_timed_per_motor() {
+        let mut group = timed_group();
+        assert_eq!(group.last_write_timing(), None);
+        assert_eq!(
+            group.write_timing_stats(),
+            Some(WriteTimingStats::default())
+        );
+
+        // The clock is read once before and after the whole write and before
+        // and after each motor
+        _ = group.set_voltage(6.0);
+        assert_eq!(
+            group.last_write_timing(),
+            Some(WriteTiming {
+                total: Duration::from_millis(7),
+                per_motor: vec![Duration::from_millis(1); 3],
+                command_generation: 1,
+            })
+        );
+
+        // The mock motors fail, so only the first one is written to
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        _ = group.set_voltage(6.0);
+        assert_eq!(
+            group.last_write_timing(),
+            Some(WriteTiming {
+                total: Duration::from_millis(3),
+                per_motor: vec![Duration::from_millis(1)],
+                command_generation: 2,
+            })
+        );
+        assert_eq!(
+            group.write_timing_stats(),
+            Some(WriteTimingStats {
+                writes: 2,
+                max: Duration::from_millis(7),
+                mean: Duration::from_millis(5),
+            })
+        );
+    }
+}
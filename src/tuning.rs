@@ -0,0 +1,123 @@
+//! Passthroughs for vexide's motor tuning APIs, which are only available with
+//! the `vexide-unstable` feature.
+
+use vexide::smart::motor::{Motor, MotorTuningConstants};
+
+use crate::{MotorGroup, MotorGroupError, WriteKind};
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets the internal velocity PID constants of every motor in the group.
+    ///
+    /// The same constants are written to every motor, with errors handled
+    /// according to the group's
+    /// [`WriteErrorStrategy`](crate::WriteErrorStrategy). The constants are
+    /// recorded even if some writes fail, and can be read back with
+    /// [`MotorGroup::velocity_pid_constants`] or as part of
+    /// [`MotorGroup::current_config`].
+    ///
+    /// # Hardware Safety
+    ///
+    /// This passes through to vexide's `dangerous-motor-tuning` API. Modifying
+    /// internal motor control is **dangerous** and can permanently damage
+    /// Smart motors if done incorrectly. VEX doesn't document the default
+    /// constants or their units, so use this entirely at your own risk.
+    ///
+    /// This method is only available with the `vexide-unstable` feature, since
+    /// the underlying vexide API can change with any release.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor encounters an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::{prelude::*, smart::motor::MotorTuningConstants};
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut motor_group = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Green, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Green, Direction::Forward),
+    ///     ]);
+    ///     motor_group
+    ///         .set_velocity_pid_constants(MotorTuningConstants {
+    ///             kf: 1.0,
+    ///             kp: 2.0,
+    ///             ki: 0.5,
+    ///             kd: 0.0,
+    ///             filter: 0.0,
+    ///             integral_limit: 10.0,
+    ///             tolerance: 0.0,
+    ///             sample_rate: Duration::from_millis(10),
+    ///         })
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn set_velocity_pid_constants(
+        &mut self,
+        constants: MotorTuningConstants,
+    ) -> Result<(), MotorGroupError> {
+        self.config.velocity_pid_constants = Some(constants);
+        self.write_each(WriteKind::Configuration, |_, motor| {
+            motor.set_velocity_tuning_constants(constants)
+        })
+    }
+
+    /// Returns the velocity PID constants last set with
+    /// [`MotorGroup::set_velocity_pid_constants`], or `None` if they haven't
+    /// been set.
+    ///
+    /// The motors can't report their PID constants, so this doesn't read from
+    /// them. A motor whose write failed may still be using other constants.
+    pub fn velocity_pid_constants(&self) -> Option<MotorTuningConstants> {
+        self.config.velocity_pid_constants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use vexide::smart::motor::MotorTuningConstants;
+
+    use crate::{GroupConfig, WriteErrorStrategy, tests::two_motor_group};
+
+    const CONSTANTS: MotorTuningConstants = MotorTuningConstants {
+        kf: 1.0,
+        kp: 2.0,
+        ki: 0.5,
+        kd: 0.0,
+        filter: 0.0,
+        integral_limit: 10.0,
+        tolerance: 0.0,
+        sample_rate: Duration::from_millis(10),
+    };
+
+    #[test]
+    fn constants_are_written_to_every_motor() {
+        let mut group = two_motor_group();
+        assert_eq!(group.velocity_pid_constants(), None);
+
+        // Every mock motor fails, but the constants are still recorded
+        let error = group.set_velocity_pid_constants(CONSTANTS).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(group.velocity_pid_constants(), Some(CONSTANTS));
+
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+        let error = group.set_velocity_pid_constants(CONSTANTS).unwrap_err();
+        assert_eq!(error.errors.len(), 1);
+    }
+
+    #[test]
+    fn constants_are_part_of_the_config() {
+        let mut group = two_motor_group();
+        let config = GroupConfig {
+            velocity_pid_constants: Some(CONSTANTS),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(group.apply_config(&config).unwrap_err().errors.len(), 2);
+        assert_eq!(group.current_config(), config);
+    }
+}
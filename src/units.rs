@@ -0,0 +1,121 @@
+use core::f64::consts::TAU;
+
+use vexide::smart::motor::Motor;
+
+use crate::{GetterResult, MotorGroup, SharedMotors, readings};
+
+/// A unit to read a motor group's velocity in, for
+/// [`MotorGroup::velocity_in`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VelocityUnit {
+    /// Rotations per minute of the motors, as returned by
+    /// [`MotorGroup::velocity`].
+    ///
+    /// This is the speed of the motors' output shafts, after their gear
+    /// cartridges.
+    Rpm,
+    /// Radians per second of the motors: RPM × 2π / 60.
+    RadiansPerSec,
+    /// Degrees per second of the motors: RPM × 360 / 60.
+    DegreesPerSec,
+    /// Rotations per minute of the mechanism the motors drive: RPM divided by
+    /// the group's external gear ratio (see [`MotorGroup::external_ratio`]).
+    OutputRpm,
+}
+
+impl VelocityUnit {
+    /// Converts `rpm` into this unit, for a group with an external gear ratio
+    /// of `external_ratio`.
+    pub(crate) fn convert_rpm(self, rpm: f64, external_ratio: f64) -> f64 {
+        match self {
+            Self::Rpm => rpm,
+            Self::RadiansPerSec => rpm * TAU / 60.0,
+            Self::DegreesPerSec => rpm * 360.0 / 60.0,
+            Self::OutputRpm => rpm / external_ratio,
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the motor group's average velocity (see
+    /// [`MotorGroup::velocity`]) in `unit`.
+    ///
+    /// See [`VelocityUnit`] for how each unit is converted from RPM.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor in the group encounters an error. Its result is the
+    ///   average of the motors that could be read, in `unit`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     // Geared up 1:5 to the flywheel
+    ///     _ = flywheel.shift_ratio(1.0 / 5.0);
+    ///
+    ///     if let Ok(rpm) = flywheel.velocity_in(VelocityUnit::OutputRpm) {
+    ///         println!("Flywheel at {rpm:.0} RPM");
+    ///     }
+    /// }
+    /// ```
+    pub fn velocity_in(&self, unit: VelocityUnit) -> GetterResult<f64> {
+        let external_ratio = self.config.external_ratio;
+        readings::map_result(self.velocity(), |rpm| unit.convert_rpm(rpm, external_ratio))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::velocity_in`].
+    pub fn velocity_in(&self, unit: VelocityUnit) -> GetterResult<f64> {
+        self.0.borrow().velocity_in(unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f64::consts::PI;
+
+    use super::VelocityUnit;
+    use crate::tests::two_motor_group;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn each_unit_is_converted_from_rpm() {
+        // 300 RPM is 5 rotations per second
+        assert_close(VelocityUnit::Rpm.convert_rpm(300.0, 2.0), 300.0);
+        assert_close(
+            VelocityUnit::RadiansPerSec.convert_rpm(300.0, 2.0),
+            10.0 * PI,
+        );
+        assert_close(VelocityUnit::DegreesPerSec.convert_rpm(300.0, 2.0), 1800.0);
+        assert_close(VelocityUnit::OutputRpm.convert_rpm(300.0, 2.0), 150.0);
+
+        // Geared up, the output turns faster than the motors
+        assert_close(VelocityUnit::OutputRpm.convert_rpm(600.0, 0.2), 3000.0);
+        assert_close(
+            VelocityUnit::RadiansPerSec.convert_rpm(-60.0, 1.0),
+            -2.0 * PI,
+        );
+    }
+
+    #[test]
+    fn read_errors_are_passed_through() {
+        let group = two_motor_group();
+        let error = group.velocity_in(VelocityUnit::DegreesPerSec).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+    }
+}
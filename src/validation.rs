@@ -0,0 +1,435 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::Motor;
+
+use crate::GroupConfig;
+
+/// Whether [`MotorGroup::apply_config`](crate::MotorGroup::apply_config)
+/// checks a configuration with [`GroupConfig::validate`] before applying it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigValidation {
+    /// Apply the configuration as given, without checking it, except for an
+    /// external gear ratio or position limits that the group can't work
+    /// with at all, which are refused as under
+    /// [`ConfigValidation::Strict`].
+    ///
+    /// This is the default.
+    #[default]
+    Lenient,
+    /// Refuse to apply a configuration that has any warnings. Nothing is
+    /// written and every warning is returned as a
+    /// [`ConfigureError::Invalid`](crate::ConfigureError::Invalid) error.
+    Strict,
+}
+
+/// A problem found in a [`GroupConfig`] by [`GroupConfig::validate`].
+///
+/// Each variant is a separate rule. Its [`Display`](core::fmt::Display)
+/// implementation gives a message that can be shown to a person.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum ConfigWarning {
+    /// A limit or maximum is NaN, infinite, or negative (or the external gear
+    /// ratio isn't positive), so it can't be used meaningfully.
+    InvalidValue {
+        /// The name of the field in [`GroupConfig`] (or a dotted path such as
+        /// `max_current_table.v5` for fields of nested settings).
+        field: &'static str,
+        /// The offending value.
+        value: f64,
+    },
+    /// A voltage or current limit is zero, so the group can't move at all.
+    ZeroLimit {
+        /// The name of the field in [`GroupConfig`].
+        field: &'static str,
+    },
+    /// The voltage limit is above the highest voltage any Smart Motor accepts
+    /// (12V), so it has no effect.
+    VoltageLimitAboveMaximum {
+        /// The configured voltage limit in volts.
+        limit: f64,
+    },
+    /// The per-motor current limit is above the maximum of every motor type in
+    /// the [`MaxCurrentTable`](crate::MaxCurrentTable), so it will always be
+    /// clamped or refused depending on the
+    /// [`CurrentLimitPolicy`](crate::CurrentLimitPolicy).
+    CurrentLimitAboveMaximum {
+        /// The configured current limit in Amperes.
+        limit: f64,
+        /// The highest maximum in the table in Amperes.
+        maximum: f64,
+    },
+    /// Both `current_limit` and `total_current_limit` are set, but only one
+    /// can apply. `current_limit` wins.
+    ConflictingCurrentLimits,
+    /// The minimum position limit is above the maximum, so no position is
+    /// allowed.
+    ReversedPositionLimits {
+        /// The minimum position limit in degrees.
+        min: f64,
+        /// The maximum position limit in degrees.
+        max: f64,
+    },
+}
+
+impl core::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidValue { field, value } => {
+                write!(f, "`{field}` is {value}, which isn't a valid setting")
+            }
+            Self::ZeroLimit { field } => {
+                write!(f, "`{field}` is zero, so the motors can't move")
+            }
+            Self::VoltageLimitAboveMaximum { limit } => write!(
+                f,
+                "`voltage_limit` is {limit}V, above the {}V maximum, so it has no effect",
+                Motor::V5_MAX_VOLTAGE
+            ),
+            Self::CurrentLimitAboveMaximum { limit, maximum } => write!(
+                f,
+                "`current_limit` is {limit}A, above the {maximum}A maximum of every motor type"
+            ),
+            Self::ConflictingCurrentLimits => write!(
+                f,
+                "both `current_limit` and `total_current_limit` are set, so `total_current_limit` is ignored"
+            ),
+            Self::ReversedPositionLimits { min, max } => write!(
+                f,
+                "`position_limits` has a minimum of {min}° above its maximum of {max}°"
+            ),
+        }
+    }
+}
+
+/// Checks a limit for [`ConfigWarning::InvalidValue`] and
+/// [`ConfigWarning::ZeroLimit`].
+fn check_limit(warnings: &mut Vec<ConfigWarning>, field: &'static str, value: f64) {
+    if !value.is_finite() || value < 0.0 {
+        warnings.push(ConfigWarning::InvalidValue { field, value });
+    } else if value == 0.0 {
+        warnings.push(ConfigWarning::ZeroLimit { field });
+    }
+}
+
+impl GroupConfig {
+    /// Checks the configuration for settings that are invalid or contradict
+    /// each other.
+    ///
+    /// This only looks at the configuration itself, not the motors it will be
+    /// applied to. These rules are checked, in order:
+    ///
+    /// 1. The max current table, voltage limit, current limits, and position
+    ///    fallback gain and maximum voltage must be finite and not negative,
+    ///    the external gear ratio must be finite and positive, and the
+    ///    position limits must be finite ([`ConfigWarning::InvalidValue`]).
+    /// 2. The voltage limit and current limits must not be zero
+    ///    ([`ConfigWarning::ZeroLimit`]).
+    /// 3. The voltage limit must not be above 12V
+    ///    ([`ConfigWarning::VoltageLimitAboveMaximum`]).
+    /// 4. The per-motor current limit must not be above every maximum in the
+    ///    max current table ([`ConfigWarning::CurrentLimitAboveMaximum`]).
+    /// 5. Only one of the per-motor and total current limits may be set
+    ///    ([`ConfigWarning::ConflictingCurrentLimits`]).
+    /// 6. The minimum position limit must not be above the maximum
+    ///    ([`ConfigWarning::ReversedPositionLimits`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns every warning found, in the order of the rules above.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide_motorgroup::*;
+    ///
+    /// const LIFT_CONFIG: GroupConfig = GroupConfig {
+    ///     voltage_limit: Some(10.0),
+    ///     current_limit: Some(2.0),
+    ///     ..GroupConfig::DEFAULT
+    /// };
+    ///
+    /// if let Err(warnings) = LIFT_CONFIG.validate() {
+    ///     for warning in warnings {
+    ///         println!("Lift config: {warning}");
+    ///     }
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ConfigWarning>> {
+        let mut warnings = Vec::new();
+
+        for (field, value) in [
+            ("max_current_table.v5", self.max_current_table.v5),
+            ("max_current_table.exp", self.max_current_table.exp),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                warnings.push(ConfigWarning::InvalidValue { field, value });
+            }
+        }
+        if !self.external_ratio.is_finite() || self.external_ratio <= 0.0 {
+            warnings.push(ConfigWarning::InvalidValue {
+                field: "external_ratio",
+                value: self.external_ratio,
+            });
+        }
+        if let Some(fallback) = self.position_fallback {
+            let values = [
+                ("position_fallback.gain", fallback.gain),
+                ("position_fallback.max_voltage", fallback.max_voltage),
+            ];
+            for (field, value) in values {
+                if !value.is_finite() || value < 0.0 {
+                    warnings.push(ConfigWarning::InvalidValue { field, value });
+                }
+            }
+        }
+        if let Some((min, max)) = self.position_limits {
+            let values = [
+                ("position_limits.min", min.as_degrees()),
+                ("position_limits.max", max.as_degrees()),
+            ];
+            for (field, value) in values {
+                if !value.is_finite() {
+                    warnings.push(ConfigWarning::InvalidValue { field, value });
+                }
+            }
+        }
+        if self.tick_interval.is_zero() {
+            warnings.push(ConfigWarning::InvalidValue {
+                field: "tick_interval",
+                value: 0.0,
+            });
+        }
+        let limits = [
+            ("voltage_limit", self.voltage_limit),
+            ("current_limit", self.current_limit),
+            ("total_current_limit", self.total_current_limit),
+        ];
+        for (field, value) in limits {
+            if let Some(value) = value {
+                check_limit(&mut warnings, field, value);
+            }
+        }
+
+        // Values that are already invalid aren't checked any further
+        if let Some(limit) = self.voltage_limit
+            && limit.is_finite()
+            && limit > Motor::V5_MAX_VOLTAGE
+        {
+            warnings.push(ConfigWarning::VoltageLimitAboveMaximum { limit });
+        }
+        let maximum = self.max_current_table.v5.max(self.max_current_table.exp);
+        if let Some(limit) = self.current_limit
+            && limit.is_finite()
+            && limit > maximum
+        {
+            warnings.push(ConfigWarning::CurrentLimitAboveMaximum { limit, maximum });
+        }
+        if self.current_limit.is_some() && self.total_current_limit.is_some() {
+            warnings.push(ConfigWarning::ConflictingCurrentLimits);
+        }
+        if let Some((min, max)) = self.position_limits
+            && min > max
+        {
+            warnings.push(ConfigWarning::ReversedPositionLimits {
+                min: min.as_degrees(),
+                max: max.as_degrees(),
+            });
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+
+    /// Returns the warnings of [`GroupConfig::validate`] about settings that
+    /// would break the group if they were applied: an external gear ratio
+    /// that can't be divided by, and position limits that no position is
+    /// within.
+    pub(crate) fn unusable(&self) -> Vec<ConfigWarning> {
+        self.validate()
+            .err()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|warning| {
+                matches!(
+                    warning,
+                    ConfigWarning::InvalidValue {
+                        field: "external_ratio" | "position_limits.min" | "position_limits.max",
+                        ..
+                    } | ConfigWarning::ReversedPositionLimits { .. }
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use vexide::math::Angle;
+
+    use super::{ConfigValidation, ConfigWarning};
+    use crate::{
+        ConfigureError, GroupConfig, MaxCurrentTable, MotorGroup, PositionFallback,
+        WriteErrorStrategy, tests::v5_motor,
+    };
+
+    #[test]
+    fn each_rule_is_checked() {
+        let cases = [
+            (
+                GroupConfig {
+                    max_current_table: MaxCurrentTable {
+                        v5: f64::NAN,
+                        exp: -1.0,
+                    },
+                    external_ratio: 0.0,
+                    ..GroupConfig::DEFAULT
+                },
+                vec![
+                    "max_current_table.v5",
+                    "max_current_table.exp",
+                    "external_ratio",
+                ],
+            ),
+            (
+                GroupConfig {
+                    voltage_limit: Some(f64::INFINITY),
+                    current_limit: Some(-2.0),
+                    ..GroupConfig::DEFAULT
+                },
+                vec!["voltage_limit", "current_limit"],
+            ),
+            (
+                GroupConfig {
+                    position_fallback: Some(PositionFallback {
+                        failures_to_enter: 2,
+                        gain: f64::NAN,
+                        max_voltage: -1.0,
+                    }),
+                    position_limits: Some((Angle::ZERO, Angle::from_degrees(f64::INFINITY))),
+                    tick_interval: Duration::ZERO,
+                    ..GroupConfig::DEFAULT
+                },
+                vec![
+                    "position_fallback.gain",
+                    "position_fallback.max_voltage",
+                    "position_limits.max",
+                    "tick_interval",
+                ],
+            ),
+        ];
+        for (config, fields) in cases {
+            let warnings = config.validate().unwrap_err();
+            assert_eq!(warnings.len(), fields.len());
+            for (warning, expected) in warnings.iter().zip(fields) {
+                assert!(
+                    matches!(warning, ConfigWarning::InvalidValue { field, .. } if *field == expected)
+                );
+            }
+        }
+
+        let cases = [
+            (
+                GroupConfig {
+                    voltage_limit: Some(0.0),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::ZeroLimit {
+                    field: "voltage_limit",
+                },
+            ),
+            (
+                GroupConfig {
+                    total_current_limit: Some(0.0),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::ZeroLimit {
+                    field: "total_current_limit",
+                },
+            ),
+            (
+                GroupConfig {
+                    voltage_limit: Some(13.0),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::VoltageLimitAboveMaximum { limit: 13.0 },
+            ),
+            (
+                GroupConfig {
+                    current_limit: Some(3.0),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::CurrentLimitAboveMaximum {
+                    limit: 3.0,
+                    maximum: 2.5,
+                },
+            ),
+            (
+                GroupConfig {
+                    current_limit: Some(2.0),
+                    total_current_limit: Some(4.0),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::ConflictingCurrentLimits,
+            ),
+            (
+                GroupConfig {
+                    position_limits: Some((Angle::from_degrees(90.0), Angle::ZERO)),
+                    ..GroupConfig::DEFAULT
+                },
+                ConfigWarning::ReversedPositionLimits {
+                    min: Angle::from_degrees(90.0).as_degrees(),
+                    max: 0.0,
+                },
+            ),
+        ];
+        for (config, warning) in cases {
+            assert_eq!(config.validate(), Err(vec![warning]));
+        }
+    }
+
+    #[test]
+    fn plausible_configs_are_valid() {
+        assert_eq!(GroupConfig::DEFAULT.validate(), Ok(()));
+        let config = GroupConfig {
+            voltage_limit: Some(12.0),
+            current_limit: Some(2.5),
+            ..GroupConfig::DEFAULT
+        };
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn strict_configs_are_checked_before_applying() {
+        let mut group = MotorGroup::new(vec![v5_motor(1)]);
+        let config = GroupConfig {
+            validation: ConfigValidation::Strict,
+            write_error_strategy: WriteErrorStrategy::Stop,
+            voltage_limit: Some(0.0),
+            ..GroupConfig::DEFAULT
+        };
+        let error = group.apply_config(&config).unwrap_err();
+        assert!(matches!(
+            error.errors[..],
+            [ConfigureError::Invalid {
+                warning: ConfigWarning::ZeroLimit { .. }
+            }]
+        ));
+        // Nothing was applied, not even the software settings
+        assert_eq!(group.current_config(), GroupConfig::DEFAULT);
+
+        // A lenient config is applied as given
+        let config = GroupConfig {
+            validation: ConfigValidation::Lenient,
+            ..config
+        };
+        let error = group.apply_config(&config).unwrap_err();
+        assert!(matches!(error.errors[..], [ConfigureError::Port { .. }]));
+        assert_eq!(group.current_config(), config);
+    }
+}
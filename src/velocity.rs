@@ -0,0 +1,221 @@
+use vexide::smart::{
+    PortError,
+    motor::{Gearset, Motor},
+};
+
+use crate::{MotorGroup, MotorGroupError, SharedMotors};
+
+/// Error returned by [`MotorGroup::set_velocity_f64`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetVelocityError {
+    /// Generic port related error.
+    Port {
+        /// The source of the error.
+        source: PortError,
+    },
+    /// The velocity is faster than the group's cartridge can spin, or isn't
+    /// a number at all.
+    OutOfRange {
+        /// The velocity given, in RPM.
+        rpm: f64,
+        /// The fastest velocity allowed, in RPM.
+        max_rpm: f64,
+    },
+}
+
+impl From<PortError> for SetVelocityError {
+    fn from(source: PortError) -> Self {
+        Self::Port { source }
+    }
+}
+
+impl core::fmt::Display for SetVelocityError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Port { source } => write!(f, "{source}"),
+            Self::OutOfRange { rpm, max_rpm } => {
+                write!(f, "velocity {rpm} RPM is outside ±{max_rpm} RPM")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SetVelocityError {}
+
+/// Rounds a velocity in RPM to the whole RPM the motors are commanded with.
+///
+/// Halves round away from zero, so `2.5` becomes `3` and `-2.5` becomes `-3`.
+/// Velocities beyond the range of an [`i32`] saturate, and NaN becomes `0`.
+/// Every velocity the crate computes as a float goes through here before
+/// it's written, so they all round the same way.
+pub(crate) fn round_rpm(rpm: f64) -> i32 {
+    rpm.round() as i32
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Spins the motor group at a fractional velocity in RPM.
+    ///
+    /// The motors only take whole RPM, so this is the one place a computed
+    /// velocity is converted for them: `rpm` is rounded to the nearest RPM,
+    /// with halves rounding away from zero (`2.5` spins the motors at `3`,
+    /// and `-2.5` at `-3`), then given to [`MotorGroup::set_velocity`]. The
+    /// effective resolution is therefore 1 RPM, which is 1/600 of a blue
+    /// cartridge's free speed and 1/100 of a red one's. Prefer this over
+    /// casting with `as i32`, which truncates towards zero and makes
+    /// `99.9` spin the motors at `99`.
+    ///
+    /// `rpm` is checked against the cartridge's free speed (see
+    /// [`MotorGroup::max_rpm`]) before anything is written. If the group's
+    /// gearset isn't known, it's checked against the fastest cartridge's
+    /// 600 RPM instead. A velocity that rounds to the free speed is allowed.
+    ///
+    /// # Errors
+    ///
+    /// - A [`SetVelocityError::OutOfRange`] error is returned, and no motor
+    ///   is written to, if `rpm` rounds to more than the free speed in either
+    ///   direction, or is NaN or infinite.
+    /// - A [`SetVelocityError::Port`] error is returned if a motor device is
+    ///   not currently connected to the Smart Port.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut flywheel = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///     _ = flywheel.set_gearset(Gearset::Blue);
+    ///
+    ///     // Run at 80% of the cartridge's free speed: 480 RPM
+    ///     if let Some(max_rpm) = flywheel.max_rpm() {
+    ///         _ = flywheel.set_velocity_f64(max_rpm * 0.8);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_velocity_f64(&mut self, rpm: f64) -> Result<(), MotorGroupError<SetVelocityError>> {
+        let max_rpm = self.max_rpm().unwrap_or(Gearset::Blue.max_rpm());
+        if !rpm.is_finite() || f64::from(round_rpm(rpm)).abs() > max_rpm {
+            return Err(MotorGroupError::new(vec![SetVelocityError::OutOfRange {
+                rpm,
+                max_rpm,
+            }]));
+        }
+        self.set_velocity(round_rpm(rpm)).map_err(|error| {
+            MotorGroupError::new(
+                error
+                    .errors
+                    .into_iter()
+                    .map(SetVelocityError::from)
+                    .collect(),
+            )
+        })
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_velocity_f64`].
+    pub fn set_velocity_f64(&mut self, rpm: f64) -> Result<(), MotorGroupError<SetVelocityError>> {
+        let result = self.0.borrow_mut().set_velocity_f64(rpm);
+        self.mirror(|shadow| {
+            shadow.set_velocity_f64(rpm).map_err(|error| {
+                MotorGroupError::new(
+                    error
+                        .errors
+                        .into_iter()
+                        .filter_map(|error| match error {
+                            SetVelocityError::Port { source } => Some(source),
+                            SetVelocityError::OutOfRange { .. } => None,
+                        })
+                        .collect(),
+                )
+            })
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{
+        prelude::*,
+        smart::{PortError, motor::MotorControl},
+    };
+
+    use super::{SetVelocityError, round_rpm};
+    use crate::{MotorGroup, tests::mock_motors};
+
+    fn motors(gearset: Gearset) -> MotorGroup {
+        let mut group = MotorGroup::new(mock_motors(1, gearset));
+        _ = group.set_gearset(gearset);
+        group
+    }
+
+    #[test]
+    fn halves_round_away_from_zero() {
+        assert_eq!(round_rpm(2.5), 3);
+        assert_eq!(round_rpm(2.49), 2);
+        assert_eq!(round_rpm(-2.5), -3);
+        assert_eq!(round_rpm(-2.49), -2);
+        assert_eq!(round_rpm(0.5), 1);
+        assert_eq!(round_rpm(-0.4), 0);
+        // Unlike a cast, which truncates towards zero
+        assert_eq!(round_rpm(99.9), 100);
+        assert_eq!(round_rpm(-99.9), -100);
+
+        assert_eq!(round_rpm(1e12), i32::MAX);
+        assert_eq!(round_rpm(-1e12), i32::MIN);
+        assert_eq!(round_rpm(f64::NAN), 0);
+    }
+
+    #[test]
+    fn rounded_velocity_is_commanded() {
+        let mut group = motors(Gearset::Green);
+        // Every write to the mock motors fails, but the target is still set
+        let error = group.set_velocity_f64(-50.5).unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![SetVelocityError::Port {
+                source: PortError::Disconnected { port: 1 }
+            }]
+        );
+        assert_eq!(group.last_command, Some(MotorControl::Velocity(-51)));
+
+        // A velocity that rounds to the free speed is allowed
+        _ = group.set_velocity_f64(200.4);
+        assert_eq!(group.last_command, Some(MotorControl::Velocity(200)));
+    }
+
+    #[test]
+    fn out_of_range_velocities_are_rejected() {
+        let mut group = motors(Gearset::Green);
+        let generation = group.command_generation();
+        for rpm in [200.5, -200.5, f64::INFINITY, f64::NEG_INFINITY] {
+            let error = group.set_velocity_f64(rpm).unwrap_err();
+            assert_eq!(
+                error.errors,
+                vec![SetVelocityError::OutOfRange {
+                    rpm,
+                    max_rpm: 200.0
+                }]
+            );
+        }
+        assert!(matches!(
+            group.set_velocity_f64(f64::NAN).unwrap_err().errors[..],
+            [SetVelocityError::OutOfRange { .. }]
+        ));
+        // Nothing was written
+        assert_eq!(group.command_generation(), generation);
+
+        // Red cartridges are slower
+        let mut group = motors(Gearset::Red);
+        assert!(matches!(
+            group.set_velocity_f64(150.0).unwrap_err().errors[..],
+            [SetVelocityError::OutOfRange { max_rpm, .. }] if max_rpm == 100.0
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::vec::Vec;
+
+use vexide::smart::motor::{Motor, MotorControl};
+
+use crate::{GetterResult, MotorGroup, readings};
+
+/// How far in volts a motor's measured voltage can be from its command for the
+/// command to count as accepted by [`MotorGroup::set_voltage_verified`].
+const VOLTAGE_TOLERANCE: f64 = 1.0;
+
+/// Returns, for each of the `len` motors of a group, whether it accepted a
+/// voltage command.
+///
+/// A motor accepted the command if its write succeeded (`written`) and its
+/// measured voltage (`measured`) is within `tolerance` of the voltage it was
+/// sent (`targets`), limited to `voltage_limit`. Motors missing from
+/// `written` or `measured` didn't accept it.
+pub(crate) fn accepted_voltages(
+    len: usize,
+    targets: &[MotorControl],
+    written: &[(usize, bool)],
+    measured: &[(usize, f64)],
+    voltage_limit: Option<f64>,
+    tolerance: f64,
+) -> Vec<bool> {
+    let mut accepted = alloc::vec![false; len];
+    for &(index, voltage) in measured {
+        let MotorControl::Voltage(expected) = targets[index] else {
+            continue;
+        };
+        let expected = match voltage_limit {
+            Some(limit) => expected.clamp(-limit.abs(), limit.abs()),
+            None => expected,
+        };
+        let was_written = written.contains(&(index, true));
+        accepted[index] = was_written && (voltage - expected).abs() <= tolerance;
+    }
+    accepted
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Sets the motor group's output voltage like [`MotorGroup::set_voltage`],
+    /// then reads each motor's voltage back to check that it was accepted.
+    ///
+    /// A fault can leave a motor connected and accepting writes while
+    /// ignoring them. This returns one entry per motor in the group, which is
+    /// `true` if the motor accepted the write and its measured voltage (see
+    /// [`Motor::voltage`]) is within 1V of the voltage it was sent. That is
+    /// `volts` scaled for the motor (see [`MotorGroup::set_scale`]) and
+    /// limited to the group's voltage limit (see
+    /// [`MotorGroup::set_voltage_limit`]) and hard voltage cap (see
+    /// [`MotorGroup::set_hard_voltage_cap`]), if it has them. Disabled and checked
+    /// out motors are neither written to nor read, and are `false`.
+    ///
+    /// This costs an extra read of every motor after the write. The read
+    /// happens straight away, and motors only report their voltage every few
+    /// milliseconds, so after a large change in voltage the read can still
+    /// show the old one. This is most useful in a loop that sends the same
+    /// voltage every iteration, where a motor that keeps reporting `false`
+    /// isn't following its commands.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if
+    ///   any motor couldn't be written to or read. Its result is still which
+    ///   motors accepted the voltage.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut intake = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         if let Ok(accepted) = intake.set_voltage_verified(8.0) {
+    ///             for (index, _) in accepted.iter().enumerate().filter(|(_, ok)| !**ok) {
+    ///                 println!("Intake motor {index} isn't following its voltage");
+    ///             }
+    ///         }
+    ///         sleep(Duration::from_millis(20)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn set_voltage_verified(&mut self, volts: f64) -> GetterResult<Vec<bool>> {
+        let target = MotorControl::Voltage(volts);
+        let targets = self.scaled_targets(target);
+        let (result, written) = self.write_command(target);
+        let (measured, read_errors) = readings::partition(self.read_each(Motor::voltage));
+
+        let voltage_limit = [
+            self.config.voltage_limit.map(f64::abs),
+            self.hard_voltage_cap,
+        ]
+        .into_iter()
+        .flatten()
+        .reduce(f64::min);
+        let accepted = accepted_voltages(
+            self.motors.as_ref().len(),
+            &targets,
+            &written,
+            &measured,
+            voltage_limit,
+            VOLTAGE_TOLERANCE,
+        );
+        let mut errors = result.err().map(|error| error.errors).unwrap_or_default();
+        errors.extend(read_errors);
+        readings::finish(Some(accepted), errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::smart::motor::MotorControl;
+
+    use super::{VOLTAGE_TOLERANCE, accepted_voltages};
+    use crate::tests::two_motor_group;
+
+    const ALL_WRITTEN: [(usize, bool); 3] = [(0, true), (1, true), (2, true)];
+
+    fn voltages(volts: [f64; 3]) -> Vec<MotorControl> {
+        volts.into_iter().map(MotorControl::Voltage).collect()
+    }
+
+    #[test]
+    fn a_motor_ignoring_the_command_is_caught() {
+        let targets = voltages([8.0; 3]);
+        // Motor 1 takes the write but stays at zero
+        let measured = [(0, 7.6), (1, 0.0), (2, 8.4)];
+        assert_eq!(
+            accepted_voltages(
+                3,
+                &targets,
+                &ALL_WRITTEN,
+                &measured,
+                None,
+                VOLTAGE_TOLERANCE
+            ),
+            [true, false, true]
+        );
+    }
+
+    #[test]
+    fn failed_writes_and_reads_are_not_accepted() {
+        let targets = voltages([0.0; 3]);
+        // Motor 0's write failed even though it reads the right voltage,
+        // and motor 2 couldn't be read
+        let written = [(0, false), (1, true), (2, true)];
+        let measured = [(0, 0.0), (1, 0.0)];
+        assert_eq!(
+            accepted_voltages(3, &targets, &written, &measured, None, VOLTAGE_TOLERANCE),
+            [false, true, false]
+        );
+    }
+
+    #[test]
+    fn scales_and_limits_set_the_expected_voltage() {
+        // Motor 1 is scaled down to 6V, and motor 2 is limited to 10V
+        let targets = voltages([12.0, 6.0, -12.0]);
+        let measured = [(0, 10.0), (1, 6.2), (2, -10.0)];
+        assert_eq!(
+            accepted_voltages(3, &targets, &ALL_WRITTEN, &measured, Some(10.0), 0.5),
+            [true, true, true]
+        );
+        assert_eq!(
+            accepted_voltages(3, &targets, &ALL_WRITTEN, &measured, None, 0.5),
+            [false, true, false]
+        );
+    }
+
+    #[test]
+    fn unreachable_motors_are_reported() {
+        let mut group = two_motor_group();
+        // Both the write and the read fail on each mock motor
+        let error = group.set_voltage_verified(6.0).unwrap_err();
+        assert_eq!(error.errors.len(), 4);
+        assert_eq!(error.result, Some(vec![false, false]));
+        assert_eq!(group.last_command, Some(MotorControl::Voltage(6.0)));
+    }
+}
@@ -0,0 +1,122 @@
+use vexide::smart::motor::Motor;
+
+use crate::{MotorGroup, SharedMotors};
+
+/// Something that looks at each motor of a group in turn, such as a
+/// telemetry collector or a logger.
+///
+/// Passed to [`MotorGroup::accept`], which calls [`MotorVisitor::visit`] once
+/// for every motor. This lets tooling outside the crate read the motors
+/// directly without the group knowing about it.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use vexide::prelude::*;
+/// use vexide_motorgroup::*;
+///
+/// /// Finds the hottest motor in a group.
+/// #[derive(Default)]
+/// struct Hottest {
+///     hottest: Option<(usize, f64)>,
+/// }
+///
+/// impl MotorVisitor for Hottest {
+///     fn visit(&mut self, index: usize, motor: &Motor) {
+///         if let Ok(temperature) = motor.temperature()
+///             && self.hottest.is_none_or(|(_, hottest)| temperature > hottest)
+///         {
+///             self.hottest = Some((index, temperature));
+///         }
+///     }
+/// }
+/// ```
+pub trait MotorVisitor {
+    /// Visits the motor at `index` in the group.
+    fn visit(&mut self, index: usize, motor: &Motor);
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Calls `visitor` with every motor in the group, in order.
+    ///
+    /// Every motor is visited, including disabled ones (see
+    /// [`MotorGroup::set_enabled`]), and the index given with each motor is
+    /// its index in the group, so it can be passed to methods such as
+    /// [`MotorGroup::is_enabled`] and [`MotorGroup::label`]. The visitor only
+    /// gets shared access, so it can read the motors but not command them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// struct PortLogger;
+    ///
+    /// impl MotorVisitor for PortLogger {
+    ///     fn visit(&mut self, index: usize, motor: &Motor) {
+    ///         println!("Motor {index} is on port {}", motor.port_number());
+    ///     }
+    /// }
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     drive.accept(&mut PortLogger);
+    /// }
+    /// ```
+    pub fn accept(&self, visitor: &mut impl MotorVisitor) {
+        for (index, motor) in self.motors.as_ref().iter().enumerate() {
+            visitor.visit(index, motor);
+        }
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::accept`].
+    pub fn accept(&self, visitor: &mut impl MotorVisitor) {
+        self.0.borrow().accept(visitor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::SmartDevice};
+
+    use super::MotorVisitor;
+    use crate::{MotorGroup, SharedMotors, tests::v5_motor};
+
+    /// Counts the motors it visits, and records their indices and ports.
+    #[derive(Default)]
+    struct Counter {
+        visited: Vec<(usize, u8)>,
+    }
+
+    impl MotorVisitor for Counter {
+        fn visit(&mut self, index: usize, motor: &Motor) {
+            self.visited.push((index, motor.port_number()));
+        }
+    }
+
+    fn motors() -> Vec<Motor> {
+        vec![v5_motor(4), v5_motor(7), v5_motor(9)]
+    }
+
+    #[test]
+    fn every_motor_is_visited_in_order() {
+        let mut group = MotorGroup::new(motors());
+        group.set_enabled(1, false);
+
+        let mut counter = Counter::default();
+        group.accept(&mut counter);
+        assert_eq!(counter.visited, vec![(0, 4), (1, 7), (2, 9)]);
+
+        // Visiting again carries on with the same visitor
+        SharedMotors::new(group).accept(&mut counter);
+        assert_eq!(counter.visited.len(), 6);
+    }
+}
@@ -0,0 +1,274 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use std::time::Instant;
+
+use vexide::smart::{PortError, motor::Motor};
+
+use crate::{GetterResult, MotorGroup, MotorGroupError, readings};
+
+/// The efficiency, in percent, that a healthy motor reaches under load. A
+/// motor at or above it gets a full efficiency term.
+const HEALTHY_EFFICIENCY: f64 = 50.0;
+
+/// The heating rate, in °C per second, at which a motor gets no heating term
+/// at all.
+const MAX_HEATING_RATE: f64 = 0.1;
+
+/// The power draw, in Watts, above which a motor is considered under load, so
+/// its efficiency says something about its wear.
+const MIN_LOADED_POWER: f64 = 1.0;
+
+/// A single sample taken by [`MotorGroup::update_wear`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct WearSample {
+    pub(crate) at: Instant,
+    pub(crate) temperature: f64,
+    /// The motor's efficiency in percent, or `None` if it wasn't under load.
+    pub(crate) efficiency: Option<f64>,
+}
+
+/// The recent wear samples of a single motor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct WearHistory {
+    samples: VecDeque<WearSample>,
+    /// The error of the last update, if it failed.
+    pub(crate) last_error: Option<PortError>,
+}
+
+impl WearHistory {
+    /// The maximum number of samples kept. Older samples are dropped.
+    pub(crate) const CAPACITY: usize = 50;
+
+    /// Adds a sample, dropping the oldest one if the history is full.
+    pub(crate) fn push(&mut self, sample: WearSample) {
+        if self.samples.len() == Self::CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.last_error = None;
+    }
+
+    /// Returns the motor's health score between `0.0` (worn) and `1.0`
+    /// (healthy).
+    ///
+    /// See [`MotorGroup::wear_score`] for the formula.
+    pub(crate) fn score(&self) -> f64 {
+        let efficiency = readings::mean(self.samples.iter().filter_map(|sample| sample.efficiency))
+            .map_or(1.0, |efficiency| {
+                (efficiency / HEALTHY_EFFICIENCY).clamp(0.0, 1.0)
+            });
+
+        let heating_rate = match (self.samples.front(), self.samples.back()) {
+            (Some(first), Some(last)) if last.at > first.at => {
+                (last.temperature - first.temperature) / (last.at - first.at).as_secs_f64()
+            }
+            _ => 0.0,
+        };
+        let heating = 1.0 - (heating_rate / MAX_HEATING_RATE).clamp(0.0, 1.0);
+
+        efficiency * heating
+    }
+}
+
+/// Reads a wear sample from `motor`.
+fn sample(motor: &Motor) -> Result<WearSample, PortError> {
+    let loaded = motor.power()? >= MIN_LOADED_POWER;
+    Ok(WearSample {
+        at: Instant::now(),
+        temperature: motor.temperature()?,
+        efficiency: if loaded {
+            Some(motor.efficiency()?)
+        } else {
+            None
+        },
+    })
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Samples every motor's efficiency and temperature for
+    /// [`MotorGroup::wear_score`].
+    ///
+    /// Call this at a steady interval, such as every 500ms from a telemetry
+    /// task. Each motor keeps its last 50 samples, so at that rate the score
+    /// covers the last 25 seconds. Efficiency is only sampled while a motor
+    /// draws at least 1W, since a motor at rest reads 0% efficiency no matter
+    /// how worn it is.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if a motor device is not
+    ///   currently connected to the Smart Port. The other motors are still
+    ///   sampled.
+    pub fn update_wear(&mut self) -> Result<(), MotorGroupError> {
+        let mut errors = Vec::new();
+        for (motor, meta) in self.motors.as_ref().iter().zip(&mut self.meta) {
+            if !meta.is_active() {
+                continue;
+            }
+            match sample(motor) {
+                Ok(sample) => meta.wear.push(sample),
+                Err(error) => {
+                    meta.wear.last_error = Some(error);
+                    errors.push(error);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupError::new(errors))
+        }
+    }
+
+    /// Returns the health score of the most worn motor in the group, between
+    /// `0.0` (worn) and `1.0` (healthy).
+    ///
+    /// Friction from a failing bearing or a bent shaft shows up as lower
+    /// efficiency and as a motor that heats up faster than it should. Each
+    /// motor's score combines both over the samples taken by
+    /// [`MotorGroup::update_wear`]:
+    ///
+    /// ```text
+    /// efficiency term = clamp(mean loaded efficiency / 50%, 0, 1)
+    /// heating term    = 1 - clamp(heating rate / 0.1 °C/s, 0, 1)
+    /// score           = efficiency term × heating term
+    /// ```
+    ///
+    /// The heating rate is the temperature change from the oldest sample to
+    /// the newest one, divided by the time between them; cooling down counts
+    /// as no heating. A term that there aren't enough samples for yet (no
+    /// loaded samples, or fewer than two samples) is `1.0`, so a motor is
+    /// assumed healthy until shown otherwise.
+    ///
+    /// The scores are only comparable between samples taken under similar
+    /// conditions, so trend them across matches (for example, read them at
+    /// the end of every match) rather than within one.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if the last
+    ///   [`MotorGroup::update_wear`] failed for any motor. Its result is the
+    ///   lowest score of the other motors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     loop {
+    ///         _ = drive.update_wear();
+    ///         if drive.wear_score().is_ok_and(|score| score < 0.5) {
+    ///             println!("Check the drive: wear scores {:?}", drive.wear_scores());
+    ///         }
+    ///         sleep(Duration::from_millis(500)).await;
+    ///     }
+    /// }
+    /// ```
+    pub fn wear_score(&self) -> GetterResult<f64> {
+        let mut errors = Vec::new();
+        let mut lowest: Option<f64> = None;
+        for meta in self.meta.iter().filter(|meta| meta.is_active()) {
+            match meta.wear.last_error {
+                Some(error) => errors.push(error),
+                None => {
+                    let score = meta.wear.score();
+                    lowest = Some(lowest.map_or(score, |lowest| lowest.min(score)));
+                }
+            }
+        }
+        readings::finish(lowest, errors)
+    }
+
+    /// Returns the health score of each motor in the group, in order.
+    ///
+    /// See [`MotorGroup::wear_score`] for how the scores are computed. Every
+    /// motor is included, scored from the samples it has, even if it's
+    /// disabled or its last update failed.
+    pub fn wear_scores(&self) -> Vec<f64> {
+        self.meta.iter().map(|meta| meta.wear.score()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use vexide::smart::PortError;
+
+    use super::{WearHistory, WearSample};
+    use crate::tests::two_motor_group;
+
+    /// Builds a history from `(seconds, temperature, efficiency)` samples.
+    fn history(samples: &[(u64, f64, Option<f64>)]) -> WearHistory {
+        let start = Instant::now();
+        let mut history = WearHistory::default();
+        for &(seconds, temperature, efficiency) in samples {
+            history.push(WearSample {
+                at: start + Duration::from_secs(seconds),
+                temperature,
+                efficiency,
+            });
+        }
+        history
+    }
+
+    #[test]
+    fn score_combines_efficiency_and_heating() {
+        // Nothing known yet, so nothing is wrong
+        assert_eq!(WearHistory::default().score(), 1.0);
+        // Efficient and cool
+        assert_eq!(
+            history(&[(0, 30.0, Some(60.0)), (10, 30.0, Some(55.0))]).score(),
+            1.0
+        );
+        // 40% efficient, heating at 0.05 °C/s: 0.8 × 0.5
+        let score = history(&[(0, 30.0, Some(40.0)), (20, 31.0, Some(40.0))]).score();
+        assert!((score - 0.4).abs() < 1e-9);
+        // Samples at rest don't lower the efficiency term, and cooling down
+        // doesn't raise the heating term
+        let score = history(&[(0, 40.0, Some(25.0)), (10, 35.0, None)]).score();
+        assert!((score - 0.5).abs() < 1e-9);
+        // Heating fast enough makes the motor fully worn
+        assert_eq!(history(&[(0, 30.0, None), (10, 32.0, None)]).score(), 0.0);
+    }
+
+    #[test]
+    fn history_keeps_only_recent_samples() {
+        let samples: Vec<_> = (0..WearHistory::CAPACITY as u64 * 2)
+            .map(|seconds| {
+                // Heating fast for the first half, then holding steady
+                let temperature = 30.0 + seconds.min(WearHistory::CAPACITY as u64) as f64;
+                (seconds, temperature, None)
+            })
+            .collect();
+        assert_eq!(history(&samples).score(), 1.0);
+    }
+
+    #[test]
+    fn failed_updates_are_reported() {
+        let mut group = two_motor_group();
+        // Before any update, every motor is assumed healthy
+        assert_eq!(group.wear_score().unwrap(), 1.0);
+
+        let error = group.update_wear().unwrap_err();
+        assert_eq!(
+            error.errors,
+            vec![
+                PortError::Disconnected { port: 1 },
+                PortError::Disconnected { port: 2 }
+            ]
+        );
+        let error = group.wear_score().unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+        assert_eq!(group.wear_scores(), vec![1.0, 1.0]);
+    }
+}
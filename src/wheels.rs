@@ -0,0 +1,143 @@
+use core::f64::consts::PI;
+
+use alloc::vec::Vec;
+
+use vexide::smart::motor::Motor;
+
+use crate::{GetterResult, MotorGroup, readings::Reading};
+
+/// Converts a velocity in RPM to the surface speed of a wheel of `diameter`,
+/// in `diameter` units per second.
+///
+/// Each revolution moves the wheel's surface by its circumference, `π × d`,
+/// and there are 60 seconds in a minute.
+pub(crate) fn surface_speed(rpm: f64, diameter: f64) -> f64 {
+    rpm * PI * diameter / 60.0
+}
+
+/// Converts each motor's velocity reading to the surface speed of its wheel.
+///
+/// Motors without a diameter are left out.
+pub(crate) fn surface_speeds(
+    velocities: impl IntoIterator<Item = Reading<f64>>,
+    diameters: &[f64],
+) -> Vec<Reading<f64>> {
+    velocities
+        .into_iter()
+        .filter_map(|(index, velocity)| {
+            let diameter = *diameters.get(index)?;
+            Some((index, velocity.map(|rpm| surface_speed(rpm, diameter))))
+        })
+        .collect()
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Returns the average surface speed of the wheels driven by the motor
+    /// group, where each motor drives a wheel of a different diameter.
+    ///
+    /// `wheel_diameters[i]` is the diameter of the wheel driven by motor `i`.
+    /// Each motor's velocity in RPM is converted to the speed of its wheel's
+    /// surface, `rpm × π × diameter / 60`, and those speeds are averaged like
+    /// [`MotorGroup::velocity`] averages velocities. The result is in the
+    /// diameters' unit per second, so diameters in inches give inches per
+    /// second.
+    ///
+    /// The velocities are those of the motors, so any external gearing (see
+    /// [`GroupConfig::external_ratio`](crate::GroupConfig::external_ratio))
+    /// between a motor and its wheel should be accounted for in its diameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there isn't exactly one diameter per motor in the group. With
+    /// the `no-panic` feature, release builds instead leave out motors without
+    /// a diameter.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`](crate::MotorGroupError) error is returned if any motor in the group encounters an error.
+    ///   Its result is the average surface speed of the motors that could be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // A 3.25" omni wheel and a 4" traction wheel
+    ///     if let Ok(speed) = drive.weighted_output_velocity(&[3.25, 4.0]) {
+    ///         println!("Driving at {speed:.1} in/s");
+    ///     }
+    /// }
+    /// ```
+    pub fn weighted_output_velocity(&self, wheel_diameters: &[f64]) -> GetterResult<f64> {
+        crate::check_invariant(
+            wheel_diameters.len() == self.motors.as_ref().len(),
+            "wheel_diameters must have one diameter per motor",
+        );
+        let velocities = self.read_each(Motor::velocity);
+        self.average_readings(surface_speeds(velocities, wheel_diameters))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{surface_speed, surface_speeds};
+    use crate::{MotorGroup, readings, tests::mock_motors};
+
+    const DISCONNECTED: PortError = PortError::Disconnected { port: 1 };
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(2, Gearset::Blue))
+    }
+
+    #[test]
+    fn a_revolution_moves_one_circumference() {
+        // 60 RPM is one revolution per second
+        assert!((surface_speed(60.0, 4.0) - 4.0 * core::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(surface_speed(0.0, 4.0), 0.0);
+        assert!(surface_speed(-60.0, 4.0) < 0.0);
+    }
+
+    #[test]
+    fn larger_wheels_weigh_more() {
+        // The same motor velocity on wheels of 2" and 6" averages to the
+        // speed of a 4" wheel, not to the speed of either
+        let velocities = [Ok(120.0), Ok(120.0)].into_iter().enumerate();
+        let speed = readings::average(surface_speeds(velocities, &[2.0, 6.0])).unwrap();
+        assert!((speed - surface_speed(120.0, 4.0)).abs() < 1e-12);
+
+        // A slower motor on a larger wheel can match a faster one on a
+        // smaller wheel
+        let velocities = [Ok(300.0), Ok(100.0)].into_iter().enumerate();
+        let speeds = surface_speeds(velocities, &[2.0, 6.0]);
+        assert!((speeds[0].1.unwrap() - speeds[1].1.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn unreadable_motors_are_left_out() {
+        let velocities = [Ok(60.0), Err(DISCONNECTED)].into_iter().enumerate();
+        let error = readings::average(surface_speeds(velocities, &[2.0, 6.0])).unwrap_err();
+        assert_eq!(error.errors, vec![DISCONNECTED]);
+        assert!((error.result.unwrap() - surface_speed(60.0, 2.0)).abs() < 1e-12);
+
+        let error = group().weighted_output_velocity(&[2.0, 6.0]).unwrap_err();
+        assert_eq!(error.errors.len(), 2);
+        assert_eq!(error.result, None);
+    }
+
+    #[cfg(any(not(feature = "no-panic"), debug_assertions))]
+    #[test]
+    #[should_panic(expected = "wheel_diameters must have one diameter per motor")]
+    fn mismatched_diameters_panic() {
+        _ = group().weighted_output_velocity(&[4.0]);
+    }
+}
@@ -0,0 +1,368 @@
+use alloc::boxed::Box;
+
+use vexide::smart::{
+    PortError,
+    motor::{Motor, SetGearsetError},
+};
+
+#[cfg(feature = "control")]
+use crate::control::TransitionError;
+use crate::{MotorGroup, SharedMotors, WriteErrorStrategy, current_limit::SetCurrentLimitError};
+
+/// What a write policy decides to do after a motor's write fails.
+///
+/// Returned by the callback given to [`MotorGroup::set_custom_write_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PolicyDecision {
+    /// Record the error and carry on writing to the remaining motors, like
+    /// [`WriteErrorStrategy::Ignore`].
+    Continue,
+    /// Record the error and skip every remaining motor, like
+    /// [`WriteErrorStrategy::Stop`].
+    StopRemaining,
+    /// Write to the same motor again.
+    ///
+    /// A motor is retried at most [`WriteContext::MAX_RETRIES`] times per
+    /// write, after which this counts as [`PolicyDecision::Continue`]. Only
+    /// the error of the last attempt is recorded, so a retry that succeeds
+    /// leaves no error behind.
+    RetryThisMotor,
+}
+
+/// What a motor group was writing when a write failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WriteKind {
+    /// A motion command, such as [`MotorGroup::set_target`] or a step of
+    /// [`MotorGroup::transition`].
+    Target,
+    /// The motors' direction, such as [`MotorGroup::set_direction`].
+    Direction,
+    /// The motors' position reference, such as
+    /// [`MotorGroup::reset_position`].
+    Position,
+    /// Any other setting, such as the gearset, current limit or voltage limit.
+    Configuration,
+}
+
+/// The state of a write when one of its motors fails, given to the callback of
+/// [`MotorGroup::set_custom_write_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WriteContext {
+    /// What the group is writing.
+    pub kind: WriteKind,
+    /// The number of motors written to successfully so far in this write.
+    pub succeeded: usize,
+    /// The number of motors whose write failed so far in this write, not
+    /// counting the one that just failed.
+    pub failed: usize,
+    /// The number of times the motor that just failed has been retried.
+    pub retries: u32,
+}
+
+impl WriteContext {
+    /// The number of times a motor is retried at most per write, however
+    /// often the policy returns [`PolicyDecision::RetryThisMotor`].
+    pub const MAX_RETRIES: u32 = 3;
+}
+
+/// The signature of a custom write policy's callback. It's `Send` so that
+/// [`MotorGroup`] stays `Send`.
+type PolicyFn = dyn FnMut(usize, &PortError, &WriteContext) -> PolicyDecision + Send;
+
+/// The callback of [`MotorGroup::set_custom_write_policy`].
+pub(crate) struct CustomWritePolicy(Box<PolicyFn>);
+
+impl core::fmt::Debug for CustomWritePolicy {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CustomWritePolicy").finish_non_exhaustive()
+    }
+}
+
+/// An error from writing to one motor, which may have come from the port.
+pub(crate) trait WriteFailure {
+    /// Returns the port error behind this error, if there is one.
+    fn port_error(&self) -> Option<&PortError>;
+}
+
+impl WriteFailure for PortError {
+    fn port_error(&self) -> Option<&PortError> {
+        Some(self)
+    }
+}
+
+impl WriteFailure for SetGearsetError {
+    fn port_error(&self) -> Option<&PortError> {
+        match self {
+            Self::Port { source } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl WriteFailure for SetCurrentLimitError {
+    fn port_error(&self) -> Option<&PortError> {
+        match self {
+            Self::Port { source } => Some(source),
+            Self::ExceedsMaximum { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "control")]
+impl WriteFailure for TransitionError {
+    fn port_error(&self) -> Option<&PortError> {
+        match self {
+            Self::Port { source } => Some(source),
+            Self::IncompatibleTargets { .. } => None,
+        }
+    }
+}
+
+/// Decides what to do after the write to motor `index` failed with `error`.
+///
+/// The built-in strategies always make the same decision. A custom policy is
+/// only asked about port errors; any other error, or a custom strategy
+/// without a policy, continues like [`WriteErrorStrategy::Ignore`].
+pub(crate) fn decide(
+    strategy: WriteErrorStrategy,
+    policy: Option<&mut CustomWritePolicy>,
+    index: usize,
+    error: &impl WriteFailure,
+    context: &WriteContext,
+) -> PolicyDecision {
+    match (strategy, policy, error.port_error()) {
+        (WriteErrorStrategy::Stop, ..) => PolicyDecision::StopRemaining,
+        (WriteErrorStrategy::Custom, Some(policy), Some(error)) => {
+            (policy.0)(index, error, context)
+        }
+        _ => PolicyDecision::Continue,
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Handles write errors with `policy` instead of a built-in strategy,
+    /// and sets the group's [`WriteErrorStrategy`] to
+    /// [`WriteErrorStrategy::Custom`].
+    ///
+    /// Whenever the write to a motor fails with a port error, `policy` is
+    /// called with the index of the motor, the error, and a [`WriteContext`]
+    /// describing the write so far, and its [`PolicyDecision`] says whether
+    /// to carry on, stop, or retry the motor. This covers policies the
+    /// built-in strategies can't, such as ignoring errors on motors that are
+    /// allowed to fail while stopping on the others.
+    ///
+    /// Errors are still returned as usual. The policy isn't part of the
+    /// group's configuration, so [`MotorGroup::current_config`] and
+    /// [`MotorGroup::apply_config`] can only carry
+    /// [`WriteErrorStrategy::Custom`] itself: a group with that strategy but no
+    /// policy acts like [`WriteErrorStrategy::Ignore`]. Setting another
+    /// strategy with [`MotorGroup::write_error_strategy`] keeps the policy
+    /// for later but stops using it.
+    ///
+    /// The policy must be `Send`, so that the group stays `Send` (see
+    /// [Tasks and threads](crate#tasks-and-threads)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut drive = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Blue, Direction::Forward),
+    ///         Motor::new(peripherals.port_3, Gearset::Blue, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Motor 2 is a spare that may be unplugged; any other failure
+    ///     // stops the write.
+    ///     drive.set_custom_write_policy(|index, _error, _context| {
+    ///         if index == 2 {
+    ///             PolicyDecision::Continue
+    ///         } else {
+    ///             PolicyDecision::StopRemaining
+    ///         }
+    ///     });
+    /// }
+    /// ```
+    pub fn set_custom_write_policy(
+        &mut self,
+        policy: impl FnMut(usize, &PortError, &WriteContext) -> PolicyDecision + Send + 'static,
+    ) -> &mut Self {
+        self.write_policy = Some(CustomWritePolicy(Box::new(policy)));
+        self.config.write_error_strategy = WriteErrorStrategy::Custom;
+        self
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_custom_write_policy`].
+    ///
+    /// The policy must be `Send` even though `SharedMotors` isn't, since it
+    /// is stored in the inner [`MotorGroup`].
+    pub fn set_custom_write_policy(
+        &mut self,
+        policy: impl FnMut(usize, &PortError, &WriteContext) -> PolicyDecision + Send + 'static,
+    ) -> &Self {
+        self.0.borrow_mut().set_custom_write_policy(policy);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use vexide::{prelude::*, smart::PortError};
+
+    use super::{PolicyDecision, WriteContext, WriteKind};
+    use crate::{MotorGroup, WriteErrorStrategy, tests::mock_motors};
+
+    fn mock_group(size: u8) -> MotorGroup {
+        MotorGroup::new(mock_motors(size, Gearset::Green))
+    }
+
+    /// Writes to every motor of `group`, failing on the motors in `failing`,
+    /// and returns the indices attempted along with the errors.
+    fn write(group: &mut MotorGroup, failing: &[usize]) -> (Vec<usize>, Vec<PortError>) {
+        let mut attempted = Vec::new();
+        let result = group.write_each(WriteKind::Target, |index, _| {
+            attempted.push(index);
+            if failing.contains(&index) {
+                Err(PortError::Disconnected {
+                    port: index as u8 + 1,
+                })
+            } else {
+                Ok(())
+            }
+        });
+        (
+            attempted,
+            result.err().map_or_else(Vec::new, |error| error.errors),
+        )
+    }
+
+    #[test]
+    fn continue_writes_every_motor() {
+        let mut group = mock_group(3);
+        group.set_custom_write_policy(|_, _, _| PolicyDecision::Continue);
+        assert_eq!(
+            group.config.write_error_strategy,
+            WriteErrorStrategy::Custom
+        );
+
+        let (attempted, errors) = write(&mut group, &[0, 2]);
+        assert_eq!(attempted, [0, 1, 2]);
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn stop_remaining_skips_the_rest() {
+        let mut group = mock_group(4);
+        // Motor 0 may fail, but nothing else may
+        group.set_custom_write_policy(|index, _, _| {
+            if index == 0 {
+                PolicyDecision::Continue
+            } else {
+                PolicyDecision::StopRemaining
+            }
+        });
+
+        let (attempted, errors) = write(&mut group, &[0, 2]);
+        assert_eq!(attempted, [0, 1, 2]);
+        assert_eq!(
+            errors,
+            [
+                PortError::Disconnected { port: 1 },
+                PortError::Disconnected { port: 3 }
+            ]
+        );
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let mut group = mock_group(2);
+        let contexts = Arc::new(Mutex::new(Vec::new()));
+        let seen = contexts.clone();
+        group.set_custom_write_policy(move |_, _, context| {
+            seen.lock().unwrap().push(*context);
+            PolicyDecision::RetryThisMotor
+        });
+
+        let (attempted, errors) = write(&mut group, &[1]);
+        let retries = WriteContext::MAX_RETRIES as usize;
+        assert_eq!(attempted.len(), 2 + retries);
+        assert!(attempted[1..].iter().all(|&index| index == 1));
+        // Only the last attempt's error is kept
+        assert_eq!(errors.len(), 1);
+
+        let contexts = contexts.lock().unwrap();
+        assert_eq!(contexts.len(), 1 + retries);
+        for (retry, context) in contexts.iter().enumerate() {
+            assert_eq!(
+                *context,
+                WriteContext {
+                    kind: WriteKind::Target,
+                    succeeded: 1,
+                    failed: 0,
+                    retries: retry as u32,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn a_successful_retry_leaves_no_error() {
+        let mut group = mock_group(2);
+        group.set_custom_write_policy(|_, _, _| PolicyDecision::RetryThisMotor);
+
+        let mut attempts = 0;
+        let result = group.write_each(WriteKind::Configuration, |_, _| {
+            attempts += 1;
+            // The first motor fails once, then succeeds
+            if attempts == 1 {
+                Err(PortError::Disconnected { port: 1 })
+            } else {
+                Ok(())
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn the_context_counts_the_write_so_far() {
+        let mut group = mock_group(4);
+        let contexts = Arc::new(Mutex::new(Vec::new()));
+        let seen = contexts.clone();
+        group.set_custom_write_policy(move |index, _, context| {
+            seen.lock()
+                .unwrap()
+                .push((index, context.succeeded, context.failed));
+            PolicyDecision::Continue
+        });
+
+        write(&mut group, &[1, 3]);
+        assert_eq!(*contexts.lock().unwrap(), [(1, 1, 0), (3, 2, 1)]);
+    }
+
+    #[test]
+    fn built_in_strategies_ignore_the_policy() {
+        let mut group = mock_group(3);
+        group.set_custom_write_policy(|_, _, _| PolicyDecision::RetryThisMotor);
+        group.write_error_strategy(WriteErrorStrategy::Stop);
+
+        let (attempted, errors) = write(&mut group, &[0]);
+        assert_eq!(attempted, [0]);
+        assert_eq!(errors.len(), 1);
+
+        // Without a policy, a custom strategy acts like ignoring errors
+        let mut group = mock_group(3);
+        group.write_error_strategy(WriteErrorStrategy::Custom);
+        let (attempted, errors) = write(&mut group, &[0, 1]);
+        assert_eq!(attempted, [0, 1, 2]);
+        assert_eq!(errors.len(), 2);
+    }
+}
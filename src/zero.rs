@@ -0,0 +1,311 @@
+use alloc::{string::String, vec::Vec};
+
+use vexide::{math::Angle, smart::motor::Motor};
+
+use crate::{GetterResult, GroupPosition, MotorGroup, MotorGroupError, SharedMotors, readings};
+
+/// The software zeros of a motor group. See [`MotorGroup::set_zero_here`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Zeros {
+    /// The zero of [`MotorGroup::position_relative`].
+    pub(crate) here: Option<GroupPosition>,
+    /// The zeros set with [`MotorGroup::set_named_zero`], in the order they
+    /// were first set.
+    pub(crate) named: Vec<(String, GroupPosition)>,
+}
+
+impl Zeros {
+    /// Returns the zero named `name`.
+    fn named(&self, name: &str) -> Option<GroupPosition> {
+        self.named
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|&(_, zero)| zero)
+    }
+
+    /// Sets the zero named `name`, replacing any zero with the same name.
+    fn set_named(&mut self, name: &str, zero: GroupPosition) {
+        match self.named.iter_mut().find(|(other, _)| other == name) {
+            Some((_, existing)) => *existing = zero,
+            None => self.named.push((name.into(), zero)),
+        }
+    }
+}
+
+/// Returns `position` measured from `zero`, or from the encoders' own zero if
+/// there isn't one. The subtraction is done in degrees, so it rounds once.
+fn relative(
+    position: GetterResult<GroupPosition>,
+    zero: Option<GroupPosition>,
+) -> GetterResult<Angle> {
+    let zero = zero.map_or(0.0, GroupPosition::degrees);
+    readings::map_result(position, |position| {
+        Angle::from_degrees(position.degrees() - zero)
+    })
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> MotorGroup<M> {
+    /// Reads the group's position for a new zero. A zero is recorded from
+    /// the motors that could be read, so it's only lost if none could.
+    fn read_zero(&self) -> (Option<GroupPosition>, Result<(), MotorGroupError>) {
+        match self.group_position() {
+            Ok(position) => (Some(position), Ok(())),
+            Err(error) => (error.result, Err(MotorGroupError::new(error.errors))),
+        }
+    }
+
+    /// Records the group's current position as its software zero, from which
+    /// [`MotorGroup::position_relative`] measures.
+    ///
+    /// Nothing is written to the motors: their encoders keep counting from
+    /// where they were, so [`MotorGroup::position`] and anything logged from
+    /// it are unaffected. This is for re-zeroing a mechanism at a known pose,
+    /// such as between the autonomous period and driver control, without
+    /// losing its absolute position like [`MotorGroup::reset_position`]
+    /// would.
+    ///
+    /// Zeros are positions of the motors, in the same frame as
+    /// [`MotorGroup::position`], so changing the external gear ratio (see
+    /// [`MotorGroup::shift_ratio`]) doesn't move them. Writing to the
+    /// motors' position reference, such as with
+    /// [`MotorGroup::reset_position`], does, since the encoders then count
+    /// from somewhere else.
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. The zero is still set from the motors that
+    ///   could be read, and is left alone if none could.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // The lift is resting on its hard stop
+    ///     _ = lift.set_zero_here();
+    ///
+    ///     if let (Ok(absolute), Ok(relative)) = (lift.position(), lift.position_relative()) {
+    ///         println!("{absolute:?} from power on, {relative:?} above the hard stop");
+    ///     }
+    /// }
+    /// ```
+    pub fn set_zero_here(&mut self) -> Result<(), MotorGroupError> {
+        let (zero, result) = self.read_zero();
+        if zero.is_some() {
+            self.zeros.here = zero;
+        }
+        result
+    }
+
+    /// Removes the zero set with [`MotorGroup::set_zero_here`], so that
+    /// [`MotorGroup::position_relative`] is the same as
+    /// [`MotorGroup::position`] again.
+    pub fn clear_zero(&mut self) {
+        self.zeros.here = None;
+    }
+
+    /// Returns the group's average position, measured from the zero set with
+    /// [`MotorGroup::set_zero_here`].
+    ///
+    /// Without a zero, this is the same as [`MotorGroup::position`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is measured from the average of the
+    ///   motors that could be read.
+    pub fn position_relative(&self) -> GetterResult<Angle> {
+        relative(self.group_position(), self.zeros.here)
+    }
+
+    /// Records the group's current position as the zero named `name`, for
+    /// [`MotorGroup::position_relative_to`].
+    ///
+    /// Named zeros are kept apart from the zero of
+    /// [`MotorGroup::set_zero_here`] and from each other, so a mechanism can
+    /// have one for each of its presets. Setting a name again moves its zero.
+    /// Like every software zero, this writes nothing to the motors; see
+    /// [`MotorGroup::set_zero_here`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. The zero is still set from the motors that
+    ///   could be read, and is left alone if none could.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use vexide::prelude::*;
+    /// use vexide_motorgroup::*;
+    ///
+    /// #[vexide::main]
+    /// async fn main(peripherals: Peripherals) {
+    ///     let mut lift = MotorGroup::new(vec![
+    ///         Motor::new(peripherals.port_1, Gearset::Red, Direction::Forward),
+    ///         Motor::new(peripherals.port_2, Gearset::Red, Direction::Forward),
+    ///     ]);
+    ///
+    ///     // Taught by hand during setup
+    ///     _ = lift.set_named_zero("score_height");
+    ///
+    ///     if let Some(Ok(offset)) = lift.position_relative_to("score_height") {
+    ///         println!("{:.1}° from scoring height", offset.as_degrees());
+    ///     }
+    /// }
+    /// ```
+    pub fn set_named_zero(&mut self, name: &str) -> Result<(), MotorGroupError> {
+        let (zero, result) = self.read_zero();
+        if let Some(zero) = zero {
+            self.zeros.set_named(name, zero);
+        }
+        result
+    }
+
+    /// Removes the zero named `name`, returning `false` if there wasn't one.
+    pub fn clear_named_zero(&mut self, name: &str) -> bool {
+        let count = self.zeros.named.len();
+        self.zeros.named.retain(|(other, _)| other != name);
+        self.zeros.named.len() != count
+    }
+
+    /// Returns the group's average position, measured from the zero named
+    /// `name`, or `None` without reading the motors if there's no such zero.
+    ///
+    /// See [`MotorGroup::set_named_zero`].
+    ///
+    /// # Errors
+    ///
+    /// - A [`MotorGroupError`] error is returned if any motor in the group
+    ///   encounters an error. Its result is measured from the average of the
+    ///   motors that could be read.
+    pub fn position_relative_to(&self, name: &str) -> Option<GetterResult<Angle>> {
+        let zero = self.zeros.named(name)?;
+        Some(relative(self.group_position(), Some(zero)))
+    }
+}
+
+impl<M: AsRef<[Motor]> + AsMut<[Motor]>> SharedMotors<M> {
+    /// See [`MotorGroup::set_zero_here`].
+    pub fn set_zero_here(&mut self) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().set_zero_here()
+    }
+
+    /// See [`MotorGroup::clear_zero`].
+    pub fn clear_zero(&mut self) {
+        self.0.borrow_mut().clear_zero();
+    }
+
+    /// See [`MotorGroup::position_relative`].
+    pub fn position_relative(&self) -> GetterResult<Angle> {
+        self.0.borrow().position_relative()
+    }
+
+    /// See [`MotorGroup::set_named_zero`].
+    pub fn set_named_zero(&mut self, name: &str) -> Result<(), MotorGroupError> {
+        self.0.borrow_mut().set_named_zero(name)
+    }
+
+    /// See [`MotorGroup::clear_named_zero`].
+    pub fn clear_named_zero(&mut self, name: &str) -> bool {
+        self.0.borrow_mut().clear_named_zero(name)
+    }
+
+    /// See [`MotorGroup::position_relative_to`].
+    pub fn position_relative_to(&self, name: &str) -> Option<GetterResult<Angle>> {
+        self.0.borrow().position_relative_to(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vexide::{math::Angle, prelude::*, smart::PortError};
+
+    use super::{Zeros, relative};
+    use crate::{GroupPosition, MotorGroup, MotorGroupError, tests::mock_motors};
+
+    fn at(degrees: f64) -> GroupPosition {
+        GroupPosition::from_degrees(degrees).unwrap()
+    }
+
+    fn assert_degrees(angle: Angle, degrees: f64) {
+        assert!(
+            (angle.as_degrees() - degrees).abs() < 1e-9,
+            "{angle:?} isn't {degrees}°"
+        );
+    }
+
+    #[test]
+    fn positions_are_measured_from_the_zero() {
+        assert_degrees(relative(Ok(at(450.0)), Some(at(90.0))).unwrap(), 360.0);
+        assert_degrees(relative(Ok(at(-30.0)), Some(at(90.0))).unwrap(), -120.0);
+        // Without a zero, the encoders' own zero is used
+        assert_degrees(relative(Ok(at(450.0)), None).unwrap(), 450.0);
+
+        // A partial reading is measured from the zero too
+        let error = PortError::Disconnected { port: 2 };
+        let partial = Err(MotorGroupError::with_result(vec![error], at(100.0)));
+        let error = relative(partial, Some(at(40.0))).unwrap_err();
+        assert_degrees(error.result.unwrap(), 60.0);
+    }
+
+    #[test]
+    fn named_zeros_are_kept_apart() {
+        let mut zeros = Zeros::default();
+        zeros.set_named("stow", at(0.0));
+        zeros.set_named("score_height", at(720.0));
+        zeros.set_named("stow", at(10.0));
+
+        assert_eq!(zeros.named("stow"), Some(at(10.0)));
+        assert_eq!(zeros.named("score_height"), Some(at(720.0)));
+        assert_eq!(zeros.named("intake"), None);
+        // Setting a name again replaces its zero in place
+        assert_eq!(zeros.named.len(), 2);
+        assert_eq!(zeros.named[0].0, "stow");
+    }
+
+    fn group() -> MotorGroup {
+        MotorGroup::new(mock_motors(1, Gearset::Red))
+    }
+
+    #[test]
+    fn zeros_are_only_set_from_readings() {
+        let mut group = group();
+        group.zeros.here = Some(at(90.0));
+
+        // The mock motor can't be read, so the zero is left alone
+        assert!(group.set_zero_here().is_err());
+        assert_eq!(group.zeros.here, Some(at(90.0)));
+        assert!(group.set_named_zero("stow").is_err());
+        assert!(group.position_relative_to("stow").is_none());
+
+        group.zeros.named.push(("stow".into(), at(0.0)));
+        let error = group.position_relative_to("stow").unwrap().unwrap_err();
+        assert_eq!(error.errors, vec![PortError::Disconnected { port: 1 }]);
+
+        assert!(group.clear_named_zero("stow"));
+        assert!(!group.clear_named_zero("stow"));
+        group.clear_zero();
+        assert_eq!(group.zeros.here, None);
+    }
+
+    #[test]
+    fn external_ratio_does_not_move_zeros() {
+        let mut group = group();
+        group.zeros.here = Some(at(90.0));
+        group.zeros.named.push(("score_height".into(), at(720.0)));
+
+        _ = group.shift_ratio(2.0);
+        assert_eq!(group.zeros.here, Some(at(90.0)));
+        assert_eq!(group.zeros.named, vec![("score_height".into(), at(720.0))]);
+    }
+}